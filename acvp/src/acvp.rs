@@ -0,0 +1,224 @@
+//! ACVP vector sets of the symmetric block cipher algorithms.
+//!
+//! Prompts are answered with responses which contain the vector set and
+//! test case identifiers and the computed results. Test cases which already
+//! contain results, e.g. in the `internalProjection.json` files of the ACVP
+//! demo server, are verified against them.
+
+use crate::{
+    algs::{self, Algorithm, Mode, ALGORITHMS},
+    decode_hex, encode_hex,
+    json::Value,
+    mct, Error, Report,
+};
+
+/// Run all vector sets from the prompt
+///
+/// The prompt is either a single vector set or an array with the ACVP
+/// version object followed by vector sets, the response has the same form.
+pub fn run(prompt: &Value) -> Result<(Report, Value), Error> {
+    let mut report = Report::default();
+    let response = match prompt {
+        Value::Array(items) => {
+            let items: Result<Vec<Value>, Error> = items
+                .iter()
+                .map(|item| match item.get("vsId") {
+                    Some(_) => vector_set(item, &mut report),
+                    None => Ok(item.clone()),
+                })
+                .collect();
+            Value::Array(items?)
+        }
+        vs => vector_set(vs, &mut report)?,
+    };
+    Ok((report, response))
+}
+
+fn vector_set(vs: &Value, report: &mut Report) -> Result<Value, Error> {
+    let name = str_field(vs, "algorithm")?;
+    let &(_, _, alg, mode) = ALGORITHMS
+        .iter()
+        .find(|a| a.0 == name)
+        .ok_or_else(|| format!("unsupported algorithm: {}", name))?;
+
+    let groups = field(vs, "testGroups")?
+        .as_array()
+        .ok_or("testGroups is not an array")?
+        .iter()
+        .map(|group| test_group(alg, mode, group, report))
+        .collect::<Result<Vec<Value>, Error>>()?;
+
+    let mut members = vec![
+        ("vsId".into(), field(vs, "vsId")?.clone()),
+        ("algorithm".into(), name.into()),
+    ];
+    if let Some(revision) = vs.get("revision") {
+        members.push(("revision".into(), revision.clone()));
+    }
+    members.push(("testGroups".into(), Value::Array(groups)));
+    Ok(Value::Object(members))
+}
+
+fn test_group(
+    alg: Algorithm,
+    mode: Mode,
+    group: &Value,
+    report: &mut Report,
+) -> Result<Value, Error> {
+    let tg_id = field(group, "tgId")?;
+    let decrypt = match str_field(group, "direction")? {
+        "encrypt" => false,
+        "decrypt" => true,
+        d => return Err(format!("tgId {}: unknown direction: {}", tg_id, d).into()),
+    };
+    let (mct, counter) = match str_field(group, "testType")? {
+        "AFT" => (false, false),
+        "MCT" => (true, false),
+        "CTR" if mode == Mode::Ctr && !decrypt => (false, true),
+        t => return Err(format!("tgId {}: unsupported test type: {}", tg_id, t).into()),
+    };
+    // the `CTR` test type leaves the counters to the implementation, the
+    // test cases of a group continue the counter of the previous one so no
+    // counter block is used twice
+    let mut next_counter = 0u64;
+    let (input_name, output_name) = if decrypt { ("ct", "pt") } else { ("pt", "ct") };
+
+    let mut tests = Vec::new();
+    for test in field(group, "tests")?
+        .as_array()
+        .ok_or("tests is not an array")?
+    {
+        let tc_id = field(test, "tcId")?;
+        let id = format!("tgId {} tcId {}", tg_id, tc_id);
+        let key = read_key(alg, test)?;
+        let input = hex_field(test, input_name)?;
+        let iv = if counter {
+            let mut iv = vec![0; alg.block_size()];
+            iv[8..].copy_from_slice(&next_counter.to_be_bytes());
+            let bs = alg.block_size();
+            let blocks = (input.len() + bs - 1) / bs;
+            next_counter = next_counter.wrapping_add(blocks as u64);
+            iv
+        } else if mode.has_iv() {
+            hex_field(test, "iv")?
+        } else {
+            Vec::new()
+        };
+        let err = |e: Error| format!("{}: {}", id, e);
+
+        let mut members = vec![("tcId".into(), tc_id.clone())];
+        if mct {
+            let results: Vec<Value> = mct::run(alg, mode, decrypt, &key, &iv, &input)
+                .map_err(err)?
+                .iter()
+                .map(|r| mct_result(alg, mode, decrypt, r))
+                .collect();
+            if let Some(Value::Array(expected)) = test.get("resultsArray") {
+                report.verified += 1;
+                let same = expected.len() == results.len()
+                    && expected.iter().zip(&results).all(|(e, r)| same_hex(e, r));
+                if !same {
+                    report.failures.push(id);
+                }
+            }
+            members.push(("resultsArray".into(), Value::Array(results)));
+        } else {
+            let mut output = algs::crypt(alg, mode, &key, &iv, decrypt, &input).map_err(err)?;
+            if let Some(bits) = test.get("payloadLen").and_then(Value::as_u64) {
+                truncate_bits(&mut output, bits as usize);
+            }
+            if let Some(expected) = test.get(output_name) {
+                report.verified += 1;
+                if expected.as_str().map(decode_hex).and_then(Result::ok) != Some(output.clone()) {
+                    report.failures.push(id);
+                }
+            }
+            members.push((output_name.into(), hex(&output)));
+        }
+        report.tests += 1;
+        tests.push(Value::Object(members));
+    }
+
+    Ok(Value::Object(vec![
+        ("tgId".into(), tg_id.clone()),
+        ("tests".into(), Value::Array(tests)),
+    ]))
+}
+
+/// Read AES key or concatenated TDES keys
+fn read_key(alg: Algorithm, test: &Value) -> Result<Vec<u8>, Error> {
+    match alg {
+        Algorithm::Tdes if test.get("key1").is_some() => {
+            let mut key = hex_field(test, "key1")?;
+            key.extend(hex_field(test, "key2")?);
+            key.extend(hex_field(test, "key3")?);
+            Ok(key)
+        }
+        _ => hex_field(test, "key"),
+    }
+}
+
+/// Results of an outer Monte Carlo iteration
+fn mct_result(alg: Algorithm, mode: Mode, decrypt: bool, r: &mct::Record) -> Value {
+    let mut members = match alg {
+        Algorithm::Aes => vec![("key".into(), hex(&r.key))],
+        Algorithm::Tdes => vec![
+            ("key1".into(), hex(&r.key[..8])),
+            ("key2".into(), hex(&r.key[8..16])),
+            ("key3".into(), hex(&r.key[16..])),
+        ],
+    };
+    if mode.has_iv() {
+        members.push(("iv".into(), hex(&r.iv)));
+    }
+    let (pt, ct) = if decrypt {
+        (&r.output, &r.input)
+    } else {
+        (&r.input, &r.output)
+    };
+    members.push(("pt".into(), hex(pt)));
+    members.push(("ct".into(), hex(ct)));
+    Value::Object(members)
+}
+
+/// Compare result objects, hex strings are compared case-insensitively
+fn same_hex(expected: &Value, actual: &Value) -> bool {
+    match actual {
+        Value::Object(members) => members.iter().all(|(name, a)| {
+            match (expected.get(name).and_then(Value::as_str), a.as_str()) {
+                (Some(e), Some(a)) => e.eq_ignore_ascii_case(a),
+                _ => false,
+            }
+        }),
+        _ => false,
+    }
+}
+
+/// Truncate output to `bits` and clear the unused bits of the last byte
+fn truncate_bits(data: &mut Vec<u8>, bits: usize) {
+    data.truncate((bits + 7) / 8);
+    if bits & 7 != 0 {
+        if let Some(last) = data.last_mut() {
+            *last &= 0xff << (8 - (bits & 7));
+        }
+    }
+}
+
+fn hex(data: &[u8]) -> Value {
+    Value::String(encode_hex(data, true))
+}
+
+fn field<'a>(v: &'a Value, name: &str) -> Result<&'a Value, Error> {
+    v.get(name)
+        .ok_or_else(|| format!("missing field: {}", name).into())
+}
+
+fn str_field<'a>(v: &'a Value, name: &str) -> Result<&'a str, Error> {
+    field(v, name)?
+        .as_str()
+        .ok_or_else(|| format!("field is not a string: {}", name).into())
+}
+
+fn hex_field(v: &Value, name: &str) -> Result<Vec<u8>, Error> {
+    decode_hex(str_field(v, name)?).map_err(|e| format!("{}: {}", name, e).into())
+}
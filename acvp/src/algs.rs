@@ -0,0 +1,191 @@
+//! Algorithms under test and a uniform interface to their implementations.
+
+use crate::Error;
+use aes::{Aes128, Aes128Ctr, Aes192, Aes192Ctr, Aes256, Aes256Ctr};
+use block_modes::{block_padding::NoPadding, BlockMode, Cbc, Cfb, Cfb8, Ecb, Ofb};
+use cipher::{
+    generic_array::typenum::Unsigned, Block, BlockCipher, BlockDecrypt, BlockEncrypt,
+    NewBlockCipher, NewCipher, StreamCipher,
+};
+use des::TdesEde3;
+use std::marker::PhantomData;
+
+/// Block cipher under test
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Algorithm {
+    Aes,
+    /// Triple DES with three 64-bit keys, two-key TDES uses the first key
+    /// as the third one
+    Tdes,
+}
+
+/// Mode of operation under test
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    Ecb,
+    Cbc,
+    Cfb8,
+    Cfb128,
+    Ofb,
+    Ctr,
+}
+
+impl Algorithm {
+    pub fn block_size(self) -> usize {
+        match self {
+            Algorithm::Aes => 16,
+            Algorithm::Tdes => 8,
+        }
+    }
+}
+
+impl Mode {
+    pub fn has_iv(self) -> bool {
+        self != Mode::Ecb
+    }
+}
+
+/// Supported algorithms: ACVP name, CAVP file name prefix, cipher and mode
+///
+/// CAVP has no response files for AES-CTR.
+pub const ALGORITHMS: &[(&str, Option<&str>, Algorithm, Mode)] = &[
+    ("ACVP-AES-ECB", Some("ECB"), Algorithm::Aes, Mode::Ecb),
+    ("ACVP-AES-CBC", Some("CBC"), Algorithm::Aes, Mode::Cbc),
+    ("ACVP-AES-CFB8", Some("CFB8"), Algorithm::Aes, Mode::Cfb8),
+    (
+        "ACVP-AES-CFB128",
+        Some("CFB128"),
+        Algorithm::Aes,
+        Mode::Cfb128,
+    ),
+    ("ACVP-AES-OFB", Some("OFB"), Algorithm::Aes, Mode::Ofb),
+    ("ACVP-AES-CTR", None, Algorithm::Aes, Mode::Ctr),
+    ("ACVP-TDES-ECB", Some("TECB"), Algorithm::Tdes, Mode::Ecb),
+    ("ACVP-TDES-CBC", Some("TCBC"), Algorithm::Tdes, Mode::Cbc),
+];
+
+/// Block cipher in a mode of operation which keeps its state between calls
+pub trait Crypter {
+    /// Encrypt or decrypt `data` in place
+    ///
+    /// The length of `data` must be a multiple of the block size except in
+    /// the last call.
+    fn process(&mut self, data: &mut [u8]);
+}
+
+/// Create crypter for the given algorithm, mode, key and IV
+pub fn new(
+    alg: Algorithm,
+    mode: Mode,
+    key: &[u8],
+    iv: &[u8],
+    decrypt: bool,
+) -> Result<Box<dyn Crypter>, Error> {
+    match (alg, mode, key.len()) {
+        (Algorithm::Aes, Mode::Ctr, 16) => stream::<Aes128Ctr>(key, iv),
+        (Algorithm::Aes, Mode::Ctr, 24) => stream::<Aes192Ctr>(key, iv),
+        (Algorithm::Aes, Mode::Ctr, 32) => stream::<Aes256Ctr>(key, iv),
+        (Algorithm::Aes, _, 16) => with_mode::<Aes128>(mode, key, iv, decrypt),
+        (Algorithm::Aes, _, 24) => with_mode::<Aes192>(mode, key, iv, decrypt),
+        (Algorithm::Aes, _, 32) => with_mode::<Aes256>(mode, key, iv, decrypt),
+        (Algorithm::Tdes, _, 24) => with_mode::<TdesEde3>(mode, key, iv, decrypt),
+        _ => Err(format!("invalid key length: {} bytes", key.len()).into()),
+    }
+}
+
+/// Encrypt or decrypt `data` in a single call
+///
+/// The length of `data` must be a multiple of the block size for the ECB
+/// and CBC modes.
+pub fn crypt(
+    alg: Algorithm,
+    mode: Mode,
+    key: &[u8],
+    iv: &[u8],
+    decrypt: bool,
+    data: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let partial = data.len() % alg.block_size() != 0;
+    if partial && (mode == Mode::Ecb || mode == Mode::Cbc) {
+        return Err("data length is not a multiple of the block size".into());
+    }
+    let mut buf = data.to_vec();
+    new(alg, mode, key, iv, decrypt)?.process(&mut buf);
+    Ok(buf)
+}
+
+fn with_mode<C>(mode: Mode, key: &[u8], iv: &[u8], decrypt: bool) -> Result<Box<dyn Crypter>, Error>
+where
+    C: BlockCipher + BlockEncrypt + BlockDecrypt + NewBlockCipher + 'static,
+{
+    match mode {
+        Mode::Ecb => blocks::<C, Ecb<C, NoPadding>>(key, iv, decrypt),
+        Mode::Cbc => blocks::<C, Cbc<C, NoPadding>>(key, iv, decrypt),
+        Mode::Cfb8 => blocks::<C, Cfb8<C, NoPadding>>(key, iv, decrypt),
+        Mode::Cfb128 => blocks::<C, Cfb<C, NoPadding>>(key, iv, decrypt),
+        Mode::Ofb => blocks::<C, Ofb<C, NoPadding>>(key, iv, decrypt),
+        Mode::Ctr => Err("CTR mode is only supported for AES".into()),
+    }
+}
+
+fn stream<S>(key: &[u8], iv: &[u8]) -> Result<Box<dyn Crypter>, Error>
+where
+    S: NewCipher + StreamCipher + 'static,
+{
+    let cipher = S::new_from_slices(key, iv).map_err(|_| "invalid key or IV length")?;
+    Ok(Box::new(Stream(cipher)))
+}
+
+fn blocks<C, M>(key: &[u8], iv: &[u8], decrypt: bool) -> Result<Box<dyn Crypter>, Error>
+where
+    C: BlockCipher + NewBlockCipher + 'static,
+    M: BlockMode<C, NoPadding> + 'static,
+{
+    let mode = M::new_from_slices(key, iv).map_err(|_| "invalid key or IV length")?;
+    Ok(Box::new(Blocks {
+        mode,
+        decrypt,
+        _c: PhantomData,
+    }))
+}
+
+/// Mode from the `block-modes` crate
+struct Blocks<C, M> {
+    mode: M,
+    decrypt: bool,
+    _c: PhantomData<C>,
+}
+
+impl<C: BlockCipher, M: BlockMode<C, NoPadding>> Crypter for Blocks<C, M> {
+    fn process(&mut self, data: &mut [u8]) {
+        let bs = C::BlockSize::USIZE;
+        // a partial block is processed zero-padded, which leaves the mode
+        // state invalid, but the output prefix is correct for the streaming
+        // modes used with partial blocks (CFB8, CFB128, OFB)
+        let mut blocks: Vec<Block<C>> = data
+            .chunks(bs)
+            .map(|chunk| {
+                let mut block = Block::<C>::default();
+                block[..chunk.len()].copy_from_slice(chunk);
+                block
+            })
+            .collect();
+        if self.decrypt {
+            self.mode.decrypt_blocks(&mut blocks);
+        } else {
+            self.mode.encrypt_blocks(&mut blocks);
+        }
+        for (chunk, block) in data.chunks_mut(bs).zip(blocks.iter()) {
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+    }
+}
+
+/// Stream cipher from the `aes` crate
+struct Stream<S>(S);
+
+impl<S: StreamCipher> Crypter for Stream<S> {
+    fn process(&mut self, data: &mut [u8]) {
+        self.0.apply_keystream(data);
+    }
+}
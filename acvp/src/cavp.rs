@@ -0,0 +1,273 @@
+//! Legacy CAVP request (`.req`) and response (`.rsp`) files.
+//!
+//! The algorithm, mode and test type are determined by the file name, e.g.
+//! `CBCMMT128.rsp` or `TECBMonte3.req`. Files consist of comment lines,
+//! `[ENCRYPT]` and `[DECRYPT]` sections and test cases of `NAME = value`
+//! lines separated by empty lines. Monte Carlo files contain a single test
+//! case per section in requests and all outer iterations in responses.
+
+use crate::{
+    algs::{self, Algorithm, Mode, ALGORITHMS},
+    decode_hex, encode_hex, mct, Error, Report,
+};
+
+/// Test names which follow the algorithm prefix in file names
+const TESTS: &[&str] = &[
+    "GFSbox", "KeySbox", "VarKey", "VarTxt", "MMT", "MCT", "Monte", "invperm", "permop", "subtab",
+    "varkey", "vartext",
+];
+
+/// Test case, a list of fields in the original order
+type Case = Vec<(String, String)>;
+
+struct Section {
+    name: String,
+    cases: Vec<Case>,
+}
+
+/// Run tests from the file content, returns the report and the response
+pub fn run(stem: &str, text: &str) -> Result<(Report, String), Error> {
+    let (alg, mode, mct) = detect(stem)?;
+    let (header, sections) = parse(text)?;
+
+    let mut report = Report::default();
+    let mut out = String::new();
+    for line in header {
+        out.push_str(line);
+        out.push('\n');
+    }
+    for section in &sections {
+        let decrypt = match section.name.as_str() {
+            "ENCRYPT" => false,
+            "DECRYPT" => true,
+            name => return Err(format!("unknown section: [{}]", name).into()),
+        };
+        let cases = if mct {
+            monte_carlo(alg, mode, decrypt, section, &mut report)?
+        } else {
+            section
+                .cases
+                .iter()
+                .map(|case| known_answer(alg, mode, decrypt, section, case, &mut report))
+                .collect::<Result<_, _>>()?
+        };
+
+        out.push_str(&format!("\n[{}]\n", section.name));
+        for case in cases {
+            out.push('\n');
+            for (name, value) in case {
+                out.push_str(&format!("{} = {}\n", name, value));
+            }
+        }
+    }
+    Ok((report, out))
+}
+
+/// Determine algorithm, mode and whether the file contains a Monte Carlo test
+fn detect(stem: &str) -> Result<(Algorithm, Mode, bool), Error> {
+    for &(_, prefix, alg, mode) in ALGORITHMS {
+        let rest = match prefix.and_then(|p| stem.strip_prefix(p)) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        if let Some(test) = TESTS.iter().find(|t| rest.starts_with(*t)) {
+            return Ok((alg, mode, *test == "MCT" || *test == "Monte"));
+        }
+    }
+    Err(format!("unsupported CAVP file name: {}", stem).into())
+}
+
+/// Split file into header comments and sections
+fn parse(text: &str) -> Result<(Vec<&str>, Vec<Section>), Error> {
+    let mut header = Vec::new();
+    let mut sections: Vec<Section> = Vec::new();
+    let mut case = Case::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let section = match sections.last_mut() {
+            Some(section) => section,
+            None if line.starts_with('#') => {
+                header.push(line);
+                continue;
+            }
+            None if line.is_empty() => continue,
+            None if !line.starts_with('[') => {
+                return Err(format!("line {}: test case outside of a section", n + 1).into())
+            }
+            None => {
+                sections.push(Section {
+                    name: line.trim_matches(|c| c == '[' || c == ']').into(),
+                    cases: Vec::new(),
+                });
+                continue;
+            }
+        };
+
+        let boundary = line.is_empty() || line.starts_with('[') || line.starts_with("COUNT");
+        if boundary && !case.is_empty() {
+            section.cases.push(std::mem::take(&mut case));
+        }
+        if line.starts_with('[') {
+            sections.push(Section {
+                name: line.trim_matches(|c| c == '[' || c == ']').into(),
+                cases: Vec::new(),
+            });
+        } else if !line.is_empty() && !line.starts_with('#') {
+            let i = line
+                .find('=')
+                .ok_or_else(|| format!("line {}: expected NAME = value", n + 1))?;
+            case.push((line[..i].trim().into(), line[i + 1..].trim().into()));
+        }
+    }
+    if let Some(section) = sections.last_mut() {
+        if !case.is_empty() {
+            section.cases.push(case);
+        }
+    }
+    Ok((header, sections))
+}
+
+fn get<'a>(case: &'a Case, name: &str) -> Option<&'a str> {
+    case.iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+fn get_hex(case: &Case, name: &str) -> Result<Vec<u8>, Error> {
+    let value = get(case, name).ok_or_else(|| format!("missing field: {}", name))?;
+    decode_hex(value).map_err(|e| format!("{}: {}", name, e).into())
+}
+
+/// Read AES key or concatenated TDES keys, TDES files use either `KEYs`
+/// for a single key repeated three times or `KEY1`, `KEY2` and `KEY3`
+fn read_key(alg: Algorithm, case: &Case) -> Result<Vec<u8>, Error> {
+    match alg {
+        Algorithm::Aes => get_hex(case, "KEY"),
+        Algorithm::Tdes if get(case, "KEYs").is_some() => Ok(get_hex(case, "KEYs")?.repeat(3)),
+        Algorithm::Tdes => {
+            let mut key = get_hex(case, "KEY1")?;
+            key.extend(get_hex(case, "KEY2")?);
+            key.extend(get_hex(case, "KEY3")?);
+            Ok(key)
+        }
+    }
+}
+
+fn field_names(decrypt: bool) -> (&'static str, &'static str) {
+    if decrypt {
+        ("CIPHERTEXT", "PLAINTEXT")
+    } else {
+        ("PLAINTEXT", "CIPHERTEXT")
+    }
+}
+
+fn case_id(section: &Section, case: &Case) -> String {
+    format!(
+        "[{}] COUNT = {}",
+        section.name,
+        get(case, "COUNT").unwrap_or("?")
+    )
+}
+
+/// Run known answer or multi-block message test case
+fn known_answer(
+    alg: Algorithm,
+    mode: Mode,
+    decrypt: bool,
+    section: &Section,
+    case: &Case,
+    report: &mut Report,
+) -> Result<Case, Error> {
+    let id = case_id(section, case);
+    let (input_name, output_name) = field_names(decrypt);
+    let key = read_key(alg, case).map_err(|e| format!("{}: {}", id, e))?;
+    let iv = match mode.has_iv() {
+        true => get_hex(case, "IV").map_err(|e| format!("{}: {}", id, e))?,
+        false => Vec::new(),
+    };
+    let input = get_hex(case, input_name).map_err(|e| format!("{}: {}", id, e))?;
+    let output =
+        algs::crypt(alg, mode, &key, &iv, decrypt, &input).map_err(|e| format!("{}: {}", id, e))?;
+    let output = encode_hex(&output, false);
+
+    report.tests += 1;
+    let mut result = case.clone();
+    match result.iter_mut().find(|(n, _)| n == output_name) {
+        Some((_, expected)) if !expected.is_empty() => {
+            report.verified += 1;
+            if !expected.eq_ignore_ascii_case(&output) {
+                report.failures.push(id);
+            }
+            *expected = output;
+        }
+        Some((_, expected)) => *expected = output,
+        None => result.push((output_name.into(), output)),
+    }
+    Ok(result)
+}
+
+/// Run Monte Carlo test starting with the first case of the section and
+/// verify the other cases if they are present
+fn monte_carlo(
+    alg: Algorithm,
+    mode: Mode,
+    decrypt: bool,
+    section: &Section,
+    report: &mut Report,
+) -> Result<Vec<Case>, Error> {
+    let first = match section.cases.first() {
+        Some(case) => case,
+        None => return Ok(Vec::new()),
+    };
+    let id = case_id(section, first);
+    let (input_name, output_name) = field_names(decrypt);
+    let key = read_key(alg, first).map_err(|e| format!("{}: {}", id, e))?;
+    let iv = match mode.has_iv() {
+        true => get_hex(first, "IV").map_err(|e| format!("{}: {}", id, e))?,
+        false => Vec::new(),
+    };
+    let input = get_hex(first, input_name).map_err(|e| format!("{}: {}", id, e))?;
+    let records =
+        mct::run(alg, mode, decrypt, &key, &iv, &input).map_err(|e| format!("{}: {}", id, e))?;
+
+    let single_key = get(first, "KEYs").is_some();
+    let cases: Vec<Case> = records
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let mut case = vec![("COUNT".into(), i.to_string())];
+            match alg {
+                Algorithm::Aes => case.push(("KEY".into(), encode_hex(&r.key, false))),
+                Algorithm::Tdes if single_key => {
+                    case.push(("KEYs".into(), encode_hex(&r.key[..8], false)))
+                }
+                Algorithm::Tdes => {
+                    for (j, k) in r.key.chunks(8).enumerate() {
+                        case.push((format!("KEY{}", j + 1), encode_hex(k, false)));
+                    }
+                }
+            }
+            if mode.has_iv() {
+                case.push(("IV".into(), encode_hex(&r.iv, false)));
+            }
+            case.push((input_name.into(), encode_hex(&r.input, false)));
+            case.push((output_name.into(), encode_hex(&r.output, false)));
+            case
+        })
+        .collect();
+
+    report.tests += 1;
+    if get(first, output_name).map_or(false, |v| !v.is_empty()) {
+        report.verified += 1;
+        let mismatch = section.cases.iter().zip(&cases).find(|(expected, case)| {
+            case.iter().any(|(name, value)| match get(expected, name) {
+                Some(e) => !e.eq_ignore_ascii_case(value),
+                None => false,
+            })
+        });
+        if let Some((expected, _)) = mismatch {
+            report.failures.push(case_id(section, expected));
+        }
+    }
+    Ok(cases)
+}
@@ -0,0 +1,169 @@
+//! `acvp` test harness: runs NIST ACVP vector sets and legacy CAVP request
+//! and response files against the AES and TDES implementations and the
+//! block modes from this repository, verifies the expected results when
+//! they are present and writes response files.
+#![forbid(unsafe_code)]
+#![warn(rust_2018_idioms)]
+
+mod acvp;
+mod algs;
+mod cavp;
+mod json;
+mod mct;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+type Error = Box<dyn std::error::Error>;
+
+const USAGE: &str = "\
+Usage:
+  acvp [-o DIR] FILE...
+
+Runs ACVP vector sets (.json) and CAVP request or response files (.req,
+.rsp) for AES-ECB/CBC/CFB8/CFB128/OFB/CTR and TDES-ECB/CBC. CAVP files are
+recognized by their names, e.g. CBCMMT128.rsp or TECBMonte3.req.
+
+Test cases which contain the expected results are verified, the exit status
+is 1 if any of them fails.
+
+Options:
+  -o, --out DIR   write response files to DIR: <name>.rsp for CAVP files,
+                  response.json for prompt.json and <name>.response.json
+                  for other ACVP files
+";
+
+/// Results of processing a file
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Number of test cases
+    pub tests: usize,
+    /// Number of test cases with expected results
+    pub verified: usize,
+    /// Test cases which results differ from the expected ones
+    pub failures: Vec<String>,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("acvp: {}", e);
+            process::exit(2);
+        }
+    }
+}
+
+/// Process files given in arguments, returns `false` if any test failed
+fn run(args: &[String]) -> Result<bool, Error> {
+    let mut out_dir: Option<PathBuf> = None;
+    let mut files = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return Ok(true);
+            }
+            "-o" | "--out" => out_dir = Some(args.next().ok_or("missing value of -o")?.into()),
+            a if a.starts_with("--out=") => out_dir = Some(a["--out=".len()..].into()),
+            a if a.starts_with('-') => return Err(format!("unknown option: {}", a).into()),
+            a => files.push(PathBuf::from(a)),
+        }
+    }
+    if files.is_empty() {
+        return Err(format!("no input files\n\n{}", USAGE).into());
+    }
+
+    let mut passed = true;
+    for path in &files {
+        let (report, response, name) = match process_file(path) {
+            Ok(res) => res,
+            Err(e) => {
+                eprintln!("acvp: {}: {}", path.display(), e);
+                passed = false;
+                continue;
+            }
+        };
+        println!(
+            "{}: {} tests, {} verified, {} failed",
+            path.display(),
+            report.tests,
+            report.verified,
+            report.failures.len()
+        );
+        for failure in &report.failures {
+            println!("  FAILED {}", failure);
+        }
+        passed &= report.failures.is_empty();
+
+        if let Some(dir) = &out_dir {
+            let out = dir.join(name);
+            if out.exists() && fs::canonicalize(&out)? == fs::canonicalize(path)? {
+                return Err(
+                    format!("{}: response would overwrite the input", out.display()).into(),
+                );
+            }
+            fs::write(&out, response).map_err(|e| format!("{}: {}", out.display(), e))?;
+        }
+    }
+    Ok(passed)
+}
+
+/// Run tests from a file, returns the report, the response and its file name
+fn process_file(path: &Path) -> Result<(Report, String, String), Error> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("invalid file name")?;
+    let (stem, ext) = match name.rfind('.') {
+        Some(i) => (&name[..i], &name[i + 1..]),
+        None => (name, ""),
+    };
+    let text = fs::read_to_string(path)?;
+    match ext {
+        "json" => {
+            let (report, response) = acvp::run(&json::Value::parse(&text)?)?;
+            let name = match stem {
+                "prompt" => "response.json".into(),
+                _ => format!("{}.response.json", stem),
+            };
+            Ok((report, format!("{}\n", response), name))
+        }
+        "req" | "rsp" => {
+            let (report, response) = cavp::run(stem, &text)?;
+            Ok((report, response, format!("{}.rsp", stem)))
+        }
+        _ => Err("unknown file type, expected .json, .req or .rsp".into()),
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, Error> {
+    let s = s.as_bytes();
+    if s.len() & 1 != 0 {
+        return Err("hex string has odd length".into());
+    }
+    s.chunks(2)
+        .map(|pair| {
+            let digit = |c: u8| (c as char).to_digit(16).ok_or("invalid hex string");
+            Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
+
+fn encode_hex(data: &[u8], upper: bool) -> String {
+    data.iter()
+        .map(|b| {
+            if upper {
+                format!("{:02X}", b)
+            } else {
+                format!("{:02x}", b)
+            }
+        })
+        .collect()
+}
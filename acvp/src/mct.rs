@@ -0,0 +1,218 @@
+//! Monte Carlo tests as defined in the AES Algorithm Validation Suite
+//! (AESAVS) and the TDES validation system (TMOVS) specifications, which
+//! are also used by ACVP.
+//!
+//! Both specifications are written in terms of plaintext and ciphertext for
+//! encryption; for decryption the roles are swapped. Here `input` is the
+//! plaintext for encryption and the ciphertext for decryption, `output` is
+//! the other one.
+
+use crate::{
+    algs::{self, Algorithm, Mode},
+    Error,
+};
+use std::mem;
+
+/// Inputs and output of one outer iteration
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    pub key: Vec<u8>,
+    pub iv: Vec<u8>,
+    pub input: Vec<u8>,
+    pub output: Vec<u8>,
+}
+
+/// Run Monte Carlo test starting with the given key, IV and input
+pub fn run(
+    alg: Algorithm,
+    mode: Mode,
+    decrypt: bool,
+    key: &[u8],
+    iv: &[u8],
+    input: &[u8],
+) -> Result<Vec<Record>, Error> {
+    match (alg, mode) {
+        (_, Mode::Ctr) => Err("Monte Carlo test is not defined for the CTR mode".into()),
+        (Algorithm::Aes, Mode::Cfb8) => aes_cfb8(decrypt, key, iv, input),
+        (Algorithm::Aes, _) => aes(mode, decrypt, key, iv, input),
+        (Algorithm::Tdes, Mode::Ecb) | (Algorithm::Tdes, Mode::Cbc) => {
+            tdes(mode, decrypt, key, iv, input)
+        }
+        (Algorithm::Tdes, _) => Err("unsupported TDES mode".into()),
+    }
+}
+
+/// AES in the ECB, CBC, CFB128 and OFB modes
+fn aes(
+    mode: Mode,
+    decrypt: bool,
+    key: &[u8],
+    iv: &[u8],
+    input: &[u8],
+) -> Result<Vec<Record>, Error> {
+    let (mut key, mut iv, mut input) = (key.to_vec(), iv.to_vec(), input.to_vec());
+    let mut records = Vec::with_capacity(100);
+    for _ in 0..100 {
+        let mut c = algs::new(Algorithm::Aes, mode, &key, &iv, decrypt)?;
+        // outputs of the previous and the current inner iteration
+        let (mut prev, mut last) = (Vec::new(), Vec::new());
+        let mut block = input.clone();
+        for j in 0..1000 {
+            c.process(&mut block);
+            let next = match mode {
+                Mode::Ecb => block.clone(),
+                _ if j == 0 => iv.clone(),
+                _ => last.clone(),
+            };
+            prev = mem::replace(&mut last, mem::replace(&mut block, next));
+        }
+
+        records.push(Record {
+            key: key.clone(),
+            iv: iv.clone(),
+            input,
+            output: last.clone(),
+        });
+
+        let mut tail = prev.clone();
+        tail.extend_from_slice(&last);
+        let n = key.len();
+        xor(&mut key, &tail[tail.len() - n..]);
+        if mode == Mode::Ecb {
+            input = last;
+        } else {
+            iv = last;
+            input = prev;
+        }
+    }
+    Ok(records)
+}
+
+/// AES in the CFB8 mode, where inputs and outputs are single bytes
+fn aes_cfb8(decrypt: bool, key: &[u8], iv: &[u8], input: &[u8]) -> Result<Vec<Record>, Error> {
+    if input.len() != 1 || iv.len() != 16 {
+        return Err("CFB8 Monte Carlo test requires 8-bit input and 128-bit IV".into());
+    }
+    let (mut key, mut iv, mut input) = (key.to_vec(), iv.to_vec(), input.to_vec());
+    let mut records = Vec::with_capacity(100);
+    for _ in 0..100 {
+        let mut c = algs::new(Algorithm::Aes, Mode::Cfb8, &key, &iv, decrypt)?;
+        // input of the inner iteration j is the initial input for j = 0,
+        // IV byte j - 1 for j <= 16 and output byte j - 17 afterwards, so
+        // inputs of the next 16 iterations are always known
+        let mut out = Vec::with_capacity(1000);
+        while out.len() < 1000 {
+            let start = out.len();
+            let mut chunk: Vec<u8> = (start..1000.min(start + 16))
+                .map(|j| match j {
+                    0 => input[0],
+                    1..=16 => iv[j - 1],
+                    _ => out[j - 17],
+                })
+                .collect();
+            c.process(&mut chunk);
+            out.extend_from_slice(&chunk);
+        }
+
+        records.push(Record {
+            key: key.clone(),
+            iv: iv.clone(),
+            input,
+            output: vec![out[999]],
+        });
+
+        let n = key.len();
+        xor(&mut key, &out[1000 - n..]);
+        iv = out[984..].to_vec();
+        input = vec![out[983]];
+    }
+    Ok(records)
+}
+
+/// TDES in the ECB and CBC modes with keys concatenated into `key`
+fn tdes(
+    mode: Mode,
+    decrypt: bool,
+    key: &[u8],
+    iv: &[u8],
+    input: &[u8],
+) -> Result<Vec<Record>, Error> {
+    let (mut key, mut iv, mut input) = (key.to_vec(), iv.to_vec(), input.to_vec());
+    let mut records = Vec::with_capacity(400);
+    for _ in 0..400 {
+        let mut c = algs::new(Algorithm::Tdes, mode, &key, &iv, decrypt)?;
+        // last three outputs and the last input
+        let mut outs = [Vec::new(), Vec::new(), Vec::new()];
+        let mut last_input = Vec::new();
+        let mut block = input.clone();
+        for j in 0..10_000 {
+            last_input.clone_from(&block);
+            c.process(&mut block);
+            let next = match mode {
+                Mode::Cbc if !decrypt && j == 0 => iv.clone(),
+                Mode::Cbc if !decrypt => outs[2].clone(),
+                _ => block.clone(),
+            };
+            outs.rotate_left(1);
+            outs[2] = mem::replace(&mut block, next);
+        }
+
+        records.push(Record {
+            key: key.clone(),
+            iv: iv.clone(),
+            input,
+            output: outs[2].clone(),
+        });
+
+        update_tdes_keys(&mut key, &outs);
+        let [_, prev, last] = outs;
+        input = match (mode, decrypt) {
+            (Mode::Cbc, false) => {
+                iv = last;
+                prev
+            }
+            (Mode::Cbc, true) => {
+                iv = last_input;
+                last
+            }
+            _ => last,
+        };
+    }
+    Ok(records)
+}
+
+/// Update TDES keys with the last three outputs and set their parity bits,
+/// the relation between keys (one, two or three distinct) is preserved
+fn update_tdes_keys(key: &mut [u8], outs: &[Vec<u8>; 3]) {
+    let (k1, k23) = key.split_at_mut(8);
+    let (k2, k3) = k23.split_at_mut(8);
+    let (same12, same13) = (k1 == k2, k1 == k3);
+
+    xor(k1, &outs[2]);
+    set_odd_parity(k1);
+    if same12 && same13 {
+        k2.copy_from_slice(k1);
+    } else {
+        xor(k2, &outs[1]);
+        set_odd_parity(k2);
+    }
+    if same13 {
+        k3.copy_from_slice(k1);
+    } else {
+        xor(k3, &outs[0]);
+        set_odd_parity(k3);
+    }
+}
+
+/// Set the least significant bit of each byte so its weight is odd
+fn set_odd_parity(key: &mut [u8]) {
+    for b in key.iter_mut() {
+        *b = (*b & 0xfe) | (((*b >> 1).count_ones() as u8 & 1) ^ 1);
+    }
+}
+
+fn xor(a: &mut [u8], b: &[u8]) {
+    for (x, y) in a.iter_mut().zip(b) {
+        *x ^= y;
+    }
+}
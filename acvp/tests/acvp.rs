@@ -0,0 +1,140 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_acvp"))
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data"))
+        .output()
+        .unwrap()
+}
+
+/// Temporary output directory unique for the test
+fn out_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("acvp-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn check(files: &[&str]) {
+    let res = run(files);
+    let stdout = String::from_utf8(res.stdout).unwrap();
+    assert!(res.status.success(), "{}", stdout);
+    assert_eq!(stdout.lines().count(), files.len());
+    for line in stdout.lines() {
+        assert!(line.ends_with(" verified, 0 failed"), "{}", line);
+        assert!(!line.contains(" 0 verified"), "{}", line);
+    }
+}
+
+/// Multi-block message and Monte Carlo tests in the CAVP format
+#[test]
+fn cavp() {
+    check(&[
+        "CBCMMT128.rsp",
+        "CFB8MMT192.rsp",
+        "CFB128MMT256.rsp",
+        "OFBMMT128.rsp",
+        "TECBMMT3.rsp",
+        "TCBCMMT2.rsp",
+        "ECBMCT128.rsp",
+        "CBCMCT128.rsp",
+        "CFB8MCT256.rsp",
+    ]);
+}
+
+/// TDES Monte Carlo test runs 4 million block operations per section, run
+/// it with `cargo test --release -- --ignored`
+#[test]
+#[ignore]
+fn cavp_tdes_monte_carlo() {
+    check(&["TCBCMonte3.rsp"]);
+}
+
+/// Functional and Monte Carlo tests in the ACVP format
+#[test]
+fn acvp() {
+    check(&["aes-cbc.json", "aes-ctr.json", "tdes-ecb.json"]);
+}
+
+#[test]
+fn responses() {
+    let dir = out_dir("responses");
+    let res = run(&["-o", dir.to_str().unwrap(), "ECBMCT128.req", "aes-ctr.json"]);
+    assert!(res.status.success());
+    assert_eq!(
+        String::from_utf8(res.stdout).unwrap(),
+        "ECBMCT128.req: 2 tests, 0 verified, 0 failed\n\
+         aes-ctr.json: 10 tests, 10 verified, 0 failed\n",
+    );
+
+    let rsp = fs::read_to_string(dir.join("ECBMCT128.rsp")).unwrap();
+    let expected = include_str!("data/ECBMCT128.rsp");
+    assert_eq!(rsp, expected);
+
+    let json = fs::read_to_string(dir.join("aes-ctr.response.json")).unwrap();
+    assert!(json.starts_with("[\n  {\n    \"acvVersion\": \"1.0\"\n  },\n"));
+    assert!(json.contains("\"vsId\": 1001,\n    \"algorithm\": \"ACVP-AES-CTR\""));
+    // inputs are not echoed in responses
+    assert!(!json.contains("\"key\""));
+
+    // the response is verified against the CAVP response file
+    let res = run(&[dir.join("ECBMCT128.rsp").to_str().unwrap()]);
+    assert!(res.status.success());
+    assert!(String::from_utf8(res.stdout)
+        .unwrap()
+        .ends_with(": 2 tests, 2 verified, 0 failed\n"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// The `CTR` test type of AES-CTR with counters chosen by the harness, the
+/// plaintext is zero so the ciphertext is the encryption of the counters
+#[test]
+fn counters() {
+    let dir = out_dir("counters");
+    let res = run(&["-o", dir.to_str().unwrap(), "aes-ctr-counter.json"]);
+    assert!(res.status.success());
+    assert_eq!(
+        String::from_utf8(res.stdout).unwrap(),
+        "aes-ctr-counter.json: 2 tests, 0 verified, 0 failed\n",
+    );
+
+    let json = fs::read_to_string(dir.join("aes-ctr-counter.response.json")).unwrap();
+    assert!(json
+        .contains("\"ct\": \"7DF76B0C1AB899B33E42F047B91B546F57127D4034B1BEBFAEF466B9C7726FC6\""));
+    assert!(json.contains("\"ct\": \"973F2EF34879E2027F1734303FF21F89\""));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn failures() {
+    let dir = out_dir("failures");
+    let rsp = include_str!("data/CBCMMT128.rsp").replacen("CIPHERTEXT = ", "CIPHERTEXT = 00", 2);
+    let rsp = rsp.replacen("CIPHERTEXT = 00", "CIPHERTEXT = ", 1);
+    let path = dir.join("CBCMMT128.rsp");
+    fs::write(&path, rsp).unwrap();
+
+    let res = run(&[path.to_str().unwrap()]);
+    assert_eq!(res.status.code(), Some(1));
+    let stdout = String::from_utf8(res.stdout).unwrap();
+    assert!(stdout.ends_with(": 10 tests, 10 verified, 1 failed\n  FAILED [ENCRYPT] COUNT = 1\n"));
+
+    // responses never overwrite inputs
+    let res = run(&["-o", dir.to_str().unwrap(), path.to_str().unwrap()]);
+    assert_eq!(res.status.code(), Some(2));
+
+    for (file, msg) in &[
+        ("CFB1MMT128.rsp", "unsupported CAVP file name"),
+        ("prompt.txt", "unknown file type"),
+    ] {
+        let path = dir.join(file);
+        fs::write(&path, "").unwrap();
+        let res = run(&[path.to_str().unwrap()]);
+        assert_eq!(res.status.code(), Some(1));
+        assert!(String::from_utf8(res.stderr).unwrap().contains(msg));
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -16,6 +16,8 @@ mod decrypt;
 mod encrypt;
 mod expand;
 
+#[cfg(feature = "hazmat")]
+use self::expand::fwd_expanded_keys;
 use self::{
     decrypt::{decrypt, decrypt8},
     encrypt::{encrypt, encrypt8},
@@ -29,6 +31,29 @@ use cipher::{
 };
 use core::arch::aarch64::*;
 
+macro_rules! impl_backend {
+    ($name:ident) => {
+        impl $name {
+            /// Backend used by this instance
+            pub(crate) fn backend(&self) -> crate::Backend {
+                crate::Backend::Armv8
+            }
+        }
+    };
+}
+
+/// Store round keys in the FIPS 197 form
+#[cfg(feature = "hazmat")]
+fn store_round_keys<const N: usize>(keys: &[uint8x16_t; N]) -> [Block; N] {
+    let mut round_keys = [Block::default(); N];
+
+    for (round_key, key) in round_keys.iter_mut().zip(keys.iter()) {
+        unsafe { vst1q_u8(round_key.as_mut_ptr(), *key) };
+    }
+
+    round_keys
+}
+
 macro_rules! define_aes_impl {
     (
         $name:ident,
@@ -57,6 +82,23 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl_backend!($name);
+
+        #[cfg(feature = "hazmat")]
+        impl $name {
+            /// Create a cipher instance from round keys in the FIPS 197 form
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                let encrypt = $name_enc::from_round_keys(round_keys);
+                let decrypt = $name_dec::from(&encrypt);
+                Self { encrypt, decrypt }
+            }
+
+            /// Round keys in the FIPS 197 form
+            pub(crate) fn round_keys(&self) -> [Block; $rounds] {
+                self.encrypt.round_keys()
+            }
+        }
+
         impl BlockCipher for $name {
             type BlockSize = U16;
             type ParBlocks = U8;
@@ -103,6 +145,27 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl_backend!($name_enc);
+
+        #[cfg(feature = "hazmat")]
+        impl $name_enc {
+            /// Create a cipher instance from round keys in the FIPS 197 form
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                let mut keys = [unsafe { vdupq_n_u8(0) }; $rounds];
+
+                for (key, round_key) in keys.iter_mut().zip(round_keys.iter()) {
+                    *key = unsafe { vld1q_u8(round_key.as_ptr()) };
+                }
+
+                Self { round_keys: keys }
+            }
+
+            /// Round keys in the FIPS 197 form
+            pub(crate) fn round_keys(&self) -> [Block; $rounds] {
+                store_round_keys(&self.round_keys)
+            }
+        }
+
         impl BlockCipher for $name_enc {
             type BlockSize = U16;
             type ParBlocks = U8;
@@ -147,6 +210,23 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl_backend!($name_dec);
+
+        #[cfg(feature = "hazmat")]
+        impl $name_dec {
+            /// Create a cipher instance from round keys in the FIPS 197 form
+            pub(crate) fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                $name_enc::from_round_keys(round_keys).into()
+            }
+
+            /// Encryption round keys in the FIPS 197 form
+            pub(crate) fn round_keys(&self) -> [Block; $rounds] {
+                let mut keys = self.round_keys;
+                fwd_expanded_keys(&mut keys);
+                store_round_keys(&keys)
+            }
+        }
+
         impl BlockCipher for $name_dec {
             type BlockSize = U16;
             type ParBlocks = U8;
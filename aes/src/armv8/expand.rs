@@ -63,6 +63,21 @@ pub(super) fn inv_expanded_keys<const N: usize>(expanded_keys: &mut [uint8x16_t;
     expanded_keys.reverse();
 }
 
+/// Compute encryption expanded keys from inverse expanded keys.
+///
+/// This is the inverse of [`inv_expanded_keys`].
+#[cfg(feature = "hazmat")]
+#[inline]
+pub(super) fn fwd_expanded_keys<const N: usize>(expanded_keys: &mut [uint8x16_t; N]) {
+    assert!(N == 11 || N == 13 || N == 15);
+
+    expanded_keys.reverse();
+
+    for ek in expanded_keys.iter_mut().take(N - 1).skip(1) {
+        unsafe { *ek = vaesmcq_u8(*ek) }
+    }
+}
+
 /// Sub bytes for a single AES word: used for key expansion.
 #[inline(always)]
 fn sub_word(input: u32) -> u32 {
@@ -4,8 +4,9 @@
 //! implementations in this crate, but instead provides raw AES-NI accelerated
 //! access to the AES round function gated under the `hazmat` crate feature.
 
+use super::expand;
 use crate::{Block, ParBlocks};
-use core::arch::aarch64::*;
+use core::{arch::aarch64::*, convert::TryInto};
 
 /// AES cipher (encrypt) round function.
 #[allow(clippy::cast_ptr_alignment)]
@@ -85,6 +86,75 @@ pub(crate) unsafe fn equiv_inv_cipher_round_par(blocks: &mut ParBlocks, round_ke
     }
 }
 
+/// AES cipher (encrypt) final round function.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_round_last(block: &mut Block, round_key: &Block) {
+    let b = vld1q_u8(block.as_ptr());
+    let k = vld1q_u8(round_key.as_ptr());
+
+    // AES single round encryption (all-zero round key, deferred until the end)
+    let mut state = vaeseq_u8(b, vdupq_n_u8(0));
+
+    // AES add round key (bitwise XOR)
+    state = veorq_u8(state, k);
+
+    vst1q_u8(block.as_mut_ptr(), state);
+}
+
+/// AES cipher (encrypt) final round function: parallel version.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_round_last_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+    for i in 0..8 {
+        let mut state = vld1q_u8(blocks[i].as_ptr());
+
+        // AES single round encryption
+        state = vaeseq_u8(state, vdupq_n_u8(0));
+
+        // AES add round key (bitwise XOR)
+        state = veorq_u8(state, vld1q_u8(round_keys[i].as_ptr()));
+
+        vst1q_u8(blocks[i].as_mut_ptr(), state);
+    }
+}
+
+/// AES equivalent inverse cipher (decrypt) final round function.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn equiv_inv_cipher_round_last(block: &mut Block, round_key: &Block) {
+    let b = vld1q_u8(block.as_ptr());
+    let k = vld1q_u8(round_key.as_ptr());
+
+    // AES single round decryption (all-zero round key, deferred until the end)
+    let mut state = vaesdq_u8(b, vdupq_n_u8(0));
+
+    // AES add round key (bitwise XOR)
+    state = veorq_u8(state, k);
+
+    vst1q_u8(block.as_mut_ptr(), state);
+}
+
+/// AES equivalent inverse cipher (decrypt) final round function: parallel version.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn equiv_inv_cipher_round_last_par(
+    blocks: &mut ParBlocks,
+    round_keys: &ParBlocks,
+) {
+    for i in 0..8 {
+        let mut state = vld1q_u8(blocks[i].as_ptr());
+
+        // AES single round decryption (all-zero round key, deferred until the end)
+        state = vaesdq_u8(state, vdupq_n_u8(0));
+
+        // AES add round key (bitwise XOR)
+        state = veorq_u8(state, vld1q_u8(round_keys[i].as_ptr()));
+
+        vst1q_u8(blocks[i].as_mut_ptr(), state);
+    }
+}
+
 /// AES mix columns function.
 #[allow(clippy::cast_ptr_alignment)]
 #[target_feature(enable = "aes")]
@@ -102,3 +172,23 @@ pub(crate) unsafe fn inv_mix_columns(block: &mut Block) {
     let out = vaesimcq_u8(b);
     vst1q_u8(block.as_mut_ptr(), out);
 }
+
+/// AES key expansion: writes the round keys for a 16, 24 or 32-byte `key`.
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn expand_key(key: &[u8], round_keys: &mut [Block]) {
+    let mut expanded_keys = [vdupq_n_u8(0); 15];
+
+    match key.len() {
+        16 => expanded_keys[..11]
+            .copy_from_slice(&expand::expand_key::<16, 11>(key.try_into().unwrap())),
+        24 => expanded_keys[..13]
+            .copy_from_slice(&expand::expand_key::<24, 13>(key.try_into().unwrap())),
+        32 => expanded_keys[..15]
+            .copy_from_slice(&expand::expand_key::<32, 15>(key.try_into().unwrap())),
+        _ => unreachable!("invalid AES key size"),
+    }
+
+    for (block, expanded_key) in round_keys.iter_mut().zip(expanded_keys.iter()) {
+        vst1q_u8(block.as_mut_ptr(), *expanded_key);
+    }
+}
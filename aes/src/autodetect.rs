@@ -1,7 +1,7 @@
 //! Autodetection support for hardware accelerated AES backends with fallback
 //! to the fixsliced "soft" implementation.
 
-use crate::{soft, Block, ParBlocks};
+use crate::{backend::is_soft_forced, soft, Backend, Block, ParBlocks};
 use cipher::{
     consts::{U16, U24, U32, U8},
     generic_array::GenericArray,
@@ -17,27 +17,21 @@ use crate::ni as intrinsics;
 
 cpufeatures::new!(aes_intrinsics, "aes");
 
-macro_rules! define_aes_impl {
+macro_rules! define_cipher {
     (
-        $name:tt,
-        $module:tt,
+        $name:ident,
+        $module:ident,
         $key_size:ty,
-        $doc:expr
+        $rounds:expr,
+        $doc:expr,
+        $kind:expr
     ) => {
         #[doc=$doc]
+        #[doc=$kind]
         pub struct $name {
-            inner: $module::Inner,
-            token: aes_intrinsics::InitToken,
-        }
-
-        mod $module {
-            use super::{intrinsics, soft};
-            use core::mem::ManuallyDrop;
-
-            pub(super) union Inner {
-                pub(super) intrinsics: ManuallyDrop<intrinsics::$name>,
-                pub(super) soft: ManuallyDrop<soft::$name>,
-            }
+            inner: $module::$name,
+            /// CPU intrinsics are detected and not disabled for the instance
+            intrinsics: bool,
         }
 
         impl NewBlockCipher for $name {
@@ -45,37 +39,107 @@ macro_rules! define_aes_impl {
 
             #[inline]
             fn new(key: &GenericArray<u8, $key_size>) -> Self {
-                let (token, aesni_present) = aes_intrinsics::init_get();
-
-                let inner = if aesni_present {
-                    $module::Inner {
-                        intrinsics: ManuallyDrop::new(intrinsics::$name::new(key)),
+                if aes_intrinsics::get() && !is_soft_forced() {
+                    Self {
+                        inner: $module::$name {
+                            intrinsics: ManuallyDrop::new(intrinsics::$name::new(key)),
+                        },
+                        intrinsics: true,
                     }
                 } else {
-                    $module::Inner {
+                    Self::new_soft(key)
+                }
+            }
+        }
+
+        impl $name {
+            /// Create a cipher instance which uses the "soft" backend
+            /// regardless of the available CPU intrinsics.
+            pub fn new_soft(key: &GenericArray<u8, $key_size>) -> Self {
+                Self {
+                    inner: $module::$name {
                         soft: ManuallyDrop::new(soft::$name::new(key)),
+                    },
+                    intrinsics: false,
+                }
+            }
+
+            /// Backend used by this instance.
+            pub fn backend(&self) -> Backend {
+                if self.intrinsics {
+                    unsafe { self.inner.intrinsics.backend() }
+                } else {
+                    Backend::Soft
+                }
+            }
+        }
+
+        #[cfg(feature = "hazmat")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+        impl $name {
+            /// ⚠️ Create a cipher instance from round keys in the FIPS 197 form.
+            ///
+            /// The round keys are expected in the order computed by
+            /// `KeyExpansion()`, e.g. as returned by the key expansion
+            /// functions of the [`hazmat`][crate::hazmat] module.
+            ///
+            /// # ☢️️ WARNING: HAZARDOUS API ☢️
+            ///
+            /// Use this function with great care! See the [module-level documentation][crate::hazmat]
+            /// for more information.
+            pub fn from_round_keys(round_keys: &[Block; $rounds]) -> Self {
+                if aes_intrinsics::get() && !is_soft_forced() {
+                    let intrinsics = intrinsics::$name::from_round_keys(round_keys);
+                    Self {
+                        inner: $module::$name {
+                            intrinsics: ManuallyDrop::new(intrinsics),
+                        },
+                        intrinsics: true,
                     }
-                };
+                } else {
+                    Self {
+                        inner: $module::$name {
+                            soft: ManuallyDrop::new(soft::$name::from_round_keys(round_keys)),
+                        },
+                        intrinsics: false,
+                    }
+                }
+            }
 
-                Self { inner, token }
+            /// ⚠️ Round keys of this instance in the FIPS 197 form.
+            ///
+            /// Returns the round keys computed by `KeyExpansion()`, which
+            /// are the encryption round keys even for a decrypt-only
+            /// instance.
+            ///
+            /// # ☢️️ WARNING: HAZARDOUS API ☢️
+            ///
+            /// Use this function with great care! See the [module-level documentation][crate::hazmat]
+            /// for more information.
+            pub fn round_keys(&self) -> [Block; $rounds] {
+                if self.intrinsics {
+                    unsafe { self.inner.intrinsics.round_keys() }
+                } else {
+                    unsafe { self.inner.soft.round_keys() }
+                }
             }
         }
 
         impl Clone for $name {
             fn clone(&self) -> Self {
-                let inner = if self.token.get() {
-                    $module::Inner {
+                let inner = if self.intrinsics {
+                    $module::$name {
                         intrinsics: unsafe { self.inner.intrinsics.clone() },
                     }
                 } else {
-                    $module::Inner {
+                    $module::$name {
                         soft: unsafe { self.inner.soft.clone() },
                     }
                 };
 
                 Self {
                     inner,
-                    token: self.token,
+                    intrinsics: self.intrinsics,
                 }
             }
         }
@@ -85,10 +149,16 @@ macro_rules! define_aes_impl {
             type ParBlocks = U8;
         }
 
+        opaque_debug::implement!($name);
+    };
+}
+
+macro_rules! impl_encrypt {
+    ($name:ident) => {
         impl BlockEncrypt for $name {
             #[inline]
             fn encrypt_block(&self, block: &mut Block) {
-                if self.token.get() {
+                if self.intrinsics {
                     unsafe { self.inner.intrinsics.encrypt_block(block) }
                 } else {
                     unsafe { self.inner.soft.encrypt_block(block) }
@@ -97,18 +167,31 @@ macro_rules! define_aes_impl {
 
             #[inline]
             fn encrypt_par_blocks(&self, blocks: &mut ParBlocks) {
-                if self.token.get() {
+                if self.intrinsics {
                     unsafe { self.inner.intrinsics.encrypt_par_blocks(blocks) }
                 } else {
                     unsafe { self.inner.soft.encrypt_par_blocks(blocks) }
                 }
             }
+
+            #[inline]
+            fn encrypt_blocks(&self, blocks: &mut [Block]) {
+                if self.intrinsics {
+                    unsafe { self.inner.intrinsics.encrypt_blocks(blocks) }
+                } else {
+                    unsafe { self.inner.soft.encrypt_blocks(blocks) }
+                }
+            }
         }
+    };
+}
 
+macro_rules! impl_decrypt {
+    ($name:ident) => {
         impl BlockDecrypt for $name {
             #[inline]
             fn decrypt_block(&self, block: &mut Block) {
-                if self.token.get() {
+                if self.intrinsics {
                     unsafe { self.inner.intrinsics.decrypt_block(block) }
                 } else {
                     unsafe { self.inner.soft.decrypt_block(block) }
@@ -117,21 +200,119 @@ macro_rules! define_aes_impl {
 
             #[inline]
             fn decrypt_par_blocks(&self, blocks: &mut ParBlocks) {
-                if self.token.get() {
+                if self.intrinsics {
                     unsafe { self.inner.intrinsics.decrypt_par_blocks(blocks) }
                 } else {
                     unsafe { self.inner.soft.decrypt_par_blocks(blocks) }
                 }
             }
+
+            #[inline]
+            fn decrypt_blocks(&self, blocks: &mut [Block]) {
+                if self.intrinsics {
+                    unsafe { self.inner.intrinsics.decrypt_blocks(blocks) }
+                } else {
+                    unsafe { self.inner.soft.decrypt_blocks(blocks) }
+                }
+            }
         }
+    };
+}
 
-        opaque_debug::implement!($name);
+macro_rules! define_aes_impl {
+    (
+        $name:ident,
+        $name_enc:ident,
+        $name_dec:ident,
+        $module:ident,
+        $key_size:ty,
+        $rounds:expr,
+        $doc:expr
+    ) => {
+        mod $module {
+            use super::{intrinsics, soft};
+            use core::mem::ManuallyDrop;
+
+            pub(super) union $name {
+                pub(super) intrinsics: ManuallyDrop<intrinsics::$name>,
+                pub(super) soft: ManuallyDrop<soft::$name>,
+            }
+
+            pub(super) union $name_enc {
+                pub(super) intrinsics: ManuallyDrop<intrinsics::$name_enc>,
+                pub(super) soft: ManuallyDrop<soft::$name_enc>,
+            }
+
+            pub(super) union $name_dec {
+                pub(super) intrinsics: ManuallyDrop<intrinsics::$name_dec>,
+                pub(super) soft: ManuallyDrop<soft::$name_dec>,
+            }
+        }
+
+        define_cipher!(
+            $name,
+            $module,
+            $key_size,
+            $rounds,
+            $doc,
+            "block cipher instance"
+        );
+        define_cipher!(
+            $name_enc,
+            $module,
+            $key_size,
+            $rounds,
+            $doc,
+            "block cipher instance (encrypt-only)"
+        );
+        define_cipher!(
+            $name_dec,
+            $module,
+            $key_size,
+            $rounds,
+            $doc,
+            "block cipher instance (decrypt-only)"
+        );
+
+        impl_encrypt!($name);
+        impl_encrypt!($name_enc);
+        impl_decrypt!($name);
+        impl_decrypt!($name_dec);
+
+        impl From<$name_enc> for $name_dec {
+            #[inline]
+            fn from(enc: $name_enc) -> $name_dec {
+                Self::from(&enc)
+            }
+        }
+
+        impl From<&$name_enc> for $name_dec {
+            #[inline]
+            fn from(enc: &$name_enc) -> $name_dec {
+                let inner = if enc.intrinsics {
+                    let dec = intrinsics::$name_dec::from(unsafe { &*enc.inner.intrinsics });
+                    $module::$name_dec {
+                        intrinsics: ManuallyDrop::new(dec),
+                    }
+                } else {
+                    let dec = soft::$name_dec::from(unsafe { &*enc.inner.soft });
+                    $module::$name_dec {
+                        soft: ManuallyDrop::new(dec),
+                    }
+                };
+
+                Self {
+                    inner,
+                    intrinsics: enc.intrinsics,
+                }
+            }
+        }
     };
 }
 
-define_aes_impl!(Aes128, aes128, U16, "AES-128 block cipher instance");
-define_aes_impl!(Aes192, aes192, U24, "AES-192 block cipher instance");
-define_aes_impl!(Aes256, aes256, U32, "AES-256 block cipher instance");
+define_aes_impl!(Aes128, Aes128Enc, Aes128Dec, aes128, U16, 11, "AES-128");
+define_aes_impl!(Aes192, Aes192Enc, Aes192Dec, aes192, U24, 13, "AES-192");
+define_aes_impl!(Aes256, Aes256Enc, Aes256Dec, aes256, U32, 15, "AES-256");
 
 #[cfg(all(feature = "ctr", target_arch = "aarch64"))]
 pub(crate) mod ctr {
@@ -150,7 +331,7 @@ pub(crate) mod ctr {
 #[cfg(all(feature = "ctr", any(target_arch = "x86_64", target_arch = "x86")))]
 pub(crate) mod ctr {
     use super::{Aes128, Aes192, Aes256};
-    use crate::{ni, soft};
+    use crate::ni;
     use cipher::{
         errors::{LoopError, OverflowError},
         generic_array::GenericArray,
@@ -171,16 +352,17 @@ pub(crate) mod ctr {
             #[cfg_attr(docsrs, doc(cfg(feature = "ctr")))]
             pub struct $name {
                 inner: $module::Inner,
-                token: aes_ssse3_cpuid::InitToken,
+                ni: bool,
             }
 
             mod $module {
-                use crate::{ni, soft};
+                use super::$cipher;
+                use crate::ni;
                 use core::mem::ManuallyDrop;
 
                 pub(super) union Inner {
                     pub(super) ni: ManuallyDrop<ni::$name>,
-                    pub(super) soft: ManuallyDrop<soft::$name>,
+                    pub(super) soft: ManuallyDrop<::ctr::Ctr64BE<$cipher>>,
                 }
             }
 
@@ -192,9 +374,10 @@ pub(crate) mod ctr {
                     cipher: $cipher,
                     nonce: &GenericArray<u8, Self::NonceSize>,
                 ) -> Self {
-                    let (token, aesni_present) = aes_ssse3_cpuid::init_get();
-
-                    let inner = if aesni_present {
+                    // the AES-NI counter mode also requires SSSE3, otherwise
+                    // the portable counter mode is used with the block cipher
+                    let ni = cipher.intrinsics && aes_ssse3_cpuid::get();
+                    let inner = if ni {
                         let ni = ni::$name::from_block_cipher(
                             unsafe { (*cipher.inner.intrinsics).clone() },
                             nonce,
@@ -204,24 +387,21 @@ pub(crate) mod ctr {
                             ni: ManuallyDrop::new(ni),
                         }
                     } else {
-                        let soft = soft::$name::from_block_cipher(
-                            unsafe { (*cipher.inner.soft).clone() },
-                            nonce,
-                        );
+                        let soft = ::ctr::Ctr64BE::from_block_cipher(cipher, nonce);
 
                         $module::Inner {
                             soft: ManuallyDrop::new(soft),
                         }
                     };
 
-                    Self { inner, token }
+                    Self { inner, ni }
                 }
             }
 
             impl StreamCipher for $name {
                 #[inline]
                 fn try_apply_keystream(&mut self, data: &mut [u8]) -> Result<(), LoopError> {
-                    if self.token.get() {
+                    if self.ni {
                         unsafe { (*self.inner.ni).try_apply_keystream(data) }
                     } else {
                         unsafe { (*self.inner.soft).try_apply_keystream(data) }
@@ -232,7 +412,7 @@ pub(crate) mod ctr {
             impl StreamCipherSeek for $name {
                 #[inline]
                 fn try_current_pos<T: SeekNum>(&self) -> Result<T, OverflowError> {
-                    if self.token.get() {
+                    if self.ni {
                         unsafe { (*self.inner.ni).try_current_pos() }
                     } else {
                         unsafe { (*self.inner.soft).try_current_pos() }
@@ -241,7 +421,7 @@ pub(crate) mod ctr {
 
                 #[inline]
                 fn try_seek<T: SeekNum>(&mut self, pos: T) -> Result<(), LoopError> {
-                    if self.token.get() {
+                    if self.ni {
                         unsafe { (*self.inner.ni).try_seek(pos) }
                     } else {
                         unsafe { (*self.inner.soft).try_seek(pos) }
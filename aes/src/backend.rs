@@ -0,0 +1,61 @@
+//! Backend introspection and runtime override.
+
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static FORCE_SOFT: AtomicBool = AtomicBool::new(false);
+
+/// AES implementation used by a cipher instance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Constant-time portable implementation based on fixslicing.
+    Soft,
+    /// AES-NI instructions on `x86`/`x86_64`.
+    AesNi,
+    /// AES-NI with the VAES instructions on 256-bit AVX2 registers.
+    VaesAvx2,
+    /// AES-NI with the VAES instructions on 512-bit AVX-512 registers.
+    VaesAvx512,
+    /// ARMv8 Cryptography Extensions.
+    Armv8,
+}
+
+impl Backend {
+    /// Short lowercase name of the backend, e.g. `"aes-ni"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Soft => "soft",
+            Backend::AesNi => "aes-ni",
+            Backend::VaesAvx2 => "vaes-avx2",
+            Backend::VaesAvx512 => "vaes-avx512",
+            Backend::Armv8 => "armv8",
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.name())
+    }
+}
+
+/// Force the "soft" backend for all ciphers created after this call.
+///
+/// Instances which already exist keep their backend. Functions in the
+/// `hazmat` module check the setting on every call. Passing `false`
+/// restores runtime detection of CPU intrinsics.
+///
+/// This setting has no effect if the crate is built with the `force-soft`
+/// feature or for a target without hardware AES support, where the "soft"
+/// backend is always used.
+pub fn set_force_soft(force: bool) {
+    FORCE_SOFT.store(force, Ordering::Relaxed);
+}
+
+/// Returns `true` if the "soft" backend was forced by [`set_force_soft`].
+pub fn is_soft_forced() -> bool {
+    FORCE_SOFT.load(Ordering::Relaxed)
+}
@@ -10,8 +10,18 @@
 //!
 //! We do NOT recommending using it to implement any algorithm which has not
 //! received extensive peer review by cryptographers.
+//!
+//! The key expansion functions return round keys in the form described in
+//! FIPS 197. With this feature the cipher types can also be created from
+//! such round keys, e.g. a key schedule provided by an HSM, using
+//! [`Aes128::from_round_keys`][crate::Aes128::from_round_keys] and export
+//! them using [`Aes128::round_keys`][crate::Aes128::round_keys].
 
 use crate::{soft::fixslice::hazmat as soft, Block, ParBlocks};
+use cipher::{
+    consts::{U16, U24, U32},
+    generic_array::GenericArray,
+};
 
 #[cfg(all(
     target_arch = "aarch64",
@@ -48,7 +58,7 @@ macro_rules! if_intrinsics_available {
             ),
             not(feature = "force-soft")
         ))]
-        if aes_intrinsics::get() {
+        if aes_intrinsics::get() && !crate::is_soft_forced() {
             unsafe { $body }
             return;
         }
@@ -135,6 +145,89 @@ pub fn equiv_inv_cipher_round_par(blocks: &mut ParBlocks, round_keys: &ParBlocks
     soft::equiv_inv_cipher_round_par(blocks, round_keys);
 }
 
+/// ⚠️ AES cipher (encrypt) final round function.
+///
+/// This API performs the following steps of the last round as described in
+/// FIPS 197 Appendix C:
+///
+/// - `s_box`: state after `SubBytes()`
+/// - `s_row`: state after `ShiftRows()`
+/// - `k_sch`: key schedule value for `round[Nr]`
+///
+/// The `MixColumns()` step of [`cipher_round`] is omitted. This series of
+/// operations is equivalent to the Intel AES-NI `AESENCLAST` instruction.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn cipher_round_last(block: &mut Block, round_key: &Block) {
+    if_intrinsics_available! {
+        intrinsics::cipher_round_last(block, round_key)
+    }
+
+    soft::cipher_round_last(block, round_key);
+}
+
+/// ⚠️ AES cipher (encrypt) final round function: parallel version.
+///
+/// Equivalent to [`cipher_round_last`], but acts on 8 blocks-at-a-time,
+/// applying the same number of round keys.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn cipher_round_last_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+    if_intrinsics_available! {
+        intrinsics::cipher_round_last_par(blocks, round_keys)
+    }
+
+    soft::cipher_round_last_par(blocks, round_keys);
+}
+
+/// ⚠️ AES equivalent inverse cipher (decrypt) final round function.
+///
+/// This API performs the following steps of the last round as described in
+/// FIPS 197 Appendix C:
+///
+/// - `is_box`: state after `InvSubBytes()`
+/// - `is_row`: state after `InvShiftRows()`
+/// - `ik_sch`: key schedule value for `round[Nr]`
+///
+/// The `InvMixColumns()` step of [`equiv_inv_cipher_round`] is omitted. This
+/// series of operations is equivalent to the Intel AES-NI `AESDECLAST`
+/// instruction.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn equiv_inv_cipher_round_last(block: &mut Block, round_key: &Block) {
+    if_intrinsics_available! {
+        intrinsics::equiv_inv_cipher_round_last(block, round_key)
+    }
+
+    soft::equiv_inv_cipher_round_last(block, round_key);
+}
+
+/// ⚠️ AES equivalent inverse cipher (decrypt) final round function: parallel version.
+///
+/// Equivalent to [`equiv_inv_cipher_round_last`], but acts on 8
+/// blocks-at-a-time, applying the same number of round keys.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn equiv_inv_cipher_round_last_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+    if_intrinsics_available! {
+        intrinsics::equiv_inv_cipher_round_last_par(blocks, round_keys)
+    }
+
+    soft::equiv_inv_cipher_round_last_par(blocks, round_keys);
+}
+
 /// ⚠️ AES mix columns function.
 ///
 /// # ☢️️ WARNING: HAZARDOUS API ☢️
@@ -164,3 +257,83 @@ pub fn inv_mix_columns(block: &mut Block) {
 
     soft::inv_mix_columns(block);
 }
+
+/// ⚠️ AES-128 key expansion.
+///
+/// Returns the 11 round keys `w[0..=43]` computed by `KeyExpansion()` as
+/// described in FIPS 197 Section 5.2, where `round_keys[r]` is the `k_sch`
+/// value for `round[r]` of the cipher.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn aes128_expand_key(key: &GenericArray<u8, U16>) -> [Block; 11] {
+    let mut round_keys = [Block::default(); 11];
+    expand_key(key, &mut round_keys);
+    round_keys
+}
+
+/// ⚠️ AES-192 key expansion.
+///
+/// Returns the 13 round keys `w[0..=51]` computed by `KeyExpansion()` as
+/// described in FIPS 197 Section 5.2, where `round_keys[r]` is the `k_sch`
+/// value for `round[r]` of the cipher.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn aes192_expand_key(key: &GenericArray<u8, U24>) -> [Block; 13] {
+    let mut round_keys = [Block::default(); 13];
+    expand_key(key, &mut round_keys);
+    round_keys
+}
+
+/// ⚠️ AES-256 key expansion.
+///
+/// Returns the 15 round keys `w[0..=59]` computed by `KeyExpansion()` as
+/// described in FIPS 197 Section 5.2, where `round_keys[r]` is the `k_sch`
+/// value for `round[r]` of the cipher.
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn aes256_expand_key(key: &GenericArray<u8, U32>) -> [Block; 15] {
+    let mut round_keys = [Block::default(); 15];
+    expand_key(key, &mut round_keys);
+    round_keys
+}
+
+/// ⚠️ Compute the round keys of the equivalent inverse cipher in-place.
+///
+/// Converts the output of [`aes128_expand_key`], [`aes192_expand_key`] or
+/// [`aes256_expand_key`] into the decryption key schedule `dw` described in
+/// FIPS 197 Section 5.3.5: the round keys are reversed and `InvMixColumns()`
+/// is applied to all of them except the first and the last. The result
+/// provides the `ik_sch` values for [`equiv_inv_cipher_round`] and
+/// [`equiv_inv_cipher_round_last`].
+///
+/// # ☢️️ WARNING: HAZARDOUS API ☢️
+///
+/// Use this function with great care! See the [module-level documentation][crate::hazmat]
+/// for more information.
+pub fn inv_expanded_keys(round_keys: &mut [Block]) {
+    let n = round_keys.len();
+
+    for round_key in round_keys.iter_mut().take(n.saturating_sub(1)).skip(1) {
+        inv_mix_columns(round_key);
+    }
+
+    round_keys.reverse();
+}
+
+/// Expand `key` into `round_keys` using the available backend.
+fn expand_key(key: &[u8], round_keys: &mut [Block]) {
+    if_intrinsics_available! {
+        intrinsics::expand_key(key, round_keys)
+    }
+
+    soft::expand_key(key, round_keys);
+}
@@ -42,6 +42,92 @@
 //! Note: runtime detection is not possible on SGX targets. Please use the
 //! afforementioned `RUSTFLAGS` to leverage AES-NI on these targets.
 //!
+//! ## `x86`/`x86_64` VAES intrinsics
+//! The `vaes` crate feature enables use of the VAES instructions, which apply
+//! AES rounds to all 128-bit lanes of 256-bit (AVX2) or 512-bit (AVX-512)
+//! registers. Availability of VAES is detected at runtime in addition to
+//! AES-NI, and the widest supported registers are used by
+//! `encrypt_par_blocks`/`decrypt_par_blocks`, by `encrypt_blocks` and
+//! `decrypt_blocks`, which keep 16 or 32 blocks in flight, by the CTR mode
+//! implementation and by the parallel `hazmat` round functions.
+//!
+//! This feature requires Rust 1.89 or newer.
+//!
+//! ## Runtime override
+//! The [`Backend`] used by a cipher instance is returned by its `backend`
+//! method. Instances created with `new_soft` always use the "soft" backend,
+//! and [`set_force_soft`] makes all ciphers created afterwards, as well as
+//! the `hazmat` functions, use it. This allows to compare backends or to
+//! avoid the use of AES instructions without building the crate with the
+//! `force-soft` feature.
+//!
+//! ```
+//! use aes::{Aes128, Backend, NewBlockCipher};
+//! use aes::cipher::generic_array::GenericArray;
+//!
+//! let key = GenericArray::from_slice(&[0u8; 16]);
+//! assert_eq!(Aes128::new_soft(&key).backend(), Backend::Soft);
+//!
+//! aes::set_force_soft(true);
+//! assert_eq!(Aes128::new(&key).backend(), Backend::Soft);
+//! aes::set_force_soft(false);
+//! ```
+//!
+//! # Encrypt-only and decrypt-only ciphers
+//! Modes such as CTR, CFB and OFB only use the encryption function of the
+//! block cipher. [`Aes128Enc`], [`Aes192Enc`] and [`Aes256Enc`] implement
+//! only `BlockEncrypt` and skip computation of the decryption round keys,
+//! while [`Aes128Dec`], [`Aes192Dec`] and [`Aes256Dec`] implement only
+//! `BlockDecrypt` and can be created from an encrypt-only instance with
+//! `From`. The fixsliced "soft" backend uses the same key schedule for both
+//! directions, so its encrypt-only and decrypt-only types store the full
+//! key schedule.
+//!
+//! # Masked implementation
+//! The fixsliced backend avoids timing side channels, but the power
+//! consumption or electromagnetic emanation of a device still depends on the
+//! processed values. The `masked` crate feature adds `MaskedAes128`,
+//! `MaskedAes192` and `MaskedAes256`, which use first-order Boolean
+//! masking of the fixsliced state as a countermeasure against differential
+//! power analysis (DPA):
+//!
+//! - the bitsliced plaintext is split into two random shares,
+//! - linear layers are applied to each share,
+//! - the S-box is computed on the shares, with every AND gate replaced by a
+//!   masked multiplication which consumes a fresh random word.
+//!
+//! Masks are drawn from a caller-supplied [`rand_core`][rand_core] RNG, e.g. the
+//! hardware TRNG of a microcontroller. Every round consumes one random word
+//! (`u32` or `u64` depending on the target) for each of the 32 (34 for
+//! decryption) AND gates of the S-box per two or four blocks, so the RNG must
+//! be fast enough for the required throughput.
+//!
+//! The masked ciphers are always implemented in software, regardless of the
+//! available CPU intrinsics. The key schedule is not masked, and neither the
+//! compiler nor the hardware is guaranteed to preserve the separation of the
+//! shares: the leakage of the resulting binary has to be assessed on the
+//! target device.
+//!
+//! ```
+//! # #[cfg(feature = "masked")]
+//! # mod example {
+//! use aes::{Block, MaskedAes128};
+//! use aes::cipher::{BlockEncrypt, generic_array::GenericArray};
+//! use rand_core::{CryptoRng, RngCore};
+//!
+//! fn encrypt<R: CryptoRng + RngCore>(key: &[u8; 16], block: &mut Block, trng: R) {
+//!     let cipher = MaskedAes128::new_with_rng(GenericArray::from_slice(key), trng);
+//!     cipher.encrypt_block(block);
+//! }
+//! # }
+//! ```
+//!
+//! # Self-test
+//! [`self_test`] runs the FIPS 197 known answer tests against the backend
+//! selected at runtime and returns [`SelfTestError`] if any of them fails.
+//! Applications which must not operate with a faulty implementation can
+//! call it on startup.
+//!
 //! # Usage example
 //! ```
 //! use aes::{Aes128, Block, ParBlocks};
@@ -78,6 +164,7 @@
 //! [`block-modes`] crate.
 //!
 //! [fixslicing]: https://eprint.iacr.org/2020/1123.pdf
+//! [rand_core]: https://docs.rs/rand_core
 //! [AES-NI]: https://en.wikipedia.org/wiki/AES_instruction_set
 //! [`block-modes`]: https://docs.rs/block-modes
 
@@ -96,6 +183,10 @@
 #[cfg(feature = "hazmat")]
 pub mod hazmat;
 
+mod backend;
+#[cfg(feature = "masked")]
+mod masked;
+mod self_test;
 mod soft;
 
 use cfg_if::cfg_if;
@@ -104,7 +195,10 @@ cfg_if! {
     if #[cfg(all(target_arch = "aarch64", feature = "armv8", not(feature = "force-soft")))] {
         mod armv8;
         mod autodetect;
-        pub use autodetect::{Aes128, Aes192, Aes256};
+        pub use autodetect::{
+            Aes128, Aes128Dec, Aes128Enc, Aes192, Aes192Dec, Aes192Enc, Aes256, Aes256Dec,
+            Aes256Enc,
+        };
 
         #[cfg(feature = "ctr")]
         pub use autodetect::ctr::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
@@ -114,18 +208,29 @@ cfg_if! {
     ))] {
         mod autodetect;
         mod ni;
-        pub use autodetect::{Aes128, Aes192, Aes256};
+        pub use autodetect::{
+            Aes128, Aes128Dec, Aes128Enc, Aes192, Aes192Dec, Aes192Enc, Aes256, Aes256Dec,
+            Aes256Enc,
+        };
 
         #[cfg(feature = "ctr")]
         pub use autodetect::ctr::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
     } else {
-        pub use soft::{Aes128, Aes192, Aes256};
+        pub use soft::{
+            Aes128, Aes128Dec, Aes128Enc, Aes192, Aes192Dec, Aes192Enc, Aes256, Aes256Dec,
+            Aes256Enc,
+        };
 
         #[cfg(feature = "ctr")]
         pub use soft::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
     }
 }
 
+pub use crate::backend::{is_soft_forced, set_force_soft, Backend};
+#[cfg(feature = "masked")]
+pub use crate::masked::{MaskedAes128, MaskedAes192, MaskedAes256};
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 pub use cipher::{self, BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher};
 
 /// 128-bit AES block
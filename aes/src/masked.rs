@@ -0,0 +1,139 @@
+//! First-order masked AES software implementation.
+//!
+//! The ciphers of this module use the fixsliced "soft" backend with the
+//! state split into two Boolean shares, which are refreshed by the S-box
+//! with randomness drawn from a caller-supplied RNG.
+
+use crate::{
+    soft::fixslice::{
+        self, masked, FixsliceKeys128, FixsliceKeys192, FixsliceKeys256, FIXSLICE_BLOCKS,
+    },
+    Block, ParBlocks,
+};
+use cipher::{
+    consts::{U16, U24, U32, U8},
+    generic_array::GenericArray,
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use core::{cell::RefCell, fmt};
+use rand_core::{CryptoRng, RngCore};
+
+macro_rules! define_masked_aes_impl {
+    (
+        $name:ident,
+        $key_size:ty,
+        $fixslice_keys:ty,
+        $fixslice_key_schedule:path,
+        $masked_decrypt:path,
+        $masked_encrypt:path,
+        $doc:expr
+    ) => {
+        #[doc=$doc]
+        #[doc = "block cipher with first-order masking"]
+        ///
+        /// Masks are drawn from the RNG of type `R` on every call of the
+        /// encryption and decryption functions.
+        #[cfg_attr(docsrs, doc(cfg(feature = "masked")))]
+        pub struct $name<R: CryptoRng + RngCore> {
+            keys: $fixslice_keys,
+            rng: RefCell<R>,
+        }
+
+        impl<R: CryptoRng + RngCore> $name<R> {
+            /// Create a cipher instance which draws the masks from `rng`.
+            pub fn new_with_rng(key: &GenericArray<u8, $key_size>, rng: R) -> Self {
+                Self {
+                    keys: $fixslice_key_schedule(key),
+                    rng: RefCell::new(rng),
+                }
+            }
+        }
+
+        impl<R: CryptoRng + RngCore + Default> NewBlockCipher for $name<R> {
+            type KeySize = $key_size;
+
+            /// Create a cipher instance using `R::default()` as RNG.
+            #[inline]
+            fn new(key: &GenericArray<u8, $key_size>) -> Self {
+                Self::new_with_rng(key, R::default())
+            }
+        }
+
+        impl<R: CryptoRng + RngCore> BlockCipher for $name<R> {
+            type BlockSize = U16;
+            type ParBlocks = U8;
+        }
+
+        impl<R: CryptoRng + RngCore> BlockEncrypt for $name<R> {
+            #[inline]
+            fn encrypt_block(&self, block: &mut Block) {
+                let mut blocks = [Block::default(); FIXSLICE_BLOCKS];
+                blocks[0].copy_from_slice(block);
+                $masked_encrypt(&self.keys, &mut blocks, &mut *self.rng.borrow_mut());
+                block.copy_from_slice(&blocks[0]);
+            }
+
+            #[inline]
+            fn encrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+                let mut rng = self.rng.borrow_mut();
+                for chunk in blocks.chunks_mut(FIXSLICE_BLOCKS) {
+                    $masked_encrypt(&self.keys, chunk, &mut *rng);
+                }
+            }
+        }
+
+        impl<R: CryptoRng + RngCore> BlockDecrypt for $name<R> {
+            #[inline]
+            fn decrypt_block(&self, block: &mut Block) {
+                let mut blocks = [Block::default(); FIXSLICE_BLOCKS];
+                blocks[0].copy_from_slice(block);
+                $masked_decrypt(&self.keys, &mut blocks, &mut *self.rng.borrow_mut());
+                block.copy_from_slice(&blocks[0]);
+            }
+
+            #[inline]
+            fn decrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+                let mut rng = self.rng.borrow_mut();
+                for chunk in blocks.chunks_mut(FIXSLICE_BLOCKS) {
+                    $masked_decrypt(&self.keys, chunk, &mut *rng);
+                }
+            }
+        }
+
+        impl<R: CryptoRng + RngCore> fmt::Debug for $name<R> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                f.write_str(concat!(stringify!($name), " { ... }"))
+            }
+        }
+    };
+}
+
+define_masked_aes_impl!(
+    MaskedAes128,
+    U16,
+    FixsliceKeys128,
+    fixslice::aes128_key_schedule,
+    masked::aes128_decrypt,
+    masked::aes128_encrypt,
+    "AES-128"
+);
+
+define_masked_aes_impl!(
+    MaskedAes192,
+    U24,
+    FixsliceKeys192,
+    fixslice::aes192_key_schedule,
+    masked::aes192_decrypt,
+    masked::aes192_encrypt,
+    "AES-192"
+);
+
+define_masked_aes_impl!(
+    MaskedAes256,
+    U32,
+    FixsliceKeys256,
+    fixslice::aes256_key_schedule,
+    masked::aes256_decrypt,
+    masked::aes256_encrypt,
+    "AES-256"
+);
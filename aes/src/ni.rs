@@ -34,12 +34,19 @@ mod ctr;
 #[cfg(feature = "hazmat")]
 pub(crate) mod hazmat;
 
+#[cfg(feature = "vaes")]
+mod vaes;
+
 #[cfg(target_arch = "x86")]
 use core::arch::x86 as arch;
 #[cfg(target_arch = "x86_64")]
 use core::arch::x86_64 as arch;
 
-pub use self::{aes128::Aes128, aes192::Aes192, aes256::Aes256};
+pub use self::{
+    aes128::{Aes128, Aes128Dec, Aes128Enc},
+    aes192::{Aes192, Aes192Dec, Aes192Enc},
+    aes256::{Aes256, Aes256Dec, Aes256Enc},
+};
 
 #[cfg(feature = "ctr")]
 pub use self::ctr::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
@@ -1,15 +1,23 @@
+#[cfg(feature = "hazmat")]
+use super::utils::{fwd_expanded_keys, load_round_keys, store_round_keys};
+#[cfg(feature = "vaes")]
+use super::vaes;
 use super::{
     arch::*,
-    utils::{aesdec8, aesdeclast8, aesenc8, aesenclast8, load8, store8, xor8, U128x8},
+    utils::{
+        aesdec8, aesdeclast8, aesenc8, aesenclast8, inv_expanded_keys, load8, store8, xor8, U128x8,
+    },
 };
-use crate::{Block, ParBlocks};
+use crate::{Backend, Block, ParBlocks};
 use cipher::{
     consts::{U16, U8},
     generic_array::GenericArray,
     BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
 };
+#[cfg(feature = "hazmat")]
+use core::mem;
 
-mod expand;
+pub(super) mod expand;
 #[cfg(test)]
 mod test_expand;
 
@@ -19,11 +27,125 @@ type RoundKeys = [__m128i; 11];
 /// AES-128 block cipher
 #[derive(Clone)]
 pub struct Aes128 {
-    encrypt_keys: RoundKeys,
-    decrypt_keys: RoundKeys,
+    encrypt: Aes128Enc,
+    decrypt: Aes128Dec,
 }
 
 impl Aes128 {
+    /// Backend used by this instance
+    pub(crate) fn backend(&self) -> Backend {
+        self.encrypt.backend()
+    }
+
+    #[cfg(all(feature = "vaes", feature = "ctr"))]
+    #[inline(always)]
+    pub(crate) fn vaes_ctr<'a>(&self, ctr: &mut __m128i, data: &'a mut [u8]) -> &'a mut [u8] {
+        self.encrypt.vaes_ctr(ctr, data)
+    }
+
+    #[cfg(feature = "ctr")]
+    #[inline(always)]
+    pub(crate) fn encrypt8(&self, blocks: U128x8) -> U128x8 {
+        self.encrypt.encrypt8(blocks)
+    }
+
+    #[cfg(feature = "ctr")]
+    #[inline(always)]
+    pub(crate) fn encrypt(&self, block: __m128i) -> __m128i {
+        self.encrypt.encrypt(block)
+    }
+}
+
+#[cfg(feature = "hazmat")]
+impl Aes128 {
+    /// Create a cipher instance from round keys in the FIPS 197 form
+    pub(crate) fn from_round_keys(round_keys: &[Block; 11]) -> Self {
+        let encrypt = Aes128Enc::from_round_keys(round_keys);
+        let decrypt = Aes128Dec::from(&encrypt);
+        Self { encrypt, decrypt }
+    }
+
+    /// Round keys in the FIPS 197 form
+    pub(crate) fn round_keys(&self) -> [Block; 11] {
+        self.encrypt.round_keys()
+    }
+}
+
+impl NewBlockCipher for Aes128 {
+    type KeySize = U16;
+
+    #[inline]
+    fn new(key: &GenericArray<u8, U16>) -> Self {
+        let encrypt = Aes128Enc::new(key);
+        let decrypt = Aes128Dec::from(&encrypt);
+        Self { encrypt, decrypt }
+    }
+}
+
+impl BlockCipher for Aes128 {
+    type BlockSize = U16;
+    type ParBlocks = U8;
+}
+
+impl BlockEncrypt for Aes128 {
+    #[inline]
+    fn encrypt_block(&self, block: &mut Block) {
+        self.encrypt.encrypt_block(block)
+    }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        self.encrypt.encrypt_par_blocks(blocks)
+    }
+
+    #[inline]
+    fn encrypt_blocks(&self, blocks: &mut [Block]) {
+        self.encrypt.encrypt_blocks(blocks)
+    }
+}
+
+impl BlockDecrypt for Aes128 {
+    #[inline]
+    fn decrypt_block(&self, block: &mut Block) {
+        self.decrypt.decrypt_block(block)
+    }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        self.decrypt.decrypt_par_blocks(blocks)
+    }
+
+    #[inline]
+    fn decrypt_blocks(&self, blocks: &mut [Block]) {
+        self.decrypt.decrypt_blocks(blocks)
+    }
+}
+
+/// AES-128 block cipher (encrypt-only)
+#[derive(Clone)]
+pub struct Aes128Enc {
+    round_keys: RoundKeys,
+    #[cfg(feature = "vaes")]
+    vaes: vaes::Level,
+}
+
+impl Aes128Enc {
+    /// Backend used by this instance
+    pub(crate) fn backend(&self) -> Backend {
+        #[cfg(feature = "vaes")]
+        return self.vaes.backend();
+        #[cfg(not(feature = "vaes"))]
+        Backend::AesNi
+    }
+
+    /// Apply CTR keystream to the leading part of `data` which can be
+    /// processed with VAES and return the rest
+    #[cfg(all(feature = "vaes", feature = "ctr"))]
+    #[inline(always)]
+    pub(crate) fn vaes_ctr<'a>(&self, ctr: &mut __m128i, data: &'a mut [u8]) -> &'a mut [u8] {
+        self.vaes.ctr(&self.round_keys, ctr, data)
+    }
+
     #[inline(always)]
     pub(crate) fn encrypt8(&self, mut blocks: U128x8) -> U128x8 {
         #[inline]
@@ -41,7 +163,7 @@ impl Aes128 {
             aesenc8(blocks, keys[9]);
             aesenclast8(blocks, keys[10]);
         }
-        unsafe { aesni128_encrypt8(&self.encrypt_keys, &mut blocks) };
+        unsafe { aesni128_encrypt8(&self.round_keys, &mut blocks) };
         blocks
     }
 
@@ -62,32 +184,53 @@ impl Aes128 {
             block = _mm_aesenc_si128(block, keys[9]);
             _mm_aesenclast_si128(block, keys[10])
         }
-        unsafe { aesni128_encrypt1(&self.encrypt_keys, block) }
+        unsafe { aesni128_encrypt1(&self.round_keys, block) }
     }
 }
 
-impl NewBlockCipher for Aes128 {
+#[cfg(feature = "hazmat")]
+impl Aes128Enc {
+    /// Create a cipher instance from round keys in the FIPS 197 form
+    pub(crate) fn from_round_keys(round_keys: &[Block; 11]) -> Self {
+        let mut keys: RoundKeys = unsafe { mem::zeroed() };
+        load_round_keys(round_keys, &mut keys);
+
+        Self {
+            round_keys: keys,
+            #[cfg(feature = "vaes")]
+            vaes: vaes::Level::get(),
+        }
+    }
+
+    /// Round keys in the FIPS 197 form
+    pub(crate) fn round_keys(&self) -> [Block; 11] {
+        let mut round_keys = [Block::default(); 11];
+        store_round_keys(&self.round_keys, &mut round_keys);
+        round_keys
+    }
+}
+
+impl NewBlockCipher for Aes128Enc {
     type KeySize = U16;
 
     #[inline]
     fn new(key: &GenericArray<u8, U16>) -> Self {
         let key = unsafe { &*(key as *const _ as *const [u8; 16]) };
 
-        let (encrypt_keys, decrypt_keys) = expand::expand(key);
-
         Self {
-            encrypt_keys,
-            decrypt_keys,
+            round_keys: expand::expand(key),
+            #[cfg(feature = "vaes")]
+            vaes: vaes::Level::get(),
         }
     }
 }
 
-impl BlockCipher for Aes128 {
+impl BlockCipher for Aes128Enc {
     type BlockSize = U16;
     type ParBlocks = U8;
 }
 
-impl BlockEncrypt for Aes128 {
+impl BlockEncrypt for Aes128Enc {
     #[inline]
     fn encrypt_block(&self, block: &mut Block) {
         // Safety: `loadu` and `storeu` support unaligned access
@@ -101,12 +244,100 @@ impl BlockEncrypt for Aes128 {
 
     #[inline]
     fn encrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        #[cfg(feature = "vaes")]
+        if self.vaes.encrypt_par(&self.round_keys, blocks) {
+            return;
+        }
+
         let b = self.encrypt8(load8(blocks));
         store8(blocks, b);
     }
+
+    #[cfg(feature = "vaes")]
+    #[inline]
+    fn encrypt_blocks(&self, blocks: &mut [Block]) {
+        let blocks = self.vaes.encrypt_blocks(&self.round_keys, blocks);
+        let mut chunks = blocks.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            self.encrypt_par_blocks(ParBlocks::from_mut_slice(chunk));
+        }
+        for block in chunks.into_remainder() {
+            self.encrypt_block(block);
+        }
+    }
 }
 
-impl BlockDecrypt for Aes128 {
+/// AES-128 block cipher (decrypt-only)
+#[derive(Clone)]
+pub struct Aes128Dec {
+    round_keys: RoundKeys,
+    #[cfg(feature = "vaes")]
+    vaes: vaes::Level,
+}
+
+impl Aes128Dec {
+    /// Backend used by this instance
+    pub(crate) fn backend(&self) -> Backend {
+        #[cfg(feature = "vaes")]
+        return self.vaes.backend();
+        #[cfg(not(feature = "vaes"))]
+        Backend::AesNi
+    }
+}
+
+#[cfg(feature = "hazmat")]
+impl Aes128Dec {
+    /// Create a cipher instance from round keys in the FIPS 197 form
+    pub(crate) fn from_round_keys(round_keys: &[Block; 11]) -> Self {
+        Aes128Enc::from_round_keys(round_keys).into()
+    }
+
+    /// Encryption round keys in the FIPS 197 form
+    pub(crate) fn round_keys(&self) -> [Block; 11] {
+        let mut keys = self.round_keys;
+        fwd_expanded_keys(&mut keys);
+
+        let mut round_keys = [Block::default(); 11];
+        store_round_keys(&keys, &mut round_keys);
+        round_keys
+    }
+}
+
+impl NewBlockCipher for Aes128Dec {
+    type KeySize = U16;
+
+    #[inline]
+    fn new(key: &GenericArray<u8, U16>) -> Self {
+        Aes128Enc::new(key).into()
+    }
+}
+
+impl From<Aes128Enc> for Aes128Dec {
+    #[inline]
+    fn from(enc: Aes128Enc) -> Aes128Dec {
+        Self::from(&enc)
+    }
+}
+
+impl From<&Aes128Enc> for Aes128Dec {
+    #[inline]
+    fn from(enc: &Aes128Enc) -> Aes128Dec {
+        let mut round_keys = enc.round_keys;
+        inv_expanded_keys(&mut round_keys);
+        Self {
+            round_keys,
+            #[cfg(feature = "vaes")]
+            vaes: enc.vaes,
+        }
+    }
+}
+
+impl BlockCipher for Aes128Dec {
+    type BlockSize = U16;
+    type ParBlocks = U8;
+}
+
+impl BlockDecrypt for Aes128Dec {
     #[inline]
     fn decrypt_block(&self, block: &mut Block) {
         #[inline]
@@ -133,11 +364,16 @@ impl BlockDecrypt for Aes128 {
             _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, b);
         }
 
-        unsafe { aes128_decrypt1(block, &self.decrypt_keys) }
+        unsafe { aes128_decrypt1(block, &self.round_keys) }
     }
 
     #[inline]
     fn decrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        #[cfg(feature = "vaes")]
+        if self.vaes.decrypt_par(&self.round_keys, blocks) {
+            return;
+        }
+
         #[inline]
         #[target_feature(enable = "aes")]
         unsafe fn aes128_decrypt8(blocks: &mut ParBlocks, keys: &RoundKeys) {
@@ -156,8 +392,23 @@ impl BlockDecrypt for Aes128 {
             store8(blocks, b);
         }
 
-        unsafe { aes128_decrypt8(blocks, &self.decrypt_keys) }
+        unsafe { aes128_decrypt8(blocks, &self.round_keys) }
+    }
+
+    #[cfg(feature = "vaes")]
+    #[inline]
+    fn decrypt_blocks(&self, blocks: &mut [Block]) {
+        let blocks = self.vaes.decrypt_blocks(&self.round_keys, blocks);
+        let mut chunks = blocks.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            self.decrypt_par_blocks(ParBlocks::from_mut_slice(chunk));
+        }
+        for block in chunks.into_remainder() {
+            self.decrypt_block(block);
+        }
     }
 }
 
 opaque_debug::implement!(Aes128);
+opaque_debug::implement!(Aes128Enc);
+opaque_debug::implement!(Aes128Dec);
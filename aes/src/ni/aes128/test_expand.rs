@@ -3,7 +3,7 @@ use crate::ni::utils::check;
 
 #[test]
 fn test() {
-    let enc_keys = expand(&[0x00; 16]).0;
+    let enc_keys = expand(&[0x00; 16]);
     check(
         &enc_keys,
         &[
@@ -21,7 +21,7 @@ fn test() {
         ],
     );
 
-    let enc_keys = expand(&[0xff; 16]).0;
+    let enc_keys = expand(&[0xff; 16]);
     check(
         &enc_keys,
         &[
@@ -42,8 +42,7 @@ fn test() {
     let enc_keys = expand(&[
         0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
         0x0f,
-    ])
-    .0;
+    ]);
     check(
         &enc_keys,
         &[
@@ -64,8 +63,7 @@ fn test() {
     let enc_keys = expand(&[
         0x69, 0x20, 0xe2, 0x99, 0xa5, 0x20, 0x2a, 0x6d, 0x65, 0x6e, 0x63, 0x68, 0x69, 0x74, 0x6f,
         0x2a,
-    ])
-    .0;
+    ]);
     check(
         &enc_keys,
         &[
@@ -86,8 +84,7 @@ fn test() {
     let enc_keys = expand(&[
         0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
         0x3c,
-    ])
-    .0;
+    ]);
     check(
         &enc_keys,
         &[
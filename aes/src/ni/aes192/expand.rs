@@ -35,17 +35,15 @@ macro_rules! shuffle {
 }
 
 #[inline(always)]
-pub(super) fn expand(key: &[u8; 24]) -> (RoundKeys, RoundKeys) {
+pub(crate) fn expand(key: &[u8; 24]) -> RoundKeys {
     unsafe {
         // SAFETY: `RoundKeys` is a `[__m128i; 13]` which can be initialized
         // with all zeroes.
         let mut enc_keys: RoundKeys = mem::zeroed();
-        let mut dec_keys: RoundKeys = mem::zeroed();
 
         macro_rules! store {
             ($i:expr, $k:expr) => {
                 enc_keys[$i] = $k;
-                dec_keys[$i] = _mm_aesimc_si128($k);
             };
         }
 
@@ -65,8 +63,7 @@ pub(super) fn expand(key: &[u8; 24]) -> (RoundKeys, RoundKeys) {
             )
         };
 
-        enc_keys[0] = k0;
-        dec_keys[0] = k0;
+        store!(0, k0);
 
         let (k1_2, k2r) = expand_round!(k0, k1l, 0x01);
         let k1 = shuffle!(k1l, k1_2, 0);
@@ -102,9 +99,8 @@ pub(super) fn expand(key: &[u8; 24]) -> (RoundKeys, RoundKeys) {
         store!(11, k11);
 
         let (k12, _) = expand_round!(k10_11, k11r, 0x80);
-        enc_keys[12] = k12;
-        dec_keys[12] = k12;
+        store!(12, k12);
 
-        (enc_keys, dec_keys)
+        enc_keys
     }
 }
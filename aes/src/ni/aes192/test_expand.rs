@@ -3,7 +3,7 @@ use crate::ni::utils::check;
 
 #[test]
 fn test() {
-    let enc_keys = expand(&[0x00; 24]).0;
+    let enc_keys = expand(&[0x00; 24]);
     check(
         &enc_keys,
         &[
@@ -23,7 +23,7 @@ fn test() {
         ],
     );
 
-    let enc_keys = expand(&[0xff; 24]).0;
+    let enc_keys = expand(&[0xff; 24]);
     check(
         &enc_keys,
         &[
@@ -46,8 +46,7 @@ fn test() {
     let enc_keys = expand(&[
         0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
         0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    ])
-    .0;
+    ]);
     check(
         &enc_keys,
         &[
@@ -70,8 +69,7 @@ fn test() {
     let enc_keys = expand(&[
         0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90, 0x79,
         0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
-    ])
-    .0;
+    ]);
     check(
         &enc_keys,
         &[
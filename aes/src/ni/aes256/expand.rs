@@ -4,7 +4,7 @@ use crate::ni::arch::*;
 use core::mem;
 
 macro_rules! expand_round {
-    ($enc_keys:expr, $dec_keys:expr, $pos:expr, $round:expr) => {
+    ($enc_keys:expr, $pos:expr, $round:expr) => {
         let mut t1 = $enc_keys[$pos - 2];
         let mut t2;
         let mut t3 = $enc_keys[$pos - 1];
@@ -21,7 +21,6 @@ macro_rules! expand_round {
         t1 = _mm_xor_si128(t1, t2);
 
         $enc_keys[$pos] = t1;
-        $dec_keys[$pos] = _mm_aesimc_si128(t1);
 
         t4 = _mm_aeskeygenassist_si128(t1, 0x00);
         t2 = _mm_shuffle_epi32(t4, 0xaa);
@@ -34,12 +33,11 @@ macro_rules! expand_round {
         t3 = _mm_xor_si128(t3, t2);
 
         $enc_keys[$pos + 1] = t3;
-        $dec_keys[$pos + 1] = _mm_aesimc_si128(t3);
     };
 }
 
 macro_rules! expand_round_last {
-    ($enc_keys:expr, $dec_keys:expr, $pos:expr, $round:expr) => {
+    ($enc_keys:expr, $pos:expr, $round:expr) => {
         let mut t1 = $enc_keys[$pos - 2];
         let mut t2;
         let t3 = $enc_keys[$pos - 1];
@@ -56,36 +54,32 @@ macro_rules! expand_round_last {
         t1 = _mm_xor_si128(t1, t2);
 
         $enc_keys[$pos] = t1;
-        $dec_keys[$pos] = t1;
     };
 }
 
 #[inline(always)]
-pub(super) fn expand(key: &[u8; 32]) -> (RoundKeys, RoundKeys) {
+pub(crate) fn expand(key: &[u8; 32]) -> RoundKeys {
     // SAFETY:
     // - `RoundKeys` is a `[__m128i; 15]` which can be initialized with all zeroes.
     // - `loadu` and `storeu` support unaligned access
     #[allow(clippy::cast_ptr_alignment)]
     unsafe {
         let mut enc_keys: RoundKeys = mem::zeroed();
-        let mut dec_keys: RoundKeys = mem::zeroed();
 
         let kp = key.as_ptr() as *const __m128i;
         let k1 = _mm_loadu_si128(kp);
         let k2 = _mm_loadu_si128(kp.offset(1));
         enc_keys[0] = k1;
-        dec_keys[0] = k1;
         enc_keys[1] = k2;
-        dec_keys[1] = _mm_aesimc_si128(k2);
 
-        expand_round!(enc_keys, dec_keys, 2, 0x01);
-        expand_round!(enc_keys, dec_keys, 4, 0x02);
-        expand_round!(enc_keys, dec_keys, 6, 0x04);
-        expand_round!(enc_keys, dec_keys, 8, 0x08);
-        expand_round!(enc_keys, dec_keys, 10, 0x10);
-        expand_round!(enc_keys, dec_keys, 12, 0x20);
-        expand_round_last!(enc_keys, dec_keys, 14, 0x40);
+        expand_round!(enc_keys, 2, 0x01);
+        expand_round!(enc_keys, 4, 0x02);
+        expand_round!(enc_keys, 6, 0x04);
+        expand_round!(enc_keys, 8, 0x08);
+        expand_round!(enc_keys, 10, 0x10);
+        expand_round!(enc_keys, 12, 0x20);
+        expand_round_last!(enc_keys, 14, 0x40);
 
-        (enc_keys, dec_keys)
+        enc_keys
     }
 }
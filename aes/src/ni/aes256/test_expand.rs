@@ -3,7 +3,7 @@ use crate::ni::utils::check;
 
 #[test]
 fn test() {
-    let enc_keys = expand(&[0x00; 32]).0;
+    let enc_keys = expand(&[0x00; 32]);
     check(
         &enc_keys,
         &[
@@ -25,7 +25,7 @@ fn test() {
         ],
     );
 
-    let enc_keys = expand(&[0xff; 32]).0;
+    let enc_keys = expand(&[0xff; 32]);
     check(
         &enc_keys,
         &[
@@ -51,8 +51,7 @@ fn test() {
         0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
         0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
         0x1e, 0x1f,
-    ])
-    .0;
+    ]);
     check(
         &enc_keys,
         &[
@@ -78,8 +77,7 @@ fn test() {
         0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77,
         0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14,
         0xdf, 0xf4,
-    ])
-    .0;
+    ]);
     check(
         &enc_keys,
         &[
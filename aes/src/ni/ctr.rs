@@ -177,6 +177,11 @@ macro_rules! impl_ctr {
                     }
                 }
 
+                #[cfg(feature = "vaes")]
+                {
+                    data = self.cipher.vaes_ctr(&mut self.ctr, data);
+                }
+
                 let mut chunks = data.chunks_exact_mut(PAR_BLOCKS_SIZE);
                 for chunk in &mut chunks {
                     xor_block8(chunk, self.next_block8());
@@ -5,10 +5,12 @@
 //! access to the AES round function gated under the `hazmat` crate feature.
 
 use super::{
+    aes128, aes192, aes256,
     arch::*,
     utils::{load8, store8},
 };
 use crate::{Block, ParBlocks};
+use core::convert::TryInto;
 
 /// AES cipher (encrypt) round function.
 #[allow(clippy::cast_ptr_alignment)]
@@ -25,6 +27,11 @@ pub(crate) unsafe fn cipher_round(block: &mut Block, round_key: &Block) {
 #[allow(clippy::cast_ptr_alignment)]
 #[target_feature(enable = "aes")]
 pub(crate) unsafe fn cipher_round_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+    #[cfg(feature = "vaes")]
+    if super::vaes::Level::get().cipher_round_par(blocks, round_keys) {
+        return;
+    }
+
     let xmm_keys = load8(round_keys);
     let mut xmm_blocks = load8(blocks);
 
@@ -50,6 +57,11 @@ pub(crate) unsafe fn equiv_inv_cipher_round(block: &mut Block, round_key: &Block
 #[allow(clippy::cast_ptr_alignment)]
 #[target_feature(enable = "aes")]
 pub(crate) unsafe fn equiv_inv_cipher_round_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+    #[cfg(feature = "vaes")]
+    if super::vaes::Level::get().equiv_inv_cipher_round_par(blocks, round_keys) {
+        return;
+    }
+
     let xmm_keys = load8(round_keys);
     let mut xmm_blocks = load8(blocks);
 
@@ -60,6 +72,59 @@ pub(crate) unsafe fn equiv_inv_cipher_round_par(blocks: &mut ParBlocks, round_ke
     store8(blocks, xmm_blocks);
 }
 
+/// AES cipher (encrypt) final round function.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_round_last(block: &mut Block, round_key: &Block) {
+    // Safety: `loadu` and `storeu` support unaligned access
+    let b = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    let k = _mm_loadu_si128(round_key.as_ptr() as *const __m128i);
+    let out = _mm_aesenclast_si128(b, k);
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
+}
+
+/// AES cipher (encrypt) final round function: parallel version.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn cipher_round_last_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+    let xmm_keys = load8(round_keys);
+    let mut xmm_blocks = load8(blocks);
+
+    for i in 0..8 {
+        xmm_blocks[i] = _mm_aesenclast_si128(xmm_blocks[i], xmm_keys[i]);
+    }
+
+    store8(blocks, xmm_blocks);
+}
+
+/// AES equivalent inverse cipher (decrypt) final round function.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn equiv_inv_cipher_round_last(block: &mut Block, round_key: &Block) {
+    // Safety: `loadu` and `storeu` support unaligned access
+    let b = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    let k = _mm_loadu_si128(round_key.as_ptr() as *const __m128i);
+    let out = _mm_aesdeclast_si128(b, k);
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
+}
+
+/// AES equivalent inverse cipher (decrypt) final round function: parallel version.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn equiv_inv_cipher_round_last_par(
+    blocks: &mut ParBlocks,
+    round_keys: &ParBlocks,
+) {
+    let xmm_keys = load8(round_keys);
+    let mut xmm_blocks = load8(blocks);
+
+    for i in 0..8 {
+        xmm_blocks[i] = _mm_aesdeclast_si128(xmm_blocks[i], xmm_keys[i]);
+    }
+
+    store8(blocks, xmm_blocks);
+}
+
 /// AES mix columns function.
 #[allow(clippy::cast_ptr_alignment)]
 #[target_feature(enable = "aes")]
@@ -84,3 +149,22 @@ pub(crate) unsafe fn inv_mix_columns(block: &mut Block) {
     let out = _mm_aesimc_si128(b);
     _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, out);
 }
+
+/// AES key expansion: writes the round keys for a 16, 24 or 32-byte `key`.
+#[allow(clippy::cast_ptr_alignment)]
+#[target_feature(enable = "aes")]
+pub(crate) unsafe fn expand_key(key: &[u8], round_keys: &mut [Block]) {
+    let mut xmm_keys = [_mm_setzero_si128(); 15];
+
+    match key.len() {
+        16 => xmm_keys[..11].copy_from_slice(&aes128::expand::expand(key.try_into().unwrap())),
+        24 => xmm_keys[..13].copy_from_slice(&aes192::expand::expand(key.try_into().unwrap())),
+        32 => xmm_keys[..15].copy_from_slice(&aes256::expand::expand(key.try_into().unwrap())),
+        _ => unreachable!("invalid AES key size"),
+    }
+
+    for (block, xmm_key) in round_keys.iter_mut().zip(xmm_keys.iter()) {
+        // Safety: `storeu` supports unaligned access
+        _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, *xmm_key);
+    }
+}
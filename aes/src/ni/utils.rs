@@ -4,6 +4,8 @@
 #![allow(clippy::needless_range_loop)]
 
 use super::arch::*;
+#[cfg(feature = "hazmat")]
+use crate::Block;
 use crate::ParBlocks;
 
 pub type U128x8 = [__m128i; 8];
@@ -17,6 +19,54 @@ pub(crate) fn check(a: &[__m128i], b: &[[u64; 2]]) {
     }
 }
 
+/// Convert encryption round keys into round keys of the equivalent inverse
+/// cipher by applying `InvMixColumns` to all but the first and last keys.
+#[inline(always)]
+pub(crate) fn inv_expanded_keys(keys: &mut [__m128i]) {
+    let n = keys.len();
+    for key in &mut keys[1..n - 1] {
+        *key = unsafe { _mm_aesimc_si128(*key) };
+    }
+}
+
+/// Convert round keys of the equivalent inverse cipher back into encryption
+/// round keys: the inverse of [`inv_expanded_keys`].
+#[cfg(feature = "hazmat")]
+#[inline(always)]
+pub(crate) fn fwd_expanded_keys(keys: &mut [__m128i]) {
+    let n = keys.len();
+    for key in &mut keys[1..n - 1] {
+        // Emulate mix columns by performing three inverse mix columns operations
+        *key = unsafe { _mm_aesimc_si128(_mm_aesimc_si128(_mm_aesimc_si128(*key))) };
+    }
+}
+
+/// Load round keys in the FIPS 197 form.
+#[cfg(feature = "hazmat")]
+#[inline(always)]
+pub(crate) fn load_round_keys(round_keys: &[Block], keys: &mut [__m128i]) {
+    for (key, round_key) in keys.iter_mut().zip(round_keys) {
+        // Safety: `loadu` supports unaligned loads
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            *key = _mm_loadu_si128(round_key.as_ptr() as *const __m128i);
+        }
+    }
+}
+
+/// Store round keys in the FIPS 197 form.
+#[cfg(feature = "hazmat")]
+#[inline(always)]
+pub(crate) fn store_round_keys(keys: &[__m128i], round_keys: &mut [Block]) {
+    for (round_key, key) in round_keys.iter_mut().zip(keys) {
+        // Safety: `storeu` supports unaligned stores
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            _mm_storeu_si128(round_key.as_mut_ptr() as *mut __m128i, *key);
+        }
+    }
+}
+
 #[inline(always)]
 pub(crate) fn load8(blocks: &ParBlocks) -> U128x8 {
     unsafe {
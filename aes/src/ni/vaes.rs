@@ -0,0 +1,397 @@
+//! VAES support for the AES-NI backend.
+//!
+//! The VAES extension applies the AES round instructions to every 128-bit
+//! lane of a 256-bit (AVX2) or 512-bit (AVX-512) register, so one
+//! instruction processes 2 or 4 blocks. Operations on many blocks keep
+//! 8 registers, i.e. 16 or 32 blocks, in flight to hide the latency of the
+//! round instructions.
+//!
+//! This module requires Rust 1.89 or newer and is enabled by the `vaes`
+//! crate feature. The widest available register size is detected at runtime.
+
+#![allow(clippy::cast_ptr_alignment)]
+
+use super::arch::*;
+use crate::{Backend, Block, ParBlocks, BLOCK_SIZE};
+
+cpufeatures::new!(vaes_avx2_cpuid, "aes", "vaes", "avx2");
+cpufeatures::new!(vaes_avx512_cpuid, "aes", "vaes", "avx512f", "avx512bw");
+
+/// Number of registers processed at once by multi-block operations
+const REGS: usize = 8;
+
+/// Widest registers which can be used with the VAES instructions
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Level {
+    /// VAES is not available, only 128-bit AES-NI instructions are used
+    None,
+    /// 256-bit registers
+    Avx2,
+    /// 512-bit registers
+    Avx512,
+}
+
+impl Level {
+    /// Detect VAES support of the CPU.
+    pub(crate) fn get() -> Self {
+        if vaes_avx512_cpuid::get() {
+            Level::Avx512
+        } else if vaes_avx2_cpuid::get() {
+            Level::Avx2
+        } else {
+            Level::None
+        }
+    }
+
+    pub(crate) fn backend(self) -> Backend {
+        match self {
+            Level::None => Backend::AesNi,
+            Level::Avx2 => Backend::VaesAvx2,
+            Level::Avx512 => Backend::VaesAvx512,
+        }
+    }
+
+    /// Encrypt 8 blocks, returns `false` if VAES is not available.
+    #[inline]
+    pub(crate) fn encrypt_par<const N: usize>(
+        self,
+        keys: &[__m128i; N],
+        blocks: &mut ParBlocks,
+    ) -> bool {
+        match self {
+            Level::Avx512 => unsafe { zmm::encrypt_par(keys, blocks) },
+            Level::Avx2 => unsafe { ymm::encrypt_par(keys, blocks) },
+            Level::None => return false,
+        }
+        true
+    }
+
+    /// Decrypt 8 blocks, returns `false` if VAES is not available.
+    #[inline]
+    pub(crate) fn decrypt_par<const N: usize>(
+        self,
+        keys: &[__m128i; N],
+        blocks: &mut ParBlocks,
+    ) -> bool {
+        match self {
+            Level::Avx512 => unsafe { zmm::decrypt_par(keys, blocks) },
+            Level::Avx2 => unsafe { ymm::decrypt_par(keys, blocks) },
+            Level::None => return false,
+        }
+        true
+    }
+
+    /// Encrypt blocks in chunks of 16 or 32 blocks and return the blocks
+    /// left over after the last full chunk.
+    #[inline]
+    pub(crate) fn encrypt_blocks<'a, const N: usize>(
+        self,
+        keys: &[__m128i; N],
+        blocks: &'a mut [Block],
+    ) -> &'a mut [Block] {
+        match self {
+            Level::Avx512 => unsafe { zmm::encrypt_blocks(keys, blocks) },
+            Level::Avx2 => unsafe { ymm::encrypt_blocks(keys, blocks) },
+            Level::None => blocks,
+        }
+    }
+
+    /// Decrypt blocks in chunks of 16 or 32 blocks and return the blocks
+    /// left over after the last full chunk.
+    #[inline]
+    pub(crate) fn decrypt_blocks<'a, const N: usize>(
+        self,
+        keys: &[__m128i; N],
+        blocks: &'a mut [Block],
+    ) -> &'a mut [Block] {
+        match self {
+            Level::Avx512 => unsafe { zmm::decrypt_blocks(keys, blocks) },
+            Level::Avx2 => unsafe { ymm::decrypt_blocks(keys, blocks) },
+            Level::None => blocks,
+        }
+    }
+
+    /// Apply CTR keystream to `data` in chunks of 16 or 32 blocks and return
+    /// the data left over after the last full chunk.
+    ///
+    /// `ctr` uses the representation of the AES-NI CTR implementation, i.e.
+    /// it is byte swapped and only its upper 64-bit lane is incremented.
+    #[cfg(feature = "ctr")]
+    #[inline]
+    pub(crate) fn ctr<'a, const N: usize>(
+        self,
+        keys: &[__m128i; N],
+        ctr: &mut __m128i,
+        data: &'a mut [u8],
+    ) -> &'a mut [u8] {
+        match self {
+            Level::Avx512 => unsafe { zmm::ctr(keys, ctr, data) },
+            Level::Avx2 => unsafe { ymm::ctr(keys, ctr, data) },
+            Level::None => data,
+        }
+    }
+
+    /// AES cipher (encrypt) round function: parallel version.
+    #[cfg(feature = "hazmat")]
+    #[inline]
+    pub(crate) fn cipher_round_par(self, blocks: &mut ParBlocks, round_keys: &ParBlocks) -> bool {
+        match self {
+            Level::Avx512 => unsafe { zmm::cipher_round_par(blocks, round_keys) },
+            Level::Avx2 => unsafe { ymm::cipher_round_par(blocks, round_keys) },
+            Level::None => return false,
+        }
+        true
+    }
+
+    /// AES equivalent inverse cipher (decrypt) round function: parallel version.
+    #[cfg(feature = "hazmat")]
+    #[inline]
+    pub(crate) fn equiv_inv_cipher_round_par(
+        self,
+        blocks: &mut ParBlocks,
+        round_keys: &ParBlocks,
+    ) -> bool {
+        match self {
+            Level::Avx512 => unsafe { zmm::equiv_inv_cipher_round_par(blocks, round_keys) },
+            Level::Avx2 => unsafe { ymm::equiv_inv_cipher_round_par(blocks, round_keys) },
+            Level::None => return false,
+        }
+        true
+    }
+}
+
+macro_rules! define_vaes_impl {
+    (
+        $module:ident,
+        $features:literal,
+        $reg:ty,
+        $lanes:expr,
+        $broadcast:ident,
+        $load:ident,
+        $store:ident,
+        $xor:ident,
+        $add:ident,
+        $shuffle:ident,
+        $aesenc:ident,
+        $aesenclast:ident,
+        $aesdec:ident,
+        $aesdeclast:ident,
+        $ctr_lanes:expr,
+        $ctr_inc:expr
+    ) => {
+        mod $module {
+            use super::*;
+
+            /// Blocks per register
+            const LANES: usize = $lanes;
+
+            /// Blocks per multi-block chunk
+            const CHUNK: usize = REGS * LANES;
+
+            #[inline(always)]
+            unsafe fn load(ptr: *const u8) -> $reg {
+                $load(ptr as *const _)
+            }
+
+            #[inline(always)]
+            unsafe fn store(ptr: *mut u8, v: $reg) {
+                $store(ptr as *mut _, v)
+            }
+
+            #[inline(always)]
+            unsafe fn encrypt<const N: usize, const R: usize>(
+                keys: &[__m128i; N],
+                b: &mut [$reg; R],
+            ) {
+                let k = $broadcast(keys[0]);
+                for v in b.iter_mut() {
+                    *v = $xor(*v, k);
+                }
+                for key in &keys[1..N - 1] {
+                    let k = $broadcast(*key);
+                    for v in b.iter_mut() {
+                        *v = $aesenc(*v, k);
+                    }
+                }
+                let k = $broadcast(keys[N - 1]);
+                for v in b.iter_mut() {
+                    *v = $aesenclast(*v, k);
+                }
+            }
+
+            #[inline(always)]
+            unsafe fn decrypt<const N: usize, const R: usize>(
+                keys: &[__m128i; N],
+                b: &mut [$reg; R],
+            ) {
+                let k = $broadcast(keys[N - 1]);
+                for v in b.iter_mut() {
+                    *v = $xor(*v, k);
+                }
+                for key in keys[1..N - 1].iter().rev() {
+                    let k = $broadcast(*key);
+                    for v in b.iter_mut() {
+                        *v = $aesdec(*v, k);
+                    }
+                }
+                let k = $broadcast(keys[0]);
+                for v in b.iter_mut() {
+                    *v = $aesdeclast(*v, k);
+                }
+            }
+
+            /// Apply `f` to the registers loaded from `blocks`.
+            #[inline(always)]
+            unsafe fn process<const R: usize>(blocks: &mut [Block], f: impl Fn(&mut [$reg; R])) {
+                debug_assert_eq!(blocks.len(), R * LANES);
+                let ptr = blocks.as_mut_ptr() as *mut u8;
+                // SAFETY: `[$reg; R]` can be initialized with all zeroes.
+                let mut b: [$reg; R] = core::mem::zeroed();
+                for (i, v) in b.iter_mut().enumerate() {
+                    *v = load(ptr.add(i * LANES * BLOCK_SIZE));
+                }
+                f(&mut b);
+                for (i, v) in b.iter().enumerate() {
+                    store(ptr.add(i * LANES * BLOCK_SIZE), *v);
+                }
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) unsafe fn encrypt_par<const N: usize>(
+                keys: &[__m128i; N],
+                blocks: &mut ParBlocks,
+            ) {
+                process::<{ 8 / LANES }>(blocks, |b| encrypt(keys, b));
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) unsafe fn decrypt_par<const N: usize>(
+                keys: &[__m128i; N],
+                blocks: &mut ParBlocks,
+            ) {
+                process::<{ 8 / LANES }>(blocks, |b| decrypt(keys, b));
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) unsafe fn encrypt_blocks<'a, const N: usize>(
+                keys: &[__m128i; N],
+                blocks: &'a mut [Block],
+            ) -> &'a mut [Block] {
+                let mut chunks = blocks.chunks_exact_mut(CHUNK);
+                for chunk in &mut chunks {
+                    process::<REGS>(chunk, |b| encrypt(keys, b));
+                }
+                chunks.into_remainder()
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) unsafe fn decrypt_blocks<'a, const N: usize>(
+                keys: &[__m128i; N],
+                blocks: &'a mut [Block],
+            ) -> &'a mut [Block] {
+                let mut chunks = blocks.chunks_exact_mut(CHUNK);
+                for chunk in &mut chunks {
+                    process::<REGS>(chunk, |b| decrypt(keys, b));
+                }
+                chunks.into_remainder()
+            }
+
+            #[cfg(feature = "ctr")]
+            #[target_feature(enable = $features)]
+            pub(super) unsafe fn ctr<'a, const N: usize>(
+                keys: &[__m128i; N],
+                ctr: &mut __m128i,
+                data: &'a mut [u8],
+            ) -> &'a mut [u8] {
+                // byte swap within every 128-bit lane
+                let mask = $broadcast(_mm_set_epi64x(0x08090a0b0c0d0e0f, 0x0001020304050607));
+                let mut next = $add($broadcast(*ctr), $ctr_lanes);
+                let inc = $ctr_inc;
+
+                let mut chunks = data.chunks_exact_mut(CHUNK * BLOCK_SIZE);
+                for chunk in &mut chunks {
+                    // SAFETY: `[$reg; REGS]` can be initialized with all zeroes.
+                    let mut b: [$reg; REGS] = core::mem::zeroed();
+                    for v in b.iter_mut() {
+                        *v = $shuffle(next, mask);
+                        next = $add(next, inc);
+                    }
+                    encrypt(keys, &mut b);
+
+                    let ptr = chunk.as_mut_ptr();
+                    for (i, v) in b.iter().enumerate() {
+                        let p = ptr.add(i * LANES * BLOCK_SIZE);
+                        store(p, $xor(load(p), *v));
+                    }
+                    *ctr = _mm_add_epi64(*ctr, _mm_set_epi64x(CHUNK as i64, 0));
+                }
+                chunks.into_remainder()
+            }
+
+            #[cfg(feature = "hazmat")]
+            #[target_feature(enable = $features)]
+            pub(super) unsafe fn cipher_round_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+                let keys = round_keys.as_ptr() as *const u8;
+                process::<{ 8 / LANES }>(blocks, |b| {
+                    for (i, v) in b.iter_mut().enumerate() {
+                        *v = $aesenc(*v, load(keys.add(i * LANES * BLOCK_SIZE)));
+                    }
+                });
+            }
+
+            #[cfg(feature = "hazmat")]
+            #[target_feature(enable = $features)]
+            pub(super) unsafe fn equiv_inv_cipher_round_par(
+                blocks: &mut ParBlocks,
+                round_keys: &ParBlocks,
+            ) {
+                let keys = round_keys.as_ptr() as *const u8;
+                process::<{ 8 / LANES }>(blocks, |b| {
+                    for (i, v) in b.iter_mut().enumerate() {
+                        *v = $aesdec(*v, load(keys.add(i * LANES * BLOCK_SIZE)));
+                    }
+                });
+            }
+        }
+    };
+}
+
+define_vaes_impl!(
+    ymm,
+    "aes,vaes,avx2",
+    __m256i,
+    2,
+    _mm256_broadcastsi128_si256,
+    _mm256_loadu_si256,
+    _mm256_storeu_si256,
+    _mm256_xor_si256,
+    _mm256_add_epi64,
+    _mm256_shuffle_epi8,
+    _mm256_aesenc_epi128,
+    _mm256_aesenclast_epi128,
+    _mm256_aesdec_epi128,
+    _mm256_aesdeclast_epi128,
+    // counter offsets of the lanes and increment between registers, only
+    // the upper 64-bit half of every lane is counted
+    _mm256_set_epi64x(1, 0, 0, 0),
+    _mm256_set_epi64x(2, 0, 2, 0)
+);
+
+define_vaes_impl!(
+    zmm,
+    "aes,vaes,avx512f,avx512bw",
+    __m512i,
+    4,
+    _mm512_broadcast_i32x4,
+    _mm512_loadu_si512,
+    _mm512_storeu_si512,
+    _mm512_xor_si512,
+    _mm512_add_epi64,
+    _mm512_shuffle_epi8,
+    _mm512_aesenc_epi128,
+    _mm512_aesenclast_epi128,
+    _mm512_aesdec_epi128,
+    _mm512_aesdeclast_epi128,
+    _mm512_set_epi64(3, 0, 2, 0, 1, 0, 0, 0),
+    _mm512_set_epi64(4, 0, 4, 0, 4, 0, 4, 0)
+);
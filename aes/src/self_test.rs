@@ -0,0 +1,93 @@
+//! Power-on self-test.
+
+use crate::{Aes128, Aes192, Aes256};
+#[cfg(feature = "ctr")]
+use crate::{Aes128Ctr, Aes192Ctr, Aes256Ctr, Block, BlockCipher, BlockEncrypt, NewBlockCipher};
+use block_ciphers_self_test::{kat, SelfTestError};
+#[cfg(feature = "ctr")]
+use cipher::{consts::U16, FromBlockCipher, StreamCipher};
+#[cfg(feature = "ctr")]
+use core::convert::TryInto;
+
+/// Key from the FIPS 197 appendix C examples, truncated for AES-128 and AES-192
+const KEY: [u8; 32] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+];
+
+/// Plaintext from the FIPS 197 appendix C examples
+const PLAINTEXT: [u8; 16] = [
+    0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+];
+
+/// FIPS 197 appendix C.1
+const CIPHERTEXT_128: [u8; 16] = [
+    0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+];
+
+/// FIPS 197 appendix C.2
+const CIPHERTEXT_192: [u8; 16] = [
+    0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d, 0x71, 0x91,
+];
+
+/// FIPS 197 appendix C.3
+const CIPHERTEXT_256: [u8; 16] = [
+    0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60, 0x89,
+];
+
+/// Length of the CTR mode check, more than 32 blocks followed by a partial
+/// block
+#[cfg(feature = "ctr")]
+const CTR_LEN: usize = 35 * 16 + 5;
+
+/// Run known answer tests of AES-128, AES-192 and AES-256.
+///
+/// Ciphers are created the same way as by the caller, so the tests run
+/// against the backend selected by CPU feature detection. Besides single
+/// blocks they cover `encrypt_par_blocks`, `encrypt_blocks` and their
+/// decryption counterparts, which have separate code paths in every
+/// backend. With the `ctr` feature the CTR mode types are checked against
+/// the block cipher as well.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Aes128>("AES-128", &KEY[..16], &PLAINTEXT, &CIPHERTEXT_128)?;
+    kat::<Aes192>("AES-192", &KEY[..24], &PLAINTEXT, &CIPHERTEXT_192)?;
+    kat::<Aes256>("AES-256", &KEY, &PLAINTEXT, &CIPHERTEXT_256)?;
+    #[cfg(feature = "ctr")]
+    {
+        ctr::<Aes128Ctr>("AES-128-CTR", &KEY[..16])?;
+        ctr::<Aes192Ctr>("AES-192-CTR", &KEY[..24])?;
+        ctr::<Aes256Ctr>("AES-256-CTR", &KEY)?;
+    }
+    Ok(())
+}
+
+/// Compare the keystream of `C` with counter blocks encrypted one by one,
+/// the low 64 bits of the counter block wrap around during the test.
+#[cfg(feature = "ctr")]
+fn ctr<C>(algorithm: &'static str, key: &[u8]) -> Result<(), SelfTestError>
+where
+    C: FromBlockCipher<NonceSize = U16> + StreamCipher,
+    C::BlockCipher: BlockCipher<BlockSize = U16> + NewBlockCipher + BlockEncrypt,
+{
+    let error = SelfTestError::new(algorithm);
+    let cipher = C::BlockCipher::new_from_slice(key).map_err(|_| error)?;
+    let mut nonce = Block::clone_from_slice(&PLAINTEXT);
+    nonce[8..].copy_from_slice(&(u64::MAX - 2).to_be_bytes());
+
+    let mut expected = [0u8; CTR_LEN];
+    let mut counter = nonce;
+    for chunk in expected.chunks_mut(16) {
+        let mut block = counter;
+        cipher.encrypt_block(&mut block);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        let low = u64::from_be_bytes(counter[8..].try_into().unwrap());
+        counter[8..].copy_from_slice(&low.wrapping_add(1).to_be_bytes());
+    }
+
+    let mut buf = [0u8; CTR_LEN];
+    C::from_block_cipher(cipher, &nonce).apply_keystream(&mut buf);
+    if buf[..] != expected[..] {
+        return Err(error);
+    }
+    Ok(())
+}
@@ -12,11 +12,24 @@
 #[cfg_attr(target_pointer_width = "64", path = "soft/fixslice64.rs")]
 pub(crate) mod fixslice;
 
-#[cfg(feature = "ctr")]
-mod ctr;
-
-#[cfg(feature = "ctr")]
-pub use self::ctr::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
+// the autodetecting backends fall back to the generic counter mode over
+// their own block cipher types
+cfg_if::cfg_if! {
+    if #[cfg(all(
+        feature = "ctr",
+        any(
+            feature = "force-soft",
+            not(any(
+                target_arch = "x86",
+                target_arch = "x86_64",
+                all(target_arch = "aarch64", feature = "armv8")
+            ))
+        )
+    ))] {
+        mod ctr;
+        pub use self::ctr::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
+    }
+}
 
 use crate::{Block, ParBlocks};
 use cipher::{
@@ -26,17 +39,88 @@ use cipher::{
 };
 use fixslice::{FixsliceKeys128, FixsliceKeys192, FixsliceKeys256, FIXSLICE_BLOCKS};
 
+/// Inherent methods of the autodetected types, only exported when there is
+/// no other backend
+macro_rules! impl_soft_only {
+    ($name:ident, $key_size:ty) => {
+        #[cfg(any(
+            feature = "force-soft",
+            not(any(
+                target_arch = "x86",
+                target_arch = "x86_64",
+                all(target_arch = "aarch64", feature = "armv8")
+            ))
+        ))]
+        impl $name {
+            /// Create a cipher instance, same as `new` as this build of the
+            /// crate always uses the "soft" backend.
+            pub fn new_soft(key: &GenericArray<u8, $key_size>) -> Self {
+                Self::new(key)
+            }
+
+            /// Backend used by this instance, always [`crate::Backend::Soft`].
+            pub fn backend(&self) -> crate::Backend {
+                crate::Backend::Soft
+            }
+        }
+    };
+}
+
+/// Hazmat constructor from round keys in the FIPS 197 form and their export
+#[cfg(feature = "hazmat")]
+macro_rules! impl_round_keys {
+    ($name:ident, $rounds:expr, |$round_keys:ident| $from:expr, |$this:ident| $to:expr) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+        impl $name {
+            /// ⚠️ Create a cipher instance from round keys in the FIPS 197 form.
+            ///
+            /// The round keys are expected in the order computed by
+            /// `KeyExpansion()`, e.g. as returned by the key expansion
+            /// functions of the [`hazmat`][crate::hazmat] module.
+            ///
+            /// # ☢️️ WARNING: HAZARDOUS API ☢️
+            ///
+            /// Use this function with great care! See the [module-level documentation][crate::hazmat]
+            /// for more information.
+            pub fn from_round_keys($round_keys: &[Block; $rounds]) -> Self {
+                $from
+            }
+
+            /// ⚠️ Round keys of this instance in the FIPS 197 form.
+            ///
+            /// Returns the round keys computed by `KeyExpansion()`, which
+            /// are the encryption round keys even for a decrypt-only
+            /// instance.
+            ///
+            /// # ☢️️ WARNING: HAZARDOUS API ☢️
+            ///
+            /// Use this function with great care! See the [module-level documentation][crate::hazmat]
+            /// for more information.
+            pub fn round_keys(&self) -> [Block; $rounds] {
+                let $this = self;
+                $to
+            }
+        }
+    };
+}
+
 macro_rules! define_aes_impl {
     (
         $name:ident,
+        $name_enc:ident,
+        $name_dec:ident,
         $key_size:ty,
         $fixslice_keys:ty,
         $fixslice_key_schedule:path,
         $fixslice_decrypt:path,
         $fixslice_encrypt:path,
+        $rounds:expr,
+        $fixslice_from_round_keys:path,
+        $fixslice_round_keys:path,
         $doc:expr
     ) => {
         #[doc=$doc]
+        #[doc = "block cipher"]
         #[derive(Clone)]
         pub struct $name {
             keys: $fixslice_keys,
@@ -53,6 +137,18 @@ macro_rules! define_aes_impl {
             }
         }
 
+        impl_soft_only!($name, $key_size);
+
+        #[cfg(feature = "hazmat")]
+        impl_round_keys!(
+            $name,
+            $rounds,
+            |round_keys| Self {
+                keys: $fixslice_from_round_keys(round_keys),
+            },
+            |cipher| $fixslice_round_keys(&cipher.keys)
+        );
+
         impl BlockCipher for $name {
             type BlockSize = U16;
             type ParBlocks = U8;
@@ -92,36 +188,166 @@ macro_rules! define_aes_impl {
             }
         }
 
+        // Fixsliced encryption and decryption use the same key schedule,
+        // so the encrypt-only and decrypt-only types wrap the full cipher.
+
+        #[doc=$doc]
+        #[doc = "block cipher (encrypt-only)"]
+        #[derive(Clone)]
+        pub struct $name_enc {
+            inner: $name,
+        }
+
+        impl NewBlockCipher for $name_enc {
+            type KeySize = $key_size;
+
+            #[inline]
+            fn new(key: &GenericArray<u8, $key_size>) -> Self {
+                Self {
+                    inner: $name::new(key),
+                }
+            }
+        }
+
+        impl_soft_only!($name_enc, $key_size);
+
+        #[cfg(feature = "hazmat")]
+        impl_round_keys!(
+            $name_enc,
+            $rounds,
+            |round_keys| Self {
+                inner: $name::from_round_keys(round_keys),
+            },
+            |cipher| cipher.inner.round_keys()
+        );
+
+        impl BlockCipher for $name_enc {
+            type BlockSize = U16;
+            type ParBlocks = U8;
+        }
+
+        impl BlockEncrypt for $name_enc {
+            #[inline]
+            fn encrypt_block(&self, block: &mut Block) {
+                self.inner.encrypt_block(block)
+            }
+
+            #[inline]
+            fn encrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+                self.inner.encrypt_par_blocks(blocks)
+            }
+        }
+
+        #[doc=$doc]
+        #[doc = "block cipher (decrypt-only)"]
+        #[derive(Clone)]
+        pub struct $name_dec {
+            inner: $name,
+        }
+
+        impl NewBlockCipher for $name_dec {
+            type KeySize = $key_size;
+
+            #[inline]
+            fn new(key: &GenericArray<u8, $key_size>) -> Self {
+                Self {
+                    inner: $name::new(key),
+                }
+            }
+        }
+
+        impl_soft_only!($name_dec, $key_size);
+
+        #[cfg(feature = "hazmat")]
+        impl_round_keys!(
+            $name_dec,
+            $rounds,
+            |round_keys| Self {
+                inner: $name::from_round_keys(round_keys),
+            },
+            |cipher| cipher.inner.round_keys()
+        );
+
+        impl From<$name_enc> for $name_dec {
+            #[inline]
+            fn from(enc: $name_enc) -> $name_dec {
+                Self { inner: enc.inner }
+            }
+        }
+
+        impl From<&$name_enc> for $name_dec {
+            #[inline]
+            fn from(enc: &$name_enc) -> $name_dec {
+                Self {
+                    inner: enc.inner.clone(),
+                }
+            }
+        }
+
+        impl BlockCipher for $name_dec {
+            type BlockSize = U16;
+            type ParBlocks = U8;
+        }
+
+        impl BlockDecrypt for $name_dec {
+            #[inline]
+            fn decrypt_block(&self, block: &mut Block) {
+                self.inner.decrypt_block(block)
+            }
+
+            #[inline]
+            fn decrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+                self.inner.decrypt_par_blocks(blocks)
+            }
+        }
+
         opaque_debug::implement!($name);
+        opaque_debug::implement!($name_enc);
+        opaque_debug::implement!($name_dec);
     };
 }
 
 define_aes_impl!(
     Aes128,
+    Aes128Enc,
+    Aes128Dec,
     U16,
     FixsliceKeys128,
     fixslice::aes128_key_schedule,
     fixslice::aes128_decrypt,
     fixslice::aes128_encrypt,
-    "AES-128 block cipher instance"
+    11,
+    fixslice::aes128_key_schedule_from_round_keys,
+    fixslice::aes128_round_keys,
+    "AES-128"
 );
 
 define_aes_impl!(
     Aes192,
+    Aes192Enc,
+    Aes192Dec,
     U24,
     FixsliceKeys192,
     fixslice::aes192_key_schedule,
     fixslice::aes192_decrypt,
     fixslice::aes192_encrypt,
-    "AES-192 block cipher instance"
+    13,
+    fixslice::aes192_key_schedule_from_round_keys,
+    fixslice::aes192_round_keys,
+    "AES-192"
 );
 
 define_aes_impl!(
     Aes256,
+    Aes256Enc,
+    Aes256Dec,
     U32,
     FixsliceKeys256,
     fixslice::aes256_key_schedule,
     fixslice::aes256_decrypt,
     fixslice::aes256_encrypt,
-    "AES-256 block cipher instance"
+    15,
+    fixslice::aes256_key_schedule_from_round_keys,
+    fixslice::aes256_round_keys,
+    "AES-256"
 );
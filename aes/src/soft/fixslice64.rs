@@ -263,6 +263,127 @@ pub(crate) fn aes256_key_schedule(key: &GenericArray<u8, U32>) -> FixsliceKeys25
     rkeys
 }
 
+/// Fully bitsliced AES-128 key schedule from round keys in the FIPS 197 form.
+#[cfg(feature = "hazmat")]
+pub(crate) fn aes128_key_schedule_from_round_keys(round_keys: &[Block; 11]) -> FixsliceKeys128 {
+    let mut rkeys = [0u64; 88];
+    bitslice_round_keys(round_keys, &mut rkeys);
+    rkeys
+}
+
+/// Fully bitsliced AES-192 key schedule from round keys in the FIPS 197 form.
+#[cfg(feature = "hazmat")]
+pub(crate) fn aes192_key_schedule_from_round_keys(round_keys: &[Block; 13]) -> FixsliceKeys192 {
+    let mut rkeys = [0u64; 104];
+    bitslice_round_keys(round_keys, &mut rkeys);
+    rkeys
+}
+
+/// Fully bitsliced AES-256 key schedule from round keys in the FIPS 197 form.
+#[cfg(feature = "hazmat")]
+pub(crate) fn aes256_key_schedule_from_round_keys(round_keys: &[Block; 15]) -> FixsliceKeys256 {
+    let mut rkeys = [0u64; 120];
+    bitslice_round_keys(round_keys, &mut rkeys);
+    rkeys
+}
+
+/// Round keys in the FIPS 197 form from the AES-128 fixsliced key schedule.
+#[cfg(feature = "hazmat")]
+pub(crate) fn aes128_round_keys(rkeys: &FixsliceKeys128) -> [Block; 11] {
+    let mut round_keys = [Block::default(); 11];
+    inv_bitslice_round_keys(rkeys, &mut round_keys);
+    round_keys
+}
+
+/// Round keys in the FIPS 197 form from the AES-192 fixsliced key schedule.
+#[cfg(feature = "hazmat")]
+pub(crate) fn aes192_round_keys(rkeys: &FixsliceKeys192) -> [Block; 13] {
+    let mut round_keys = [Block::default(); 13];
+    inv_bitslice_round_keys(rkeys, &mut round_keys);
+    round_keys
+}
+
+/// Round keys in the FIPS 197 form from the AES-256 fixsliced key schedule.
+#[cfg(feature = "hazmat")]
+pub(crate) fn aes256_round_keys(rkeys: &FixsliceKeys256) -> [Block; 15] {
+    let mut round_keys = [Block::default(); 15];
+    inv_bitslice_round_keys(rkeys, &mut round_keys);
+    round_keys
+}
+
+/// Bitslice round keys in the FIPS 197 form into the fully-fixsliced
+/// representation produced by the key schedules above.
+#[cfg(feature = "hazmat")]
+fn bitslice_round_keys(round_keys: &[Block], rkeys: &mut [u64]) {
+    let rounds = round_keys.len() - 1;
+
+    for (round_key, rkey) in round_keys.iter().zip(rkeys.chunks_exact_mut(8)) {
+        bitslice(rkey, round_key, round_key, round_key, round_key);
+    }
+
+    // Adjust to match fixslicing format (the last round key is never adjusted)
+    for (i, rkey) in rkeys.chunks_exact_mut(8).enumerate().take(rounds).skip(1) {
+        #[cfg(feature = "compact")]
+        {
+            if i % 2 == 1 {
+                inv_shift_rows_1(rkey);
+            }
+        }
+        #[cfg(not(feature = "compact"))]
+        {
+            match i % 4 {
+                1 => inv_shift_rows_1(rkey),
+                2 => inv_shift_rows_2(rkey),
+                3 => inv_shift_rows_3(rkey),
+                _ => (),
+            }
+        }
+    }
+
+    // Account for NOTs removed from sub_bytes
+    for rkey in rkeys.chunks_exact_mut(8).skip(1) {
+        sub_bytes_nots(rkey);
+    }
+}
+
+/// Convert a fully-fixsliced key schedule back into round keys in the
+/// FIPS 197 form: the inverse of [`bitslice_round_keys`].
+#[cfg(feature = "hazmat")]
+fn inv_bitslice_round_keys(rkeys: &[u64], round_keys: &mut [Block]) {
+    let rounds = round_keys.len() - 1;
+    let mut blocks = [Block::default(); FIXSLICE_BLOCKS];
+
+    for (i, (rkey, round_key)) in rkeys.chunks_exact(8).zip(round_keys.iter_mut()).enumerate() {
+        let mut state = State::default();
+        state.copy_from_slice(rkey);
+
+        if i > 0 {
+            sub_bytes_nots(&mut state);
+        }
+
+        if i > 0 && i < rounds {
+            #[cfg(feature = "compact")]
+            {
+                if i % 2 == 1 {
+                    shift_rows_1(&mut state);
+                }
+            }
+            #[cfg(not(feature = "compact"))]
+            {
+                match i % 4 {
+                    1 => shift_rows_1(&mut state),
+                    2 => shift_rows_2(&mut state),
+                    3 => shift_rows_3(&mut state),
+                    _ => (),
+                }
+            }
+        }
+
+        inv_bitslice(&state, &mut blocks);
+        round_key.copy_from_slice(&blocks[0]);
+    }
+}
+
 /// Fully-fixsliced AES-128 decryption (the InvShiftRows is completely omitted).
 ///
 /// Decrypts four blocks in-place and in parallel.
@@ -1273,6 +1394,15 @@ fn bitslice(output: &mut [u64], input0: &[u8], input1: &[u8], input2: &[u8], inp
     output[7] = t7;
 }
 
+/// Bitslice four 128-bit input blocks into a new internal state.
+#[cfg(feature = "masked")]
+fn bitslice_blocks(blocks: &[Block]) -> State {
+    debug_assert_eq!(blocks.len(), FIXSLICE_BLOCKS);
+    let mut state = State::default();
+    bitslice(&mut state, &blocks[0], &blocks[1], &blocks[2], &blocks[3]);
+    state
+}
+
 /// Un-bitslice a 512-bit internal state into four 128-bit blocks of output.
 fn inv_bitslice(input: &[u64], output: &mut [Block]) {
     debug_assert_eq!(input.len(), 8);
@@ -1427,6 +1557,11 @@ fn rotate_rows_and_columns_2_2(x: u64) -> u64 {
     (ror(x, ror_distance(1, 2)) & 0xff00ff00ff00ff00)
 }
 
+/// First-order masked AES rounds gated under the `masked` crate feature.
+#[cfg(feature = "masked")]
+#[path = "fixslice_masked.rs"]
+pub(crate) mod masked;
+
 /// Low-level "hazmat" AES functions.
 ///
 /// Note: this isn't actually used in the `Aes128`/`Aes192`/`Aes256`
@@ -1440,6 +1575,9 @@ pub(crate) mod hazmat {
     };
     use crate::{Block, ParBlocks};
 
+    /// AES round constants.
+    const ROUND_CONSTS: [u32; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
     /// XOR the `src` block into the `dst` block in-place.
     fn xor_in_place(dst: &mut Block, src: &Block) {
         for (a, b) in dst.iter_mut().zip(src.as_slice()) {
@@ -1495,7 +1633,7 @@ pub(crate) mod hazmat {
     #[inline]
     pub(crate) fn equiv_inv_cipher_round(block: &mut Block, round_key: &Block) {
         let mut state = State::default();
-        bitslice(&mut state, &block, &block, &block, &block);
+        bitslice(&mut state, block, block, block, block);
         sub_bytes_nots(&mut state);
         inv_sub_bytes(&mut state);
         inv_shift_rows_1(&mut state);
@@ -1522,6 +1660,62 @@ pub(crate) mod hazmat {
         }
     }
 
+    /// AES cipher (encrypt) final round function.
+    #[inline]
+    pub(crate) fn cipher_round_last(block: &mut Block, round_key: &Block) {
+        let mut state = bitslice_block(block);
+        sub_bytes(&mut state);
+        sub_bytes_nots(&mut state);
+        shift_rows_1(&mut state);
+        inv_bitslice_block(block, &state);
+        xor_in_place(block, round_key);
+    }
+
+    /// AES cipher (encrypt) final round function: parallel version.
+    #[inline]
+    pub(crate) fn cipher_round_last_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+        for (chunk, keys) in blocks.chunks_exact_mut(4).zip(round_keys.chunks_exact(4)) {
+            let mut state = State::default();
+            bitslice(&mut state, &chunk[0], &chunk[1], &chunk[2], &chunk[3]);
+            sub_bytes(&mut state);
+            sub_bytes_nots(&mut state);
+            shift_rows_1(&mut state);
+            inv_bitslice(&state, chunk);
+
+            for i in 0..4 {
+                xor_in_place(&mut chunk[i], &keys[i]);
+            }
+        }
+    }
+
+    /// AES equivalent inverse cipher (decrypt) final round function.
+    #[inline]
+    pub(crate) fn equiv_inv_cipher_round_last(block: &mut Block, round_key: &Block) {
+        let mut state = bitslice_block(block);
+        sub_bytes_nots(&mut state);
+        inv_sub_bytes(&mut state);
+        inv_shift_rows_1(&mut state);
+        inv_bitslice_block(block, &state);
+        xor_in_place(block, round_key);
+    }
+
+    /// AES equivalent inverse cipher (decrypt) final round function: parallel version.
+    #[inline]
+    pub(crate) fn equiv_inv_cipher_round_last_par(blocks: &mut ParBlocks, round_keys: &ParBlocks) {
+        for (chunk, keys) in blocks.chunks_exact_mut(4).zip(round_keys.chunks_exact(4)) {
+            let mut state = State::default();
+            bitslice(&mut state, &chunk[0], &chunk[1], &chunk[2], &chunk[3]);
+            sub_bytes_nots(&mut state);
+            inv_sub_bytes(&mut state);
+            inv_shift_rows_1(&mut state);
+            inv_bitslice(&state, chunk);
+
+            for i in 0..4 {
+                xor_in_place(&mut chunk[i], &keys[i]);
+            }
+        }
+    }
+
     /// AES mix columns function.
     #[inline]
     pub(crate) fn mix_columns(block: &mut Block) {
@@ -1537,4 +1731,52 @@ pub(crate) mod hazmat {
         inv_mix_columns_0(&mut state);
         inv_bitslice_block(block, &state);
     }
+
+    /// AES key expansion: writes the round keys for a 16, 24 or 32-byte
+    /// `key` in the order described in FIPS 197 Section 5.2.
+    #[inline]
+    pub(crate) fn expand_key(key: &[u8], round_keys: &mut [Block]) {
+        // Enough words for the 15 round keys of AES-256
+        let mut words = [0u32; 60];
+
+        for (word, chunk) in words.iter_mut().zip(key.chunks_exact(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+
+        // From "The Rijndael Block Cipher" Section 4.1:
+        // > The number of columns of the Cipher Key is denoted by `Nk` and is
+        // > equal to the key length divided by 32 [bits].
+        let nk = key.len() / 4;
+
+        for i in nk..(round_keys.len() * 4) {
+            let mut word = words[i - 1];
+
+            if i % nk == 0 {
+                word = sub_word(word.rotate_left(8)) ^ (ROUND_CONSTS[i / nk - 1] << 24);
+            } else if nk > 6 && i % nk == 4 {
+                word = sub_word(word);
+            }
+
+            words[i] = words[i - nk] ^ word;
+        }
+
+        for (block, chunk) in round_keys.iter_mut().zip(words.chunks_exact(4)) {
+            for (dst, word) in block.chunks_exact_mut(4).zip(chunk) {
+                dst.copy_from_slice(&word.to_be_bytes());
+            }
+        }
+    }
+
+    /// Sub bytes for a single AES word: used for key expansion.
+    fn sub_word(word: u32) -> u32 {
+        let mut block = Block::default();
+        block[..4].copy_from_slice(&word.to_be_bytes());
+
+        let mut state = bitslice_block(&block);
+        sub_bytes(&mut state);
+        sub_bytes_nots(&mut state);
+        inv_bitslice_block(&mut block, &state);
+
+        u32::from_be_bytes([block[0], block[1], block[2], block[3]])
+    }
 }
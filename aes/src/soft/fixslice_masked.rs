@@ -0,0 +1,731 @@
+//! First-order masked implementation of the fixsliced AES rounds.
+//!
+//! The state is split into two Boolean shares whose XOR is the fixsliced
+//! state of [`super`]. Linear layers are applied to each share, round keys
+//! are added to the first share and the AND gates of the bitsliced S-box
+//! circuits are replaced by the masked multiplication of Ishai, Sahai and
+//! Wagner (ISW) with a fresh random word for each gate.
+//!
+//! Plaintext and ciphertext are public, so blocks are bitsliced before
+//! masking and unmasked after the last round.
+
+use super::{
+    add_round_key, bitslice_blocks, inv_bitslice, inv_mix_columns_0, inv_mix_columns_1,
+    inv_shift_rows_2, mix_columns_0, mix_columns_1, shift_rows_2, FixsliceKeys128, FixsliceKeys192,
+    FixsliceKeys256, State, FIXSLICE_BLOCKS,
+};
+#[cfg(not(feature = "compact"))]
+use super::{inv_mix_columns_2, inv_mix_columns_3, mix_columns_2, mix_columns_3};
+use crate::Block;
+use core::ops::{BitAnd, BitXor};
+use rand_core::RngCore;
+
+/// Two Boolean shares of the fixsliced state
+type MaskedState = [State; 2];
+
+/// Apply a linear function to both shares of the state.
+macro_rules! linear {
+    ($func:ident, $state:ident) => {
+        for share in $state.iter_mut() {
+            $func(share);
+        }
+    };
+}
+
+/// Word of the fixsliced state which can be sampled from an RNG
+trait Word: Copy + BitAnd<Output = Self> + BitXor<Output = Self> {
+    fn random<R: RngCore>(rng: &mut R) -> Self;
+}
+
+impl Word for u32 {
+    #[inline(always)]
+    fn random<R: RngCore>(rng: &mut R) -> Self {
+        rng.next_u32()
+    }
+}
+
+impl Word for u64 {
+    #[inline(always)]
+    fn random<R: RngCore>(rng: &mut R) -> Self {
+        rng.next_u64()
+    }
+}
+
+/// Masked word `x = self.0 ^ self.1`
+#[derive(Clone, Copy)]
+struct Shares<W>(W, W);
+
+impl<W: Word> BitXor for Shares<W> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: Self) -> Self {
+        Shares(self.0 ^ rhs.0, self.1 ^ rhs.1)
+    }
+}
+
+/// ISW multiplication: masked AND of `a` and `b` refreshed with a random word.
+///
+/// The cross products are added to the random word one at a time, so no
+/// intermediate value depends on both shares of an input.
+#[inline(always)]
+fn and<W: Word, R: RngCore>(a: Shares<W>, b: Shares<W>, rng: &mut R) -> Shares<W> {
+    let r = W::random(rng);
+    let c0 = (a.0 & b.0) ^ r;
+    let c1 = (a.1 & b.1) ^ ((r ^ (a.0 & b.1)) ^ (a.1 & b.0));
+    Shares(c0, c1)
+}
+
+#[inline(always)]
+fn load<W: Word>(state: &[[W; 8]; 2], i: usize) -> Shares<W> {
+    Shares(state[0][i], state[1][i])
+}
+
+#[inline(always)]
+fn store<W: Word>(state: &mut [[W; 8]; 2], i: usize, x: Shares<W>) {
+    state[0][i] = x.0;
+    state[1][i] = x.1;
+}
+
+/// Split the bitsliced plaintext (or ciphertext) into two shares.
+fn mask<W: Word, R: RngCore>(state: &[W; 8], rng: &mut R) -> [[W; 8]; 2] {
+    let mut masked = [*state; 2];
+    for i in 0..8 {
+        let m = W::random(rng);
+        masked[0][i] = state[i] ^ m;
+        masked[1][i] = m;
+    }
+    masked
+}
+
+/// Recombine the shares of the output state.
+fn unmask<W: Word>(state: &[[W; 8]; 2]) -> [W; 8] {
+    let mut output = state[0];
+    for (x0, x1) in output.iter_mut().zip(state[1].iter()) {
+        *x0 = *x0 ^ *x1;
+    }
+    output
+}
+
+/// Masked fully-fixsliced AES-128 decryption (the InvShiftRows is completely omitted).
+///
+/// Decrypts [`FIXSLICE_BLOCKS`] blocks in-place and in parallel.
+pub(crate) fn aes128_decrypt<R: RngCore>(
+    rkeys: &FixsliceKeys128,
+    blocks: &mut [Block],
+    rng: &mut R,
+) {
+    debug_assert_eq!(blocks.len(), FIXSLICE_BLOCKS);
+    let mut state = mask(&bitslice_blocks(blocks), rng);
+
+    add_round_key(&mut state[0], &rkeys[80..]);
+    inv_sub_bytes(&mut state, rng);
+
+    #[cfg(not(feature = "compact"))]
+    {
+        linear!(inv_shift_rows_2, state);
+    }
+
+    let mut rk_off = 72;
+    loop {
+        #[cfg(feature = "compact")]
+        {
+            linear!(inv_shift_rows_2, state);
+        }
+
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        linear!(inv_mix_columns_1, state);
+        inv_sub_bytes(&mut state, rng);
+        rk_off -= 8;
+
+        if rk_off == 0 {
+            break;
+        }
+
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        linear!(inv_mix_columns_0, state);
+        inv_sub_bytes(&mut state, rng);
+        rk_off -= 8;
+
+        #[cfg(not(feature = "compact"))]
+        {
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            linear!(inv_mix_columns_3, state);
+            inv_sub_bytes(&mut state, rng);
+            rk_off -= 8;
+
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            linear!(inv_mix_columns_2, state);
+            inv_sub_bytes(&mut state, rng);
+            rk_off -= 8;
+        }
+    }
+
+    add_round_key(&mut state[0], &rkeys[..8]);
+
+    inv_bitslice(&unmask(&state), blocks);
+}
+
+/// Masked fully-fixsliced AES-128 encryption (the ShiftRows is completely omitted).
+///
+/// Encrypts [`FIXSLICE_BLOCKS`] blocks in-place and in parallel.
+pub(crate) fn aes128_encrypt<R: RngCore>(
+    rkeys: &FixsliceKeys128,
+    blocks: &mut [Block],
+    rng: &mut R,
+) {
+    debug_assert_eq!(blocks.len(), FIXSLICE_BLOCKS);
+    let mut state = mask(&bitslice_blocks(blocks), rng);
+
+    add_round_key(&mut state[0], &rkeys[..8]);
+
+    let mut rk_off = 8;
+    loop {
+        sub_bytes(&mut state, rng);
+        linear!(mix_columns_1, state);
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        rk_off += 8;
+
+        #[cfg(feature = "compact")]
+        {
+            linear!(shift_rows_2, state);
+        }
+
+        if rk_off == 80 {
+            break;
+        }
+
+        #[cfg(not(feature = "compact"))]
+        {
+            sub_bytes(&mut state, rng);
+            linear!(mix_columns_2, state);
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            rk_off += 8;
+
+            sub_bytes(&mut state, rng);
+            linear!(mix_columns_3, state);
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            rk_off += 8;
+        }
+
+        sub_bytes(&mut state, rng);
+        linear!(mix_columns_0, state);
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        rk_off += 8;
+    }
+
+    #[cfg(not(feature = "compact"))]
+    {
+        linear!(shift_rows_2, state);
+    }
+
+    sub_bytes(&mut state, rng);
+    add_round_key(&mut state[0], &rkeys[80..]);
+
+    inv_bitslice(&unmask(&state), blocks);
+}
+
+/// Masked fully-fixsliced AES-192 decryption (the InvShiftRows is completely omitted).
+///
+/// Decrypts [`FIXSLICE_BLOCKS`] blocks in-place and in parallel.
+pub(crate) fn aes192_decrypt<R: RngCore>(
+    rkeys: &FixsliceKeys192,
+    blocks: &mut [Block],
+    rng: &mut R,
+) {
+    debug_assert_eq!(blocks.len(), FIXSLICE_BLOCKS);
+    let mut state = mask(&bitslice_blocks(blocks), rng);
+
+    add_round_key(&mut state[0], &rkeys[96..]);
+    inv_sub_bytes(&mut state, rng);
+
+    let mut rk_off = 88;
+    loop {
+        #[cfg(feature = "compact")]
+        {
+            linear!(inv_shift_rows_2, state);
+        }
+        #[cfg(not(feature = "compact"))]
+        {
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            linear!(inv_mix_columns_3, state);
+            inv_sub_bytes(&mut state, rng);
+            rk_off -= 8;
+
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            linear!(inv_mix_columns_2, state);
+            inv_sub_bytes(&mut state, rng);
+            rk_off -= 8;
+        }
+
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        linear!(inv_mix_columns_1, state);
+        inv_sub_bytes(&mut state, rng);
+        rk_off -= 8;
+
+        if rk_off == 0 {
+            break;
+        }
+
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        linear!(inv_mix_columns_0, state);
+        inv_sub_bytes(&mut state, rng);
+        rk_off -= 8;
+    }
+
+    add_round_key(&mut state[0], &rkeys[..8]);
+
+    inv_bitslice(&unmask(&state), blocks);
+}
+
+/// Masked fully-fixsliced AES-192 encryption (the ShiftRows is completely omitted).
+///
+/// Encrypts [`FIXSLICE_BLOCKS`] blocks in-place and in parallel.
+pub(crate) fn aes192_encrypt<R: RngCore>(
+    rkeys: &FixsliceKeys192,
+    blocks: &mut [Block],
+    rng: &mut R,
+) {
+    debug_assert_eq!(blocks.len(), FIXSLICE_BLOCKS);
+    let mut state = mask(&bitslice_blocks(blocks), rng);
+
+    add_round_key(&mut state[0], &rkeys[..8]);
+
+    let mut rk_off = 8;
+    loop {
+        sub_bytes(&mut state, rng);
+        linear!(mix_columns_1, state);
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        rk_off += 8;
+
+        #[cfg(feature = "compact")]
+        {
+            linear!(shift_rows_2, state);
+        }
+        #[cfg(not(feature = "compact"))]
+        {
+            sub_bytes(&mut state, rng);
+            linear!(mix_columns_2, state);
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            rk_off += 8;
+
+            sub_bytes(&mut state, rng);
+            linear!(mix_columns_3, state);
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            rk_off += 8;
+        }
+
+        if rk_off == 96 {
+            break;
+        }
+
+        sub_bytes(&mut state, rng);
+        linear!(mix_columns_0, state);
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        rk_off += 8;
+    }
+
+    sub_bytes(&mut state, rng);
+    add_round_key(&mut state[0], &rkeys[96..]);
+
+    inv_bitslice(&unmask(&state), blocks);
+}
+
+/// Masked fully-fixsliced AES-256 decryption (the InvShiftRows is completely omitted).
+///
+/// Decrypts [`FIXSLICE_BLOCKS`] blocks in-place and in parallel.
+pub(crate) fn aes256_decrypt<R: RngCore>(
+    rkeys: &FixsliceKeys256,
+    blocks: &mut [Block],
+    rng: &mut R,
+) {
+    debug_assert_eq!(blocks.len(), FIXSLICE_BLOCKS);
+    let mut state = mask(&bitslice_blocks(blocks), rng);
+
+    add_round_key(&mut state[0], &rkeys[112..]);
+    inv_sub_bytes(&mut state, rng);
+
+    #[cfg(not(feature = "compact"))]
+    {
+        linear!(inv_shift_rows_2, state);
+    }
+
+    let mut rk_off = 104;
+    loop {
+        #[cfg(feature = "compact")]
+        {
+            linear!(inv_shift_rows_2, state);
+        }
+
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        linear!(inv_mix_columns_1, state);
+        inv_sub_bytes(&mut state, rng);
+        rk_off -= 8;
+
+        if rk_off == 0 {
+            break;
+        }
+
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        linear!(inv_mix_columns_0, state);
+        inv_sub_bytes(&mut state, rng);
+        rk_off -= 8;
+
+        #[cfg(not(feature = "compact"))]
+        {
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            linear!(inv_mix_columns_3, state);
+            inv_sub_bytes(&mut state, rng);
+            rk_off -= 8;
+
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            linear!(inv_mix_columns_2, state);
+            inv_sub_bytes(&mut state, rng);
+            rk_off -= 8;
+        }
+    }
+
+    add_round_key(&mut state[0], &rkeys[..8]);
+
+    inv_bitslice(&unmask(&state), blocks);
+}
+
+/// Masked fully-fixsliced AES-256 encryption (the ShiftRows is completely omitted).
+///
+/// Encrypts [`FIXSLICE_BLOCKS`] blocks in-place and in parallel.
+pub(crate) fn aes256_encrypt<R: RngCore>(
+    rkeys: &FixsliceKeys256,
+    blocks: &mut [Block],
+    rng: &mut R,
+) {
+    debug_assert_eq!(blocks.len(), FIXSLICE_BLOCKS);
+    let mut state = mask(&bitslice_blocks(blocks), rng);
+
+    add_round_key(&mut state[0], &rkeys[..8]);
+
+    let mut rk_off = 8;
+    loop {
+        sub_bytes(&mut state, rng);
+        linear!(mix_columns_1, state);
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        rk_off += 8;
+
+        #[cfg(feature = "compact")]
+        {
+            linear!(shift_rows_2, state);
+        }
+
+        if rk_off == 112 {
+            break;
+        }
+
+        #[cfg(not(feature = "compact"))]
+        {
+            sub_bytes(&mut state, rng);
+            linear!(mix_columns_2, state);
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            rk_off += 8;
+
+            sub_bytes(&mut state, rng);
+            linear!(mix_columns_3, state);
+            add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+            rk_off += 8;
+        }
+
+        sub_bytes(&mut state, rng);
+        linear!(mix_columns_0, state);
+        add_round_key(&mut state[0], &rkeys[rk_off..(rk_off + 8)]);
+        rk_off += 8;
+    }
+
+    #[cfg(not(feature = "compact"))]
+    {
+        linear!(shift_rows_2, state);
+    }
+
+    sub_bytes(&mut state, rng);
+    add_round_key(&mut state[0], &rkeys[112..]);
+
+    inv_bitslice(&unmask(&state), blocks);
+}
+
+/// Masked [`super::inv_sub_bytes`], which is a true inverse of [`sub_bytes`].
+fn inv_sub_bytes<R: RngCore>(state: &mut MaskedState, rng: &mut R) {
+    let u7 = load(state, 0);
+    let u6 = load(state, 1);
+    let u5 = load(state, 2);
+    let u4 = load(state, 3);
+    let u3 = load(state, 4);
+    let u2 = load(state, 5);
+    let u1 = load(state, 6);
+    let u0 = load(state, 7);
+    let t23 = u0 ^ u3;
+    let t8 = u1 ^ t23;
+    let m2 = and(t23, t8, rng);
+    let t4 = u4 ^ t8;
+    let t22 = u1 ^ u3;
+    let t2 = u0 ^ u1;
+    let t1 = u3 ^ u4;
+    let t9 = u7 ^ t1;
+    let m7 = and(t22, t9, rng);
+    let t24 = u4 ^ u7;
+    let t10 = t2 ^ t24;
+    let m14 = and(t2, t10, rng);
+    let r5 = u6 ^ u7;
+    let t3 = t1 ^ r5;
+    let t13 = t2 ^ r5;
+    let t19 = t22 ^ r5;
+    let t17 = u2 ^ t19;
+    let t25 = u2 ^ t1;
+    let r13 = u1 ^ u6;
+    let t20 = t24 ^ r13;
+    let m9 = and(t20, t17, rng);
+    let r17 = u2 ^ u5;
+    let t6 = t22 ^ r17;
+    let m1 = and(t13, t6, rng);
+    let y5 = u0 ^ r17;
+    let m4 = and(t19, y5, rng);
+    let m5 = m4 ^ m1;
+    let m17 = m5 ^ t24;
+    let r18 = u5 ^ u6;
+    let t27 = t1 ^ r18;
+    let t15 = t10 ^ t27;
+    let m11 = and(t1, t15, rng);
+    let m15 = m14 ^ m11;
+    let m21 = m17 ^ m15;
+    let m12 = and(t4, t27, rng);
+    let m13 = m12 ^ m11;
+    let t14 = t10 ^ r18;
+    let m3 = t14 ^ m1;
+    let m16 = m3 ^ m2;
+    let m20 = m16 ^ m13;
+    let r19 = u2 ^ u4;
+    let t16 = r13 ^ r19;
+    let t26 = t3 ^ t16;
+    let m6 = and(t3, t16, rng);
+    let m8 = t26 ^ m6;
+    let m18 = m8 ^ m7;
+    let m22 = m18 ^ m13;
+    let m25 = and(m22, m20, rng);
+    let m26 = m21 ^ m25;
+    let m10 = m9 ^ m6;
+    let m19 = m10 ^ m15;
+    let m23 = m19 ^ t25;
+    let m28 = m23 ^ m25;
+    let m24 = m22 ^ m23;
+    let m30 = and(m26, m24, rng);
+    let m39 = m23 ^ m30;
+    let m48 = and(m39, y5, rng);
+    let m57 = and(m39, t19, rng);
+    let m36 = m24 ^ m25;
+    let m31 = and(m20, m23, rng);
+    let m27 = m20 ^ m21;
+    let m32 = and(m27, m31, rng);
+    let m29 = and(m28, m27, rng);
+    let m37 = m21 ^ m29;
+    let m42 = m37 ^ m39;
+    let m52 = and(m42, t15, rng);
+    let m61 = and(m42, t1, rng);
+    let p0 = m52 ^ m61;
+    let p16 = m57 ^ m61;
+    let m60 = and(m37, t20, rng);
+    let m51 = and(m37, t17, rng);
+    let m33 = m27 ^ m25;
+    let m38 = m32 ^ m33;
+    let m43 = m37 ^ m38;
+    let m49 = and(m43, t16, rng);
+    let p6 = m49 ^ m60;
+    let p13 = m49 ^ m51;
+    let m58 = and(m43, t3, rng);
+    let m50 = and(m38, t9, rng);
+    let m59 = and(m38, t22, rng);
+    let p1 = m58 ^ m59;
+    let p7 = p0 ^ p1;
+    let m34 = and(m21, m22, rng);
+    let m35 = and(m24, m34, rng);
+    let m40 = m35 ^ m36;
+    let m41 = m38 ^ m40;
+    let m45 = m42 ^ m41;
+    let m53 = and(m45, t27, rng);
+    let p8 = m50 ^ m53;
+    let p23 = p7 ^ p8;
+    let m62 = and(m45, t4, rng);
+    let p14 = m49 ^ m62;
+    let s6 = p14 ^ p23;
+    let m54 = and(m41, t10, rng);
+    let p2 = m54 ^ m62;
+    let p22 = p2 ^ p7;
+    let s0 = p13 ^ p22;
+    let p17 = m58 ^ p2;
+    let p15 = m54 ^ m59;
+    let m63 = and(m41, t2, rng);
+    let m44 = m39 ^ m40;
+    let m46 = and(m44, t6, rng);
+    let p5 = m46 ^ m51;
+    let p18 = m63 ^ p5;
+    let p24 = p5 ^ p7;
+    let p12 = m46 ^ m48;
+    let s3 = p12 ^ p22;
+    let m55 = and(m44, t13, rng);
+    let p9 = m55 ^ m63;
+    let s7 = p9 ^ p16;
+    let m47 = and(m40, t8, rng);
+    let p3 = m47 ^ m50;
+    let p19 = p2 ^ p3;
+    let s5 = p19 ^ p24;
+    let p11 = p0 ^ p3;
+    let p26 = p9 ^ p11;
+    let m56 = and(m40, t23, rng);
+    let p4 = m48 ^ m56;
+    let p20 = p4 ^ p6;
+    let p29 = p15 ^ p20;
+    let s1 = p26 ^ p29;
+    let p10 = m57 ^ p4;
+    let p27 = p10 ^ p18;
+    let s4 = p23 ^ p27;
+    let p25 = p6 ^ p10;
+    let p28 = p11 ^ p25;
+    let s2 = p17 ^ p28;
+    store(state, 0, s7);
+    store(state, 1, s6);
+    store(state, 2, s5);
+    store(state, 3, s4);
+    store(state, 4, s3);
+    store(state, 5, s2);
+    store(state, 6, s1);
+    store(state, 7, s0);
+}
+
+/// Masked [`super::sub_bytes`] with the NOT operations moved to the key
+/// schedule, so they only apply to the first share.
+fn sub_bytes<R: RngCore>(state: &mut MaskedState, rng: &mut R) {
+    let u7 = load(state, 0);
+    let u6 = load(state, 1);
+    let u5 = load(state, 2);
+    let u4 = load(state, 3);
+    let u3 = load(state, 4);
+    let u2 = load(state, 5);
+    let u1 = load(state, 6);
+    let u0 = load(state, 7);
+    let y14 = u3 ^ u5;
+    let y13 = u0 ^ u6;
+    let y12 = y13 ^ y14;
+    let t1 = u4 ^ y12;
+    let y15 = t1 ^ u5;
+    let t2 = and(y12, y15, rng);
+    let y6 = y15 ^ u7;
+    let y20 = t1 ^ u1;
+    let y9 = u0 ^ u3;
+    let y11 = y20 ^ y9;
+    let t12 = and(y9, y11, rng);
+    let y7 = u7 ^ y11;
+    let y8 = u0 ^ u5;
+    let t0 = u1 ^ u2;
+    let y10 = y15 ^ t0;
+    let y17 = y10 ^ y11;
+    let t13 = and(y14, y17, rng);
+    let t14 = t13 ^ t12;
+    let y19 = y10 ^ y8;
+    let t15 = and(y8, y10, rng);
+    let t16 = t15 ^ t12;
+    let y16 = t0 ^ y11;
+    let y21 = y13 ^ y16;
+    let t7 = and(y13, y16, rng);
+    let y18 = u0 ^ y16;
+    let y1 = t0 ^ u7;
+    let y4 = y1 ^ u3;
+    let t5 = and(y4, u7, rng);
+    let t6 = t5 ^ t2;
+    let t18 = t6 ^ t16;
+    let t22 = t18 ^ y19;
+    let y2 = y1 ^ u0;
+    let t10 = and(y2, y7, rng);
+    let t11 = t10 ^ t7;
+    let t20 = t11 ^ t16;
+    let t24 = t20 ^ y18;
+    let y5 = y1 ^ u6;
+    let t8 = and(y5, y1, rng);
+    let t9 = t8 ^ t7;
+    let t19 = t9 ^ t14;
+    let t23 = t19 ^ y21;
+    let y3 = y5 ^ y8;
+    let t3 = and(y3, y6, rng);
+    let t4 = t3 ^ t2;
+    let t17 = t4 ^ y20;
+    let t21 = t17 ^ t14;
+    let t26 = and(t21, t23, rng);
+    let t27 = t24 ^ t26;
+    let t31 = t22 ^ t26;
+    let t25 = t21 ^ t22;
+    let t28 = and(t25, t27, rng);
+    let t29 = t28 ^ t22;
+    let z14 = and(t29, y2, rng);
+    let z5 = and(t29, y7, rng);
+    let t30 = t23 ^ t24;
+    let t32 = and(t31, t30, rng);
+    let t33 = t32 ^ t24;
+    let t35 = t27 ^ t33;
+    let t36 = and(t24, t35, rng);
+    let t38 = t27 ^ t36;
+    let t39 = and(t29, t38, rng);
+    let t40 = t25 ^ t39;
+    let t43 = t29 ^ t40;
+    let z3 = and(t43, y16, rng);
+    let tc12 = z3 ^ z5;
+    let z12 = and(t43, y13, rng);
+    let z13 = and(t40, y5, rng);
+    let z4 = and(t40, y1, rng);
+    let tc6 = z3 ^ z4;
+    let t34 = t23 ^ t33;
+    let t37 = t36 ^ t34;
+    let t41 = t40 ^ t37;
+    let z8 = and(t41, y10, rng);
+    let z17 = and(t41, y8, rng);
+    let t44 = t33 ^ t37;
+    let z0 = and(t44, y15, rng);
+    let z9 = and(t44, y12, rng);
+    let z10 = and(t37, y3, rng);
+    let z1 = and(t37, y6, rng);
+    let tc5 = z1 ^ z0;
+    let tc11 = tc6 ^ tc5;
+    let z11 = and(t33, y4, rng);
+    let t42 = t29 ^ t33;
+    let t45 = t42 ^ t41;
+    let z7 = and(t45, y17, rng);
+    let tc8 = z7 ^ tc6;
+    let z16 = and(t45, y14, rng);
+    let z6 = and(t42, y11, rng);
+    let tc16 = z6 ^ tc8;
+    let z15 = and(t42, y9, rng);
+    let tc20 = z15 ^ tc16;
+    let tc1 = z15 ^ z16;
+    let tc2 = z10 ^ tc1;
+    let tc21 = tc2 ^ z11;
+    let tc3 = z9 ^ tc2;
+    let s0 = tc3 ^ tc16;
+    let s3 = tc3 ^ tc11;
+    let s1 = s3 ^ tc16;
+    let tc13 = z13 ^ tc1;
+    let z2 = and(t33, u7, rng);
+    let tc4 = z0 ^ z2;
+    let tc7 = z12 ^ tc4;
+    let tc9 = z8 ^ tc7;
+    let tc10 = tc8 ^ tc9;
+    let tc17 = z14 ^ tc10;
+    let s5 = tc21 ^ tc17;
+    let tc26 = tc17 ^ tc20;
+    let s2 = tc26 ^ z17;
+    let tc14 = tc4 ^ tc12;
+    let tc18 = tc13 ^ tc14;
+    let s6 = tc10 ^ tc18;
+    let s7 = z12 ^ tc18;
+    let s4 = tc14 ^ s3;
+    store(state, 0, s7);
+    store(state, 1, s6);
+    store(state, 2, s5);
+    store(state, 3, s4);
+    store(state, 4, s3);
+    store(state, 5, s2);
+    store(state, 6, s1);
+    store(state, 7, s0);
+}
@@ -0,0 +1,121 @@
+//! Runtime backend selection. The override is process-wide, so everything
+//! is checked in a single test.
+
+#![cfg_attr(all(feature = "armv8", target_arch = "aarch64"), feature(stdsimd))]
+
+use aes::cipher::{
+    consts::{U16, U8},
+    generic_array::GenericArray,
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use aes::{Aes128, Aes192, Aes256, Backend, ParBlocks};
+
+/// Backend selected by runtime detection
+fn detected() -> Backend {
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(feature = "force-soft")
+    ))]
+    {
+        use std::is_x86_feature_detected;
+
+        if is_x86_feature_detected!("aes") {
+            #[cfg(feature = "vaes")]
+            {
+                if is_x86_feature_detected!("vaes") {
+                    if is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw") {
+                        return Backend::VaesAvx512;
+                    }
+                    if is_x86_feature_detected!("avx2") {
+                        return Backend::VaesAvx2;
+                    }
+                }
+            }
+            return Backend::AesNi;
+        }
+    }
+    #[cfg(all(
+        target_arch = "aarch64",
+        feature = "armv8",
+        not(feature = "force-soft")
+    ))]
+    {
+        use std::arch::is_aarch64_feature_detected;
+
+        if is_aarch64_feature_detected!("aes") {
+            return Backend::Armv8;
+        }
+    }
+    Backend::Soft
+}
+
+fn check<C>(a: &C, b: &C)
+where
+    C: BlockCipher<BlockSize = U16, ParBlocks = U8> + BlockEncrypt + BlockDecrypt,
+{
+    let mut blocks1 = ParBlocks::default();
+    for (i, block) in blocks1.iter_mut().enumerate() {
+        block[0] = i as u8;
+    }
+    let mut blocks2 = blocks1;
+    a.encrypt_par_blocks(&mut blocks1);
+    b.encrypt_par_blocks(&mut blocks2);
+    assert_eq!(blocks1, blocks2);
+
+    let mut block1 = blocks1[0];
+    let mut block2 = blocks1[0];
+    a.decrypt_block(&mut block1);
+    b.decrypt_block(&mut block2);
+    assert_eq!(block1, block2);
+
+    // long enough for the largest chunks of multi-block operations, followed
+    // by a parallel chunk and single blocks
+    let mut blocks1 = [blocks1[1]; 45];
+    for (i, block) in blocks1.iter_mut().enumerate() {
+        block[1] = i as u8;
+    }
+    let mut blocks2 = blocks1;
+    a.encrypt_blocks(&mut blocks1);
+    b.encrypt_blocks(&mut blocks2);
+    assert_eq!(blocks1, blocks2);
+    a.decrypt_blocks(&mut blocks1);
+    b.decrypt_blocks(&mut blocks2);
+    assert_eq!(blocks1, blocks2);
+}
+
+#[test]
+fn backend_override() {
+    let key = GenericArray::from([0x42; 32]);
+    let key128 = GenericArray::from_slice(&key[..16]);
+    let key192 = GenericArray::from_slice(&key[..24]);
+
+    assert!(!aes::is_soft_forced());
+    let aes128 = Aes128::new(key128);
+    let aes192 = Aes192::new(key192);
+    let aes256 = Aes256::new(&key);
+    assert_eq!(aes128.backend(), detected());
+    assert_eq!(aes192.backend(), detected());
+    assert_eq!(aes256.clone().backend(), detected());
+
+    let soft128 = Aes128::new_soft(key128);
+    let soft192 = Aes192::new_soft(key192);
+    let soft256 = Aes256::new_soft(&key);
+    assert_eq!(soft128.backend(), Backend::Soft);
+    assert_eq!(soft192.backend(), Backend::Soft);
+    assert_eq!(soft256.clone().backend(), Backend::Soft);
+    check(&aes128, &soft128);
+    check(&aes192, &soft192);
+    check(&aes256, &soft256);
+
+    aes::set_force_soft(true);
+    assert!(aes::is_soft_forced());
+    assert_eq!(Aes128::new(key128).backend(), Backend::Soft);
+    assert_eq!(Aes256::new(&key).backend(), Backend::Soft);
+    // existing instances keep their backend
+    assert_eq!(aes128.backend(), detected());
+    assert_eq!(aes::self_test(), Ok(()));
+
+    aes::set_force_soft(false);
+    assert_eq!(Aes128::new(key128).backend(), detected());
+    assert_eq!(Backend::AesNi.to_string(), "aes-ni");
+}
@@ -0,0 +1,84 @@
+//! Tests for the encrypt-only and decrypt-only cipher types.
+//!
+//! Test vectors are from FIPS 197 Appendix C.
+
+use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher};
+use aes::{Block, ParBlocks};
+use hex_literal::hex;
+
+const PLAINTEXT: [u8; 16] = hex!("00112233445566778899aabbccddeeff");
+
+macro_rules! enc_dec_test {
+    ($name:ident, $cipher:ty, $enc:ty, $dec:ty, $key:expr, $ciphertext:expr) => {
+        #[test]
+        fn $name() {
+            let key = GenericArray::from_slice(&$key);
+            let enc = <$enc>::new(key);
+            let dec = <$dec>::new(key);
+
+            let mut block = Block::clone_from_slice(&PLAINTEXT);
+            enc.encrypt_block(&mut block);
+            assert_eq!(block[..], $ciphertext[..]);
+            dec.decrypt_block(&mut block);
+            assert_eq!(block[..], PLAINTEXT[..]);
+
+            // compare with the full cipher on parallel and multi-block
+            // code paths
+            let cipher = <$cipher>::new(key);
+            assert_eq!(enc.backend(), cipher.backend());
+            assert_eq!(dec.backend(), cipher.backend());
+
+            let mut blocks1 = [Block::default(); 45];
+            for (i, block) in blocks1.iter_mut().enumerate() {
+                block[0] = i as u8;
+            }
+            let pt = blocks1;
+            let mut blocks2 = blocks1;
+            enc.encrypt_blocks(&mut blocks1);
+            cipher.encrypt_blocks(&mut blocks2);
+            assert_eq!(blocks1, blocks2);
+
+            let mut par1 = ParBlocks::clone_from_slice(&pt[..8]);
+            let mut par2 = par1.clone();
+            enc.encrypt_par_blocks(&mut par1);
+            cipher.encrypt_par_blocks(&mut par2);
+            assert_eq!(par1, par2);
+            dec.decrypt_par_blocks(&mut par1);
+            assert_eq!(par1[..], pt[..8]);
+
+            // decryption keys derived from the encryption keys
+            <$dec>::from(&enc).decrypt_blocks(&mut blocks1);
+            assert_eq!(blocks1, pt);
+            let dec: $dec = <$enc>::new_soft(key).into();
+            dec.decrypt_blocks(&mut blocks2);
+            assert_eq!(blocks2, pt);
+        }
+    };
+}
+
+enc_dec_test!(
+    aes128_enc_dec,
+    aes::Aes128,
+    aes::Aes128Enc,
+    aes::Aes128Dec,
+    hex!("000102030405060708090a0b0c0d0e0f"),
+    hex!("69c4e0d86a7b0430d8cdb78070b4c55a")
+);
+
+enc_dec_test!(
+    aes192_enc_dec,
+    aes::Aes192,
+    aes::Aes192Enc,
+    aes::Aes192Dec,
+    hex!("000102030405060708090a0b0c0d0e0f1011121314151617"),
+    hex!("dda97ca4864cdfe06eaf70a0ec0d7191")
+);
+
+enc_dec_test!(
+    aes256_enc_dec,
+    aes::Aes256,
+    aes::Aes256Enc,
+    aes::Aes256Dec,
+    hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+    hex!("8ea2b7ca516745bfeafc49904b496089")
+);
@@ -153,3 +153,227 @@ fn inv_mix_columns_fips197_vector() {
     aes::hazmat::inv_mix_columns(&mut block);
     assert_eq!(block.as_slice(), &hex!("4773b91ff72f354361cb018ea1e6cf2c"))
 }
+
+#[test]
+fn cipher_round_last_fips197_vector() {
+    // round 10
+    let mut block = Block::from(hex!("bd6e7c3df2b5779e0b61216e8b10b689"));
+    aes::hazmat::cipher_round_last(&mut block, &hex!("13111d7fe3944a17f307a78b4d2b30c5").into());
+    assert_eq!(block.as_slice(), &hex!("69c4e0d86a7b0430d8cdb78070b4c55a"));
+}
+
+#[test]
+fn cipher_round_last_par_fips197_vector() {
+    let mut blocks = ParBlocks::default();
+    let mut round_keys = ParBlocks::default();
+
+    for i in 0..8 {
+        blocks[i] = Block::from(hex!("bd6e7c3df2b5779e0b61216e8b10b689"));
+        round_keys[i] = Block::from(hex!("13111d7fe3944a17f307a78b4d2b30c5"));
+    }
+
+    aes::hazmat::cipher_round_last_par(&mut blocks, &round_keys);
+
+    for block in blocks.iter() {
+        assert_eq!(block.as_slice(), &hex!("69c4e0d86a7b0430d8cdb78070b4c55a"));
+    }
+}
+
+#[test]
+fn equiv_inv_cipher_round_last_fips197_vector() {
+    // round 10
+    let mut block = Block::from(hex!("6353e08c0960e104cd70b751bacad0e7"));
+    aes::hazmat::equiv_inv_cipher_round_last(
+        &mut block,
+        &hex!("000102030405060708090a0b0c0d0e0f").into(),
+    );
+    assert_eq!(block.as_slice(), &hex!("00112233445566778899aabbccddeeff"));
+}
+
+#[test]
+fn equiv_inv_cipher_round_last_par_fips197_vector() {
+    let mut blocks = ParBlocks::default();
+    let mut round_keys = ParBlocks::default();
+
+    for i in 0..8 {
+        blocks[i] = Block::from(hex!("6353e08c0960e104cd70b751bacad0e7"));
+        round_keys[i] = Block::from(hex!("000102030405060708090a0b0c0d0e0f"));
+    }
+
+    aes::hazmat::equiv_inv_cipher_round_last_par(&mut blocks, &round_keys);
+
+    for block in blocks.iter() {
+        assert_eq!(block.as_slice(), &hex!("00112233445566778899aabbccddeeff"));
+    }
+}
+
+/// FIPS 197 Appendix A.1: Expansion of a 128-bit Cipher Key
+const AES128_EXP_KEYS: [[u8; 16]; 11] = [
+    hex!("2b7e151628aed2a6abf7158809cf4f3c"),
+    hex!("a0fafe1788542cb123a339392a6c7605"),
+    hex!("f2c295f27a96b9435935807a7359f67f"),
+    hex!("3d80477d4716fe3e1e237e446d7a883b"),
+    hex!("ef44a541a8525b7fb671253bdb0bad00"),
+    hex!("d4d1c6f87c839d87caf2b8bc11f915bc"),
+    hex!("6d88a37a110b3efddbf98641ca0093fd"),
+    hex!("4e54f70e5f5fc9f384a64fb24ea6dc4f"),
+    hex!("ead27321b58dbad2312bf5607f8d292f"),
+    hex!("ac7766f319fadc2128d12941575c006e"),
+    hex!("d014f9a8c9ee2589e13f0cc8b6630ca6"),
+];
+
+/// Equivalent inverse cipher round keys for [`AES128_EXP_KEYS`]
+const AES128_EXP_INVKEYS: [[u8; 16]; 11] = [
+    hex!("d014f9a8c9ee2589e13f0cc8b6630ca6"),
+    hex!("0c7b5a631319eafeb0398890664cfbb4"),
+    hex!("df7d925a1f62b09da320626ed6757324"),
+    hex!("12c07647c01f22c7bc42d2f37555114a"),
+    hex!("6efcd876d2df54807c5df034c917c3b9"),
+    hex!("6ea30afcbc238cf6ae82a4b4b54a338d"),
+    hex!("90884413d280860a12a128421bc89739"),
+    hex!("7c1f13f74208c219c021ae480969bf7b"),
+    hex!("cc7505eb3e17d1ee82296c51c9481133"),
+    hex!("2b3708a7f262d405bc3ebdbf4b617d62"),
+    hex!("2b7e151628aed2a6abf7158809cf4f3c"),
+];
+
+/// FIPS 197 Appendix A.2: Expansion of a 192-bit Cipher Key
+const AES192_EXP_KEYS: [[u8; 16]; 13] = [
+    hex!("8e73b0f7da0e6452c810f32b809079e5"),
+    hex!("62f8ead2522c6b7bfe0c91f72402f5a5"),
+    hex!("ec12068e6c827f6b0e7a95b95c56fec2"),
+    hex!("4db7b4bd69b5411885a74796e92538fd"),
+    hex!("e75fad44bb095386485af05721efb14f"),
+    hex!("a448f6d94d6dce24aa326360113b30e6"),
+    hex!("a25e7ed583b1cf9a27f939436a94f767"),
+    hex!("c0a69407d19da4e1ec1786eb6fa64971"),
+    hex!("485f703222cb8755e26d135233f0b7b3"),
+    hex!("40beeb282f18a2596747d26b458c553e"),
+    hex!("a7e1466c9411f1df821f750aad07d753"),
+    hex!("ca4005388fcc5006282d166abc3ce7b5"),
+    hex!("e98ba06f448c773c8ecc720401002202"),
+];
+
+/// FIPS 197 Appendix A.3: Expansion of a 256-bit Cipher Key
+const AES256_EXP_KEYS: [[u8; 16]; 15] = [
+    hex!("603deb1015ca71be2b73aef0857d7781"),
+    hex!("1f352c073b6108d72d9810a30914dff4"),
+    hex!("9ba354118e6925afa51a8b5f2067fcde"),
+    hex!("a8b09c1a93d194cdbe49846eb75d5b9a"),
+    hex!("d59aecb85bf3c917fee94248de8ebe96"),
+    hex!("b5a9328a2678a647983122292f6c79b3"),
+    hex!("812c81addadf48ba24360af2fab8b464"),
+    hex!("98c5bfc9bebd198e268c3ba709e04214"),
+    hex!("68007bacb2df331696e939e46c518d80"),
+    hex!("c814e20476a9fb8a5025c02d59c58239"),
+    hex!("de1369676ccc5a71fa2563959674ee15"),
+    hex!("5886ca5d2e2f31d77e0af1fa27cf73c3"),
+    hex!("749c47ab18501ddae2757e4f7401905a"),
+    hex!("cafaaae3e4d59b349adf6acebd10190d"),
+    hex!("fe4890d1e6188d0b046df344706c631e"),
+];
+
+fn assert_round_keys(round_keys: &[Block], expected: &[[u8; 16]]) {
+    assert_eq!(round_keys.len(), expected.len());
+
+    for (round_key, expected) in round_keys.iter().zip(expected) {
+        assert_eq!(round_key.as_slice(), expected);
+    }
+}
+
+#[test]
+fn expand_key_fips197_vectors() {
+    let key = hex!("2b7e151628aed2a6abf7158809cf4f3c");
+    let round_keys = aes::hazmat::aes128_expand_key(&key.into());
+    assert_round_keys(&round_keys, &AES128_EXP_KEYS);
+
+    let key = hex!("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b");
+    let round_keys = aes::hazmat::aes192_expand_key(&key.into());
+    assert_round_keys(&round_keys, &AES192_EXP_KEYS);
+
+    let key = hex!("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");
+    let round_keys = aes::hazmat::aes256_expand_key(&key.into());
+    assert_round_keys(&round_keys, &AES256_EXP_KEYS);
+}
+
+#[test]
+fn inv_expanded_keys_fips197_vector() {
+    let mut round_keys = [Block::default(); 11];
+    for (round_key, expected) in round_keys.iter_mut().zip(AES128_EXP_KEYS.iter()) {
+        *round_key = Block::from(*expected);
+    }
+
+    aes::hazmat::inv_expanded_keys(&mut round_keys);
+    assert_round_keys(&round_keys, &AES128_EXP_INVKEYS);
+}
+
+/// Encrypt and decrypt a block using only the hazmat functions.
+fn check_cipher(round_keys: &mut [Block], plaintext: &[u8; 16], ciphertext: &[u8; 16]) {
+    let n = round_keys.len();
+    let mut block = Block::from(*plaintext);
+
+    for (i, round_key) in round_keys.iter().enumerate() {
+        match i {
+            0 => {
+                for (a, b) in block.iter_mut().zip(round_key) {
+                    *a ^= *b;
+                }
+            }
+            _ if i == n - 1 => aes::hazmat::cipher_round_last(&mut block, round_key),
+            _ => aes::hazmat::cipher_round(&mut block, round_key),
+        }
+    }
+    assert_eq!(block.as_slice(), ciphertext);
+
+    aes::hazmat::inv_expanded_keys(round_keys);
+
+    for (i, round_key) in round_keys.iter().enumerate() {
+        match i {
+            0 => {
+                for (a, b) in block.iter_mut().zip(round_key) {
+                    *a ^= *b;
+                }
+            }
+            _ if i == n - 1 => aes::hazmat::equiv_inv_cipher_round_last(&mut block, round_key),
+            _ => aes::hazmat::equiv_inv_cipher_round(&mut block, round_key),
+        }
+    }
+    assert_eq!(block.as_slice(), plaintext);
+}
+
+/// Full cipher test vectors from FIPS 197 Appendix C.
+fn check_cipher_fips197_vectors() {
+    let plaintext = hex!("00112233445566778899aabbccddeeff");
+
+    let key = hex!("000102030405060708090a0b0c0d0e0f");
+    check_cipher(
+        &mut aes::hazmat::aes128_expand_key(&key.into()),
+        &plaintext,
+        &hex!("69c4e0d86a7b0430d8cdb78070b4c55a"),
+    );
+
+    let key = hex!("000102030405060708090a0b0c0d0e0f1011121314151617");
+    check_cipher(
+        &mut aes::hazmat::aes192_expand_key(&key.into()),
+        &plaintext,
+        &hex!("dda97ca4864cdfe06eaf70a0ec0d7191"),
+    );
+
+    let key = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+    check_cipher(
+        &mut aes::hazmat::aes256_expand_key(&key.into()),
+        &plaintext,
+        &hex!("8ea2b7ca516745bfeafc49904b496089"),
+    );
+}
+
+#[test]
+fn cipher_fips197_vectors() {
+    check_cipher_fips197_vectors();
+
+    // All backends produce the same results, so forcing the "soft" backend
+    // does not affect tests running concurrently.
+    aes::set_force_soft(true);
+    check_cipher_fips197_vectors();
+    aes::set_force_soft(false);
+}
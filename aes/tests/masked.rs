@@ -0,0 +1,95 @@
+//! Tests for the first-order masked ciphers.
+//!
+//! Test vectors are from NESSIE:
+//! https://www.cosic.esat.kuleuven.be/nessie/testvectors/
+
+#![cfg(feature = "masked")]
+
+use aes::cipher::{
+    blobby::Blob3Iterator, generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use aes::{Block, ParBlocks};
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// Deterministic xorshift RNG for reproducible masks, do NOT use it outside
+/// of tests.
+#[derive(Default)]
+struct TestRng(u64);
+
+impl RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // `xorshift64` has no zero state, an RNG always returning zero
+        // disables masking
+        if self.0 != 0 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+        }
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for TestRng {}
+
+macro_rules! masked_test {
+    ($name:ident, $test_name:expr, $masked:ident, $cipher:ty) => {
+        #[test]
+        fn $name() {
+            let data = include_bytes!(concat!("data/", $test_name, ".blb"));
+            for (i, row) in Blob3Iterator::new(data).unwrap().enumerate() {
+                let [key, pt, ct] = row.unwrap();
+                let key = GenericArray::from_slice(key);
+
+                let unmasked = <$cipher>::new(key);
+                for &seed in &[0, 1, 0x0123_4567_89ab_cdef] {
+                    let cipher = aes::$masked::new_with_rng(key, TestRng(seed));
+
+                    let mut block = Block::clone_from_slice(pt);
+                    cipher.encrypt_block(&mut block);
+                    assert_eq!(block[..], ct[..], "encryption of vector {}", i);
+                    cipher.decrypt_block(&mut block);
+                    assert_eq!(block[..], pt[..], "decryption of vector {}", i);
+
+                    // compare with the unmasked cipher on the parallel code path
+                    let mut blocks1 = ParBlocks::default();
+                    for (j, b) in blocks1.iter_mut().enumerate() {
+                        b.copy_from_slice(pt);
+                        b[0] = b[0].wrapping_add(j as u8);
+                    }
+                    let mut blocks2 = blocks1.clone();
+                    cipher.encrypt_par_blocks(&mut blocks1);
+                    unmasked.encrypt_par_blocks(&mut blocks2);
+                    assert_eq!(blocks1, blocks2, "parallel encryption of vector {}", i);
+                    cipher.decrypt_par_blocks(&mut blocks1);
+                    unmasked.decrypt_par_blocks(&mut blocks2);
+                    assert_eq!(blocks1, blocks2, "parallel decryption of vector {}", i);
+                }
+            }
+
+            // `NewBlockCipher` uses the `Default` RNG
+            let key = Default::default();
+            let mut block = Block::default();
+            let mut expected = Block::default();
+            aes::$masked::<TestRng>::new(&key).encrypt_block(&mut block);
+            <$cipher>::new(&key).encrypt_block(&mut expected);
+            assert_eq!(block, expected);
+        }
+    };
+}
+
+masked_test!(masked_aes128_test, "aes128", MaskedAes128, aes::Aes128);
+masked_test!(masked_aes192_test, "aes192", MaskedAes192, aes::Aes192);
+masked_test!(masked_aes256_test, "aes256", MaskedAes256, aes::Aes256);
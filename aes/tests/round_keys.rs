@@ -0,0 +1,124 @@
+//! Tests for construction from round keys and their export. The backend
+//! override is process-wide, so all ciphers are checked in a single test.
+#![cfg(feature = "hazmat")]
+
+use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher};
+use aes::hazmat::{aes128_expand_key, aes192_expand_key, aes256_expand_key};
+use aes::{Backend, Block};
+use hex_literal::hex;
+
+/// FIPS 197 Appendix A.1: Expansion of a 128-bit Cipher Key
+const AES128_KEY: [u8; 16] = hex!("2b7e151628aed2a6abf7158809cf4f3c");
+const AES128_LAST_ROUND_KEY: [u8; 16] = hex!("d014f9a8c9ee2589e13f0cc8b6630ca6");
+
+/// FIPS 197 Appendix A.2: Expansion of a 192-bit Cipher Key
+const AES192_KEY: [u8; 24] = hex!("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b");
+const AES192_LAST_ROUND_KEY: [u8; 16] = hex!("e98ba06f448c773c8ecc720401002202");
+
+/// FIPS 197 Appendix A.3: Expansion of a 256-bit Cipher Key
+const AES256_KEY: [u8; 32] =
+    hex!("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");
+const AES256_LAST_ROUND_KEY: [u8; 16] = hex!("fe4890d1e6188d0b046df344706c631e");
+
+macro_rules! round_keys_test {
+    (
+        $name:ident,
+        $cipher:ty,
+        $enc:ty,
+        $dec:ty,
+        $expand_key:path,
+        $key:expr,
+        $last_round_key:expr
+    ) => {
+        fn $name() {
+            let key = GenericArray::from_slice(&$key);
+            let round_keys = $expand_key(key);
+            assert_eq!(round_keys[0][..], $key[..16]);
+            assert_eq!(round_keys[round_keys.len() - 1][..], $last_round_key[..]);
+
+            let cipher = <$cipher>::new(key);
+            let enc = <$enc>::new(key);
+            let dec = <$dec>::new(key);
+            assert_eq!(cipher.round_keys(), round_keys);
+            assert_eq!(enc.round_keys(), round_keys);
+            assert_eq!(dec.round_keys(), round_keys);
+            assert_eq!(<$cipher>::new_soft(key).round_keys(), round_keys);
+            assert_eq!(<$dec>::new_soft(key).round_keys(), round_keys);
+
+            let mut blocks = [Block::default(); 13];
+            for (i, block) in blocks.iter_mut().enumerate() {
+                block[0] = i as u8;
+            }
+            let pt = blocks;
+            cipher.encrypt_blocks(&mut blocks);
+            let ct = blocks;
+
+            // The backend is selected on construction, just as with `new`
+            for &force_soft in &[false, true] {
+                aes::set_force_soft(force_soft);
+
+                let cipher = <$cipher>::from_round_keys(&round_keys);
+                let enc = <$enc>::from_round_keys(&round_keys);
+                let dec = <$dec>::from_round_keys(&round_keys);
+                if force_soft {
+                    assert_eq!(cipher.backend(), Backend::Soft);
+                }
+                assert_eq!(enc.backend(), cipher.backend());
+                assert_eq!(dec.backend(), cipher.backend());
+                assert_eq!(cipher.round_keys(), round_keys);
+                assert_eq!(enc.round_keys(), round_keys);
+                assert_eq!(dec.round_keys(), round_keys);
+
+                let mut blocks = pt;
+                cipher.encrypt_blocks(&mut blocks);
+                assert_eq!(blocks, ct);
+                cipher.decrypt_blocks(&mut blocks);
+                assert_eq!(blocks, pt);
+
+                enc.encrypt_blocks(&mut blocks);
+                assert_eq!(blocks, ct);
+                dec.decrypt_blocks(&mut blocks);
+                assert_eq!(blocks, pt);
+
+                aes::set_force_soft(false);
+            }
+        }
+    };
+}
+
+round_keys_test!(
+    aes128_round_keys,
+    aes::Aes128,
+    aes::Aes128Enc,
+    aes::Aes128Dec,
+    aes128_expand_key,
+    AES128_KEY,
+    AES128_LAST_ROUND_KEY
+);
+
+round_keys_test!(
+    aes192_round_keys,
+    aes::Aes192,
+    aes::Aes192Enc,
+    aes::Aes192Dec,
+    aes192_expand_key,
+    AES192_KEY,
+    AES192_LAST_ROUND_KEY
+);
+
+round_keys_test!(
+    aes256_round_keys,
+    aes::Aes256,
+    aes::Aes256Enc,
+    aes::Aes256Dec,
+    aes256_expand_key,
+    AES256_KEY,
+    AES256_LAST_ROUND_KEY
+);
+
+#[test]
+fn round_keys() {
+    aes128_round_keys();
+    aes192_round_keys();
+    aes256_round_keys();
+}
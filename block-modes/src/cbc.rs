@@ -52,7 +52,7 @@ where
         self.iv = {
             let mut iv = &self.iv;
             for block in blocks {
-                xor(block, &iv);
+                xor(block, iv);
                 self.cipher.encrypt_block(block);
                 iv = block;
             }
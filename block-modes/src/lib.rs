@@ -93,8 +93,11 @@ mod cfb8;
 mod ecb;
 mod ige;
 mod ofb;
+mod openpgp_cfb;
 mod pcbc;
+mod self_test;
 
+pub use block_ciphers_self_test::SelfTestError;
 pub use block_padding;
 pub use cipher;
 
@@ -106,6 +109,8 @@ pub use crate::{
     errors::{BlockModeError, InvalidKeyIvLength},
     ige::Ige,
     ofb::Ofb,
+    openpgp_cfb::OpenPgpCfb,
     pcbc::Pcbc,
+    self_test::self_test,
     traits::{BlockMode, IvState},
 };
@@ -0,0 +1,155 @@
+use crate::{errors::BlockModeError, utils::Block};
+use cipher::{generic_array::typenum::Unsigned, BlockCipher, BlockEncrypt};
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// OpenPGP variant of the [cipher feedback][1] (CFB) mode defined in
+/// [RFC 4880 section 13.9][2].
+///
+/// The mode uses a zero IV. Instead, the message is prefixed with a random
+/// block followed by a repetition of its last two bytes, which allows to
+/// quickly check that the session key is correct during decryption.
+///
+/// Two forms are supported:
+/// - without resynchronisation, used by the Symmetrically Encrypted
+///   Integrity Protected Data (SEIPD) packets: the whole data is encrypted
+///   in the normal CFB mode;
+/// - with resynchronisation, used by the legacy Symmetrically Encrypted
+///   Data packets: after encrypting the prefix, CFB is restarted with the
+///   last block of the encrypted prefix as IV.
+///
+/// Note that the quick check leaks 16 bits of information about the
+/// plaintext, so decryption errors should not be reported to an attacker
+/// able to submit chosen ciphertexts.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#Cipher_feedback_(CFB)
+/// [2]: https://tools.ietf.org/html/rfc4880#section-13.9
+#[derive(Clone)]
+pub struct OpenPgpCfb<C: BlockCipher + BlockEncrypt> {
+    cipher: C,
+    resync: bool,
+}
+
+impl<C: BlockCipher + BlockEncrypt> OpenPgpCfb<C> {
+    /// Create mode instance without resynchronisation, used for SEIPD
+    /// packets.
+    pub fn new(cipher: C) -> Self {
+        Self {
+            cipher,
+            resync: false,
+        }
+    }
+
+    /// Create mode instance with resynchronisation, used for legacy
+    /// Symmetrically Encrypted Data packets.
+    pub fn new_resync(cipher: C) -> Self {
+        Self {
+            cipher,
+            resync: true,
+        }
+    }
+
+    /// Length of the random prefix with the quick check bytes.
+    pub fn prefix_len() -> usize {
+        C::BlockSize::USIZE + 2
+    }
+
+    /// Encrypt message in-place.
+    ///
+    /// `&buffer[Self::prefix_len()..]` is used as a message, while the
+    /// beginning of `buffer` is overwritten by `prefix` followed by the
+    /// quick check bytes. `prefix` must be generated by a cryptographically
+    /// secure RNG for each message.
+    ///
+    /// Returns an error if `buffer` is shorter than the prefix.
+    pub fn encrypt(&self, prefix: &Block<C>, buffer: &mut [u8]) -> Result<(), BlockModeError> {
+        let bs = C::BlockSize::USIZE;
+        if buffer.len() < bs + 2 {
+            return Err(BlockModeError);
+        }
+        buffer[..bs].copy_from_slice(prefix);
+        buffer[bs..bs + 2].copy_from_slice(&prefix[bs - 2..]);
+
+        let mut iv = Block::<C>::default();
+        if self.resync {
+            let (head, tail) = buffer.split_at_mut(bs + 2);
+            self.encrypt_cfb(&mut iv, head);
+            iv.copy_from_slice(&head[2..]);
+            self.encrypt_cfb(&mut iv, tail);
+        } else {
+            self.encrypt_cfb(&mut iv, buffer);
+        }
+        Ok(())
+    }
+
+    /// Decrypt message in-place.
+    ///
+    /// Returns the decrypted message without the prefix, or an error if
+    /// `buffer` is shorter than the prefix or the quick check fails, which
+    /// usually means that the key is wrong.
+    pub fn decrypt<'a>(&self, buffer: &'a mut [u8]) -> Result<&'a [u8], BlockModeError> {
+        let bs = C::BlockSize::USIZE;
+        if buffer.len() < bs + 2 {
+            return Err(BlockModeError);
+        }
+
+        let mut iv = Block::<C>::default();
+        if self.resync {
+            let (head, tail) = buffer.split_at_mut(bs + 2);
+            let mut next_iv = Block::<C>::default();
+            next_iv.copy_from_slice(&head[2..]);
+            self.decrypt_cfb(&mut iv, head);
+            self.decrypt_cfb(&mut next_iv, tail);
+        } else {
+            self.decrypt_cfb(&mut iv, buffer);
+        }
+
+        if buffer[bs - 2..bs] != buffer[bs..bs + 2] {
+            return Err(BlockModeError);
+        }
+        Ok(&buffer[bs + 2..])
+    }
+
+    /// Encrypt message and store result in vector.
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_vec(&self, prefix: &Block<C>, plaintext: &[u8]) -> Vec<u8> {
+        let mut buf = vec![0; Self::prefix_len() + plaintext.len()];
+        buf[Self::prefix_len()..].copy_from_slice(plaintext);
+        // the buffer is never shorter than the prefix
+        self.encrypt(prefix, &mut buf).unwrap();
+        buf
+    }
+
+    /// Decrypt message and store result in vector.
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_vec(&self, ciphertext: &[u8]) -> Result<Vec<u8>, BlockModeError> {
+        let mut buf = ciphertext.to_vec();
+        let n = self.decrypt(&mut buf)?.len();
+        buf.drain(..buf.len() - n);
+        Ok(buf)
+    }
+
+    /// Encrypt data in the CFB mode, the last chunk may be partial
+    fn encrypt_cfb(&self, iv: &mut Block<C>, data: &mut [u8]) {
+        for chunk in data.chunks_mut(C::BlockSize::USIZE) {
+            self.cipher.encrypt_block(iv);
+            for (a, b) in chunk.iter_mut().zip(iv.iter_mut()) {
+                *a ^= *b;
+                *b = *a;
+            }
+        }
+    }
+
+    /// Decrypt data in the CFB mode, the last chunk may be partial
+    fn decrypt_cfb(&self, iv: &mut Block<C>, data: &mut [u8]) {
+        for chunk in data.chunks_mut(C::BlockSize::USIZE) {
+            self.cipher.encrypt_block(iv);
+            for (a, b) in chunk.iter_mut().zip(iv.iter_mut()) {
+                let t = *a;
+                *a ^= *b;
+                *b = t;
+            }
+        }
+    }
+}
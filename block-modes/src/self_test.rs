@@ -0,0 +1,218 @@
+use crate::{
+    traits::BlockMode,
+    utils::{xor, Block, Key},
+    Cbc, Cfb, Cfb8, Ecb, Ige, Ofb, OpenPgpCfb, Pcbc,
+};
+use block_ciphers_self_test::SelfTestError;
+use block_padding::NoPadding;
+use cipher::{
+    generic_array::{
+        sequence::GenericSequence,
+        typenum::{Prod, Sum, Unsigned, U2, U3},
+        ArrayLength, GenericArray,
+    },
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use core::ops::{Add, Mul};
+
+/// Test message, long enough to be processed by the parallel code paths
+type Message<C> = GenericArray<Block<C>, Sum<<C as BlockCipher>::ParBlocks, U2>>;
+
+/// Run self-test of all block modes with the block cipher `C`.
+///
+/// Every mode encrypts and decrypts a fixed message with a fixed key and IV
+/// and the result is compared with the mode computed directly from single
+/// block operations of `C`. The message is encrypted in two parts, so the
+/// test also checks that the mode state carries over between calls, and it
+/// is long enough to take the code paths which process blocks in parallel.
+///
+/// The test does not check the block cipher itself; cipher crates in the
+/// RustCrypto/block-ciphers repository provide their own `self_test`
+/// functions with known answer tests, which should be run first.
+///
+/// # Example
+/// ```
+/// use aes::Aes128;
+///
+/// aes::self_test().expect("AES self-test failed");
+/// block_modes::self_test::<Aes128>().expect("block modes self-test failed");
+/// ```
+pub fn self_test<C>() -> Result<(), SelfTestError>
+where
+    C: BlockCipher + NewBlockCipher + BlockEncrypt + BlockDecrypt,
+    C::BlockSize: Add + Mul<U3>,
+    C::ParBlocks: Add<U2>,
+    Sum<C::BlockSize, C::BlockSize>: ArrayLength<u8>,
+    Prod<C::BlockSize, U3>: ArrayLength<u8>,
+    Sum<C::ParBlocks, U2>: ArrayLength<Block<C>>,
+{
+    let bs = C::BlockSize::USIZE;
+    let key = Key::<C>::generate(|i| i as u8);
+    let iv = GenericArray::generate(|i| 0x80 ^ i as u8);
+    let pt = Message::<C>::generate(|i| Block::<C>::generate(|j| (i * bs + j) as u8));
+    let cipher = C::new(&key);
+
+    let mut ct = pt.clone();
+    for block in ct.iter_mut() {
+        cipher.encrypt_block(block);
+    }
+    check::<C, Ecb<C, NoPadding>>("ECB", &key, &Default::default(), &pt, &ct)?;
+
+    let mut prev = iv.clone();
+    for block in ct.iter_mut().zip(pt.iter()) {
+        *block.0 = block.1.clone();
+        xor(block.0, &prev);
+        cipher.encrypt_block(block.0);
+        prev = block.0.clone();
+    }
+    check::<C, Cbc<C, NoPadding>>("CBC", &key, &iv, &pt, &ct)?;
+
+    let mut prev = iv.clone();
+    for block in ct.iter_mut().zip(pt.iter()) {
+        *block.0 = block.1.clone();
+        xor(block.0, &prev);
+        cipher.encrypt_block(block.0);
+        prev = block.0.clone();
+        xor(&mut prev, block.1);
+    }
+    check::<C, Pcbc<C, NoPadding>>("PCBC", &key, &iv, &pt, &ct)?;
+
+    let mut prev = iv.clone();
+    for block in ct.iter_mut().zip(pt.iter()) {
+        cipher.encrypt_block(&mut prev);
+        *block.0 = block.1.clone();
+        xor(block.0, &prev);
+        prev = block.0.clone();
+    }
+    check::<C, Cfb<C, NoPadding>>("CFB", &key, &iv, &pt, &ct)?;
+
+    let mut reg = iv.clone();
+    for block in ct.iter_mut().zip(pt.iter()) {
+        for (c, p) in block.0.iter_mut().zip(block.1.iter()) {
+            let mut t = reg.clone();
+            cipher.encrypt_block(&mut t);
+            *c = p ^ t[0];
+            reg.copy_within(1.., 0);
+            reg[bs - 1] = *c;
+        }
+    }
+    check::<C, Cfb8<C, NoPadding>>("CFB8", &key, &iv, &pt, &ct)?;
+
+    let mut prev = iv.clone();
+    for block in ct.iter_mut().zip(pt.iter()) {
+        cipher.encrypt_block(&mut prev);
+        *block.0 = block.1.clone();
+        xor(block.0, &prev);
+    }
+    check::<C, Ofb<C, NoPadding>>("OFB", &key, &iv, &pt, &ct)?;
+
+    // IGE IV is the previous ciphertext block followed by the previous
+    // plaintext block
+    let ige_iv = GenericArray::generate(|i| 0x80 ^ i as u8);
+    let mut y = Block::<C>::clone_from_slice(&ige_iv[..bs]);
+    let mut x = Block::<C>::clone_from_slice(&ige_iv[bs..]);
+    for block in ct.iter_mut().zip(pt.iter()) {
+        *block.0 = block.1.clone();
+        xor(block.0, &y);
+        cipher.encrypt_block(block.0);
+        xor(block.0, &x);
+        x = block.1.clone();
+        y = block.0.clone();
+    }
+    check::<C, Ige<C, NoPadding>>("IGE", &key, &ige_iv, &pt, &ct)?;
+
+    check_openpgp_cfb::<C>(&key, false)?;
+    check_openpgp_cfb::<C>(&key, true)
+}
+
+/// Encrypt and decrypt `pt` with the mode `M` and compare the results
+fn check<C, M>(
+    mode: &'static str,
+    key: &Key<C>,
+    iv: &GenericArray<u8, M::IvSize>,
+    pt: &Message<C>,
+    ct: &Message<C>,
+) -> Result<(), SelfTestError>
+where
+    C: BlockCipher + NewBlockCipher + BlockEncrypt + BlockDecrypt,
+    C::ParBlocks: Add<U2>,
+    Sum<C::ParBlocks, U2>: ArrayLength<Block<C>>,
+    M: BlockMode<C, NoPadding>,
+{
+    let error = SelfTestError::new(mode);
+    let mut buf = pt.clone();
+
+    let mut m = M::new(C::new(key), iv);
+    let (head, tail) = buf.split_at_mut(1);
+    m.encrypt_blocks(head);
+    m.encrypt_blocks(tail);
+    if buf != *ct {
+        return Err(error);
+    }
+
+    let mut m = M::new(C::new(key), iv);
+    m.decrypt_blocks(&mut buf);
+    if buf != *pt {
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Check OpenPGP CFB with a message which does not end at a block boundary
+/// of the encrypted data
+fn check_openpgp_cfb<C>(key: &Key<C>, resync: bool) -> Result<(), SelfTestError>
+where
+    C: BlockCipher + NewBlockCipher + BlockEncrypt + BlockDecrypt,
+    C::BlockSize: Mul<U3>,
+    Prod<C::BlockSize, U3>: ArrayLength<u8>,
+{
+    let error = SelfTestError::new(if resync {
+        "OpenPGP CFB with resync"
+    } else {
+        "OpenPGP CFB"
+    });
+    let bs = C::BlockSize::USIZE;
+    let cipher = C::new(key);
+    let prefix = Block::<C>::generate(|i| 0x40 ^ i as u8);
+    let msg = GenericArray::<u8, Prod<C::BlockSize, U3>>::generate(|i| i as u8);
+    let msg = &msg[bs + 2..];
+
+    let mut expected = GenericArray::<u8, Prod<C::BlockSize, U3>>::default();
+    expected[..bs].copy_from_slice(&prefix);
+    expected[bs..bs + 2].copy_from_slice(&prefix[bs - 2..]);
+    expected[bs + 2..].copy_from_slice(msg);
+    let mut iv = Block::<C>::default();
+    if resync {
+        let (head, tail) = expected.split_at_mut(bs + 2);
+        cfb(&cipher, &mut iv, head);
+        iv.copy_from_slice(&head[2..]);
+        cfb(&cipher, &mut iv, tail);
+    } else {
+        cfb(&cipher, &mut iv, &mut expected);
+    }
+
+    let mode = if resync {
+        OpenPgpCfb::new_resync(cipher)
+    } else {
+        OpenPgpCfb::new(cipher)
+    };
+    let mut buf = GenericArray::<u8, Prod<C::BlockSize, U3>>::default();
+    buf[bs + 2..].copy_from_slice(msg);
+    mode.encrypt(&prefix, &mut buf).map_err(|_| error)?;
+    if buf != expected {
+        return Err(error);
+    }
+    match mode.decrypt(&mut buf) {
+        Ok(res) if res == msg => Ok(()),
+        _ => Err(error),
+    }
+}
+
+/// Encrypt data in the CFB mode, the last chunk may be partial
+fn cfb<C: BlockCipher + BlockEncrypt>(cipher: &C, iv: &mut Block<C>, data: &mut [u8]) {
+    for chunk in data.chunks_mut(C::BlockSize::USIZE) {
+        cipher.encrypt_block(iv);
+        xor(chunk, &iv[..chunk.len()]);
+        iv[..chunk.len()].copy_from_slice(chunk);
+    }
+}
@@ -378,3 +378,10 @@ where
         slice::from_raw_parts_mut(data.as_ptr() as *mut GenericArray<u8, N>, data.len() / n)
     }
 }
+
+#[test]
+fn self_test() {
+    assert_eq!(block_modes::self_test::<Aes128>(), Ok(()));
+    assert_eq!(block_modes::self_test::<aes::Aes256>(), Ok(()));
+    assert_eq!(block_modes::self_test::<cast5::Cast5>(), Ok(()));
+}
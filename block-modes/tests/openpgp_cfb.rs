@@ -0,0 +1,95 @@
+//! Test vectors generated with GnuPG
+use aes::Aes128;
+use block_modes::OpenPgpCfb;
+use cast5::Cast5;
+use cipher::{generic_array::GenericArray, BlockCipher, BlockEncrypt, NewBlockCipher};
+use hex_literal::hex;
+
+const MESSAGE: &[u8] = b"Hello, OpenPGP CFB!\n";
+
+/// Decrypt `ciphertext`, check that it contains the literal data packet with
+/// the message and that encryption with the recovered prefix reproduces
+/// `ciphertext`
+fn check<C: BlockCipher + BlockEncrypt>(mode: OpenPgpCfb<C>, ciphertext: &[u8]) {
+    let bs = OpenPgpCfb::<C>::prefix_len() - 2;
+    let mut buf = ciphertext.to_vec();
+    let plaintext = mode.decrypt(&mut buf).unwrap().to_vec();
+    assert!(plaintext.windows(MESSAGE.len()).any(|w| w == MESSAGE));
+
+    let prefix = GenericArray::from_slice(&buf[..bs]);
+    assert_eq!(mode.encrypt_vec(prefix, &plaintext), ciphertext);
+    assert_eq!(mode.decrypt_vec(ciphertext).unwrap(), plaintext);
+}
+
+#[test]
+fn seipd() {
+    // SEIPD packet bodies without the version byte
+    let key = hex!("52DAEE06472C849DB83C8D2DAE94759D");
+    let ciphertext = hex!(
+        "
+        a6f1e77b6619a76da1d131b22b30bf78b568815982e9180b2f01eaf11e9a2263
+        b75100baa5316ccb2670cb147181d6bdff7c953ca4cac0d1d1fc5a91aea8b425
+        1ad79671c2bee1a9551bdb
+        "
+    );
+    check(OpenPgpCfb::new(Aes128::new(&key.into())), &ciphertext);
+
+    let key = hex!("FC291EB93F4AC56F454B4D2B82F269FF");
+    let ciphertext = hex!(
+        "
+        788cf4df40cada715612eb775f855ae73716952ee7152b4e150d1d90d8ce275f
+        c1abf0d2e9383382faa28e1202753be032d4f2e10f3dd6b7c0da16d1dac26e98
+        aa507d
+        "
+    );
+    check(OpenPgpCfb::new(Cast5::new(&key.into())), &ciphertext);
+}
+
+#[test]
+fn resync() {
+    // Symmetrically Encrypted Data packet bodies
+    let key = hex!("A25E9C3C3E5186EF3A0A3D521A15AF6B");
+    let ciphertext = hex!(
+        "
+        755dbb0bfe90b97c9837c0d4603cde362470d48eaa973a482ea7b184e3dc2140
+        d4e8c298b0d81ad0bee83bc8d1e4b149a53268ce0b
+        "
+    );
+    check(
+        OpenPgpCfb::new_resync(Aes128::new(&key.into())),
+        &ciphertext,
+    );
+
+    let key = hex!("0E1D74807B98538E24E5C60D1FAC3924");
+    let ciphertext = hex!(
+        "
+        ccd4b32c5e82af9e6a4cf89e8e3ce3c0d779ea89ee86bf6c7265fa6ce6c429dd
+        5ca0f964c77026b260459661a8
+        "
+    );
+    check(OpenPgpCfb::new_resync(Cast5::new(&key.into())), &ciphertext);
+}
+
+#[test]
+fn quick_check() {
+    let key = hex!("A25E9C3C3E5186EF3A0A3D521A15AF6B");
+    let prefix = hex!("000102030405060708090a0b0c0d0e0f");
+    let cipher = Aes128::new(&key.into());
+    let ciphertext = OpenPgpCfb::new_resync(cipher.clone()).encrypt_vec(&prefix.into(), MESSAGE);
+    assert_eq!(ciphertext.len(), 18 + MESSAGE.len());
+
+    // wrong key or mode
+    let wrong = Aes128::new(&[0u8; 16].into());
+    assert!(OpenPgpCfb::new_resync(wrong)
+        .decrypt_vec(&ciphertext)
+        .is_err());
+    let res = OpenPgpCfb::new(cipher.clone())
+        .decrypt_vec(&ciphertext)
+        .unwrap();
+    assert_ne!(res, MESSAGE);
+
+    // truncated prefix
+    assert!(OpenPgpCfb::new_resync(cipher)
+        .decrypt_vec(&ciphertext[..17])
+        .is_err());
+}
@@ -0,0 +1,200 @@
+//! bcrypt password hashes in the modular crypt format.
+
+use super::Error;
+use crate::Blowfish;
+use alloc::string::String;
+
+/// Minimum cost
+pub const MIN_COST: u32 = 4;
+
+/// Maximum cost
+pub const MAX_COST: u32 = 31;
+
+/// Alphabet of the bcrypt radix-64 encoding
+const ALPHABET: &[u8; 64] = b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Maximum number of password bytes used, including the terminating NUL
+const MAX_PASSWORD_LEN: usize = 72;
+
+/// Plaintext encrypted by the EksBlowfish state
+const MAGIC: &[u8; 24] = b"OrpheanBeholderScryDoubt";
+
+/// Number of encryptions of the magic plaintext
+const MAGIC_ROUNDS: usize = 64;
+
+/// Length of the encoded salt
+const SALT_LEN: usize = 22;
+
+/// Length of the encoded hash
+const HASH_LEN: usize = 31;
+
+/// Length of the modular crypt format hash
+const FULL_LEN: usize = 7 + SALT_LEN + HASH_LEN;
+
+/// bcrypt version prefix.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Version {
+    /// `$2a$`
+    TwoA,
+    /// `$2b$`, used by OpenBSD since 2014.
+    TwoB,
+    /// `$2y$`, used by crypt_blowfish and PHP.
+    TwoY,
+}
+
+impl Version {
+    fn prefix(self) -> &'static str {
+        match self {
+            Version::TwoA => "$2a$",
+            Version::TwoB => "$2b$",
+            Version::TwoY => "$2y$",
+        }
+    }
+
+    fn from_prefix(prefix: &[u8]) -> Result<Self, Error> {
+        match prefix {
+            b"$2a$" => Ok(Version::TwoA),
+            b"$2b$" => Ok(Version::TwoB),
+            b"$2y$" => Ok(Version::TwoY),
+            _ => Err(Error::InvalidHash),
+        }
+    }
+}
+
+/// Compute raw 192-bit bcrypt output for `password`.
+///
+/// Unlike [`hash`] no NUL byte is appended to `password`, which length
+/// must be in the `1..=72` range. Only the first 23 bytes of the output
+/// are used by the modular crypt format.
+pub fn bcrypt(cost: u32, salt: &[u8; 16], password: &[u8]) -> Result<[u8; 24], Error> {
+    if !(MIN_COST..=MAX_COST).contains(&cost) {
+        return Err(Error::InvalidCost);
+    }
+    if !(1..=MAX_PASSWORD_LEN).contains(&password.len()) {
+        return Err(Error::InvalidPasswordLength);
+    }
+
+    let mut state = Blowfish::init_state();
+    state.salted_expand_key(salt, password);
+    for _ in 0..1u64 << cost {
+        state.expand_key(password);
+        state.expand_key(salt);
+    }
+
+    let mut ctext = [0u32; 6];
+    for (c, chunk) in ctext.iter_mut().zip(MAGIC.chunks_exact(4)) {
+        *c = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    for pair in ctext.chunks_exact_mut(2) {
+        for _ in 0..MAGIC_ROUNDS {
+            let (l, r) = state.encrypt(pair[0], pair[1]);
+            pair[0] = l;
+            pair[1] = r;
+        }
+    }
+
+    let mut res = [0u8; 24];
+    for (chunk, c) in res.chunks_exact_mut(4).zip(ctext.iter()) {
+        chunk.copy_from_slice(&c.to_be_bytes());
+    }
+    Ok(res)
+}
+
+/// Hash `password` with the given cost and salt.
+pub fn hash_with_salt(
+    password: &[u8],
+    cost: u32,
+    salt: &[u8; 16],
+    version: Version,
+) -> Result<String, Error> {
+    let mut key = [0u8; MAX_PASSWORD_LEN];
+    let key = password_key(password, &mut key);
+    let output = bcrypt(cost, salt, key)?;
+
+    let mut res = String::with_capacity(FULL_LEN);
+    res.push_str(version.prefix());
+    res.push((b'0' + (cost / 10) as u8) as char);
+    res.push((b'0' + (cost % 10) as u8) as char);
+    res.push('$');
+    encode(&mut res, salt);
+    encode(&mut res, &output[..23]);
+    Ok(res)
+}
+
+/// Hash `password` using the version, cost and salt from `setting`.
+///
+/// `setting` is either an existing hash, or its first 29 characters, e.g.
+/// `$2b$12$GhvMmNVjRW29ulnudl.Lbu`.
+pub fn hash(password: &[u8], setting: &str) -> Result<String, Error> {
+    let setting = setting.as_bytes();
+    if setting.len() < 7 + SALT_LEN || setting[6] != b'$' {
+        return Err(Error::InvalidHash);
+    }
+    let version = Version::from_prefix(&setting[..4])?;
+    let cost = match (setting[4], setting[5]) {
+        (d1 @ b'0'..=b'9', d2 @ b'0'..=b'9') => u32::from(10 * (d1 - b'0') + (d2 - b'0')),
+        _ => return Err(Error::InvalidHash),
+    };
+    let salt = decode_salt(&setting[7..7 + SALT_LEN])?;
+    hash_with_salt(password, cost, &salt, version)
+}
+
+/// Verify `password` against `hash`.
+///
+/// The computed hash is compared in constant time.
+pub fn verify(password: &[u8], hash: &str) -> bool {
+    if hash.len() != FULL_LEN {
+        return false;
+    }
+    let computed = match self::hash(password, hash) {
+        Ok(computed) => computed,
+        Err(_) => return false,
+    };
+    let diff = computed
+        .bytes()
+        .zip(hash.bytes())
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+    diff == 0
+}
+
+/// Password bytes up to the first NUL byte followed by the terminating
+/// NUL byte, truncated to 72 bytes
+fn password_key<'a>(password: &[u8], buf: &'a mut [u8; MAX_PASSWORD_LEN]) -> &'a [u8] {
+    let password = password.split(|&b| b == 0).next().unwrap_or(&[]);
+    let len = password.len().min(MAX_PASSWORD_LEN);
+    buf[..len].copy_from_slice(&password[..len]);
+    // `buf` is zeroed, so the terminating NUL byte is already in place
+    &buf[..(len + 1).min(MAX_PASSWORD_LEN)]
+}
+
+/// Encode bytes using the bcrypt radix-64 encoding without padding
+fn encode(res: &mut String, data: &[u8]) {
+    for chunk in data.chunks(3) {
+        let mut buf = [0u8; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let v = (u32::from(buf[0]) << 16) | (u32::from(buf[1]) << 8) | u32::from(buf[2]);
+        for i in 0..=chunk.len() {
+            res.push(ALPHABET[(v >> (18 - 6 * i)) as usize & 63] as char);
+        }
+    }
+}
+
+/// Decode 22-character salt, the 4 unused bits of the last character are
+/// ignored
+fn decode_salt(s: &[u8]) -> Result<[u8; 16], Error> {
+    let mut res = [0u8; 16];
+    for (chunk, out) in s.chunks(4).zip(res.chunks_mut(3)) {
+        let mut v = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let d = ALPHABET
+                .iter()
+                .position(|a| a == c)
+                .ok_or(Error::InvalidHash)?;
+            v |= (d as u32) << (18 - 6 * i);
+        }
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = (v >> (16 - 8 * i)) as u8;
+        }
+    }
+    Ok(res)
+}
@@ -0,0 +1,56 @@
+//! bcrypt password hashing and key derivation.
+//!
+//! With the `bcrypt-hash` feature the module provides `hash` and
+//! `verify` for password hashes in the modular crypt format
+//! `$2b$<cost>$<salt><hash>`, where cost is a 2-digit base-2 logarithm of
+//! the number of key expansion iterations, followed by 22 characters of the
+//! 128-bit salt and 31 characters of the 184-bit hash, e.g.
+//! `$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW`.
+//!
+//! Passwords are processed up to the first NUL byte and a terminating NUL
+//! byte is appended, then they are truncated to 72 bytes. The `$2a$`,
+//! `$2b$` and `$2y$` prefixes produce the same hashes for such passwords
+//! and differ only in bugs of historic implementations, which are not
+//! reproduced.
+//!
+//! With the `bcrypt-pbkdf` feature the module provides `bcrypt_pbkdf`,
+//! the key derivation function used by OpenSSH to encrypt private keys.
+
+use core::fmt;
+
+#[cfg(feature = "bcrypt-hash")]
+mod mcf;
+#[cfg(feature = "bcrypt-pbkdf")]
+mod pbkdf;
+
+#[cfg(feature = "bcrypt-hash")]
+pub use self::mcf::{bcrypt, hash, hash_with_salt, verify, Version, MAX_COST, MIN_COST};
+#[cfg(feature = "bcrypt-pbkdf")]
+pub use self::pbkdf::bcrypt_pbkdf;
+
+/// bcrypt error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Cost is outside of the `4..=31` range.
+    InvalidCost,
+    /// Hash or salt string is malformed or has an unsupported prefix.
+    InvalidHash,
+    /// Raw password length is outside of the `1..=72` range.
+    InvalidPasswordLength,
+    /// bcrypt_pbkdf parameters are out of range.
+    InvalidPbkdfParams,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Error::InvalidCost => "invalid bcrypt cost",
+            Error::InvalidHash => "invalid bcrypt hash",
+            Error::InvalidPasswordLength => "invalid bcrypt password length",
+            Error::InvalidPbkdfParams => "invalid bcrypt_pbkdf parameters",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,95 @@
+//! bcrypt_pbkdf key derivation function.
+
+use super::Error;
+use crate::Blowfish;
+use sha2::{Digest, Sha512};
+
+/// Plaintext encrypted by the bcrypt_pbkdf hash function
+const PBKDF_MAGIC: &[u8; 32] = b"OxychromaticBlowfishSwatDynamite";
+
+/// Number of key expansions and encryptions in the bcrypt_pbkdf hash
+/// function
+const PBKDF_ROUNDS: usize = 64;
+
+/// Output length of the bcrypt_pbkdf hash function
+const PBKDF_HASH_LEN: usize = 32;
+
+/// Maximum bcrypt_pbkdf output length
+const PBKDF_MAX_OUTPUT_LEN: usize = PBKDF_HASH_LEN * PBKDF_HASH_LEN;
+
+/// Derive `output` key from `password` and `salt` using bcrypt_pbkdf, the
+/// key derivation function used by OpenSSH for private key encryption.
+///
+/// `rounds`, `password` and `salt` must not be zero or empty, `output` must
+/// not be empty or longer than 1024 bytes.
+pub fn bcrypt_pbkdf(
+    password: &[u8],
+    salt: &[u8],
+    rounds: u32,
+    output: &mut [u8],
+) -> Result<(), Error> {
+    if rounds == 0
+        || password.is_empty()
+        || salt.is_empty()
+        || output.is_empty()
+        || output.len() > PBKDF_MAX_OUTPUT_LEN
+    {
+        return Err(Error::InvalidPbkdfParams);
+    }
+    // output bytes are spread across the blocks to make all blocks required
+    let stride = (output.len() - 1) / PBKDF_HASH_LEN + 1;
+
+    let sha2_password = Sha512::digest(password);
+    for (count, block_len) in (1u32..).zip(block_lens(output.len(), stride)) {
+        let mut sha2_salt = Sha512::new()
+            .chain(salt)
+            .chain(count.to_be_bytes())
+            .finalize();
+        let mut tmp = pbkdf_hash(&sha2_password, &sha2_salt);
+        let mut out = tmp;
+        for _ in 1..rounds {
+            sha2_salt = Sha512::digest(&tmp);
+            tmp = pbkdf_hash(&sha2_password, &sha2_salt);
+            for (o, t) in out.iter_mut().zip(tmp.iter()) {
+                *o ^= t;
+            }
+        }
+        for (i, &b) in out[..block_len].iter().enumerate() {
+            output[i * stride + count as usize - 1] = b;
+        }
+    }
+    Ok(())
+}
+
+/// Number of output bytes taken from each bcrypt_pbkdf block
+fn block_lens(len: usize, stride: usize) -> impl Iterator<Item = usize> {
+    (0..stride).map(move |i| (len - i - 1) / stride + 1)
+}
+
+/// bcrypt variant used by bcrypt_pbkdf
+fn pbkdf_hash(sha2_password: &[u8], sha2_salt: &[u8]) -> [u8; PBKDF_HASH_LEN] {
+    let mut state = Blowfish::init_state();
+    state.salted_expand_key(sha2_salt, sha2_password);
+    for _ in 0..PBKDF_ROUNDS {
+        state.expand_key(sha2_salt);
+        state.expand_key(sha2_password);
+    }
+
+    let mut ctext = [0u32; 8];
+    for (c, chunk) in ctext.iter_mut().zip(PBKDF_MAGIC.chunks_exact(4)) {
+        *c = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    for _ in 0..PBKDF_ROUNDS {
+        for pair in ctext.chunks_exact_mut(2) {
+            let (l, r) = state.encrypt(pair[0], pair[1]);
+            pair[0] = l;
+            pair[1] = r;
+        }
+    }
+
+    let mut res = [0u8; PBKDF_HASH_LEN];
+    for (chunk, c) in res.chunks_exact_mut(4).zip(ctext.iter()) {
+        chunk.copy_from_slice(&c.to_le_bytes());
+    }
+    res
+}
@@ -1,4 +1,12 @@
 //! Blowfish block cipher
+//!
+//! # Crate features
+//! - `bcrypt`: enables the EksBlowfish primitives used by bcrypt.
+//! - `bcrypt-hash`: enables bcrypt password hashing and verification in the
+//!   [`bcrypt`] module (requires `alloc`).
+//! - `bcrypt-pbkdf`: enables `bcrypt::bcrypt_pbkdf`, the key derivation
+//!   function used by OpenSSH.
+//! - `std`: implements `std::error::Error` for error types.
 
 #![no_std]
 #![doc(
@@ -8,6 +16,11 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs, rust_2018_idioms)]
 
+#[cfg(feature = "bcrypt-hash")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 pub use cipher;
 
 use byteorder::{ByteOrder, BE, LE};
@@ -19,7 +32,13 @@ use cipher::{
 };
 use core::marker::PhantomData;
 
+#[cfg(any(feature = "bcrypt-hash", feature = "bcrypt-pbkdf"))]
+pub mod bcrypt;
 mod consts;
+mod self_test;
+
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 
 /// Blowfish variant which uses Little Endian byte order read/writes.s.
 pub type BlowfishLE = Blowfish<LE>;
@@ -113,7 +132,7 @@ impl<T: ByteOrder> NewBlockCipher for Blowfish<T> {
     type KeySize = U56;
 
     fn new(key: &GenericArray<u8, U56>) -> Self {
-        Self::new_from_slice(&key).unwrap()
+        Self::new_from_slice(key).unwrap()
     }
 
     fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
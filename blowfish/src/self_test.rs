@@ -0,0 +1,23 @@
+//! Power-on self-test.
+
+use crate::{Blowfish, BlowfishLE};
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// Test vector by Eric Young
+const KEY: [u8; 8] = [0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
+
+/// Plaintext of the Eric Young test vector
+const PLAINTEXT: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+
+/// Ciphertext of the Eric Young test vector
+const CIPHERTEXT: [u8; 8] = [0x0a, 0xce, 0xab, 0x0f, 0xc6, 0xa0, 0xa2, 0x8d];
+
+/// Ciphertext with 32-bit words read and written in little endian order
+const CIPHERTEXT_LE: [u8; 8] = [0x0d, 0x47, 0x4a, 0xde, 0x6a, 0x10, 0x00, 0x14];
+
+/// Run known answer tests of Blowfish with both byte orders.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Blowfish>("Blowfish", &KEY, &PLAINTEXT, &CIPHERTEXT)?;
+    kat::<BlowfishLE>("BlowfishLE", &KEY, &PLAINTEXT, &CIPHERTEXT_LE)?;
+    Ok(())
+}
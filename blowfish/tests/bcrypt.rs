@@ -0,0 +1,101 @@
+#![cfg(feature = "bcrypt-hash")]
+use blowfish::bcrypt::{self, Error, Version};
+
+/// Test vectors generated with libxcrypt
+const VECTORS: &[(&[u8], &str)] = &[
+    (
+        b"U*U",
+        "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW",
+    ),
+    (
+        b"U*U*",
+        "$2a$05$CCCCCCCCCCCCCCCCCCCCC.VGOzA784oUp/Z0DY336zx7pLYAy0lwK",
+    ),
+    (
+        b"U*U*U",
+        "$2a$05$XXXXXXXXXXXXXXXXXXXXXOAcXxm9kjPGEMsLznoKqmqw7tc8WCx4a",
+    ),
+    (
+        b"",
+        "$2a$05$CCCCCCCCCCCCCCCCCCCCC.7uG0VCzI2bS7j6ymqJi9CdcdxiRTWNy",
+    ),
+    (
+        b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789chars after 72 are ignored",
+        "$2a$05$abcdefghijklmnopqrstuu5s2v8.iXieOjg/.AySBTTZIIVFJeBui",
+    ),
+    (
+        b"\xc3\xbf\xc2\xa334\xc3\xbf\xc3\xbf\xc3\xbf\xc2\xa3345",
+        "$2b$05$/OK.fbVrR/bpIqNJ5ianF.d86S8fljPilyL4uGqyZZh0AdnQmCddK",
+    ),
+    (
+        b"password",
+        "$2y$04$EGdrhbKUv8Oc9vGiXX0HQOWV7K1WDpM8mE69zv71Wj9qLVbq..DSy",
+    ),
+];
+
+#[test]
+fn hash() {
+    for &(password, hash) in VECTORS {
+        assert_eq!(bcrypt::hash(password, hash).unwrap(), hash);
+        assert_eq!(bcrypt::hash(password, &hash[..29]).unwrap(), hash);
+        assert!(bcrypt::verify(password, hash));
+        assert!(!bcrypt::verify(b"wrong", hash));
+    }
+}
+
+#[test]
+fn hash_with_salt() {
+    let salt = [
+        0x10, 0x41, 0x04, 0x10, 0x41, 0x04, 0x10, 0x41, 0x04, 0x10, 0x41, 0x04, 0x10, 0x41, 0x04,
+        0x10,
+    ];
+    let hash = bcrypt::hash_with_salt(b"U*U", 5, &salt, Version::TwoA).unwrap();
+    assert_eq!(hash, VECTORS[0].1);
+
+    // the prefix does not affect the hash
+    let hash = bcrypt::hash_with_salt(b"U*U", 5, &salt, Version::TwoB).unwrap();
+    assert_eq!(&hash[..4], "$2b$");
+    assert_eq!(hash[4..], VECTORS[0].1[4..]);
+}
+
+#[test]
+fn truncation() {
+    let long = [b'a'; 100];
+    let hash = bcrypt::hash(&long, "$2b$04$......................").unwrap();
+    assert!(bcrypt::verify(&long[..72], &hash));
+    assert!(!bcrypt::verify(&long[..71], &hash));
+
+    // passwords are processed up to the first NUL byte
+    assert!(bcrypt::verify(b"U*U\0ignored", VECTORS[0].1));
+}
+
+#[test]
+fn errors() {
+    let salt = [0u8; 16];
+    assert_eq!(
+        bcrypt::hash_with_salt(b"", 3, &salt, Version::TwoB),
+        Err(Error::InvalidCost)
+    );
+    assert_eq!(
+        bcrypt::hash_with_salt(b"", 32, &salt, Version::TwoB),
+        Err(Error::InvalidCost)
+    );
+    assert_eq!(
+        bcrypt::bcrypt(4, &salt, &[]),
+        Err(Error::InvalidPasswordLength)
+    );
+    assert_eq!(
+        bcrypt::bcrypt(4, &salt, &[0; 73]),
+        Err(Error::InvalidPasswordLength)
+    );
+
+    for setting in &[
+        "$2x$05$CCCCCCCCCCCCCCCCCCCCC.",
+        "$2b$5$CCCCCCCCCCCCCCCCCCCCC.",
+        "$2b$05$CCCCCCCCCCCCCCCCCCCC",
+        "$2b$05$CCCCCCCCCCCCCCCCCCCC!.",
+    ] {
+        assert_eq!(bcrypt::hash(b"", setting), Err(Error::InvalidHash));
+    }
+    assert!(!bcrypt::verify(b"U*U", &VECTORS[0].1[..59]));
+}
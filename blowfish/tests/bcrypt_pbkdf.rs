@@ -0,0 +1,42 @@
+#![cfg(feature = "bcrypt-pbkdf")]
+use blowfish::bcrypt::{self, Error};
+use hex_literal::hex;
+
+#[test]
+fn bcrypt_pbkdf() {
+    // OpenBSD regression test vector
+    let mut output = [0u8; 32];
+    bcrypt::bcrypt_pbkdf(b"password", b"salt", 4, &mut output).unwrap();
+    assert_eq!(
+        output,
+        hex!("5bbf0cc293587f1c3635555c27796598d47e579071bf427e9d8fbe842aba34d9")
+    );
+
+    // AES-256-CTR key and IV of an ed25519 key generated by ssh-keygen
+    let salt = hex!("8102ae9361ff805a59cfd2847e6ed6fc");
+    let mut output = [0u8; 48];
+    bcrypt::bcrypt_pbkdf(b"correct horse", &salt, 3, &mut output).unwrap();
+    assert_eq!(
+        output[..],
+        hex!(
+            "
+            7ff214500858018822996af8fd51d2f702128705687b693622f7c1fcf276d705
+            15d19e3b689e2c57ab6c66740f9376fc
+            "
+        )[..]
+    );
+
+    for &(password, salt, rounds, len) in &[
+        (&b"password"[..], &b"salt"[..], 0, 32),
+        (b"", b"salt", 4, 32),
+        (b"password", b"", 4, 32),
+        (b"password", b"salt", 4, 0),
+        (b"password", b"salt", 4, 1025),
+    ] {
+        let mut output = [0u8; 1025];
+        assert_eq!(
+            bcrypt::bcrypt_pbkdf(password, salt, rounds, &mut output[..len]),
+            Err(Error::InvalidPbkdfParams)
+        );
+    }
+}
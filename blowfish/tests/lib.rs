@@ -1 +1,6 @@
 cipher::block_cipher_test!(blowfish_test, "blowfish", blowfish::Blowfish);
+
+#[test]
+fn self_test() {
+    assert_eq!(blowfish::self_test(), Ok(()));
+}
@@ -34,5 +34,8 @@ pub use cipher::{self, BlockCipher};
 mod cast5;
 mod consts;
 mod schedule;
+mod self_test;
 
 pub use crate::cast5::Cast5;
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
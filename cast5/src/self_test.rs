@@ -0,0 +1,20 @@
+//! Power-on self-test.
+
+use crate::Cast5;
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// RFC 2144 appendix B.1
+const KEY: [u8; 16] = [
+    0x01, 0x23, 0x45, 0x67, 0x12, 0x34, 0x56, 0x78, 0x23, 0x45, 0x67, 0x89, 0x34, 0x56, 0x78, 0x9a,
+];
+
+/// Plaintext of the RFC 2144 example
+const PLAINTEXT: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+
+/// Ciphertext of the RFC 2144 example with the 128-bit key
+const CIPHERTEXT: [u8; 8] = [0x23, 0x8b, 0x4f, 0xe5, 0x84, 0x7e, 0x44, 0xb2];
+
+/// Run the known answer test of CAST5 with a 128-bit key.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Cast5>("CAST5", &KEY, &PLAINTEXT, &CIPHERTEXT)
+}
@@ -80,3 +80,8 @@ fn full_maintance_test() {
 // Test vectors from NESSIE:
 // https://www.cosic.esat.kuleuven.be/nessie/testvectors/bc/cast-128/Cast-128-128-64.verified.test-vectors
 cipher::block_cipher_test!(cast5_nessie, "cast5", cast5::Cast5);
+
+#[test]
+fn self_test() {
+    assert_eq!(cast5::self_test(), Ok(()));
+}
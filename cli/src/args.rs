@@ -0,0 +1,235 @@
+//! Command-line argument parsing.
+
+use crate::Error;
+use std::{fs, path::PathBuf, time::Duration};
+
+/// Default PBKDF2 iteration count, same as `openssl enc -pbkdf2`
+pub const DEFAULT_ITERATIONS: u32 = 10_000;
+
+pub const USAGE: &str = "\
+Usage:
+  block-ciphers enc [OPTIONS]     encrypt data
+  block-ciphers dec [OPTIONS]     decrypt data
+  block-ciphers speed [OPTIONS]   measure throughput
+  block-ciphers list              list supported ciphers, modes and paddings
+
+Options of enc and dec:
+  -c, --cipher NAME       cipher, e.g. aes256 or kuznyechik
+  -m, --mode NAME         mode of operation [default: cbc]
+  -p, --padding NAME      padding of block modes [default: pkcs7]
+  -K, --key HEX           key as hex string
+      --key-file PATH     file with the raw key
+      --iv HEX            IV as hex string
+      --iv-file PATH      file with the raw IV
+  -k, --pass PASSWORD     derive key and IV from password
+      --pass-file PATH    read password from the first line of a file
+      --iter N            PBKDF2 iteration count [default: 10000]
+  -i, --in PATH           input file [default: stdin]
+  -o, --out PATH          output file [default: stdout]
+
+With a password the key and IV are derived with PBKDF2-HMAC-SHA256 and a
+random salt stored in the `Salted__` header, like `openssl enc -pbkdf2`.
+
+Options of speed:
+  -c, --cipher NAME       cipher to measure, may be repeated [default: all]
+  -m, --mode NAME         mode to measure, may be repeated
+                          [default: ecb, cbc and gost-ctr]
+  -s, --seconds SECS      duration of each measurement [default: 1]
+";
+
+/// Padding used by the block modes
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Padding {
+    Pkcs7,
+    Iso7816,
+    AnsiX923,
+    Zero,
+    None,
+}
+
+pub const PADDINGS: &[&str] = &["pkcs7", "iso7816", "ansix923", "zero", "none"];
+
+impl Padding {
+    fn from_name(name: &str) -> Result<Self, Error> {
+        Ok(match name {
+            "pkcs7" => Padding::Pkcs7,
+            "iso7816" => Padding::Iso7816,
+            "ansix923" => Padding::AnsiX923,
+            "zero" => Padding::Zero,
+            "none" => Padding::None,
+            _ => return Err(format!("unknown padding: {}", name).into()),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Encrypt(CryptOpts),
+    Decrypt(CryptOpts),
+    Speed(SpeedOpts),
+    List,
+    Help,
+}
+
+/// Options of the `enc` and `dec` commands
+#[derive(Debug)]
+pub struct CryptOpts {
+    pub cipher: String,
+    pub mode: String,
+    pub padding: Padding,
+    pub key: Option<Vec<u8>>,
+    pub iv: Option<Vec<u8>>,
+    pub password: Option<Vec<u8>>,
+    pub iterations: u32,
+    pub input: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+}
+
+/// Options of the `speed` command
+#[derive(Debug)]
+pub struct SpeedOpts {
+    pub ciphers: Vec<String>,
+    pub modes: Vec<String>,
+    pub duration: Duration,
+}
+
+/// Parse arguments without the program name
+pub fn parse(args: &[String]) -> Result<Command, Error> {
+    let (cmd, args) = match args.split_first() {
+        Some((cmd, args)) => (cmd.as_str(), args),
+        None => return Ok(Command::Help),
+    };
+    match cmd {
+        "enc" => parse_crypt(args).map(Command::Encrypt),
+        "dec" => parse_crypt(args).map(Command::Decrypt),
+        "speed" => parse_speed(args).map(Command::Speed),
+        "list" if args.is_empty() => Ok(Command::List),
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        _ => Err(format!("unknown command: {}", cmd).into()),
+    }
+}
+
+fn parse_crypt(args: &[String]) -> Result<CryptOpts, Error> {
+    let mut opts = CryptOpts {
+        cipher: String::new(),
+        mode: "cbc".into(),
+        padding: Padding::Pkcs7,
+        key: None,
+        iv: None,
+        password: None,
+        iterations: DEFAULT_ITERATIONS,
+        input: None,
+        output: None,
+    };
+    let mut args = Args::new(args);
+    while let Some((opt, value)) = args.next()? {
+        match opt {
+            "-c" | "--cipher" => opts.cipher = value.into(),
+            "-m" | "--mode" => opts.mode = value.into(),
+            "-p" | "--padding" => opts.padding = Padding::from_name(value)?,
+            "-K" | "--key" => opts.key = Some(decode_hex(value)?),
+            "--key-file" => opts.key = Some(fs::read(value)?),
+            "--iv" => opts.iv = Some(decode_hex(value)?),
+            "--iv-file" => opts.iv = Some(fs::read(value)?),
+            "-k" | "--pass" => opts.password = Some(value.as_bytes().to_vec()),
+            "--pass-file" => {
+                let data = fs::read(value)?;
+                let mut line = data.split(|&b| b == b'\n').next().unwrap_or(&[]);
+                if line.last() == Some(&b'\r') {
+                    line = &line[..line.len() - 1];
+                }
+                opts.password = Some(line.to_vec());
+            }
+            "--iter" => {
+                opts.iterations = value.parse().map_err(|_| "invalid iteration count")?;
+                if opts.iterations == 0 {
+                    return Err("invalid iteration count".into());
+                }
+            }
+            "-i" | "--in" => opts.input = Some(value.into()),
+            "-o" | "--out" => opts.output = Some(value.into()),
+            _ => return Err(format!("unknown option: {}", opt).into()),
+        }
+    }
+
+    if opts.cipher.is_empty() {
+        return Err("cipher is not specified".into());
+    }
+    match (&opts.key, &opts.password) {
+        (Some(_), Some(_)) => return Err("key and password are mutually exclusive".into()),
+        (None, None) => return Err("key or password is not specified".into()),
+        (None, Some(_)) if opts.iv.is_some() => {
+            return Err("IV is derived from the password and can not be specified".into())
+        }
+        _ => {}
+    }
+    Ok(opts)
+}
+
+fn parse_speed(args: &[String]) -> Result<SpeedOpts, Error> {
+    let mut opts = SpeedOpts {
+        ciphers: Vec::new(),
+        modes: Vec::new(),
+        duration: Duration::from_secs(1),
+    };
+    let mut args = Args::new(args);
+    while let Some((opt, value)) = args.next()? {
+        match opt {
+            "-c" | "--cipher" => opts.ciphers.push(value.into()),
+            "-m" | "--mode" => opts.modes.push(value.into()),
+            "-s" | "--seconds" => {
+                let secs: f64 = value.parse().map_err(|_| "invalid duration")?;
+                if !secs.is_finite() || secs <= 0.0 {
+                    return Err("invalid duration".into());
+                }
+                opts.duration = Duration::from_secs_f64(secs);
+            }
+            _ => return Err(format!("unknown option: {}", opt).into()),
+        }
+    }
+    Ok(opts)
+}
+
+/// Iterator over options with values, accepts `--opt value` and
+/// `--opt=value` forms
+struct Args<'a> {
+    args: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Args<'a> {
+    fn new(args: &'a [String]) -> Self {
+        Self { args: args.iter() }
+    }
+
+    fn next(&mut self) -> Result<Option<(&'a str, &'a str)>, Error> {
+        let arg = match self.args.next() {
+            Some(arg) => arg.as_str(),
+            None => return Ok(None),
+        };
+        if !arg.starts_with('-') {
+            return Err(format!("unexpected argument: {}", arg).into());
+        }
+        if arg.starts_with("--") {
+            if let Some(i) = arg.find('=') {
+                return Ok(Some((&arg[..i], &arg[i + 1..])));
+            }
+        }
+        match self.args.next() {
+            Some(value) => Ok(Some((arg, value))),
+            None => Err(format!("missing value of {}", arg).into()),
+        }
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, Error> {
+    let s = s.as_bytes();
+    if s.len() & 1 != 0 {
+        return Err("hex string has odd length".into());
+    }
+    s.chunks(2)
+        .map(|pair| {
+            let digit = |c: u8| (c as char).to_digit(16).ok_or("invalid hex string");
+            Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+//! Mapping of cipher, mode and padding names onto the types from the
+//! workspace crates.
+
+use crate::{args::Padding, Error};
+use block_modes::{
+    block_padding::{AnsiX923, Iso7816, NoPadding, Padding as PaddingTrait, Pkcs7, ZeroPadding},
+    BlockMode, Cbc, Cfb, Cfb8, Ecb, Ige, Ofb, Pcbc,
+};
+use cipher::{AsyncStreamCipher, BlockCipher, NewBlockCipher, NewCipher, StreamCipher};
+use gost_modes::{GostCbc, GostCfb, GostCtr128, GostCtr64, GostOfb};
+
+/// Operation generic over the cipher and mode types
+pub trait Runner {
+    type Output;
+
+    /// Run operation with a block mode which requires padding
+    fn padded<C, P, M>(self) -> Self::Output
+    where
+        C: BlockCipher + NewBlockCipher,
+        P: PaddingTrait,
+        M: BlockMode<C, P>;
+
+    /// Run operation with a synchronous stream mode
+    fn stream<M: NewCipher + StreamCipher>(self) -> Self::Output;
+
+    /// Run operation with an asynchronous stream mode
+    fn async_stream<M: NewCipher + AsyncStreamCipher>(self) -> Self::Output;
+}
+
+pub const MODES: &[&str] = &[
+    "ecb", "cbc", "pcbc", "cfb", "cfb8", "ofb", "ige", "gost-cbc", "gost-cfb", "gost-ofb",
+    "gost-ctr",
+];
+
+/// Name of the backend used by the cipher implementation.
+pub fn backend(cipher: &str) -> &'static str {
+    match cipher {
+        "aes128" | "aes192" | "aes256" => aes_backend(),
+        "kuznyechik" => kuznyechik_backend(),
+        _ => "soft",
+    }
+}
+
+/// Backend selected by the `aes` crate for new cipher instances
+fn aes_backend() -> &'static str {
+    aes::Aes128::new(&Default::default()).backend().name()
+}
+
+/// Backend selected by the `kuznyechik` crate for new cipher instances
+fn kuznyechik_backend() -> &'static str {
+    kuznyechik::Kuznyechik::new(&Default::default()).backend()
+}
+
+macro_rules! padded {
+    ($C:ty, $M:ident, $padding:expr, $runner:expr) => {
+        match $padding {
+            Padding::Pkcs7 => $runner.padded::<$C, Pkcs7, $M<$C, Pkcs7>>(),
+            Padding::Iso7816 => $runner.padded::<$C, Iso7816, $M<$C, Iso7816>>(),
+            Padding::AnsiX923 => $runner.padded::<$C, AnsiX923, $M<$C, AnsiX923>>(),
+            Padding::Zero => $runner.padded::<$C, ZeroPadding, $M<$C, ZeroPadding>>(),
+            Padding::None => $runner.padded::<$C, NoPadding, $M<$C, NoPadding>>(),
+        }
+    };
+}
+
+macro_rules! gost_ctr {
+    (ctr128, $C:ty, $runner:expr) => {
+        Ok($runner.stream::<GostCtr128<$C>>())
+    };
+    (ctr64, $C:ty, $runner:expr) => {
+        Ok($runner.stream::<GostCtr64<$C>>())
+    };
+    (none, $C:ty, $runner:expr) => {
+        Err("gost-ctr mode requires a 64 or 128-bit block cipher".into())
+    };
+}
+
+macro_rules! ciphers {
+    ($($name:literal => $C:ty, $ctr:ident;)*) => {
+        pub const CIPHERS: &[&str] = &[$($name),*];
+
+        /// Run `runner` with the types selected by names
+        pub fn dispatch<R: Runner>(
+            cipher: &str,
+            mode: &str,
+            padding: Padding,
+            runner: R,
+        ) -> Result<R::Output, Error> {
+            match cipher {
+                $(
+                    $name => match mode {
+                        "ecb" => Ok(padded!($C, Ecb, padding, runner)),
+                        "cbc" => Ok(padded!($C, Cbc, padding, runner)),
+                        "pcbc" => Ok(padded!($C, Pcbc, padding, runner)),
+                        "cfb" => Ok(padded!($C, Cfb, padding, runner)),
+                        "cfb8" => Ok(padded!($C, Cfb8, padding, runner)),
+                        "ofb" => Ok(padded!($C, Ofb, padding, runner)),
+                        "ige" => Ok(padded!($C, Ige, padding, runner)),
+                        "gost-cbc" => Ok(padded!($C, GostCbc, padding, runner)),
+                        "gost-cfb" => Ok(runner.async_stream::<GostCfb<$C>>()),
+                        "gost-ofb" => Ok(runner.stream::<GostOfb<$C>>()),
+                        "gost-ctr" => gost_ctr!($ctr, $C, runner),
+                        _ => Err(format!("unknown mode: {}", mode).into()),
+                    },
+                )*
+                _ => Err(format!("unknown cipher: {}", cipher).into()),
+            }
+        }
+    };
+}
+
+ciphers! {
+    "aes128" => aes::Aes128, ctr128;
+    "aes192" => aes::Aes192, ctr128;
+    "aes256" => aes::Aes256, ctr128;
+    "blowfish" => blowfish::Blowfish, ctr64;
+    "cast5" => cast5::Cast5, ctr64;
+    "des" => des::Des, ctr64;
+    "desx" => des::DesX, ctr64;
+    "tdes-ede2" => des::TdesEde2, ctr64;
+    "tdes-ede3" => des::TdesEde3, ctr64;
+    "tdes-eee2" => des::TdesEee2, ctr64;
+    "tdes-eee3" => des::TdesEee3, ctr64;
+    "idea" => idea::Idea, ctr64;
+    "kuznyechik" => kuznyechik::Kuznyechik, ctr128;
+    "magma" => magma::Magma, ctr64;
+    "rc2" => rc2::Rc2, ctr64;
+    "serpent" => serpent::Serpent, ctr128;
+    "sm4" => sm4::Sm4, ctr128;
+    "threefish256" => threefish::Threefish256, none;
+    "threefish512" => threefish::Threefish512, none;
+    "threefish1024" => threefish::Threefish1024, none;
+    "twofish" => twofish::Twofish, ctr128;
+}
@@ -0,0 +1,199 @@
+//! Streaming encryption and decryption used by the `enc` and `dec` commands.
+
+use crate::{args::CryptOpts, ciphers::Runner, Error};
+use block_modes::{block_padding::Padding, BlockMode};
+use cipher::{
+    generic_array::{typenum::Unsigned, GenericArray},
+    AsyncStreamCipher, Block, BlockCipher, NewBlockCipher, NewCipher, StreamCipher,
+};
+use pbes::openssl_enc::{self, KeyDerivation, Md};
+use rand_core::{OsRng, RngCore};
+use std::io::{self, Read, Write};
+
+/// Size of processed chunks, a multiple of all supported block sizes
+const CHUNK_SIZE: usize = 1 << 16;
+
+pub struct Crypt<'a> {
+    pub opts: &'a CryptOpts,
+    pub decrypt: bool,
+    pub input: &'a mut dyn Read,
+    pub output: &'a mut dyn Write,
+}
+
+impl Crypt<'_> {
+    /// Get key and IV from options or derive them from the password
+    ///
+    /// The salt header is written to the output during encryption and read
+    /// from the input during decryption.
+    fn key_iv(&mut self, key_len: usize, iv_len: usize) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let password = match &self.opts.password {
+            Some(password) => password,
+            None => {
+                let key = self.opts.key.clone().unwrap_or_default();
+                let iv = match &self.opts.iv {
+                    Some(iv) => iv.clone(),
+                    None if iv_len == 0 => Vec::new(),
+                    None => return Err("mode requires an IV (--iv or --iv-file)".into()),
+                };
+                return Ok((key, iv));
+            }
+        };
+
+        let mut salt = [0u8; 8];
+        if self.decrypt {
+            let mut header = [0u8; 16];
+            read_full(self.input, &mut header)?;
+            match openssl_enc::parse(&header) {
+                Some((s, _)) => salt = *s,
+                None => return Err("input does not start with the salt header".into()),
+            }
+        } else {
+            OsRng.fill_bytes(&mut salt);
+            self.output.write_all(&openssl_enc::header(&salt))?;
+        }
+
+        let kdf = KeyDerivation::Pbkdf2 {
+            md: Md::Sha256,
+            iterations: self.opts.iterations,
+        };
+        let mut key = vec![0u8; key_len];
+        let mut iv = vec![0u8; iv_len];
+        kdf.derive_into(password, Some(&salt), &mut key, &mut iv);
+        Ok((key, iv))
+    }
+
+    /// Process input in chunks with `f`
+    fn stream_with(&mut self, mut f: impl FnMut(&mut [u8])) -> Result<(), Error> {
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        loop {
+            let n = read_full(self.input, &mut buf)?;
+            f(&mut buf[..n]);
+            self.output.write_all(&buf[..n])?;
+            if n < buf.len() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl Runner for Crypt<'_> {
+    type Output = Result<(), Error>;
+
+    fn padded<C, P, M>(mut self) -> Self::Output
+    where
+        C: BlockCipher + NewBlockCipher,
+        P: Padding,
+        M: BlockMode<C, P>,
+    {
+        let (key_len, iv_len) = (C::KeySize::USIZE, M::IvSize::USIZE);
+        let (key, iv) = self.key_iv(key_len, iv_len)?;
+        let mut mode = M::new_from_slices(&key, &iv).map_err(|_| {
+            format!(
+                "invalid key or IV length, expected {} and {} bytes",
+                key_len, iv_len
+            )
+        })?;
+
+        let bs = C::BlockSize::USIZE;
+        let mut buf = vec![0u8; CHUNK_SIZE + bs];
+        if self.decrypt {
+            // the last block is kept until the end of input to remove padding
+            let mut pending = 0;
+            loop {
+                let n = pending + read_full(self.input, &mut buf[pending..])?;
+                if n < buf.len() {
+                    if n % bs != 0 {
+                        return Err("input length is not a multiple of the block size".into());
+                    }
+                    process(&mut mode, &mut buf[..n], true);
+                    let data = P::unpad(&buf[..n]).map_err(|_| "invalid padding")?;
+                    self.output.write_all(data)?;
+                    return Ok(());
+                }
+                process(&mut mode, &mut buf[..CHUNK_SIZE], true);
+                self.output.write_all(&buf[..CHUNK_SIZE])?;
+                buf.copy_within(CHUNK_SIZE.., 0);
+                pending = bs;
+            }
+        } else {
+            loop {
+                let n = read_full(self.input, &mut buf[..CHUNK_SIZE])?;
+                if n < CHUNK_SIZE {
+                    let data = P::pad(&mut buf, n, bs)
+                        .map_err(|_| "input length is not a multiple of the block size")?;
+                    process(&mut mode, data, false);
+                    self.output.write_all(data)?;
+                    return Ok(());
+                }
+                process(&mut mode, &mut buf[..CHUNK_SIZE], false);
+                self.output.write_all(&buf[..CHUNK_SIZE])?;
+            }
+        }
+    }
+
+    fn stream<M: NewCipher + StreamCipher>(mut self) -> Self::Output {
+        let mut mode = self.new_stream::<M>()?;
+        self.stream_with(|data| mode.apply_keystream(data))
+    }
+
+    fn async_stream<M: NewCipher + AsyncStreamCipher>(mut self) -> Self::Output {
+        let mut mode = self.new_stream::<M>()?;
+        let decrypt = self.decrypt;
+        self.stream_with(|data| {
+            if decrypt {
+                mode.decrypt(data)
+            } else {
+                mode.encrypt(data)
+            }
+        })
+    }
+}
+
+impl Crypt<'_> {
+    fn new_stream<M: NewCipher>(&mut self) -> Result<M, Error> {
+        let (key_len, iv_len) = (M::KeySize::USIZE, M::NonceSize::USIZE);
+        let (key, iv) = self.key_iv(key_len, iv_len)?;
+        M::new_from_slices(&key, &iv).map_err(|_| {
+            format!(
+                "invalid key or IV length, expected {} and {} bytes",
+                key_len, iv_len
+            )
+            .into()
+        })
+    }
+}
+
+/// Encrypt or decrypt `data`, which length is a multiple of the block size
+fn process<C: BlockCipher, P: Padding, M: BlockMode<C, P>>(
+    mode: &mut M,
+    data: &mut [u8],
+    decrypt: bool,
+) {
+    let bs = C::BlockSize::USIZE;
+    let mut blocks: Vec<Block<C>> = data
+        .chunks_exact(bs)
+        .map(GenericArray::clone_from_slice)
+        .collect();
+    if decrypt {
+        mode.decrypt_blocks(&mut blocks);
+    } else {
+        mode.encrypt_blocks(&mut blocks);
+    }
+    for (chunk, block) in data.chunks_exact_mut(bs).zip(blocks.iter()) {
+        chunk.copy_from_slice(block);
+    }
+}
+
+/// Read until `buf` is full or the end of input is reached
+fn read_full(input: &mut dyn Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut n = 0;
+    while n < buf.len() {
+        match input.read(&mut buf[n..]) {
+            Ok(0) => break,
+            Ok(m) => n += m,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(n)
+}
@@ -0,0 +1,118 @@
+//! `block-ciphers` command-line tool: encryption and decryption of files
+//! with the block ciphers and modes from this repository, and throughput
+//! measurement similar to `openssl speed`.
+#![forbid(unsafe_code)]
+#![warn(rust_2018_idioms)]
+
+mod args;
+mod ciphers;
+mod crypt;
+mod speed;
+
+use crate::{
+    args::{Command, CryptOpts, Padding, SpeedOpts},
+    ciphers::{backend, dispatch, CIPHERS, MODES},
+    crypt::Crypt,
+    speed::Speed,
+};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    process,
+};
+
+type Error = Box<dyn std::error::Error>;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = args::parse(&args).and_then(run) {
+        eprintln!("block-ciphers: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(cmd: Command) -> Result<(), Error> {
+    match cmd {
+        Command::Encrypt(opts) => crypt(&opts, false),
+        Command::Decrypt(opts) => crypt(&opts, true),
+        Command::Speed(opts) => speed(&opts),
+        Command::List => {
+            println!("ciphers:  {}", CIPHERS.join(" "));
+            println!("modes:    {}", MODES.join(" "));
+            println!("paddings: {}", args::PADDINGS.join(" "));
+            Ok(())
+        }
+        Command::Help => {
+            print!("{}", args::USAGE);
+            Ok(())
+        }
+    }
+}
+
+fn crypt(opts: &CryptOpts, decrypt: bool) -> Result<(), Error> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut input: Box<dyn Read> = match &opts.input {
+        Some(path) => Box::new(File::open(path)?),
+        None => Box::new(stdin.lock()),
+    };
+    let mut output: Box<dyn Write> = match &opts.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(stdout.lock()),
+    };
+    let runner = Crypt {
+        opts,
+        decrypt,
+        input: &mut input,
+        output: &mut output,
+    };
+    dispatch(&opts.cipher, &opts.mode, opts.padding, runner)??;
+    output.flush()?;
+    Ok(())
+}
+
+fn speed(opts: &SpeedOpts) -> Result<(), Error> {
+    let all_ciphers: Vec<String> = CIPHERS.iter().map(|&s| s.into()).collect();
+    let default_modes: Vec<String> = vec!["ecb".into(), "cbc".into(), "gost-ctr".into()];
+    let ciphers = if opts.ciphers.is_empty() {
+        &all_ciphers
+    } else {
+        &opts.ciphers
+    };
+    let modes = if opts.modes.is_empty() {
+        &default_modes
+    } else {
+        &opts.modes
+    };
+
+    if let Some(cipher) = ciphers.iter().find(|c| !CIPHERS.contains(&c.as_str())) {
+        return Err(format!("unknown cipher: {}", cipher).into());
+    }
+
+    println!(
+        "{:<14} {:<9} {:<8} {:>10}",
+        "cipher", "mode", "backend", "MB/s"
+    );
+    for cipher in ciphers {
+        for mode in modes {
+            let runner = Speed {
+                duration: opts.duration,
+            };
+            let speed = match dispatch(cipher, mode, Padding::None, runner) {
+                Ok(speed) => speed,
+                // skip combinations not supported by default modes
+                Err(_) if opts.modes.is_empty() => continue,
+                Err(e) => return Err(e),
+            };
+            println!(
+                "{:<14} {:<9} {:<8} {:>10.1}",
+                cipher,
+                mode,
+                backend(cipher),
+                speed / 1e6
+            );
+            io::stdout().flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Throughput measurement used by the `speed` command.
+
+use crate::ciphers::Runner;
+use block_modes::{block_padding::Padding, BlockMode};
+use cipher::{
+    generic_array::typenum::Unsigned, AsyncStreamCipher, Block, BlockCipher, NewBlockCipher,
+    NewCipher, StreamCipher,
+};
+use std::time::{Duration, Instant};
+
+/// Size of the buffer processed in each iteration
+const BUF_SIZE: usize = 1 << 14;
+
+/// Measure encryption throughput in bytes per second
+pub struct Speed {
+    pub duration: Duration,
+}
+
+impl Speed {
+    fn measure(&self, mut f: impl FnMut()) -> f64 {
+        let start = Instant::now();
+        let mut bytes = 0u64;
+        loop {
+            f();
+            bytes += BUF_SIZE as u64;
+            let elapsed = start.elapsed();
+            if elapsed >= self.duration {
+                return bytes as f64 / elapsed.as_secs_f64();
+            }
+        }
+    }
+}
+
+impl Runner for Speed {
+    type Output = f64;
+
+    fn padded<C, P, M>(self) -> f64
+    where
+        C: BlockCipher + NewBlockCipher,
+        P: Padding,
+        M: BlockMode<C, P>,
+    {
+        let key = vec![0u8; C::KeySize::USIZE];
+        let iv = vec![0u8; M::IvSize::USIZE];
+        let mut mode = M::new_from_slices(&key, &iv).unwrap();
+        let mut blocks = vec![Block::<C>::default(); BUF_SIZE / C::BlockSize::USIZE];
+        self.measure(|| mode.encrypt_blocks(&mut blocks))
+    }
+
+    fn stream<M: NewCipher + StreamCipher>(self) -> f64 {
+        let mut mode = M::new(&Default::default(), &Default::default());
+        let mut buf = vec![0u8; BUF_SIZE];
+        self.measure(|| mode.apply_keystream(&mut buf))
+    }
+
+    fn async_stream<M: NewCipher + AsyncStreamCipher>(self) -> f64 {
+        let mut mode = M::new(&Default::default(), &Default::default());
+        let mut buf = vec![0u8; BUF_SIZE];
+        self.measure(|| mode.encrypt(&mut buf))
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn run(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_block-ciphers"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn decode_hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f";
+const IV: &str = "0f0e0d0c0b0a09080706050403020100";
+
+#[test]
+fn roundtrip() {
+    let msg: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+    for &(cipher, mode) in &[
+        ("aes128", "cbc"),
+        ("kuznyechik", "gost-ctr"),
+        ("magma", "gost-cfb"),
+        ("sm4", "ige"),
+        ("threefish1024", "ecb"),
+    ] {
+        let args = ["-c", cipher, "-m", mode, "-k", "password", "--iter", "10"];
+        let enc = run(&[&["enc"], &args[..]].concat(), &msg);
+        assert!(enc.status.success(), "{} {}", cipher, mode);
+        assert_ne!(enc.stdout, msg);
+        let dec = run(&[&["dec"], &args[..]].concat(), &enc.stdout);
+        assert!(dec.status.success(), "{} {}", cipher, mode);
+        assert_eq!(dec.stdout, msg, "{} {}", cipher, mode);
+    }
+}
+
+/// AES-128 CBC test vector from NIST SP 800-38A
+#[test]
+fn raw_key() {
+    let key = "2b7e151628aed2a6abf7158809cf4f3c";
+    let iv = "000102030405060708090a0b0c0d0e0f";
+    let msg = decode_hex("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51");
+    let res = run(
+        &["enc", "-c", "aes128", "-p", "none", "-K", key, "--iv", iv],
+        &msg,
+    );
+    assert!(res.status.success());
+    assert_eq!(
+        res.stdout,
+        decode_hex("7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2")
+    );
+}
+
+/// Output of `openssl enc -aes-256-cbc -pbkdf2 -pass pass:hunter2`
+#[test]
+fn openssl_compat() {
+    let data = decode_hex(
+        "53616c7465645f5fb0553a0d78d85a67fb21b8ecf0f4e765771b541b64f7b3f8\
+         4b3d5ae28189a2436de0ae21e2f83c7656359a0c3f1a2cb114b4af49ba8f7b56",
+    );
+    let res = run(&["dec", "-c", "aes256", "-k", "hunter2"], &data);
+    assert!(res.status.success());
+    assert_eq!(
+        res.stdout,
+        b"The quick brown fox jumps over the lazy dog.\n"
+    );
+}
+
+#[test]
+fn errors() {
+    let res = run(&["dec", "-c", "aes256", "-k", "hunter3"], &[0; 32]);
+    assert!(!res.status.success());
+    let res = run(&["enc", "-c", "aes128", "-K", KEY], b"");
+    assert!(!res.status.success());
+    let res = run(&["enc", "-c", "aes128", "-m", "xts", "-K", KEY], b"");
+    assert!(!res.status.success());
+    let res = run(
+        &["enc", "-c", "aes128", "-K", KEY, "--iv", IV, "-k", "x"],
+        b"",
+    );
+    assert!(!res.status.success());
+    let res = run(
+        &["enc", "-c", "aes128", "-m", "ecb", "-p", "none", "-K", KEY],
+        b"abc",
+    );
+    assert!(!res.status.success());
+    let res = run(
+        &["enc", "-c", "threefish256", "-m", "gost-ctr", "-k", "x"],
+        b"",
+    );
+    assert!(!res.status.success());
+}
+
+#[test]
+fn speed() {
+    let res = run(&["speed", "-c", "aes128", "-m", "ctr", "-s", "0.01"], b"");
+    assert!(!res.status.success());
+    let res = run(&["speed", "-c", "aes128", "-m", "cbc", "-s", "0.01"], b"");
+    assert!(res.status.success());
+    let out = String::from_utf8(res.stdout).unwrap();
+    assert!(out.lines().nth(1).unwrap().starts_with("aes128"));
+}
@@ -1,3 +1,27 @@
 #![feature(test)]
 
 cipher::block_cipher_bench!(des::Des, 8);
+
+#[bench]
+pub fn encrypt_par(bh: &mut Bencher) {
+    let state = <des::Des>::new_from_slice(&[1u8; 8]).unwrap();
+    let mut blocks = cipher::ParBlocks::<des::Des>::default();
+
+    bh.iter(|| {
+        state.encrypt_par_blocks(&mut blocks);
+        test::black_box(&blocks);
+    });
+    bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+}
+
+#[bench]
+pub fn decrypt_par(bh: &mut Bencher) {
+    let state = <des::Des>::new_from_slice(&[1u8; 8]).unwrap();
+    let mut blocks = cipher::ParBlocks::<des::Des>::default();
+
+    bh.iter(|| {
+        state.decrypt_par_blocks(&mut blocks);
+        test::black_box(&blocks);
+    });
+    bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+}
@@ -1,3 +1,27 @@
 #![feature(test)]
 
 cipher::block_cipher_bench!(des::TdesEde3, 24);
+
+#[bench]
+pub fn encrypt_par(bh: &mut Bencher) {
+    let state = <des::TdesEde3>::new_from_slice(&[1u8; 24]).unwrap();
+    let mut blocks = cipher::ParBlocks::<des::TdesEde3>::default();
+
+    bh.iter(|| {
+        state.encrypt_par_blocks(&mut blocks);
+        test::black_box(&blocks);
+    });
+    bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+}
+
+#[bench]
+pub fn decrypt_par(bh: &mut Bencher) {
+    let state = <des::TdesEde3>::new_from_slice(&[1u8; 24]).unwrap();
+    let mut blocks = cipher::ParBlocks::<des::TdesEde3>::default();
+
+    bh.iter(|| {
+        state.decrypt_par_blocks(&mut blocks);
+        test::black_box(&blocks);
+    });
+    bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+}
@@ -0,0 +1,180 @@
+//! Bitsliced DES implementation which processes 64 blocks in parallel.
+//!
+//! The blocks are transposed into 64 words, so that the word `i` holds the
+//! bit `i + 1` (in the FIPS 46-3 numbering, i.e. counting from the most
+//! significant bit) of every block. The IP, E, P and FP permutations become
+//! simple re-indexing and the S-boxes are evaluated using boolean circuits,
+//! so no secret-dependent table lookups or branches are performed.
+
+use byteorder::{ByteOrder, BE};
+use cipher::{
+    consts::{U64, U8},
+    generic_array::GenericArray,
+};
+
+mod sboxes;
+
+/// 64 DES blocks processed in parallel
+pub(crate) type Blocks = GenericArray<GenericArray<u8, U8>, U64>;
+
+/// Initial permutation (1-based, as in FIPS 46-3)
+#[rustfmt::skip]
+const IP: [u8; 64] = [
+    58, 50, 42, 34, 26, 18, 10, 2, 60, 52, 44, 36, 28, 20, 12, 4,
+    62, 54, 46, 38, 30, 22, 14, 6, 64, 56, 48, 40, 32, 24, 16, 8,
+    57, 49, 41, 33, 25, 17,  9, 1, 59, 51, 43, 35, 27, 19, 11, 3,
+    61, 53, 45, 37, 29, 21, 13, 5, 63, 55, 47, 39, 31, 23, 15, 7,
+];
+
+/// Final permutation (1-based, as in FIPS 46-3)
+#[rustfmt::skip]
+const FP: [u8; 64] = [
+    40, 8, 48, 16, 56, 24, 64, 32, 39, 7, 47, 15, 55, 23, 63, 31,
+    38, 6, 46, 14, 54, 22, 62, 30, 37, 5, 45, 13, 53, 21, 61, 29,
+    36, 4, 44, 12, 52, 20, 60, 28, 35, 3, 43, 11, 51, 19, 59, 27,
+    34, 2, 42, 10, 50, 18, 58, 26, 33, 1, 41,  9, 49, 17, 57, 25,
+];
+
+/// Expansion permutation (1-based, as in FIPS 46-3)
+#[rustfmt::skip]
+const E: [u8; 48] = [
+    32,  1,  2,  3,  4,  5,  4,  5,  6,  7,  8,  9,
+     8,  9, 10, 11, 12, 13, 12, 13, 14, 15, 16, 17,
+    16, 17, 18, 19, 20, 21, 20, 21, 22, 23, 24, 25,
+    24, 25, 26, 27, 28, 29, 28, 29, 30, 31, 32,  1,
+];
+
+/// Permutation applied to the S-box outputs (1-based, as in FIPS 46-3)
+#[rustfmt::skip]
+const P: [u8; 32] = [
+    16,  7, 20, 21, 29, 12, 28, 17,  1, 15, 23, 26,  5, 18, 31, 10,
+     2,  8, 24, 14, 32, 27,  3,  9, 19, 13, 30,  6, 22, 11,  4, 25,
+];
+
+/// Transpose 64x64 bit matrix
+fn transpose(m: &mut [u64; 64]) {
+    let mut j = 32;
+    let mut mask = 0x0000_0000_FFFF_FFFFu64;
+    while j != 0 {
+        let mut k = 0;
+        while k < 64 {
+            let t = (m[k] ^ (m[k | j] >> j)) & mask;
+            m[k] ^= t;
+            m[k | j] ^= t << j;
+            k = ((k | j) + 1) & !j;
+        }
+        j >>= 1;
+        mask ^= mask << j;
+    }
+}
+
+/// XOR result of the DES round function `f(r, key)` into `l`
+#[inline(always)]
+fn f(l: &mut [u64; 32], r: &[u64; 32], key: u64) {
+    let mut x = [0u64; 48];
+    for (j, v) in x.iter_mut().enumerate() {
+        let k = 0u64.wrapping_sub((key >> (63 - j)) & 1);
+        *v = r[E[j] as usize - 1] ^ k;
+    }
+
+    let mut out = [0u64; 32];
+    macro_rules! sbox {
+        ($i:expr, $f:path) => {
+            let x = &x[6 * $i..];
+            let o = $f(x[0], x[1], x[2], x[3], x[4], x[5]);
+            out[4 * $i..4 * $i + 4].copy_from_slice(&o);
+        };
+    }
+    sbox!(0, sboxes::s1);
+    sbox!(1, sboxes::s2);
+    sbox!(2, sboxes::s3);
+    sbox!(3, sboxes::s4);
+    sbox!(4, sboxes::s5);
+    sbox!(5, sboxes::s6);
+    sbox!(6, sboxes::s7);
+    sbox!(7, sboxes::s8);
+
+    for j in 0..32 {
+        l[j] ^= out[P[j] as usize - 1];
+    }
+}
+
+/// Bitsliced state after the initial permutation
+pub(crate) struct State {
+    l: [u64; 32],
+    r: [u64; 32],
+}
+
+impl State {
+    /// Load blocks and apply the initial permutation
+    pub(crate) fn new(blocks: &Blocks) -> Self {
+        let mut m = [0u64; 64];
+        for (w, block) in m.iter_mut().zip(blocks.iter()) {
+            *w = BE::read_u64(block);
+        }
+        transpose(&mut m);
+
+        let mut l = [0u64; 32];
+        let mut r = [0u64; 32];
+        for i in 0..32 {
+            l[i] = m[IP[i] as usize - 1];
+            r[i] = m[IP[32 + i] as usize - 1];
+        }
+        Self { l, r }
+    }
+
+    /// Apply the final permutation and store blocks
+    pub(crate) fn write(&self, blocks: &mut Blocks) {
+        let mut m = [0u64; 64];
+        for (i, w) in m.iter_mut().enumerate() {
+            let j = FP[i] as usize - 1;
+            *w = if j < 32 { self.l[j] } else { self.r[j - 32] };
+        }
+        transpose(&mut m);
+
+        for (w, block) in m.iter().zip(blocks.iter_mut()) {
+            BE::write_u64(block, *w);
+        }
+    }
+
+    /// Encrypt state using subkeys generated by `gen_keys`
+    pub(crate) fn encrypt(&mut self, keys: &[u64; 16]) {
+        for k in keys.chunks_exact(2) {
+            f(&mut self.l, &self.r, k[0]);
+            f(&mut self.r, &self.l, k[1]);
+        }
+        core::mem::swap(&mut self.l, &mut self.r);
+    }
+
+    /// Decrypt state using subkeys generated by `gen_keys`
+    pub(crate) fn decrypt(&mut self, keys: &[u64; 16]) {
+        for k in keys.rchunks_exact(2) {
+            f(&mut self.l, &self.r, k[1]);
+            f(&mut self.r, &self.l, k[0]);
+        }
+        core::mem::swap(&mut self.l, &mut self.r);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sboxes::*;
+    use crate::consts::SBOXES;
+
+    #[test]
+    fn sboxes() {
+        let funcs = [s1, s2, s3, s4, s5, s6, s7, s8];
+        for (sbox, func) in SBOXES.iter().zip(funcs.iter()) {
+            for v in 0..64usize {
+                let bit = |i: usize| 0u64.wrapping_sub(((v >> (5 - i)) & 1) as u64);
+                let out = func(bit(0), bit(1), bit(2), bit(3), bit(4), bit(5));
+                let mut res = 0;
+                for o in out.iter() {
+                    assert!(*o == 0 || *o == !0);
+                    res = (res << 1) | (*o & 1) as u8;
+                }
+                assert_eq!(res, sbox[v]);
+            }
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! Bitsliced DES S-boxes.
+//!
+//! Each function evaluates one S-box on 64 independent inputs, where `a1`
+//! holds the most significant input bit and the first output word holds the
+//! most significant output bit. The circuits were derived from the algebraic
+//! normal form of the S-box tables in `consts.rs`, factored over the outer
+//! (row) bits `a1` and `a6`, with shared XOR terms eliminated. They consist
+//! only of bitwise operations and are checked against the tables in tests.
+
+#![allow(clippy::many_single_char_names)]
+
+#[inline(always)]
+pub(super) fn s1(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a2_a3 ^ a2_a3_a4;
+    let x1 = a3_a4 ^ a5;
+    let x2 = a2 ^ a2_a4;
+    let x3 = a2_a4_a5 ^ x0;
+    let x4 = a2_a3_a5 ^ a2_a5;
+    let x5 = a3_a4_a5 ^ x1;
+    let x6 = a3_a5 ^ a4;
+    let x7 = a3 ^ a4_a5;
+    let x8 = x2 ^ x3;
+    let x9 = x5 ^ x6;
+    let x10 = a2 ^ x0;
+    let x11 = a2_a3 ^ x2;
+    let x12 = a2_a3_a4 ^ a4;
+    let x13 = a2_a4 ^ x4;
+    let x14 = x3 ^ x9;
+    let g00_0 = !(a3 ^ x10 ^ x5);
+    let g00_1 = !(a2_a4_a5 ^ a3_a5 ^ x2 ^ x7);
+    let g00_2 = !(a4_a5 ^ x0 ^ x1 ^ x13 ^ x6);
+    let g00_3 = a2 ^ a2_a4_a5 ^ a2_a5 ^ x6;
+    let g01_0 = !(a3_a4 ^ a4_a5);
+    let g01_1 = !(x11 ^ x9);
+    let g01_2 = !(a3_a4 ^ x7 ^ x8);
+    let g01_3 = a2_a3_a5 ^ a5 ^ x11;
+    let g10_0 = !(a2_a4 ^ x14);
+    let g10_1 = x1 ^ x10 ^ x4 ^ x7;
+    let g10_2 = !(a2_a3_a5 ^ x1 ^ x8);
+    let g10_3 = a2_a5 ^ x14 ^ x7;
+    let g11_0 = a3_a4 ^ x12 ^ x13;
+    let g11_1 = !(a2 ^ a2_a3_a5 ^ a3_a4_a5 ^ a3_a5 ^ x3);
+    let g11_2 = a3_a4_a5 ^ a5 ^ x4 ^ x8;
+    let g11_3 = !(x12 ^ x4 ^ x5);
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & g10_0) ^ (a6 & g01_0) ^ (a1_a6 & g11_0),
+        g00_1 ^ (a1 & g10_1) ^ (a6 & g01_1) ^ (a1_a6 & g11_1),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ (a1 & g10_3) ^ (a6 & g01_3) ^ (a1_a6 & g11_3),
+    ]
+}
+
+#[inline(always)]
+pub(super) fn s2(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a2_a3 ^ a2_a4;
+    let x1 = a2_a3_a5 ^ a2_a4_a5;
+    let x2 = a3 ^ a4_a5;
+    let x3 = a2 ^ a3_a5;
+    let x4 = a2_a5 ^ x0;
+    let x5 = a2 ^ a5;
+    let x6 = a2_a3 ^ a2_a4_a5;
+    let x7 = a2_a3_a5 ^ x2;
+    let x8 = a2_a5 ^ x3;
+    let x9 = a3_a4 ^ a3_a4_a5;
+    let x10 = a4_a5 ^ x6;
+    let x11 = x3 ^ x9;
+    let x12 = x4 ^ x7;
+    let g00_0 = !(a2_a4_a5 ^ a5 ^ x0 ^ x2);
+    let g00_1 = !(a4 ^ x0 ^ x5);
+    let g00_2 = !(a4 ^ a5 ^ x11);
+    let g00_3 = !(a3 ^ a3_a5 ^ a4 ^ x1);
+    let g01_0 = !(a2 ^ a2_a3);
+    let g01_1 = !(a2_a4 ^ a3_a4_a5 ^ x2);
+    let g01_2 = a2_a4_a5 ^ a3_a4 ^ x4;
+    let g01_3 = a2 ^ x1 ^ x2;
+    let g10_0 = !x10;
+    let g10_1 = !x1;
+    let g10_2 = !(a2_a3_a4 ^ x11 ^ x12);
+    let g10_3 = !(a3 ^ x8);
+    let g11_0 = a5 ^ x10 ^ x8;
+    let g11_2 = a2_a4 ^ a3_a4 ^ x1 ^ x5;
+    let g11_3 = !(a3_a5 ^ a5 ^ x12);
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & g10_0) ^ (a6 & g01_0) ^ (a1_a6 & g11_0),
+        g00_1 ^ (a1 & g10_1) ^ (a6 & g01_1) ^ (a1_a6 & x1),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ (a1 & g10_3) ^ (a6 & g01_3) ^ (a1_a6 & g11_3),
+    ]
+}
+
+#[inline(always)]
+pub(super) fn s3(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a2_a3 ^ a2_a5;
+    let x1 = a3_a5 ^ a4_a5;
+    let x2 = a2_a3_a4 ^ a2_a4;
+    let x3 = a2 ^ a4;
+    let x4 = a2_a3_a5 ^ x0;
+    let x5 = a2_a4_a5 ^ a3_a4;
+    let x6 = a3 ^ x1;
+    let x7 = x2 ^ x5;
+    let x8 = a2 ^ x4;
+    let x9 = a3_a4_a5 ^ a4;
+    let x10 = x3 ^ x7;
+    let x11 = a2_a3_a4 ^ x0;
+    let x12 = a2_a4_a5 ^ x9;
+    let x13 = a2_a5 ^ x1;
+    let x14 = a3_a4_a5 ^ x10;
+    let x15 = a5 ^ x6;
+    let g00_0 = !(a2 ^ a2_a3_a5 ^ x15 ^ x7);
+    let g00_1 = x2 ^ x4 ^ x6;
+    let g00_2 = !(a3_a5 ^ a5 ^ x0 ^ x14);
+    let g00_3 = x1 ^ x3;
+    let g01_0 = a2_a3_a5 ^ a4_a5 ^ x12;
+    let g01_1 = !(a2_a4 ^ a4_a5 ^ x3 ^ x4);
+    let g01_2 = !(a3_a4 ^ x11 ^ x6 ^ x9);
+    let g10_0 = a2_a3 ^ a3 ^ a4_a5 ^ x10;
+    let g10_1 = !(x2 ^ x8);
+    let g10_2 = !(a4 ^ x13 ^ x2);
+    let g10_3 = !(x15 ^ x8);
+    let g11_0 = !(x1 ^ x12);
+    let g11_1 = a3_a4_a5 ^ a4_a5 ^ x8;
+    let g11_2 = !(x13 ^ x14);
+    let g11_3 = !(x11 ^ x3);
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & g10_0) ^ (a6 & g01_0) ^ (a1_a6 & g11_0),
+        g00_1 ^ (a1 & g10_1) ^ (a6 & g01_1) ^ (a1_a6 & g11_1),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ (a1 & g10_3) ^ a6 ^ (a1_a6 & g11_3),
+    ]
+}
+
+#[inline(always)]
+pub(super) fn s4(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a2_a5 ^ a4;
+    let x1 = a2 ^ a2_a3_a4;
+    let x2 = a2_a3_a5 ^ a5;
+    let x3 = a2_a4_a5 ^ x0;
+    let x4 = a3 ^ a4_a5;
+    let x5 = a3_a4_a5 ^ x2;
+    let x6 = x1 ^ x3;
+    let x7 = a2_a3 ^ x4;
+    let x8 = a2_a4 ^ a3_a5;
+    let x9 = a3_a4_a5 ^ a5;
+    let x10 = a2_a3 ^ x6;
+    let x11 = a2_a4_a5 ^ x8;
+    let x12 = a3_a4 ^ a3_a5;
+    let x13 = a4 ^ x5;
+    let x14 = x0 ^ x12;
+    let x15 = x10 ^ x9;
+    let x16 = x13 ^ x7;
+    let x17 = x14 ^ x5;
+    let x18 = x2 ^ x4;
+    let x19 = x18 ^ x6;
+    let g00_0 = a2_a3 ^ a3_a5 ^ x2 ^ x3;
+    let g00_1 = !(a3_a5 ^ x1 ^ x7);
+    let g00_2 = !(x1 ^ x4 ^ x9);
+    let g00_3 = !(x3 ^ x7);
+    let g01_0 = !x19;
+    let g01_2 = !x15;
+    let g10_0 = !(a2_a3_a4 ^ a2_a3_a5 ^ a2_a4 ^ a3_a4 ^ a3_a4_a5 ^ x3);
+    let g10_1 = !(a2_a3_a4 ^ a5 ^ x11);
+    let g10_2 = a4_a5 ^ x5 ^ x6 ^ x8;
+    let g10_3 = !(a2_a3 ^ a2_a5 ^ a3 ^ x1 ^ x11);
+    let g11_2 = !x16;
+    let g11_3 = !x16;
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & g10_0) ^ (a6 & g01_0) ^ (a1_a6 & x17),
+        g00_1 ^ (a1 & g10_1) ^ (a6 & x19) ^ (a1_a6 & x17),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ (a1 & g10_3) ^ (a6 & x15) ^ (a1_a6 & g11_3),
+    ]
+}
+
+#[inline(always)]
+pub(super) fn s5(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a3 ^ a3_a4_a5;
+    let x1 = a2_a4 ^ a5;
+    let x2 = a3_a4 ^ x0;
+    let x3 = a2 ^ a2_a4_a5;
+    let x4 = a2_a3 ^ a2_a3_a4;
+    let x5 = a2_a3_a5 ^ a2_a5;
+    let x6 = a4 ^ x1;
+    let x7 = a4_a5 ^ x4;
+    let x8 = a2_a5 ^ a3_a5;
+    let x9 = a2_a3 ^ x2;
+    let x10 = a2_a3_a4 ^ a5;
+    let x11 = a2_a4 ^ a4;
+    let x12 = a3 ^ x6;
+    let x13 = a3_a4 ^ a3_a4_a5;
+    let x14 = a3_a5 ^ a4_a5;
+    let x15 = x0 ^ x5;
+    let x16 = x1 ^ x3;
+    let x17 = x10 ^ x3;
+    let x18 = x11 ^ x7;
+    let x19 = x2 ^ x8;
+    let g00_0 = a4_a5 ^ x13 ^ x16;
+    let g00_2 = !(a4 ^ x13 ^ x14 ^ x17 ^ x5);
+    let g00_3 = a2_a4 ^ x14 ^ x15;
+    let g01_0 = !(a2_a3_a5 ^ x6 ^ x9);
+    let g01_1 = !(a3_a5 ^ x2 ^ x4);
+    let g01_2 = a2_a3_a4 ^ x2 ^ x5 ^ x6;
+    let g01_3 = x16 ^ x19;
+    let g10_0 = a2_a3_a4 ^ a2_a4_a5 ^ x0 ^ x1;
+    let g10_1 = !(a3_a4_a5 ^ x7);
+    let g10_2 = !(x18 ^ x19);
+    let g10_3 = x15 ^ x18 ^ x3;
+    let g11_0 = a2_a3 ^ a2_a4_a5 ^ x12 ^ x8;
+    let g11_1 = a2 ^ x1 ^ x7;
+    let g11_2 = !(a2_a3_a5 ^ x17 ^ x2);
+    let g11_3 = !(a2 ^ x5 ^ x9);
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & g10_0) ^ (a6 & g01_0) ^ (a1_a6 & g11_0),
+        x12 ^ (a1 & g10_1) ^ (a6 & g01_1) ^ (a1_a6 & g11_1),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ (a1 & g10_3) ^ (a6 & g01_3) ^ (a1_a6 & g11_3),
+    ]
+}
+
+#[inline(always)]
+pub(super) fn s6(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a3 ^ a3_a5;
+    let x1 = a3_a4 ^ a3_a4_a5;
+    let x2 = a2_a3 ^ a2_a4_a5;
+    let x3 = a4_a5 ^ x1;
+    let x4 = a5 ^ x0;
+    let x5 = a2_a3_a5 ^ a4_a5;
+    let x6 = a4 ^ x4;
+    let x7 = a2 ^ a2_a4;
+    let x8 = a2_a3 ^ a2_a3_a4;
+    let x9 = a2_a3_a4 ^ x3;
+    let x10 = x0 ^ x5;
+    let x11 = x2 ^ x5;
+    let g00_0 = !(a2 ^ a2_a3 ^ a5 ^ x3);
+    let g00_1 = !(a3_a4_a5 ^ x6 ^ x7);
+    let g00_2 = a2_a3_a5 ^ a3_a5 ^ a4 ^ x2;
+    let g00_3 = a2_a4 ^ a3 ^ a5 ^ x1 ^ x8;
+    let g01_0 = x6 ^ x9;
+    let g01_1 = !a2_a4_a5;
+    let g01_2 = !(a2_a5 ^ a4_a5);
+    let g01_3 = a2_a4_a5 ^ x9;
+    let g10_1 = !(a3 ^ a3_a4_a5 ^ x11);
+    let g10_2 = a2 ^ x2 ^ x4;
+    let g10_3 = !(a3_a5 ^ x3);
+    let g11_0 = !(a2_a3_a4 ^ a2_a4 ^ x11 ^ x6);
+    let g11_1 = x10 ^ x8;
+    let g11_2 = !(a2_a4_a5 ^ x10);
+    let g11_3 = !(x2 ^ x3 ^ x7);
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & x4) ^ (a6 & g01_0) ^ (a1_a6 & g11_0),
+        g00_1 ^ (a1 & g10_1) ^ (a6 & g01_1) ^ (a1_a6 & g11_1),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ (a1 & g10_3) ^ (a6 & g01_3) ^ (a1_a6 & g11_3),
+    ]
+}
+
+#[inline(always)]
+pub(super) fn s7(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a2_a3 ^ a3_a4_a5;
+    let x1 = a3 ^ a5;
+    let x2 = a2_a3_a4 ^ x0;
+    let x3 = a2 ^ a4;
+    let x4 = a2_a4 ^ a2_a4_a5;
+    let x5 = a4_a5 ^ x1;
+    let x6 = a2_a3_a5 ^ a3_a5;
+    let x7 = a2_a3 ^ x4;
+    let x8 = a2_a3_a4 ^ a3_a4_a5;
+    let x9 = a2_a4_a5 ^ x5;
+    let x10 = a3_a4 ^ x0;
+    let x11 = a5 ^ x2;
+    let x12 = x11 ^ x6;
+    let g00_0 = a2_a4 ^ x1 ^ x2;
+    let g00_1 = !(a2_a3 ^ a2_a4 ^ a5 ^ x3);
+    let g00_2 = x3 ^ x9;
+    let g00_3 = a2 ^ x10 ^ x5;
+    let g01_0 = !x2;
+    let g01_1 = a2 ^ a2_a4_a5 ^ a3_a4_a5;
+    let g01_2 = a3_a4 ^ x8 ^ x9;
+    let g01_3 = !x4;
+    let g10_0 = x12 ^ x3 ^ x4;
+    let g10_1 = !(a3 ^ x3 ^ x8);
+    let g10_2 = x1 ^ x6 ^ x7;
+    let g11_0 = !(x2 ^ x5);
+    let g11_1 = !x7;
+    let g11_2 = !(a3_a4 ^ x12);
+    let g11_3 = a2_a4 ^ a2_a5 ^ a4 ^ a4_a5 ^ x10;
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & g10_0) ^ (a6 & g01_0) ^ (a1_a6 & g11_0),
+        g00_1 ^ (a1 & g10_1) ^ (a6 & g01_1) ^ (a1_a6 & g11_1),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ a1 ^ (a6 & g01_3) ^ (a1_a6 & g11_3),
+    ]
+}
+
+#[inline(always)]
+pub(super) fn s8(a1: u64, a2: u64, a3: u64, a4: u64, a5: u64, a6: u64) -> [u64; 4] {
+    let a4_a5 = a4 & a5;
+    let a3_a5 = a3 & a5;
+    let a3_a4 = a3 & a4;
+    let a2_a5 = a2 & a5;
+    let a2_a4 = a2 & a4;
+    let a2_a3 = a2 & a3;
+    let a3_a4_a5 = a3_a4 & a5;
+    let a2_a4_a5 = a2_a4 & a5;
+    let a2_a3_a5 = a2_a3 & a5;
+    let a2_a3_a4 = a2_a3 & a4;
+    let x0 = a2_a3_a4 ^ a3_a4;
+    let x1 = a2_a4 ^ a2_a4_a5;
+    let x2 = a3_a5 ^ a5;
+    let x3 = a2_a3 ^ x1;
+    let x4 = a2_a5 ^ a4;
+    let x5 = a2_a3_a5 ^ a4_a5;
+    let x6 = a2 ^ a4_a5;
+    let x7 = a3 ^ a5;
+    let x8 = a3_a5 ^ x0;
+    let x9 = x2 ^ x4;
+    let x10 = a2 ^ x3;
+    let x11 = a2_a4_a5 ^ a4;
+    let x12 = a3 ^ x2;
+    let x13 = x0 ^ x7;
+    let g00_0 = !(a2_a5 ^ x1 ^ x13);
+    let g00_1 = !(x10 ^ x9);
+    let g00_2 = x12 ^ x6;
+    let g00_3 = !(x11 ^ x6 ^ x7);
+    let g01_0 = !(a2_a4 ^ x0 ^ x4 ^ x6);
+    let g01_2 = a2_a3_a4 ^ a2_a5 ^ x10;
+    let g01_3 = a2_a3 ^ a3_a4 ^ a3_a4_a5 ^ x9;
+    let g10_0 = !(a4_a5 ^ x8);
+    let g10_1 = a3 ^ x3 ^ x4 ^ x8;
+    let g10_2 = !(x5 ^ x9);
+    let g10_3 = a2_a3_a5 ^ x12 ^ x3;
+    let g11_0 = !(x13 ^ x3 ^ x5);
+    let g11_1 = a4 ^ x0 ^ x2;
+    let g11_2 = a5 ^ x11 ^ x5;
+    let g11_3 = !(a2_a4 ^ a2_a5 ^ x5 ^ x8);
+    let a1_a6 = a1 & a6;
+    [
+        g00_0 ^ (a1 & g10_0) ^ (a6 & g01_0) ^ (a1_a6 & g11_0),
+        g00_1 ^ (a1 & g10_1) ^ a6 ^ (a1_a6 & g11_1),
+        g00_2 ^ (a1 & g10_2) ^ (a6 & g01_2) ^ (a1_a6 & g11_2),
+        g00_3 ^ (a1 & g10_3) ^ (a6 & g01_3) ^ (a1_a6 & g11_3),
+    ]
+}
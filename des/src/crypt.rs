@@ -0,0 +1,167 @@
+//! DES-based `crypt(3)` password hashing.
+//!
+//! Two formats are supported:
+//! - traditional: 2-character salt, 25 iterations and passwords truncated
+//!   to 8 characters, e.g. `CCNf8Sbh3HDfQ`;
+//! - BSDi extended: `_` followed by 4 characters of the iteration count
+//!   and 4 characters of the 24-bit salt, passwords of any length, e.g.
+//!   `_J9..CCCCXBrJUJV154M`.
+//!
+//! Both formats use DES with the E expansion modified by the salt and are
+//! provided only for compatibility with legacy systems, they must not be
+//! used for new password hashes.
+
+use crate::des::{gen_keys, Des};
+use alloc::string::String;
+use core::fmt;
+
+/// Alphabet used for salts, iteration counts and hashes
+const ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Number of iterations used by the traditional format
+const TRADITIONAL_ROUNDS: u32 = 25;
+
+/// Maximum iteration count of the extended format
+const MAX_ROUNDS: u32 = (1 << 24) - 1;
+
+/// Invalid salt or hash setting error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidSetting;
+
+impl fmt::Display for InvalidSetting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("InvalidSetting")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSetting {}
+
+/// Hash `password` using the salt and the iteration count from `setting`.
+///
+/// `setting` is either a traditional 2-character salt, or `_` followed by
+/// the 4-character iteration count and the 4-character salt. An existing
+/// hash can be passed as well, in which case only its setting part is
+/// used. As in C implementations, `password` is processed only up to the
+/// first NUL byte.
+pub fn hash(password: &[u8], setting: &str) -> Result<String, InvalidSetting> {
+    let password = until_nul(password);
+    let setting = setting.as_bytes();
+    if setting.first() == Some(&b'_') {
+        if setting.len() < 9 {
+            return Err(InvalidSetting);
+        }
+        let rounds = decode(&setting[1..5])?;
+        let salt = decode(&setting[5..9])?;
+        hash_extended(password, rounds, salt)
+    } else {
+        if setting.len() < 2 {
+            return Err(InvalidSetting);
+        }
+        let salt = decode(&setting[..2])?;
+        let key = password_key(password);
+        let mut res = String::with_capacity(13);
+        res.push(setting[0] as char);
+        res.push(setting[1] as char);
+        encode_hash(&mut res, des_crypt(key, salt, TRADITIONAL_ROUNDS));
+        Ok(res)
+    }
+}
+
+/// Hash `password` in the BSDi extended format using the given number of
+/// iterations and 24-bit salt.
+///
+/// `rounds` must be in the range `1..2^24`.
+pub fn hash_extended(password: &[u8], rounds: u32, salt: u32) -> Result<String, InvalidSetting> {
+    if rounds == 0 || rounds > MAX_ROUNDS || salt > MAX_ROUNDS {
+        return Err(InvalidSetting);
+    }
+    let mut password = until_nul(password);
+    let mut key = password_key(password);
+    while password.len() > 8 {
+        password = &password[8..];
+        let des = Des {
+            keys: gen_keys(key),
+        };
+        key = des.encrypt(key) ^ password_key(password);
+    }
+
+    let mut res = String::with_capacity(20);
+    res.push('_');
+    encode24(&mut res, rounds);
+    encode24(&mut res, salt);
+    encode_hash(&mut res, des_crypt(key, salt, rounds));
+    Ok(res)
+}
+
+/// Verify `password` against a traditional or an extended `hash`.
+///
+/// The computed hash is compared in constant time.
+pub fn verify(password: &[u8], hash: &str) -> bool {
+    let computed = match self::hash(password, hash) {
+        Ok(computed) => computed,
+        Err(_) => return false,
+    };
+    if computed.len() != hash.len() {
+        return false;
+    }
+    let diff = computed
+        .bytes()
+        .zip(hash.bytes())
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+    diff == 0
+}
+
+/// Encrypt zero block `rounds` times using the salted E expansion
+fn des_crypt(key: u64, salt: u32, rounds: u32) -> u64 {
+    // salt bit `i` swaps bits `i` and `i + 24` of the E expansion output
+    let mut mask = 0u64;
+    for i in 0..24 {
+        mask |= u64::from((salt >> i) & 1) << (39 - i);
+    }
+    let des = Des {
+        keys: gen_keys(key),
+    };
+    let mut data = 0;
+    for _ in 0..rounds {
+        data = des.encrypt_salted(data, mask);
+    }
+    data
+}
+
+/// Password bytes up to the first NUL byte
+fn until_nul(password: &[u8]) -> &[u8] {
+    password.split(|&b| b == 0).next().unwrap_or(&[])
+}
+
+/// DES key built from the first 8 password bytes shifted left by one bit
+fn password_key(password: &[u8]) -> u64 {
+    password
+        .iter()
+        .take(8)
+        .enumerate()
+        .fold(0, |key, (i, &b)| key | (u64::from(b << 1) << (56 - 8 * i)))
+}
+
+/// Decode little-endian base-64 number
+fn decode(s: &[u8]) -> Result<u32, InvalidSetting> {
+    s.iter().enumerate().try_fold(0, |acc, (i, c)| {
+        let v = ALPHABET.iter().position(|a| a == c).ok_or(InvalidSetting)?;
+        Ok(acc | ((v as u32) << (6 * i)))
+    })
+}
+
+/// Encode 24-bit number as 4 little-endian base-64 characters
+fn encode24(res: &mut String, v: u32) {
+    for i in 0..4 {
+        res.push(ALPHABET[(v >> (6 * i)) as usize & 63] as char);
+    }
+}
+
+/// Encode 64-bit hash as 11 big-endian base-64 characters
+fn encode_hash(res: &mut String, hash: u64) {
+    let v = u128::from(hash) << 2;
+    for i in 0..11 {
+        res.push(ALPHABET[(v >> (60 - 6 * i)) as usize & 63] as char);
+    }
+}
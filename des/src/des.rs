@@ -4,11 +4,12 @@
 
 use byteorder::{ByteOrder, BE};
 use cipher::{
-    consts::{U1, U8},
+    consts::{U64, U8},
     generic_array::GenericArray,
     BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
 };
 
+use crate::bitsliced;
 use crate::consts::{SBOXES, SHIFTS};
 
 /// Data Encryption Standard (DES) block cipher.
@@ -142,15 +143,19 @@ fn rotate(mut val: u64, shift: u8) -> u64 {
     (val | top_bits) & 0x0FFFFFFF
 }
 
-fn round(input: u64, key: u64) -> u64 {
+/// Performs a single round. Every bit set in `salt` swaps the corresponding
+/// bits of the two halves of the E expansion output (used by `crypt(3)`).
+#[inline(always)]
+fn round(input: u64, key: u64, salt: u64) -> u64 {
     let l = input & (0xFFFF_FFFF << 32);
     let r = input << 32;
 
-    r | ((f(r, key) ^ l) >> 32)
+    r | ((f(r, key, salt) ^ l) >> 32)
 }
 
-fn f(input: u64, key: u64) -> u64 {
-    let mut val = e(input as u64);
+#[inline(always)]
+fn f(input: u64, key: u64, salt: u64) -> u64 {
+    let mut val = delta_swap(e(input), 24, salt);
     val ^= key;
     val = apply_sboxes(val);
     p(val)
@@ -172,17 +177,28 @@ impl Des {
     pub(crate) fn encrypt(&self, mut data: u64) -> u64 {
         data = ip(data);
         for key in &self.keys {
-            data = round(data, *key);
+            data = round(data, *key, 0);
         }
-        fp((data << 32) | (data >> 32))
+        fp(data.rotate_left(32))
+    }
+
+    /// Encrypt `data` using the E expansion modified by `salt`, which must
+    /// have bits set only in the positions 16..40
+    #[cfg(feature = "crypt")]
+    pub(crate) fn encrypt_salted(&self, mut data: u64, salt: u64) -> u64 {
+        data = ip(data);
+        for key in &self.keys {
+            data = round(data, *key, salt);
+        }
+        fp(data.rotate_left(32))
     }
 
     pub(crate) fn decrypt(&self, mut data: u64) -> u64 {
         data = ip(data);
         for key in self.keys.iter().rev() {
-            data = round(data, *key);
+            data = round(data, *key, 0);
         }
-        fp((data << 32) | (data >> 32))
+        fp(data.rotate_left(32))
     }
 }
 
@@ -198,7 +214,7 @@ impl NewBlockCipher for Des {
 
 impl BlockCipher for Des {
     type BlockSize = U8;
-    type ParBlocks = U1;
+    type ParBlocks = U64;
 }
 
 impl BlockEncrypt for Des {
@@ -206,6 +222,12 @@ impl BlockEncrypt for Des {
         let data = BE::read_u64(block);
         BE::write_u64(block, self.encrypt(data));
     }
+
+    fn encrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.encrypt(&self.keys);
+        state.write(blocks);
+    }
 }
 
 impl BlockDecrypt for Des {
@@ -213,6 +235,12 @@ impl BlockDecrypt for Des {
         let data = BE::read_u64(block);
         BE::write_u64(block, self.decrypt(data));
     }
+
+    fn decrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.decrypt(&self.keys);
+        state.write(blocks);
+    }
 }
 
 opaque_debug::implement!(Des);
@@ -0,0 +1,83 @@
+//! DES-X block cipher.
+
+use crate::bitsliced;
+use crate::des::{gen_keys, Des};
+use byteorder::{ByteOrder, BE};
+use cipher::{
+    consts::{U24, U64, U8},
+    generic_array::GenericArray,
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+
+/// DES-X block cipher.
+///
+/// The 24-byte key consists of the DES key followed by the input and the
+/// output whitening keys (the same layout as used by OpenSSL), i.e.
+/// `C = K3 ^ DES(K1, P ^ K2)`.
+#[derive(Copy, Clone)]
+pub struct DesX {
+    d: Des,
+    inw: u64,
+    outw: u64,
+}
+
+impl DesX {
+    fn whiten(blocks: &mut bitsliced::Blocks, w: u64) {
+        let w = w.to_be_bytes();
+        for block in blocks.iter_mut() {
+            for (b, w) in block.iter_mut().zip(w.iter()) {
+                *b ^= w;
+            }
+        }
+    }
+}
+
+impl NewBlockCipher for DesX {
+    type KeySize = U24;
+
+    fn new(key: &GenericArray<u8, U24>) -> Self {
+        let d = Des {
+            keys: gen_keys(BE::read_u64(&key[0..8])),
+        };
+        let inw = BE::read_u64(&key[8..16]);
+        let outw = BE::read_u64(&key[16..24]);
+        Self { d, inw, outw }
+    }
+}
+
+impl BlockCipher for DesX {
+    type BlockSize = U8;
+    type ParBlocks = U64;
+}
+
+impl BlockEncrypt for DesX {
+    fn encrypt_block(&self, block: &mut GenericArray<u8, U8>) {
+        let data = BE::read_u64(block);
+        BE::write_u64(block, self.d.encrypt(data ^ self.inw) ^ self.outw);
+    }
+
+    fn encrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        Self::whiten(blocks, self.inw);
+        let mut state = bitsliced::State::new(blocks);
+        state.encrypt(&self.d.keys);
+        state.write(blocks);
+        Self::whiten(blocks, self.outw);
+    }
+}
+
+impl BlockDecrypt for DesX {
+    fn decrypt_block(&self, block: &mut GenericArray<u8, U8>) {
+        let data = BE::read_u64(block);
+        BE::write_u64(block, self.d.decrypt(data ^ self.outw) ^ self.inw);
+    }
+
+    fn decrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        Self::whiten(blocks, self.outw);
+        let mut state = bitsliced::State::new(blocks);
+        state.decrypt(&self.d.keys);
+        state.write(blocks);
+        Self::whiten(blocks, self.inw);
+    }
+}
+
+opaque_debug::implement!(DesX);
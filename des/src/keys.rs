@@ -0,0 +1,220 @@
+//! Key validation: parity bits, weak and semi-weak keys, TDES keying options.
+//!
+//! See NIST SP 800-67 Rev. 2, sections 3.1 and 3.3.2.
+
+use crate::{Des, TdesEde2, TdesEde3, TdesEee2, TdesEee3};
+use byteorder::{ByteOrder, BE};
+use cipher::{
+    consts::{U16, U24, U8},
+    generic_array::GenericArray,
+    NewBlockCipher,
+};
+use core::fmt;
+
+/// Mask which clears parity bits of a DES key
+const PARITY_MASK: u64 = 0xFEFE_FEFE_FEFE_FEFE;
+
+/// Weak DES keys (with parity bits cleared)
+const WEAK_KEYS: [u64; 4] = [
+    0x0000_0000_0000_0000,
+    0xFEFE_FEFE_FEFE_FEFE,
+    0xE0E0_E0E0_F0F0_F0F0,
+    0x1E1E_1E1E_0E0E_0E0E,
+];
+
+/// Semi-weak DES keys (with parity bits cleared), stored as pairs of keys
+/// which decrypt each other's ciphertexts
+const SEMI_WEAK_KEYS: [u64; 12] = [
+    0x00FE_00FE_00FE_00FE,
+    0xFE00_FE00_FE00_FE00,
+    0x1EE0_1EE0_0EF0_0EF0,
+    0xE01E_E01E_F00E_F00E,
+    0x00E0_00E0_00F0_00F0,
+    0xE000_E000_F000_F000,
+    0x1EFE_1EFE_0EFE_0EFE,
+    0xFE1E_FE1E_FE0E_FE0E,
+    0x001E_001E_000E_000E,
+    0x1E00_1E00_0E00_0E00,
+    0xE0FE_E0FE_F0FE_F0FE,
+    0xFEE0_FEE0_FEF0_FEF0,
+];
+
+/// Key validation error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyError {
+    /// Key has invalid length.
+    InvalidLength,
+    /// At least one key byte does not have odd parity.
+    Parity,
+    /// Key (or one of TDES keys) is a weak DES key.
+    WeakKey,
+    /// Key (or one of TDES keys) is a semi-weak DES key.
+    SemiWeakKey,
+    /// TDES keys are not pairwise distinct.
+    NotDistinct,
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            KeyError::InvalidLength => "invalid key length",
+            KeyError::Parity => "invalid key parity",
+            KeyError::WeakKey => "weak DES key",
+            KeyError::SemiWeakKey => "semi-weak DES key",
+            KeyError::NotDistinct => "TDES keys are not distinct",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyError {}
+
+/// TDES keying options defined in NIST SP 800-67 Rev. 2 section 3.1.
+///
+/// Keys are compared ignoring their parity bits. The classification is
+/// done for the EDE construction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyingOption {
+    /// Keying option 1: `K1`, `K2` and `K3` are distinct.
+    Option1,
+    /// Keying option 2: `K1` and `K2` are distinct and `K3 = K1`.
+    Option2,
+    /// Keying option 3: `K1 = K2 = K3`, equivalent to single DES.
+    Option3,
+    /// `K1 = K2` or `K2 = K3` with a distinct third key, equivalent to
+    /// single DES with the third key.
+    Degenerate,
+}
+
+/// Check that every byte of a DES or TDES key has odd parity.
+pub fn check_parity(key: &[u8]) -> Result<(), KeyError> {
+    if key.iter().all(|b| b.count_ones() % 2 == 1) {
+        Ok(())
+    } else {
+        Err(KeyError::Parity)
+    }
+}
+
+/// Set the least significant bit of every key byte, so that each byte
+/// has odd parity.
+pub fn fix_parity(key: &mut [u8]) {
+    for b in key.iter_mut() {
+        let high = *b & 0xFE;
+        *b = high | ((high.count_ones() as u8 & 1) ^ 1);
+    }
+}
+
+/// Returns `true` if `key` is one of the 4 weak DES keys.
+///
+/// Parity bits are ignored.
+pub fn is_weak_key(key: &GenericArray<u8, U8>) -> bool {
+    let k = BE::read_u64(key) & PARITY_MASK;
+    WEAK_KEYS.contains(&k)
+}
+
+/// Returns `true` if `key` is one of the 12 semi-weak DES keys.
+///
+/// Parity bits are ignored.
+pub fn is_semi_weak_key(key: &GenericArray<u8, U8>) -> bool {
+    let k = BE::read_u64(key) & PARITY_MASK;
+    SEMI_WEAK_KEYS.contains(&k)
+}
+
+/// Classify 16-byte (two-key) or 24-byte (three-key) TDES key.
+///
+/// Two-key TDES keys are treated as three-key ones with `K3 = K1`.
+pub fn tdes_keying_option(key: &[u8]) -> Result<KeyingOption, KeyError> {
+    let (k1, k2, k3) = split_tdes_key(key)?;
+    let option = if k1 == k2 && k2 == k3 {
+        KeyingOption::Option3
+    } else if k1 == k2 || k2 == k3 {
+        KeyingOption::Degenerate
+    } else if k1 == k3 {
+        KeyingOption::Option2
+    } else {
+        KeyingOption::Option1
+    };
+    Ok(option)
+}
+
+/// Split TDES key into its three DES keys with cleared parity bits
+fn split_tdes_key(key: &[u8]) -> Result<(u64, u64, u64), KeyError> {
+    let k = |i: usize| BE::read_u64(&key[8 * i..8 * (i + 1)]) & PARITY_MASK;
+    match key.len() {
+        16 => Ok((k(0), k(1), k(0))),
+        24 => Ok((k(0), k(1), k(2))),
+        _ => Err(KeyError::InvalidLength),
+    }
+}
+
+/// Reject weak and semi-weak DES keys
+fn check_des_key(key: &[u8]) -> Result<(), KeyError> {
+    let key = GenericArray::from_slice(key);
+    if is_weak_key(key) {
+        Err(KeyError::WeakKey)
+    } else if is_semi_weak_key(key) {
+        Err(KeyError::SemiWeakKey)
+    } else {
+        Ok(())
+    }
+}
+
+/// Reject TDES keys containing weak or semi-weak DES keys and keys which
+/// are not pairwise distinct
+fn check_tdes_key(key: &[u8]) -> Result<(), KeyError> {
+    for k in key.chunks_exact(8) {
+        check_des_key(k)?;
+    }
+    match tdes_keying_option(key)? {
+        KeyingOption::Option1 => Ok(()),
+        // `K3 = K1` is expected for two-key TDES
+        KeyingOption::Option2 if key.len() == 16 => Ok(()),
+        _ => Err(KeyError::NotDistinct),
+    }
+}
+
+impl Des {
+    /// Create new DES instance, rejecting weak and semi-weak keys.
+    ///
+    /// Parity bits are not checked, use [`check_parity`] for that.
+    pub fn new_checked(key: &GenericArray<u8, U8>) -> Result<Self, KeyError> {
+        check_des_key(key)?;
+        Ok(Self::new(key))
+    }
+}
+
+macro_rules! impl_new_checked {
+    ($name:ident, $key_size:ty, $doc:expr) => {
+        impl $name {
+            #[doc = $doc]
+            ///
+            /// Weak and semi-weak DES keys are rejected as well.
+            /// Parity bits are not checked, use [`check_parity`] for that.
+            pub fn new_checked(key: &GenericArray<u8, $key_size>) -> Result<Self, KeyError> {
+                check_tdes_key(key)?;
+                Ok(Self::new(key))
+            }
+        }
+    };
+}
+
+impl_new_checked!(
+    TdesEde3,
+    U24,
+    "Create new instance, rejecting keys for which `K1`, `K2` and `K3` are not distinct."
+);
+impl_new_checked!(
+    TdesEee3,
+    U24,
+    "Create new instance, rejecting keys for which `K1`, `K2` and `K3` are not distinct."
+);
+impl_new_checked!(
+    TdesEde2,
+    U16,
+    "Create new instance, rejecting keys for which `K1` and `K2` are equal."
+);
+impl_new_checked!(
+    TdesEee2,
+    U16,
+    "Create new instance, rejecting keys for which `K1` and `K2` are equal."
+);
@@ -1,6 +1,31 @@
 //! Pure Rust implementation of the [DES cipher][1], including triple DES (3DES).
 //!
+//! # Parallel processing
+//! All ciphers in this crate process 64 blocks at once in the
+//! `encrypt_par_blocks` and `decrypt_par_blocks` methods using a
+//! [bitsliced][2] implementation, which is faster than processing blocks
+//! one by one and runs in constant time. Methods which process a single
+//! block use S-box lookups with secret-dependent indices and thus may be
+//! vulnerable to cache timing attacks.
+//!
+//! # Key validation
+//! The [`check_parity`], [`fix_parity`], [`is_weak_key`],
+//! [`is_semi_weak_key`] and [`tdes_keying_option`] functions can be used to
+//! validate keys. Every cipher also provides a `new_checked` constructor
+//! which rejects weak and semi-weak keys, as well as TDES keys with
+//! repeated DES keys, returning [`KeyError`].
+//!
+//! # Self-test
+//! [`self_test`] checks every cipher in this crate against known answers,
+//! using both the single block and the bitsliced implementations.
+//!
+//! # Crate features
+//! - `crypt`: enables the [`crypt`] module with DES-based `crypt(3)`
+//!   password hashing (requires `alloc`).
+//! - `std`: implements `std::error::Error` for error types.
+//!
 //! [1]: https://en.wikipedia.org/wiki/Data_Encryption_Standard
+//! [2]: https://www.darkside.com.au/bitslice/
 
 #![no_std]
 #![doc(
@@ -10,11 +35,29 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs, rust_2018_idioms)]
 
+#[cfg(feature = "crypt")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 pub use cipher;
 
+mod bitsliced;
 mod consts;
+#[cfg(feature = "crypt")]
+pub mod crypt;
 mod des;
+mod desx;
+mod keys;
+mod self_test;
 mod tdes;
 
 pub use crate::des::Des;
+pub use crate::desx::DesX;
+pub use crate::keys::{
+    check_parity, fix_parity, is_semi_weak_key, is_weak_key, tdes_keying_option, KeyError,
+    KeyingOption,
+};
+pub use crate::self_test::self_test;
 pub use crate::tdes::{TdesEde2, TdesEde3, TdesEee2, TdesEee3};
+pub use block_ciphers_self_test::SelfTestError;
@@ -0,0 +1,73 @@
+//! Power-on self-test.
+
+use crate::{Des, DesX, TdesEde2, TdesEde3, TdesEee2, TdesEee3};
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// Example from "The DES Algorithm Illustrated" by J. Orlin Grabbe
+const DES_KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9b, 0xbc, 0xdf, 0xf1];
+
+/// Plaintext of the DES example
+const DES_PLAINTEXT: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+
+/// Ciphertext of the DES example
+const DES_CIPHERTEXT: [u8; 8] = [0x85, 0xe8, 0x13, 0x54, 0x0f, 0x0a, 0xb4, 0x05];
+
+/// Keys from the NIST SP 800-67 example, two-key variants use the first 16 bytes.
+/// DES-X uses them as the DES key and the input and output whitening keys.
+const TDES_KEY: [u8; 24] = [
+    0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01,
+    0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x23,
+];
+
+/// First block of the NIST SP 800-67 example plaintext
+const TDES_PLAINTEXT: [u8; 8] = [0x54, 0x68, 0x65, 0x20, 0x71, 0x75, 0x66, 0x63];
+
+/// NIST SP 800-67 example
+const TDES_EDE3_CIPHERTEXT: [u8; 8] = [0xa8, 0x26, 0xfd, 0x8c, 0xe5, 0x3b, 0x85, 0x5f];
+
+/// TDES-EDE2 with the first two example keys
+const TDES_EDE2_CIPHERTEXT: [u8; 8] = [0xc4, 0x48, 0x62, 0xf7, 0x0c, 0xf2, 0xfb, 0xdc];
+
+/// TDES-EEE3 with the example keys
+const TDES_EEE3_CIPHERTEXT: [u8; 8] = [0xce, 0x27, 0x19, 0xff, 0x40, 0x8a, 0x7a, 0xfa];
+
+/// TDES-EEE2 with the first two example keys
+const TDES_EEE2_CIPHERTEXT: [u8; 8] = [0x50, 0x00, 0x13, 0x53, 0x31, 0x51, 0xe9, 0x0c];
+
+/// DES-X with the example keys
+const DESX_CIPHERTEXT: [u8; 8] = [0x30, 0x8a, 0xdf, 0x73, 0x55, 0x5b, 0xaa, 0x86];
+
+/// Run known answer tests of DES, DES-X and all TDES variants.
+///
+/// The tests cover both the table-based single block implementation and
+/// the bitsliced implementation used by `encrypt_par_blocks` and
+/// `decrypt_par_blocks`.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Des>("DES", &DES_KEY, &DES_PLAINTEXT, &DES_CIPHERTEXT)?;
+    kat::<DesX>("DES-X", &TDES_KEY, &TDES_PLAINTEXT, &DESX_CIPHERTEXT)?;
+    kat::<TdesEde3>(
+        "TDES-EDE3",
+        &TDES_KEY,
+        &TDES_PLAINTEXT,
+        &TDES_EDE3_CIPHERTEXT,
+    )?;
+    kat::<TdesEde2>(
+        "TDES-EDE2",
+        &TDES_KEY[..16],
+        &TDES_PLAINTEXT,
+        &TDES_EDE2_CIPHERTEXT,
+    )?;
+    kat::<TdesEee3>(
+        "TDES-EEE3",
+        &TDES_KEY,
+        &TDES_PLAINTEXT,
+        &TDES_EEE3_CIPHERTEXT,
+    )?;
+    kat::<TdesEee2>(
+        "TDES-EEE2",
+        &TDES_KEY[..16],
+        &TDES_PLAINTEXT,
+        &TDES_EEE2_CIPHERTEXT,
+    )?;
+    Ok(())
+}
@@ -1,9 +1,10 @@
 //! Triple DES (3DES) block cipher.
 
+use crate::bitsliced;
 use crate::des::{gen_keys, Des};
 use byteorder::{ByteOrder, BE};
 use cipher::{
-    consts::{U1, U16, U24, U8},
+    consts::{U16, U24, U64, U8},
     generic_array::GenericArray,
     BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
 };
@@ -57,7 +58,7 @@ impl NewBlockCipher for TdesEde3 {
 
 impl BlockCipher for TdesEde3 {
     type BlockSize = U8;
-    type ParBlocks = U1;
+    type ParBlocks = U64;
 }
 
 impl BlockEncrypt for TdesEde3 {
@@ -70,6 +71,14 @@ impl BlockEncrypt for TdesEde3 {
 
         BE::write_u64(block, data);
     }
+
+    fn encrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.encrypt(&self.d1.keys);
+        state.decrypt(&self.d2.keys);
+        state.encrypt(&self.d3.keys);
+        state.write(blocks);
+    }
 }
 
 impl BlockDecrypt for TdesEde3 {
@@ -82,6 +91,14 @@ impl BlockDecrypt for TdesEde3 {
 
         BE::write_u64(block, data);
     }
+
+    fn decrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.decrypt(&self.d3.keys);
+        state.encrypt(&self.d2.keys);
+        state.decrypt(&self.d1.keys);
+        state.write(blocks);
+    }
 }
 
 impl NewBlockCipher for TdesEee3 {
@@ -103,7 +120,7 @@ impl NewBlockCipher for TdesEee3 {
 
 impl BlockCipher for TdesEee3 {
     type BlockSize = U8;
-    type ParBlocks = U1;
+    type ParBlocks = U64;
 }
 
 impl BlockEncrypt for TdesEee3 {
@@ -116,6 +133,14 @@ impl BlockEncrypt for TdesEee3 {
 
         BE::write_u64(block, data);
     }
+
+    fn encrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.encrypt(&self.d1.keys);
+        state.encrypt(&self.d2.keys);
+        state.encrypt(&self.d3.keys);
+        state.write(blocks);
+    }
 }
 
 impl BlockDecrypt for TdesEee3 {
@@ -128,6 +153,14 @@ impl BlockDecrypt for TdesEee3 {
 
         BE::write_u64(block, data);
     }
+
+    fn decrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.decrypt(&self.d3.keys);
+        state.decrypt(&self.d2.keys);
+        state.decrypt(&self.d1.keys);
+        state.write(blocks);
+    }
 }
 
 impl NewBlockCipher for TdesEde2 {
@@ -146,7 +179,7 @@ impl NewBlockCipher for TdesEde2 {
 
 impl BlockCipher for TdesEde2 {
     type BlockSize = U8;
-    type ParBlocks = U1;
+    type ParBlocks = U64;
 }
 
 impl BlockEncrypt for TdesEde2 {
@@ -159,6 +192,14 @@ impl BlockEncrypt for TdesEde2 {
 
         BE::write_u64(block, data);
     }
+
+    fn encrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.encrypt(&self.d1.keys);
+        state.decrypt(&self.d2.keys);
+        state.encrypt(&self.d1.keys);
+        state.write(blocks);
+    }
 }
 
 impl BlockDecrypt for TdesEde2 {
@@ -171,6 +212,14 @@ impl BlockDecrypt for TdesEde2 {
 
         BE::write_u64(block, data);
     }
+
+    fn decrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.decrypt(&self.d1.keys);
+        state.encrypt(&self.d2.keys);
+        state.decrypt(&self.d1.keys);
+        state.write(blocks);
+    }
 }
 
 impl NewBlockCipher for TdesEee2 {
@@ -189,7 +238,7 @@ impl NewBlockCipher for TdesEee2 {
 
 impl BlockCipher for TdesEee2 {
     type BlockSize = U8;
-    type ParBlocks = U1;
+    type ParBlocks = U64;
 }
 
 impl BlockEncrypt for TdesEee2 {
@@ -202,6 +251,14 @@ impl BlockEncrypt for TdesEee2 {
 
         BE::write_u64(block, data);
     }
+
+    fn encrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.encrypt(&self.d1.keys);
+        state.encrypt(&self.d2.keys);
+        state.encrypt(&self.d1.keys);
+        state.write(blocks);
+    }
 }
 
 impl BlockDecrypt for TdesEee2 {
@@ -214,6 +271,14 @@ impl BlockDecrypt for TdesEee2 {
 
         BE::write_u64(block, data);
     }
+
+    fn decrypt_par_blocks(&self, blocks: &mut bitsliced::Blocks) {
+        let mut state = bitsliced::State::new(blocks);
+        state.decrypt(&self.d1.keys);
+        state.decrypt(&self.d2.keys);
+        state.decrypt(&self.d1.keys);
+        state.write(blocks);
+    }
 }
 
 opaque_debug::implement!(TdesEde3);
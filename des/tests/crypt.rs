@@ -0,0 +1,61 @@
+//! Test vectors were generated using libxcrypt.
+#![cfg(feature = "crypt")]
+
+use des::crypt::{hash, hash_extended, verify, InvalidSetting};
+
+const TRADITIONAL: &[(&[u8], &str)] = &[
+    (b"U*U*U*U*", "CCNf8Sbh3HDfQ"),
+    (b"", "..X8NBuQ4l6uQ"),
+    (b"password", "abJnggxhB/yWI"),
+    (b"a very long password longer than 8", "zZOamkzit.WA."),
+];
+
+const EXTENDED: &[(&[u8], &str)] = &[
+    (b"U*U*U*U*", "_J9..CCCCXBrJUJV154M"),
+    (b"", "_J9..SALTxR6uiWkKh62"),
+    (b"test", "_/.../...YUUo0GVAXOs"),
+    (b"x", "_/...zzzzmgMRAEE/bSE"),
+    (
+        b"a long password with more than 16 characters",
+        "_z...abcdFjKkHvTogvk",
+    ),
+];
+
+#[test]
+fn traditional() {
+    for &(password, expected) in TRADITIONAL {
+        assert_eq!(hash(password, &expected[..2]).unwrap(), expected);
+        assert_eq!(hash(password, expected).unwrap(), expected);
+        assert!(verify(password, expected));
+        assert!(!verify(b"wrong", expected));
+    }
+    // only the first 8 bytes are used
+    assert!(verify(b"a very long", "zZOamkzit.WA."));
+    // password ends at the first NUL byte
+    assert!(verify(b"password\0ignored", "abJnggxhB/yWI"));
+}
+
+#[test]
+fn extended() {
+    for &(password, expected) in EXTENDED {
+        assert_eq!(hash(password, &expected[..9]).unwrap(), expected);
+        assert!(verify(password, expected));
+        assert!(!verify(b"wrong", expected));
+    }
+    assert_eq!(
+        hash_extended(b"U*U*U*U*", 725, 0x38_E3_8E).unwrap(),
+        hash(b"U*U*U*U*", "_J9..CCCC").unwrap(),
+    );
+}
+
+#[test]
+fn invalid_settings() {
+    assert_eq!(hash(b"x", "a"), Err(InvalidSetting));
+    assert_eq!(hash(b"x", "a!"), Err(InvalidSetting));
+    assert_eq!(hash(b"x", "_J9..CCC"), Err(InvalidSetting));
+    assert_eq!(hash(b"x", "_K1.!crsm"), Err(InvalidSetting));
+    assert_eq!(hash(b"x", "_....CCCC"), Err(InvalidSetting));
+    assert_eq!(hash_extended(b"x", 1 << 24, 0), Err(InvalidSetting));
+    assert_eq!(hash_extended(b"x", 1, 1 << 24), Err(InvalidSetting));
+    assert!(!verify(b"x", ""));
+}
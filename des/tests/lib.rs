@@ -1,6 +1,206 @@
 //! Test vectors are from NESSIE:
 //! https://www.cosic.esat.kuleuven.be/nessie/testvectors/
 
+use hex_literal::hex;
+
 cipher::block_cipher_test!(des_test, "des", des::Des);
 cipher::block_cipher_test!(tdes_ede3_test, "tdes", des::TdesEde3);
 cipher::block_cipher_test!(tdes_ede2_test, "tdes2", des::TdesEde2);
+
+/// Check that parallel processing agrees with processing blocks one by one
+fn par_blocks<C: cipher::BlockCipher + cipher::BlockEncrypt + cipher::BlockDecrypt>(cipher: C) {
+    let mut blocks1 = cipher::ParBlocks::<C>::default();
+    for (i, block) in blocks1.iter_mut().enumerate() {
+        for (j, b) in block.iter_mut().enumerate() {
+            *b = (i * 8 + j) as u8;
+        }
+    }
+    let mut blocks2 = blocks1.clone();
+
+    cipher.encrypt_par_blocks(&mut blocks1);
+    for block in blocks2.iter_mut() {
+        cipher.encrypt_block(block);
+    }
+    assert_eq!(blocks1, blocks2);
+
+    cipher.decrypt_par_blocks(&mut blocks1);
+    for block in blocks2.iter_mut() {
+        cipher.decrypt_block(block);
+    }
+    assert_eq!(blocks1, blocks2);
+}
+
+#[test]
+fn tdes_eee_par_blocks() {
+    use cipher::NewBlockCipher;
+
+    let key: Vec<u8> = (0..24).map(|i| (i * 37) as u8).collect();
+    par_blocks(des::TdesEee3::new_from_slice(&key).unwrap());
+    par_blocks(des::TdesEee2::new_from_slice(&key[..16]).unwrap());
+}
+
+/// Test vectors were generated using OpenSSL
+#[test]
+fn desx() {
+    use cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher};
+
+    let key = hex!("0123456789abcdef f1e0d3c2b5a49786 fedcba9876543210");
+    let vectors = [
+        (hex!("4e6f772069732074"), hex!("c327092461e40219")),
+        (hex!("ab42295008896739"), hex!("d0539feed41fd63b")),
+        (hex!("b63cedceb573ba1b"), hex!("81563deaf04542fa")),
+    ];
+    let cipher = des::DesX::new(GenericArray::from_slice(&key));
+    for (pt, ct) in vectors.iter() {
+        let mut block = GenericArray::clone_from_slice(pt);
+        cipher.encrypt_block(&mut block);
+        assert_eq!(&block[..], &ct[..]);
+        cipher.decrypt_block(&mut block);
+        assert_eq!(&block[..], &pt[..]);
+    }
+    par_blocks(cipher);
+}
+
+#[test]
+fn weak_keys() {
+    use cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher};
+    use des::{Des, KeyError};
+
+    let weak: [[u8; 8]; 4] = [
+        [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01],
+        [0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE],
+        [0xE0, 0xE0, 0xE0, 0xE0, 0xF1, 0xF1, 0xF1, 0xF1],
+        [0x1F, 0x1F, 0x1F, 0x1F, 0x0E, 0x0E, 0x0E, 0x0E],
+    ];
+    for key in weak.iter() {
+        let key = GenericArray::from_slice(key);
+        assert!(des::is_weak_key(key));
+        assert!(!des::is_semi_weak_key(key));
+        assert_eq!(Des::new_checked(key).unwrap_err(), KeyError::WeakKey);
+
+        // encryption with a weak key is an involution
+        let cipher = Des::new(key);
+        let mut block = GenericArray::clone_from_slice(b"weak key");
+        cipher.encrypt_block(&mut block);
+        cipher.encrypt_block(&mut block);
+        assert_eq!(&block[..], b"weak key");
+        cipher.decrypt_block(&mut block);
+        cipher.decrypt_block(&mut block);
+        assert_eq!(&block[..], b"weak key");
+    }
+}
+
+#[test]
+fn semi_weak_keys() {
+    use cipher::{generic_array::GenericArray, BlockEncrypt, NewBlockCipher};
+    use des::{Des, KeyError};
+
+    let pairs: [[u64; 2]; 6] = [
+        [0x01FE_01FE_01FE_01FE, 0xFE01_FE01_FE01_FE01],
+        [0x1FE0_1FE0_0EF1_0EF1, 0xE01F_E01F_F10E_F10E],
+        [0x01E0_01E0_01F1_01F1, 0xE001_E001_F101_F101],
+        [0x1FFE_1FFE_0EFE_0EFE, 0xFE1F_FE1F_FE0E_FE0E],
+        [0x011F_011F_010E_010E, 0x1F01_1F01_0E01_0E01],
+        [0xE0FE_E0FE_F1FE_F1FE, 0xFEE0_FEE0_FEF1_FEF1],
+    ];
+    for pair in pairs.iter() {
+        let k1 = pair[0].to_be_bytes();
+        let k2 = pair[1].to_be_bytes();
+        let (k1, k2) = (GenericArray::from_slice(&k1), GenericArray::from_slice(&k2));
+        for &key in [k1, k2].iter() {
+            assert!(des::is_semi_weak_key(key));
+            assert!(!des::is_weak_key(key));
+            assert_eq!(Des::new_checked(key).unwrap_err(), KeyError::SemiWeakKey);
+        }
+
+        // keys in a pair decrypt each other's ciphertexts
+        let mut block = GenericArray::clone_from_slice(b"semiweak");
+        Des::new(k1).encrypt_block(&mut block);
+        Des::new(k2).encrypt_block(&mut block);
+        assert_eq!(&block[..], b"semiweak");
+    }
+
+    let key = GenericArray::from_slice(&[0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]);
+    assert!(!des::is_weak_key(key));
+    assert!(!des::is_semi_weak_key(key));
+    assert!(Des::new_checked(key).is_ok());
+}
+
+#[test]
+fn parity() {
+    let mut key = *b"\x00\x01\x02\x03\xFC\xFD\xFE\xFF";
+    assert_eq!(des::check_parity(&key), Err(des::KeyError::Parity));
+    des::fix_parity(&mut key);
+    assert_eq!(key, *b"\x01\x01\x02\x02\xFD\xFD\xFE\xFE");
+    assert_eq!(des::check_parity(&key), Ok(()));
+
+    for b in 0..=255u8 {
+        let mut key = [b];
+        des::fix_parity(&mut key);
+        assert_eq!(key[0] & 0xFE, b & 0xFE);
+        assert!(des::check_parity(&key).is_ok());
+    }
+}
+
+#[test]
+fn keying_options() {
+    use cipher::generic_array::GenericArray;
+    use des::{KeyError, KeyingOption, TdesEde2, TdesEde3, TdesEee2, TdesEee3};
+
+    let k1 = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+    let k2 = [0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01];
+    let k3 = [0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23];
+    // equal to `k1` up to parity bits
+    let k1p = [0x00, 0x22, 0x44, 0x66, 0x88, 0xAA, 0xCC, 0xEE];
+    let key = |keys: &[&[u8; 8]]| {
+        keys.iter()
+            .flat_map(|k| k.iter().copied())
+            .collect::<Vec<u8>>()
+    };
+
+    let check = |keys: &[&[u8; 8]], option| {
+        assert_eq!(des::tdes_keying_option(&key(keys)), Ok(option));
+    };
+    check(&[&k1, &k2, &k3], KeyingOption::Option1);
+    check(&[&k1, &k2, &k1], KeyingOption::Option2);
+    check(&[&k1, &k2, &k1p], KeyingOption::Option2);
+    check(&[&k1, &k1p, &k1], KeyingOption::Option3);
+    check(&[&k1, &k1, &k3], KeyingOption::Degenerate);
+    check(&[&k1, &k3, &k3], KeyingOption::Degenerate);
+    check(&[&k1, &k2], KeyingOption::Option2);
+    check(&[&k1, &k1p], KeyingOption::Option3);
+    assert_eq!(des::tdes_keying_option(&k1), Err(KeyError::InvalidLength));
+
+    let new3 = |keys: &[&[u8; 8]]| {
+        let key = key(keys);
+        let key = GenericArray::from_slice(&key);
+        let res1 = TdesEde3::new_checked(key).map(|_| ());
+        let res2 = TdesEee3::new_checked(key).map(|_| ());
+        assert_eq!(res1, res2);
+        res1
+    };
+    assert_eq!(new3(&[&k1, &k2, &k3]), Ok(()));
+    assert_eq!(new3(&[&k1, &k2, &k1]), Err(KeyError::NotDistinct));
+    assert_eq!(new3(&[&k1, &k1p, &k3]), Err(KeyError::NotDistinct));
+    assert_eq!(new3(&[&k1, &k2, &[0xFE; 8]]), Err(KeyError::WeakKey));
+
+    let new2 = |keys: &[&[u8; 8]]| {
+        let key = key(keys);
+        let key = GenericArray::from_slice(&key);
+        let res1 = TdesEde2::new_checked(key).map(|_| ());
+        let res2 = TdesEee2::new_checked(key).map(|_| ());
+        assert_eq!(res1, res2);
+        res1
+    };
+    assert_eq!(new2(&[&k1, &k2]), Ok(()));
+    assert_eq!(new2(&[&k1, &k1p]), Err(KeyError::NotDistinct));
+    assert_eq!(
+        new2(&[&k1, &[0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE]]),
+        Err(KeyError::SemiWeakKey)
+    );
+}
+
+#[test]
+fn self_test() {
+    assert_eq!(des::self_test(), Ok(()));
+}
@@ -40,6 +40,12 @@
 //! assert_eq!(buf, hex!("fddb196e81812e4174d1c9f741a3457a88"));
 //! ```
 //!
+//! # Crate features
+//! - `kuznyechik`, `magma`: enable [`self_test`], which runs the self-test
+//!   of the respective cipher crate and the examples from GOST R 34.13-2015
+//!   appendix A for every mode of operation.
+//! - `std`: implements `std::error::Error` for error types.
+//!
 //! [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation
 //! [GOST R 34.13-2015]: https://tc26.ru/standard/gost/GOST_R_3413-2015.pdf
 //! [`cipher`]: https://docs.rs/cipher/
@@ -65,6 +71,8 @@ mod cfb;
 mod ctr128;
 mod ctr64;
 mod ofb;
+#[cfg(any(feature = "kuznyechik", feature = "magma"))]
+mod self_test;
 mod utils;
 
 /// Block padding procedure number 2 as defined in GOST R 34.13-2015
@@ -72,8 +80,12 @@ mod utils;
 /// Fully equivalent to ISO 7816.
 pub type GostPadding = block_padding::Iso7816;
 
+#[cfg(any(feature = "kuznyechik", feature = "magma"))]
+pub use block_ciphers_self_test::SelfTestError;
 pub use cbc::GostCbc;
 pub use cfb::GostCfb;
 pub use ctr128::GostCtr128;
 pub use ctr64::GostCtr64;
 pub use ofb::GostOfb;
+#[cfg(any(feature = "kuznyechik", feature = "magma"))]
+pub use self_test::self_test;
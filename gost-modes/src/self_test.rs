@@ -0,0 +1,248 @@
+//! Power-on self-test with the examples from GOST R 34.13-2015 appendix A.
+
+use crate::{
+    block_padding::NoPadding, consts::U2, AsyncStreamCipher, BlockMode, Ecb, GostCbc, GostCfb,
+    GostOfb, NewCipher, StreamCipher,
+};
+use block_ciphers_self_test::SelfTestError;
+use cipher::{
+    generic_array::GenericArray, BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+
+/// Run self-test of the block ciphers enabled with the `kuznyechik` and
+/// `magma` crate features and of all modes of operation with them.
+///
+/// The self-tests of the cipher crates run first, then every mode encrypts
+/// and decrypts the example message from GOST R 34.13-2015 appendix A with
+/// the parameters used there.
+pub fn self_test() -> Result<(), SelfTestError> {
+    #[cfg(feature = "kuznyechik")]
+    kuznyechik()?;
+    #[cfg(feature = "magma")]
+    magma()?;
+    Ok(())
+}
+
+#[cfg(feature = "kuznyechik")]
+fn kuznyechik() -> Result<(), SelfTestError> {
+    use crate::{consts::U32, GostCtr128};
+    use kuznyechik::Kuznyechik;
+
+    const KEY: [u8; 32] = [
+        0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66,
+        0x77, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab,
+        0xcd, 0xef,
+    ];
+    const IV: [u8; 32] = [
+        0x12, 0x34, 0x56, 0x78, 0x90, 0xab, 0xce, 0xf0, 0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0xf0, 0x01,
+        0x12, 0x23, 0x34, 0x45, 0x56, 0x67, 0x78, 0x89, 0x90, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        0x18, 0x19,
+    ];
+    const CTR_IV: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab, 0xce, 0xf0];
+    const PT: [u8; 64] = [
+        0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x00, 0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99,
+        0x88, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xee,
+        0xff, 0x0a, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xee,
+        0xff, 0x0a, 0x00, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xee,
+        0xff, 0x0a, 0x00, 0x11,
+    ];
+    const ECB_CT: [u8; 64] = [
+        0x7f, 0x67, 0x9d, 0x90, 0xbe, 0xbc, 0x24, 0x30, 0x5a, 0x46, 0x8d, 0x42, 0xb9, 0xd4, 0xed,
+        0xcd, 0xb4, 0x29, 0x91, 0x2c, 0x6e, 0x00, 0x32, 0xf9, 0x28, 0x54, 0x52, 0xd7, 0x67, 0x18,
+        0xd0, 0x8b, 0xf0, 0xca, 0x33, 0x54, 0x9d, 0x24, 0x7c, 0xee, 0xf3, 0xf5, 0xa5, 0x31, 0x3b,
+        0xd4, 0xb1, 0x57, 0xd0, 0xb0, 0x9c, 0xcd, 0xe8, 0x30, 0xb9, 0xeb, 0x3a, 0x02, 0xc4, 0xc5,
+        0xaa, 0x8a, 0xda, 0x98,
+    ];
+    const CTR_CT: [u8; 64] = [
+        0xf1, 0x95, 0xd8, 0xbe, 0xc1, 0x0e, 0xd1, 0xdb, 0xd5, 0x7b, 0x5f, 0xa2, 0x40, 0xbd, 0xa1,
+        0xb8, 0x85, 0xee, 0xe7, 0x33, 0xf6, 0xa1, 0x3e, 0x5d, 0xf3, 0x3c, 0xe4, 0xb3, 0x3c, 0x45,
+        0xde, 0xe4, 0xa5, 0xea, 0xe8, 0x8b, 0xe6, 0x35, 0x6e, 0xd3, 0xd5, 0xe8, 0x77, 0xf1, 0x35,
+        0x64, 0xa3, 0xa5, 0xcb, 0x91, 0xfa, 0xb1, 0xf2, 0x0c, 0xba, 0xb6, 0xd1, 0xc6, 0xd1, 0x58,
+        0x20, 0xbd, 0xba, 0x73,
+    ];
+    const OFB_CT: [u8; 64] = [
+        0x81, 0x80, 0x0a, 0x59, 0xb1, 0x84, 0x2b, 0x24, 0xff, 0x1f, 0x79, 0x5e, 0x89, 0x7a, 0xbd,
+        0x95, 0xed, 0x5b, 0x47, 0xa7, 0x04, 0x8c, 0xfa, 0xb4, 0x8f, 0xb5, 0x21, 0x36, 0x9d, 0x93,
+        0x26, 0xbf, 0x66, 0xa2, 0x57, 0xac, 0x3c, 0xa0, 0xb8, 0xb1, 0xc8, 0x0f, 0xe7, 0xfc, 0x10,
+        0x28, 0x8a, 0x13, 0x20, 0x3e, 0xbb, 0xc0, 0x66, 0x13, 0x86, 0x60, 0xa0, 0x29, 0x22, 0x43,
+        0xf6, 0x90, 0x31, 0x50,
+    ];
+    const CBC_CT: [u8; 64] = [
+        0x68, 0x99, 0x72, 0xd4, 0xa0, 0x85, 0xfa, 0x4d, 0x90, 0xe5, 0x2e, 0x3d, 0x6d, 0x7d, 0xcc,
+        0x27, 0x28, 0x26, 0xe6, 0x61, 0xb4, 0x78, 0xec, 0xa6, 0xaf, 0x1e, 0x8e, 0x44, 0x8d, 0x5e,
+        0xa5, 0xac, 0xfe, 0x7b, 0xab, 0xf1, 0xe9, 0x19, 0x99, 0xe8, 0x56, 0x40, 0xe8, 0xb0, 0xf4,
+        0x9d, 0x90, 0xd0, 0x16, 0x76, 0x88, 0x06, 0x5a, 0x89, 0x5c, 0x63, 0x1a, 0x2d, 0x9a, 0x15,
+        0x60, 0xb6, 0x39, 0x70,
+    ];
+    const CFB_CT: [u8; 64] = [
+        0x81, 0x80, 0x0a, 0x59, 0xb1, 0x84, 0x2b, 0x24, 0xff, 0x1f, 0x79, 0x5e, 0x89, 0x7a, 0xbd,
+        0x95, 0xed, 0x5b, 0x47, 0xa7, 0x04, 0x8c, 0xfa, 0xb4, 0x8f, 0xb5, 0x21, 0x36, 0x9d, 0x93,
+        0x26, 0xbf, 0x79, 0xf2, 0xa8, 0xeb, 0x5c, 0xc6, 0x8d, 0x38, 0x84, 0x2d, 0x26, 0x4e, 0x97,
+        0xa2, 0x38, 0xb5, 0x4f, 0xfe, 0xbe, 0xcd, 0x4e, 0x92, 0x2d, 0xe6, 0xc7, 0x5b, 0xd9, 0xdd,
+        0x44, 0xfb, 0xf4, 0xd1,
+    ];
+
+    kuznyechik::self_test()?;
+
+    let key = GenericArray::from_slice(&KEY);
+    let cipher = Kuznyechik::new(key);
+    let ecb = Ecb::<_, NoPadding>::new(cipher, &Default::default());
+    block_mode("Kuznyechik-ECB", ecb, &PT, &ECB_CT)?;
+    let cbc = GostCbc::<_, NoPadding, U2>::new(cipher, GenericArray::from_slice(&IV));
+    block_mode("Kuznyechik-CBC", cbc, &PT, &CBC_CT)?;
+    let ctr = GostCtr128::<Kuznyechik>::new(key, GenericArray::from_slice(&CTR_IV));
+    stream("Kuznyechik-CTR", ctr, &PT, &CTR_CT)?;
+    let ofb = GostOfb::<Kuznyechik, U2>::new(key, GenericArray::from_slice(&IV));
+    stream("Kuznyechik-OFB", ofb, &PT, &OFB_CT)?;
+    let cfb = GostCfb::<Kuznyechik, U32>::new(key, GenericArray::from_slice(&IV));
+    async_stream("Kuznyechik-CFB", cfb, &PT, &CFB_CT)
+}
+
+#[cfg(feature = "magma")]
+fn magma() -> Result<(), SelfTestError> {
+    use crate::{
+        consts::{U16, U3},
+        GostCtr64,
+    };
+    use magma::Magma;
+
+    const KEY: [u8; 32] = [
+        0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11,
+        0x00, 0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd,
+        0xfe, 0xff,
+    ];
+    const IV: [u8; 16] = [
+        0x12, 0x34, 0x56, 0x78, 0x90, 0xab, 0xcd, 0xef, 0x23, 0x45, 0x67, 0x89, 0x0a, 0xbc, 0xde,
+        0xf1,
+    ];
+    const CBC_IV: [u8; 24] = [
+        0x12, 0x34, 0x56, 0x78, 0x90, 0xab, 0xcd, 0xef, 0x23, 0x45, 0x67, 0x89, 0x0a, 0xbc, 0xde,
+        0xf1, 0x34, 0x56, 0x78, 0x90, 0xab, 0xcd, 0xef, 0x12,
+    ];
+    const CTR_IV: [u8; 4] = [0x12, 0x34, 0x56, 0x78];
+    const PT: [u8; 32] = [
+        0x92, 0xde, 0xf0, 0x6b, 0x3c, 0x13, 0x0a, 0x59, 0xdb, 0x54, 0xc7, 0x04, 0xf8, 0x18, 0x9d,
+        0x20, 0x4a, 0x98, 0xfb, 0x2e, 0x67, 0xa8, 0x02, 0x4c, 0x89, 0x12, 0x40, 0x9b, 0x17, 0xb5,
+        0x7e, 0x41,
+    ];
+    const ECB_CT: [u8; 32] = [
+        0x2b, 0x07, 0x3f, 0x04, 0x94, 0xf3, 0x72, 0xa0, 0xde, 0x70, 0xe7, 0x15, 0xd3, 0x55, 0x6e,
+        0x48, 0x11, 0xd8, 0xd9, 0xe9, 0xea, 0xcf, 0xbc, 0x1e, 0x7c, 0x68, 0x26, 0x09, 0x96, 0xc6,
+        0x7e, 0xfb,
+    ];
+    const CTR_CT: [u8; 32] = [
+        0x4e, 0x98, 0x11, 0x0c, 0x97, 0xb7, 0xb9, 0x3c, 0x3e, 0x25, 0x0d, 0x93, 0xd6, 0xe8, 0x5d,
+        0x69, 0x13, 0x6d, 0x86, 0x88, 0x07, 0xb2, 0xdb, 0xef, 0x56, 0x8e, 0xb6, 0x80, 0xab, 0x52,
+        0xa1, 0x2d,
+    ];
+    const OFB_CT: [u8; 32] = [
+        0xdb, 0x37, 0xe0, 0xe2, 0x66, 0x90, 0x3c, 0x83, 0x0d, 0x46, 0x64, 0x4c, 0x1f, 0x9a, 0x08,
+        0x9c, 0xa0, 0xf8, 0x30, 0x62, 0x43, 0x0e, 0x32, 0x7e, 0xc8, 0x24, 0xef, 0xb8, 0xbd, 0x4f,
+        0xdb, 0x05,
+    ];
+    const CBC_CT: [u8; 32] = [
+        0x96, 0xd1, 0xb0, 0x5e, 0xea, 0x68, 0x39, 0x19, 0xaf, 0xf7, 0x61, 0x29, 0xab, 0xb9, 0x37,
+        0xb9, 0x50, 0x58, 0xb4, 0xa1, 0xc4, 0xbc, 0x00, 0x19, 0x20, 0xb7, 0x8b, 0x1a, 0x7c, 0xd7,
+        0xe6, 0x67,
+    ];
+    const CFB_CT: [u8; 32] = [
+        0xdb, 0x37, 0xe0, 0xe2, 0x66, 0x90, 0x3c, 0x83, 0x0d, 0x46, 0x64, 0x4c, 0x1f, 0x9a, 0x08,
+        0x9c, 0x24, 0xbd, 0xd2, 0x03, 0x53, 0x15, 0xd3, 0x8b, 0xbc, 0xc0, 0x32, 0x14, 0x21, 0x07,
+        0x55, 0x05,
+    ];
+
+    magma::self_test()?;
+
+    let key = GenericArray::from_slice(&KEY);
+    let cipher = Magma::new(key);
+    let ecb = Ecb::<_, NoPadding>::new(cipher, &Default::default());
+    block_mode("Magma-ECB", ecb, &PT, &ECB_CT)?;
+    let cbc = GostCbc::<_, NoPadding, U3>::new(cipher, GenericArray::from_slice(&CBC_IV));
+    block_mode("Magma-CBC", cbc, &PT, &CBC_CT)?;
+    let ctr = GostCtr64::<Magma>::new(key, GenericArray::from_slice(&CTR_IV));
+    stream("Magma-CTR", ctr, &PT, &CTR_CT)?;
+    let ofb = GostOfb::<Magma, U2>::new(key, GenericArray::from_slice(&IV));
+    stream("Magma-OFB", ofb, &PT, &OFB_CT)?;
+    let cfb = GostCfb::<Magma, U16>::new(key, GenericArray::from_slice(&IV));
+    async_stream("Magma-CFB", cfb, &PT, &CFB_CT)
+}
+
+/// Largest example message
+const MAX_LEN: usize = 64;
+
+fn block_mode<C, M>(
+    algorithm: &'static str,
+    mode: M,
+    pt: &[u8],
+    ct: &[u8],
+) -> Result<(), SelfTestError>
+where
+    C: BlockCipher + BlockEncrypt + BlockDecrypt,
+    M: BlockMode<C, NoPadding> + Clone,
+{
+    let error = SelfTestError::new(algorithm);
+    let mut buf = [0u8; MAX_LEN];
+    let buf = &mut buf[..pt.len()];
+    buf.copy_from_slice(pt);
+    let res = mode.clone().encrypt(buf, pt.len()).map_err(|_| error)?;
+    if res != ct {
+        return Err(error);
+    }
+    match mode.decrypt(buf) {
+        Ok(res) if res == pt => Ok(()),
+        _ => Err(error),
+    }
+}
+
+/// Check a stream cipher, the message is processed in chunks which do not
+/// end at block boundaries
+fn stream<S>(algorithm: &'static str, mode: S, pt: &[u8], ct: &[u8]) -> Result<(), SelfTestError>
+where
+    S: StreamCipher + Clone,
+{
+    let error = SelfTestError::new(algorithm);
+    let mut buf = [0u8; MAX_LEN];
+    let buf = &mut buf[..pt.len()];
+    buf.copy_from_slice(pt);
+    let mut c = mode.clone();
+    for chunk in buf.chunks_mut(7) {
+        c.try_apply_keystream(chunk).map_err(|_| error)?;
+    }
+    if buf != ct {
+        return Err(error);
+    }
+    mode.clone().try_apply_keystream(buf).map_err(|_| error)?;
+    if buf != pt {
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Check a self-synchronizing stream cipher, the message is processed in
+/// chunks which do not end at block boundaries
+fn async_stream<S>(
+    algorithm: &'static str,
+    mode: S,
+    pt: &[u8],
+    ct: &[u8],
+) -> Result<(), SelfTestError>
+where
+    S: AsyncStreamCipher + Clone,
+{
+    let error = SelfTestError::new(algorithm);
+    let mut buf = [0u8; MAX_LEN];
+    let buf = &mut buf[..pt.len()];
+    buf.copy_from_slice(pt);
+    let mut c = mode.clone();
+    for chunk in buf.chunks_mut(7) {
+        c.encrypt(chunk);
+    }
+    if buf != ct {
+        return Err(error);
+    }
+    mode.clone().decrypt(buf);
+    if buf != pt {
+        return Err(error);
+    }
+    Ok(())
+}
@@ -204,3 +204,9 @@ fn magma_modes() {
 
 cipher::stream_cipher_seek_test!(kuznyechik_ctr_seek, GostCtr128::<Kuznyechik, U14>);
 cipher::stream_cipher_seek_test!(magma_ctr_seek, GostCtr64::<Magma, U5>);
+
+#[test]
+#[cfg(all(feature = "kuznyechik", feature = "magma"))]
+fn self_test() {
+    assert_eq!(gost_modes::self_test(), Ok(()));
+}
@@ -19,7 +19,10 @@ use cipher::{
 };
 
 mod consts;
+mod self_test;
 use crate::consts::{FUYI, LENGTH_SUB_KEYS, MAXIM, ONE, ROUNDS};
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 
 /// The International Data Encryption Algorithm (IDEA) block cipher.
 #[derive(Copy, Clone)]
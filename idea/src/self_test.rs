@@ -0,0 +1,20 @@
+//! Power-on self-test.
+
+use crate::Idea;
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// Example from the IDEA specification by X. Lai
+const KEY: [u8; 16] = [
+    0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04, 0x00, 0x05, 0x00, 0x06, 0x00, 0x07, 0x00, 0x08,
+];
+
+/// Plaintext of the example
+const PLAINTEXT: [u8; 8] = [0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03];
+
+/// Ciphertext of the example
+const CIPHERTEXT: [u8; 8] = [0x11, 0xfb, 0xed, 0x2b, 0x01, 0x98, 0x6d, 0xe5];
+
+/// Run the known answer test of IDEA.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Idea>("IDEA", &KEY, &PLAINTEXT, &CIPHERTEXT)
+}
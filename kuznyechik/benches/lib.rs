@@ -1,3 +1,12 @@
 #![feature(test)]
 
 cipher::block_cipher_bench!(kuznyechik::Kuznyechik, 32);
+
+#[bench]
+pub fn key_schedule(bh: &mut Bencher) {
+    let key = [1u8; 32];
+    bh.iter(|| {
+        let state = kuznyechik::Kuznyechik::new_from_slice(test::black_box(&key)).unwrap();
+        test::black_box(&state);
+    });
+}
@@ -0,0 +1,113 @@
+//! Autodetection support for the SSE2 backend with fallback to the portable
+//! "soft" implementation.
+
+use crate::{soft, sse2};
+use cipher::{
+    consts::{U16, U32},
+    generic_array::{typenum::Unsigned, GenericArray},
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use core::mem::ManuallyDrop;
+
+cpufeatures::new!(sse2_cpuid, "sse2");
+
+type Block = GenericArray<u8, U16>;
+type ParBlocks = GenericArray<Block, sse2::ParBlocks>;
+type SoftParBlocks = <soft::Kuznyechik as BlockCipher>::ParBlocks;
+
+#[derive(Clone, Copy)]
+union Inner {
+    sse2: ManuallyDrop<sse2::Kuznyechik>,
+    soft: ManuallyDrop<soft::Kuznyechik>,
+}
+
+/// Kuznyechik (GOST R 34.12-2015) block cipher
+#[derive(Clone, Copy)]
+pub struct Kuznyechik {
+    inner: Inner,
+    token: sse2_cpuid::InitToken,
+}
+
+impl Kuznyechik {
+    /// Name of the backend used by this instance: `"sse2"`, or the name of
+    /// the software backend if SSE2 is not available.
+    pub fn backend(&self) -> &'static str {
+        if self.token.get() {
+            "sse2"
+        } else {
+            soft::NAME
+        }
+    }
+}
+
+impl NewBlockCipher for Kuznyechik {
+    type KeySize = U32;
+
+    #[inline]
+    fn new(key: &GenericArray<u8, U32>) -> Self {
+        let (token, sse2_present) = sse2_cpuid::init_get();
+
+        let inner = if sse2_present {
+            Inner {
+                sse2: ManuallyDrop::new(sse2::Kuznyechik::new(key)),
+            }
+        } else {
+            Inner {
+                soft: ManuallyDrop::new(soft::Kuznyechik::new(key)),
+            }
+        };
+
+        Self { inner, token }
+    }
+}
+
+impl BlockCipher for Kuznyechik {
+    type BlockSize = U16;
+    type ParBlocks = sse2::ParBlocks;
+}
+
+impl BlockEncrypt for Kuznyechik {
+    #[inline]
+    fn encrypt_block(&self, block: &mut Block) {
+        if self.token.get() {
+            unsafe { self.inner.sse2.encrypt_block(block) }
+        } else {
+            unsafe { self.inner.soft.encrypt_block(block) }
+        }
+    }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        if self.token.get() {
+            unsafe { self.inner.sse2.encrypt_par_blocks(blocks) }
+        } else {
+            let soft = unsafe { &self.inner.soft };
+            for chunk in blocks.chunks_mut(SoftParBlocks::USIZE) {
+                soft.encrypt_par_blocks(GenericArray::from_mut_slice(chunk));
+            }
+        }
+    }
+}
+
+impl BlockDecrypt for Kuznyechik {
+    #[inline]
+    fn decrypt_block(&self, block: &mut Block) {
+        if self.token.get() {
+            unsafe { self.inner.sse2.decrypt_block(block) }
+        } else {
+            unsafe { self.inner.soft.decrypt_block(block) }
+        }
+    }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        if self.token.get() {
+            unsafe { self.inner.sse2.decrypt_par_blocks(blocks) }
+        } else {
+            let soft = unsafe { &self.inner.soft };
+            for chunk in blocks.chunks_mut(SoftParBlocks::USIZE) {
+                soft.decrypt_par_blocks(GenericArray::from_mut_slice(chunk));
+            }
+        }
+    }
+}
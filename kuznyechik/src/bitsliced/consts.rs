@@ -0,0 +1,142 @@
+//! Tables used by the bitsliced implementation.
+//!
+//! All tables are derived at compile time from the substitution tables and
+//! the coefficients of the linear transformation.
+
+use crate::consts::{l, l_inv, P, P_INV, ROUND_CONSTS};
+
+/// One bit per 16-bit lane, used to replicate values across the four blocks
+pub(super) const LANES: u64 = 0x0001_0001_0001_0001;
+
+/// Algebraic normal form of an 8-bit S-box computed using the Moebius
+/// transform. Bit `j` of the element `u` is set if the monomial `u` is
+/// present in the polynomial of the output bit `j`.
+const fn anf(sbox: &[u8; 256]) -> [u8; 256] {
+    let mut res = *sbox;
+    let mut i = 0;
+    while i < 8 {
+        let mut u = 0;
+        while u < 256 {
+            if u & (1 << i) != 0 {
+                res[u] ^= res[u ^ (1 << i)];
+            }
+            u += 1;
+        }
+        i += 1;
+    }
+    res
+}
+
+/// Algebraic normal form of an 8-bit S-box in the form of masks.
+///
+/// Element `[u][j]` is equal to `!0` if the monomial `u` is present in
+/// the polynomial of the output bit `j` and to zero otherwise.
+type Anf = [[u64; 8]; 256];
+
+const fn anf_masks(sbox: &[u8; 256]) -> Anf {
+    let anf = anf(sbox);
+    let mut res = [[0u64; 8]; 256];
+    let mut u = 0;
+    while u < 256 {
+        let mut j = 0;
+        while j < 8 {
+            res[u][j] = 0u64.wrapping_sub(((anf[u] >> j) & 1) as u64);
+            j += 1;
+        }
+        u += 1;
+    }
+    res
+}
+
+/// Matrix of a linear transformation in the bitsliced representation.
+///
+/// Element `[k][c][p]` has bit `16 * b + j` set if bit `c` of the input
+/// byte `k` contributes to bit `p` of the output byte `j` (replicated for
+/// every block `b`).
+type Matrix = [[[u64; 8]; 8]; 16];
+
+const fn matrix(inverse: bool) -> Matrix {
+    let mut res = [[[0u64; 8]; 8]; 16];
+    let mut k = 0;
+    while k < 16 {
+        let mut c = 0;
+        while c < 8 {
+            let mut v = [0u8; 16];
+            v[k] = 1 << c;
+            let v = if inverse { l_inv(v) } else { l(v) };
+            let mut p = 0;
+            while p < 8 {
+                let mut mask = 0u64;
+                let mut j = 0;
+                while j < 16 {
+                    mask |= (((v[j] >> p) & 1) as u64) << j;
+                    j += 1;
+                }
+                res[k][c][p] = mask * LANES;
+                p += 1;
+            }
+            c += 1;
+        }
+        k += 1;
+    }
+    res
+}
+
+/// Bitsliced representation of a single block stored in the first lane
+const fn bitslice1(block: &[u8; 16]) -> [u64; 8] {
+    let mut res = [0u64; 8];
+    let mut c = 0;
+    while c < 8 {
+        let mut k = 0;
+        while k < 16 {
+            res[c] |= (((block[k] >> c) & 1) as u64) << k;
+            k += 1;
+        }
+        c += 1;
+    }
+    res
+}
+
+/// Bitsliced iteration constants used by the key schedule
+const fn round_consts() -> [[u64; 8]; 32] {
+    let mut res = [[0u64; 8]; 32];
+    let mut i = 0;
+    while i < 32 {
+        res[i] = bitslice1(&ROUND_CONSTS[i]);
+        i += 1;
+    }
+    res
+}
+
+/// Algebraic normal form of the substitution
+pub(super) static ANF_P: Anf = anf_masks(&P);
+
+/// Algebraic normal form of the inverse substitution
+pub(super) static ANF_P_INV: Anf = anf_masks(&P_INV);
+
+/// Bitsliced matrix of `L`
+pub(super) static L_MATRIX: Matrix = matrix(false);
+
+/// Bitsliced matrix of `L^-1`
+pub(super) static L_INV_MATRIX: Matrix = matrix(true);
+
+/// Bitsliced key schedule constants
+pub(super) static BITSLICED_ROUND_CONSTS: [[u64; 8]; 32] = round_consts();
+
+#[test]
+fn test_anf() {
+    // evaluate the ANF for every input and compare with the S-box
+    for (sbox, anf) in [(&P, &ANF_P), (&P_INV, &ANF_P_INV)].iter() {
+        for x in 0..256usize {
+            let mut y = 0u8;
+            for u in 0..256usize {
+                if u & x == u {
+                    for j in 0..8 {
+                        y ^= (anf[u][j] as u8 & 1) << j;
+                    }
+                }
+            }
+            assert_eq!(y, sbox[x]);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Constant-time bitsliced implementation.
+//!
+//! Four blocks are processed in parallel. The 512-bit state is stored as
+//! eight 64-bit "bit planes": bit `c` of byte `k` of block `b` is stored in
+//! the plane `c` at position `16 * b + k`. The substitution is evaluated
+//! using its algebraic normal form and the linear transformation using
+//! its binary matrix, so no secret-dependent table lookups or branches
+//! are performed, including in the key schedule.
+//!
+//! This implementation is significantly slower than the table-based ones.
+
+#![deny(unsafe_code)]
+
+use cipher::{
+    consts::{U16, U32, U4},
+    generic_array::GenericArray,
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+
+mod consts;
+
+use consts::{ANF_P, ANF_P_INV, BITSLICED_ROUND_CONSTS, LANES, L_INV_MATRIX, L_MATRIX};
+
+type Block = GenericArray<u8, U16>;
+type ParBlocks = GenericArray<Block, U4>;
+
+/// Name of this backend
+pub(crate) const NAME: &str = "bitsliced";
+
+/// Number of blocks processed in parallel
+const BLOCKS: usize = 4;
+
+/// 512-bit bitsliced state
+type State = [u64; 8];
+
+/// Kuznyechik (GOST R 34.12-2015) block cipher
+#[derive(Clone, Copy)]
+pub struct Kuznyechik {
+    keys: [State; 10],
+}
+
+/// Transpose 8x8 bit matrix, i.e. bit `c` of byte `k` is moved to
+/// bit `k` of byte `c`
+#[inline(always)]
+fn transpose8x8(mut x: u64) -> u64 {
+    let t = (x ^ (x >> 7)) & 0x00AA_00AA_00AA_00AA;
+    x ^= t ^ (t << 7);
+    let t = (x ^ (x >> 14)) & 0x0000_CCCC_0000_CCCC;
+    x ^= t ^ (t << 14);
+    let t = (x ^ (x >> 28)) & 0x0000_0000_F0F0_F0F0;
+    x ^ t ^ (t << 28)
+}
+
+/// Convert up to four blocks into the bitsliced representation
+#[inline(always)]
+fn bitslice(blocks: &[Block]) -> State {
+    debug_assert!(blocks.len() <= BLOCKS);
+    let mut state = [0u64; 8];
+    for (b, block) in blocks.iter().enumerate() {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&block[..8]);
+        let lo = transpose8x8(u64::from_le_bytes(buf));
+        buf.copy_from_slice(&block[8..]);
+        let hi = transpose8x8(u64::from_le_bytes(buf));
+        for c in 0..8 {
+            let plane = ((lo >> (8 * c)) & 0xFF) | (((hi >> (8 * c)) & 0xFF) << 8);
+            state[c] |= plane << (16 * b);
+        }
+    }
+    state
+}
+
+/// Convert the bitsliced representation back into up to four blocks
+#[inline(always)]
+fn inv_bitslice(state: &State, blocks: &mut [Block]) {
+    debug_assert!(blocks.len() <= BLOCKS);
+    for (b, block) in blocks.iter_mut().enumerate() {
+        let mut lo = 0u64;
+        let mut hi = 0u64;
+        for c in 0..8 {
+            let plane = state[c] >> (16 * b);
+            lo |= (plane & 0xFF) << (8 * c);
+            hi |= ((plane >> 8) & 0xFF) << (8 * c);
+        }
+        block[..8].copy_from_slice(&transpose8x8(lo).to_le_bytes());
+        block[8..].copy_from_slice(&transpose8x8(hi).to_le_bytes());
+    }
+}
+
+/// Copy the first lane into the remaining ones
+#[inline(always)]
+fn broadcast(state: &State) -> State {
+    let mut res = *state;
+    for plane in res.iter_mut() {
+        *plane = (*plane & 0xFFFF) * LANES;
+    }
+    res
+}
+
+#[inline(always)]
+fn x(state: &mut State, key: &State) {
+    for i in 0..8 {
+        state[i] ^= key[i];
+    }
+}
+
+/// Substitution evaluated as a polynomial over GF(2)
+#[inline(always)]
+fn s(state: &mut State, anf: &[[u64; 8]; 256]) {
+    let mut monomials = [0u64; 256];
+    monomials[0] = !0;
+    for u in 1..256 {
+        let h = 7 - (u as u8).leading_zeros() as usize;
+        monomials[u] = monomials[u ^ (1 << h)] & state[h];
+    }
+
+    let mut res = [0u64; 8];
+    for u in 0..256 {
+        for j in 0..8 {
+            res[j] ^= monomials[u] & anf[u][j];
+        }
+    }
+    *state = res;
+}
+
+/// Linear transformation given by its bitsliced binary matrix
+#[inline(always)]
+fn l(state: &mut State, matrix: &[[[u64; 8]; 8]; 16]) {
+    let mut res = [0u64; 8];
+    for k in 0..16 {
+        for c in 0..8 {
+            // spread bit `k` of every 16-bit lane over the whole lane
+            let t = (state[c] >> k) & LANES;
+            let t = (t << 16).wrapping_sub(t);
+            for p in 0..8 {
+                res[p] ^= t & matrix[k][c][p];
+            }
+        }
+    }
+    *state = res;
+}
+
+#[inline(always)]
+fn lsx(state: &mut State, key: &State) {
+    x(state, key);
+    s(state, &ANF_P);
+    l(state, &L_MATRIX);
+}
+
+impl Kuznyechik {
+    fn expand_key(key: &GenericArray<u8, U32>) -> [State; 10] {
+        let mut keys = [[0u64; 8]; 10];
+
+        let mut k1 = bitslice(&[*Block::from_slice(&key[..16])]);
+        let mut k2 = bitslice(&[*Block::from_slice(&key[16..])]);
+
+        keys[0] = broadcast(&k1);
+        keys[1] = broadcast(&k2);
+
+        for i in 0..4 {
+            for j in 0..4 {
+                let mut t = k1;
+                lsx(&mut t, &BITSLICED_ROUND_CONSTS[8 * i + 2 * j]);
+                x(&mut k2, &t);
+
+                let mut t = k2;
+                lsx(&mut t, &BITSLICED_ROUND_CONSTS[8 * i + 2 * j + 1]);
+                x(&mut k1, &t);
+            }
+            keys[2 * i + 2] = broadcast(&k1);
+            keys[2 * i + 3] = broadcast(&k2);
+        }
+
+        keys
+    }
+
+    fn encrypt(&self, blocks: &mut [Block]) {
+        let mut state = bitslice(blocks);
+        unroll9! {
+            i, { lsx(&mut state, &self.keys[i]) ; }
+        }
+        x(&mut state, &self.keys[9]);
+        inv_bitslice(&state, blocks);
+    }
+
+    fn decrypt(&self, blocks: &mut [Block]) {
+        let mut state = bitslice(blocks);
+        unroll9! {
+            i, {
+                x(&mut state, &self.keys[9 - i]);
+                l(&mut state, &L_INV_MATRIX);
+                s(&mut state, &ANF_P_INV);
+            }
+        }
+        x(&mut state, &self.keys[0]);
+        inv_bitslice(&state, blocks);
+    }
+}
+
+impl NewBlockCipher for Kuznyechik {
+    type KeySize = U32;
+
+    fn new(key: &GenericArray<u8, U32>) -> Self {
+        Self {
+            keys: Self::expand_key(key),
+        }
+    }
+}
+
+impl BlockCipher for Kuznyechik {
+    type BlockSize = U16;
+    type ParBlocks = U4;
+}
+
+impl BlockEncrypt for Kuznyechik {
+    #[inline]
+    fn encrypt_block(&self, block: &mut Block) {
+        self.encrypt(core::slice::from_mut(block));
+    }
+
+    #[inline]
+    fn encrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        self.encrypt(blocks);
+    }
+}
+
+impl BlockDecrypt for Kuznyechik {
+    #[inline]
+    fn decrypt_block(&self, block: &mut Block) {
+        self.decrypt(core::slice::from_mut(block));
+    }
+
+    #[inline]
+    fn decrypt_par_blocks(&self, blocks: &mut ParBlocks) {
+        self.decrypt(blocks);
+    }
+}
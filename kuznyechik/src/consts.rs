@@ -38,9 +38,104 @@ pub const P_INV: [u8; 256] = [
     0x12, 0x1A, 0x48, 0x68, 0xF5, 0x81, 0x8B, 0xC7, 0xD6, 0x20, 0x0A, 0x08, 0x00, 0x4C, 0xD7, 0x74,
 ];
 
+/// Coefficients of the linear transformation `l` (GOST R 34.12-2015 4.1.2)
+/// in the memory byte order used by this crate
+const L_COEFS: [u8; 16] = [
+    148, 32, 133, 16, 194, 192, 1, 251, 1, 192, 194, 16, 133, 32, 148, 1,
+];
+
+/// Multiplication in GF(2^8) modulo x^8 + x^7 + x^6 + x + 1
+pub const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut r = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            r ^= a;
+        }
+        let hi = a & 0x80;
+        a <<= 1;
+        if hi != 0 {
+            a ^= 0xC3;
+        }
+        b >>= 1;
+    }
+    r
+}
+
+/// Single step of the linear transformation, it's an involution
+const fn l_step(mut msg: [u8; 16], i: usize) -> [u8; 16] {
+    let mut x = 0;
+    let mut b: usize = 0;
+    while b < 16 {
+        x ^= gf_mul(msg[b.wrapping_sub(i) & 0x0F], L_COEFS[b]);
+        b += 1;
+    }
+    msg[15usize.wrapping_sub(i) & 0x0F] = x;
+    msg
+}
+
+/// Linear transformation `L`
+pub const fn l(mut msg: [u8; 16]) -> [u8; 16] {
+    let mut i = 0;
+    while i < 16 {
+        msg = l_step(msg, i);
+        i += 1;
+    }
+    msg
+}
+
+/// Inverse linear transformation `L^-1`
+#[allow(dead_code)] // not used by the table-based software backend
+pub const fn l_inv(mut msg: [u8; 16]) -> [u8; 16] {
+    let mut i = 16;
+    while i > 0 {
+        i -= 1;
+        msg = l_step(msg, i);
+    }
+    msg
+}
+
+const fn round_consts() -> [[u8; 16]; 32] {
+    let mut res = [[0u8; 16]; 32];
+    let mut i = 0;
+    while i < 32 {
+        let mut v = [0u8; 16];
+        v[15] = (i + 1) as u8;
+        res[i] = l(v);
+        i += 1;
+    }
+    res
+}
+
+/// Iteration constants `C_i = L(i)` used by the key schedule
+pub const ROUND_CONSTS: [[u8; 16]; 32] = round_consts();
+
 #[test]
 fn test_subst_tables() {
     for i in 0..256 {
         assert_eq!(P_INV[P[i] as usize], i as u8);
     }
 }
+
+#[test]
+fn test_linear() {
+    let v = *b"\x01\x23\x45\x67\x89\xAB\xCD\xEF\xFE\xDC\xBA\x98\x76\x54\x32\x10";
+    assert_eq!(l_inv(l(v)), v);
+    assert_ne!(l(v), v);
+}
+
+/// Iteration constants from GOST R 34.12-2015 A.1.4
+#[test]
+fn test_round_consts() {
+    assert_eq!(
+        ROUND_CONSTS[0],
+        *b"\x6e\xa2\x76\x72\x6c\x48\x7a\xb8\x5d\x27\xbd\x10\xdd\x84\x94\x01",
+    );
+    assert_eq!(
+        ROUND_CONSTS[1],
+        *b"\xdc\x87\xec\xe4\xd8\x90\xf4\xb3\xba\x4e\xb9\x20\x79\xcb\xeb\x02",
+    );
+    assert_eq!(
+        ROUND_CONSTS[7],
+        *b"\xf6\x59\x36\x16\xe6\x05\x56\x89\xad\xfb\xa1\x80\x27\xaa\x2a\x08",
+    );
+}
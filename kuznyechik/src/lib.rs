@@ -1,5 +1,31 @@
 //! Pure Rust implementation of the [Kuznyechik][1] (GOST R 34.12-2015) block cipher.
 //!
+//! # Supported backends
+//! On `x86`/`x86_64` targets this crate performs runtime detection of SSE2
+//! and uses a table-based SSE2 backend if it is available, falling back to
+//! the portable software implementation otherwise. This allows a single
+//! binary built for a generic target to use the fast path on every host
+//! which supports it.
+//!
+//! Enabling the `force-soft` Cargo feature disables runtime detection and
+//! always uses the software implementation, which relies on smaller tables.
+//!
+//! The name of the backend used by a cipher instance is returned by its
+//! `backend` method.
+//!
+//! # Constant-time software backend
+//! Both table-based implementations perform secret-dependent memory accesses
+//! and thus may leak key material through cache timing side channels.
+//! Enabling the `bitsliced` Cargo feature replaces the software
+//! implementation with a constant-time bitsliced one, which processes four
+//! blocks in parallel at the cost of decreased performance. Combine it with
+//! `force-soft` to use the constant-time implementation on all targets.
+//!
+//! # Self-test
+//! [`self_test`] runs the known answer test from GOST R 34.12-2015 against
+//! the backend which is in use, which allows to detect a faulty backend
+//! before processing any data.
+//!
 //! [1]: https://en.wikipedia.org/wiki/Kuznyechik
 #![no_std]
 #![doc(
@@ -15,24 +41,48 @@ use core::fmt;
 #[macro_use]
 mod macros;
 mod consts;
+mod self_test;
+
+#[cfg_attr(feature = "bitsliced", path = "bitsliced/mod.rs")]
+mod soft;
+
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "x86"),
+    not(feature = "force-soft"),
+))]
+mod autodetect;
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "x86"),
+    not(feature = "force-soft"),
+))]
+mod sse2;
 
 #[cfg(all(
     any(target_arch = "x86_64", target_arch = "x86"),
-    target_feature = "sse2",
     not(feature = "force-soft"),
 ))]
-#[path = "sse2/mod.rs"]
-mod imp;
+pub use autodetect::Kuznyechik;
 
 #[cfg(not(all(
     any(target_arch = "x86_64", target_arch = "x86"),
-    target_feature = "sse2",
     not(feature = "force-soft"),
 )))]
-#[path = "soft/mod.rs"]
-mod imp;
+pub use soft::Kuznyechik;
+
+#[cfg(not(all(
+    any(target_arch = "x86_64", target_arch = "x86"),
+    not(feature = "force-soft"),
+)))]
+impl Kuznyechik {
+    /// Name of the backend used by this instance: `"soft"`, or `"bitsliced"`
+    /// if the `bitsliced` feature is enabled.
+    pub fn backend(&self) -> &'static str {
+        soft::NAME
+    }
+}
 
-pub use imp::Kuznyechik;
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 
 impl fmt::Debug for Kuznyechik {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
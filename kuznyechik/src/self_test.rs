@@ -0,0 +1,29 @@
+//! Power-on self-test.
+
+use crate::Kuznyechik;
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// Example from GOST R 34.12-2015 appendix A.1
+const KEY: [u8; 32] = [
+    0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+    0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+];
+
+/// Plaintext of the GOST R 34.12-2015 example
+const PLAINTEXT: [u8; 16] = [
+    0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x00, 0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88,
+];
+
+/// Ciphertext of the GOST R 34.12-2015 example
+const CIPHERTEXT: [u8; 16] = [
+    0x7f, 0x67, 0x9d, 0x90, 0xbe, 0xbc, 0x24, 0x30, 0x5a, 0x46, 0x8d, 0x42, 0xb9, 0xd4, 0xed, 0xcd,
+];
+
+/// Run the known answer test of Kuznyechik.
+///
+/// On `x86`/`x86_64` the test runs against the backend selected by runtime
+/// detection of SSE2, the same one which is used by ciphers created by the
+/// caller.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Kuznyechik>("Kuznyechik", &KEY, &PLAINTEXT, &CIPHERTEXT)
+}
@@ -1,6 +1,4 @@
-pub use cipher;
-
-use crate::consts::{P, P_INV};
+use crate::consts::{P, P_INV, ROUND_CONSTS};
 use cipher::{
     consts::{U1, U16, U32},
     generic_array::GenericArray,
@@ -9,6 +7,9 @@ use cipher::{
 
 mod consts;
 
+/// Name of this backend
+pub(crate) const NAME: &str = "soft";
+
 type Block = GenericArray<u8, U16>;
 
 /// Kuznyechik (GOST R 34.12-2015) block cipher
@@ -71,23 +72,14 @@ fn lsx_inv(msg: &mut [u8; 16], key: &[u8; 16]) {
     unroll16! {i, { msg[15 - i] = P_INV[msg[15 - i] as usize]; }};
 }
 
-fn get_c(n: usize) -> [u8; 16] {
-    let mut v = [0u8; 16];
-    v[15] = n as u8;
-    for i in 0..16 {
-        l_step(&mut v, i);
-    }
-    v
-}
-
 fn f(k1: &mut [u8; 16], k2: &mut [u8; 16], n: usize) {
     for i in 0..4 {
         let mut k1_cpy = *k1;
-        lsx(&mut k1_cpy, &get_c(8 * n + 2 * i + 1));
+        lsx(&mut k1_cpy, &ROUND_CONSTS[8 * n + 2 * i]);
         x(k2, &k1_cpy);
 
         let mut k2_cpy = *k2;
-        lsx(&mut k2_cpy, &get_c(8 * n + 2 * i + 2));
+        lsx(&mut k2_cpy, &ROUND_CONSTS[8 * n + 2 * i + 1]);
         x(k1, &k2_cpy);
     }
 }
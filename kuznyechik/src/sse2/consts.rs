@@ -1,10 +1,63 @@
+use crate::consts::{l, l_inv, P, P_INV, ROUND_CONSTS};
+
 #[repr(align(16))]
 pub struct Align16<T>(pub T);
 
 pub type Table = Align16<[u8; 16 * 4096]>;
 
-pub static ENC_TABLE: Table = Align16(*include_bytes!("enc_table.bin"));
+/// Table of the combined transformation `L(S(x))` (or `L^-1(S^-1(x))` if
+/// `inverse` is true) for every byte position and byte value
+const fn table(sbox: &[u8; 256], inverse: bool) -> [u8; 16 * 4096] {
+    // `L` is linear, so it's sufficient to compute it only for basis vectors
+    let mut basis = [[[0u8; 16]; 8]; 16];
+    let mut i = 0;
+    while i < 16 {
+        let mut c = 0;
+        while c < 8 {
+            let mut v = [0u8; 16];
+            v[i] = 1 << c;
+            basis[i][c] = if inverse { l_inv(v) } else { l(v) };
+            c += 1;
+        }
+        i += 1;
+    }
+
+    let mut res = [0u8; 16 * 4096];
+    let mut i = 0;
+    while i < 16 {
+        let mut b = 0;
+        while b < 256 {
+            let offset = 16 * (256 * i + b);
+            let mut c = 0;
+            while c < 8 {
+                if (sbox[b] >> c) & 1 == 1 {
+                    let mut j = 0;
+                    while j < 16 {
+                        res[offset + j] ^= basis[i][c][j];
+                        j += 1;
+                    }
+                }
+                c += 1;
+            }
+            b += 1;
+        }
+        i += 1;
+    }
+    res
+}
+
+const fn rkey_gen() -> [u8; 16 * 32] {
+    let mut res = [0u8; 16 * 32];
+    let mut i = 0;
+    while i < 16 * 32 {
+        res[i] = ROUND_CONSTS[i / 16][i % 16];
+        i += 1;
+    }
+    res
+}
+
+pub static ENC_TABLE: Table = Align16(table(&P, false));
 
-pub static DEC_TABLE: Table = Align16(*include_bytes!("dec_table.bin"));
+pub static DEC_TABLE: Table = Align16(table(&P_INV, true));
 
-pub static RKEY_GEN: Align16<[u8; 16 * 32]> = Align16(*include_bytes!("rkey_gen.bin"));
+pub static RKEY_GEN: Align16<[u8; 16 * 32]> = Align16(rkey_gen());
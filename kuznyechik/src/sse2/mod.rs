@@ -1,6 +1,8 @@
 //! SSE2-based implementation based on https://github.com/aprelev/lg15
-
-pub use cipher;
+//!
+//! Functions which use SSE2 intrinsics are marked with the `sse2` target
+//! feature, so this backend can be compiled for targets without SSE2 enabled
+//! and selected at runtime (see the `autodetect` module).
 
 use crate::consts::{P, P_INV};
 use cipher::{
@@ -8,9 +10,13 @@ use cipher::{
     generic_array::{typenum::Unsigned, GenericArray},
     BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
 };
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
 use core::arch::x86_64::*;
 
-type ParBlocks = cipher::consts::U4;
+pub(crate) type ParBlocks = cipher::consts::U4;
 
 #[rustfmt::skip]
 macro_rules! unroll_par {
@@ -113,58 +119,170 @@ unsafe fn transform(block: __m128i, table: &Table) -> __m128i {
     _mm_xor_si128(lt, rt)
 }
 
-impl NewBlockCipher for Kuznyechik {
-    type KeySize = U32;
+#[target_feature(enable = "sse2")]
+unsafe fn expand_key(key: &GenericArray<u8, U32>) -> Kuznyechik {
+    macro_rules! next_const {
+        ($i:expr) => {{
+            let p = consts::RKEY_GEN.0.as_ptr() as *const __m128i;
+            // correct aligment of `p` is guaranteed since the table
+            // is aligned to 16 bytes
+            let p = p.add($i);
+            debug_assert_eq!(p as usize % 16, 0);
+            $i += 1;
+            _mm_load_si128(p)
+        }};
+    }
 
-    fn new(key: &GenericArray<u8, U32>) -> Self {
-        macro_rules! next_const {
-            ($i:expr) => {{
-                let p = consts::RKEY_GEN.0.as_ptr() as *const __m128i;
-                // correct aligment of `p` is guaranteed since the table
-                // is aligned to 16 bytes
-                let p = p.add($i);
-                debug_assert_eq!(p as usize % 16, 0);
-                $i += 1;
-                _mm_load_si128(p)
-            }};
+    let mut enc_keys = [_mm_setzero_si128(); 10];
+    let mut dec_keys = [_mm_setzero_si128(); 8];
+
+    let pk: *const __m128i = key.as_ptr() as *const __m128i;
+    let mut k1 = _mm_loadu_si128(pk);
+    let mut k2 = _mm_loadu_si128(pk.add(1));
+    enc_keys[0] = k1;
+    enc_keys[1] = k2;
+
+    let mut cidx = 0;
+    for i in 1..5 {
+        for _ in 0..4 {
+            let mut t = _mm_xor_si128(k1, next_const!(cidx));
+            t = transform(t, &ENC_TABLE);
+            k2 = _mm_xor_si128(k2, t);
+
+            let mut t = _mm_xor_si128(k2, next_const!(cidx));
+            t = transform(t, &ENC_TABLE);
+            k1 = _mm_xor_si128(k1, t);
         }
 
-        unsafe {
-            let mut enc_keys = [_mm_setzero_si128(); 10];
-            let mut dec_keys = [_mm_setzero_si128(); 8];
-
-            let pk: *const __m128i = key.as_ptr() as *const __m128i;
-            let mut k1 = _mm_loadu_si128(pk);
-            let mut k2 = _mm_loadu_si128(pk.add(1));
-            enc_keys[0] = k1;
-            enc_keys[1] = k2;
-
-            let mut cidx = 0;
-            for i in 1..5 {
-                for _ in 0..4 {
-                    let mut t = _mm_xor_si128(k1, next_const!(cidx));
-                    t = transform(t, &ENC_TABLE);
-                    k2 = _mm_xor_si128(k2, t);
-
-                    let mut t = _mm_xor_si128(k2, next_const!(cidx));
-                    t = transform(t, &ENC_TABLE);
-                    k1 = _mm_xor_si128(k1, t);
-                }
+        enc_keys[2 * i] = k1;
+        enc_keys[2 * i + 1] = k2;
+    }
+
+    for i in 1..9 {
+        let k = sub_bytes(enc_keys[i], &P);
+        dec_keys[8 - i] = transform(k, &DEC_TABLE);
+    }
+
+    Kuznyechik { enc_keys, dec_keys }
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn encrypt1(k: &[__m128i; 10], block: &mut Block) {
+    let block_ptr = block.as_mut_ptr() as *mut __m128i;
+    let mut block = _mm_loadu_si128(block_ptr);
+
+    unroll9! {
+        i, {
+            block = _mm_xor_si128(block, k[i]);
+            block = transform(block, &ENC_TABLE);
+        }
+    };
+    block = _mm_xor_si128(block, k[9]);
+    _mm_storeu_si128(block_ptr, block)
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn encrypt_par(k: &[__m128i; 10], blocks: &mut GenericArray<Block, ParBlocks>) {
+    let bptr = blocks.as_mut_ptr() as *mut __m128i;
+    let mut blocks = [_mm_setzero_si128(); ParBlocks::USIZE];
+    unroll_par! {
+        i, {
+            blocks[i] = _mm_loadu_si128(bptr.add(i));
+        }
+    };
 
-                enc_keys[2 * i] = k1;
-                enc_keys[2 * i + 1] = k2;
+    unroll9! {
+        i, {
+            unroll_par!{
+                j, {
+                    let t = _mm_xor_si128(blocks[j], k[i]);
+                    blocks[j] = transform(t, &ENC_TABLE);
+                }
             }
+        }
+    }
+
+    unroll_par! {
+        i, {
+            let t = _mm_xor_si128(blocks[i], k[9]);
+            _mm_storeu_si128(bptr.add(i), t);
+        }
+    }
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn decrypt1(ek: &[__m128i; 10], dk: &[__m128i; 8], block: &mut Block) {
+    let block_ptr = block.as_mut_ptr() as *mut __m128i;
+    let mut block = _mm_loadu_si128(block_ptr);
+
+    block = _mm_xor_si128(block, ek[9]);
+
+    block = sub_bytes(block, &P);
+    block = transform(block, &DEC_TABLE);
+
+    unroll8! {
+        i, {
+            block = transform(block, &DEC_TABLE);
+            block = _mm_xor_si128(block, dk[i]);
+        }
+    }
+
+    block = sub_bytes(block, &P_INV);
+    block = _mm_xor_si128(block, ek[0]);
+    _mm_storeu_si128(block_ptr, block)
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn decrypt_par(
+    ek: &[__m128i; 10],
+    dk: &[__m128i; 8],
+    blocks: &mut GenericArray<Block, ParBlocks>,
+) {
+    let bptr = blocks.as_mut_ptr() as *mut __m128i;
+    let mut blocks = [_mm_setzero_si128(); ParBlocks::USIZE];
+    unroll_par! {
+        i, {
+            blocks[i] = _mm_loadu_si128(bptr.add(i));
+        }
+    };
 
-            for i in 1..9 {
-                let k = sub_bytes(enc_keys[i], &P);
-                dec_keys[8 - i] = transform(k, &DEC_TABLE);
+    unroll_par! {
+        i, {
+            let t = _mm_xor_si128(blocks[i], ek[9]);
+            let t = sub_bytes(t, &P);
+            blocks[i] = transform(t, &DEC_TABLE);
+        }
+    }
+
+    unroll8! {
+        i, {
+            unroll_par!{
+                j, {
+                    let t = transform(blocks[j], &DEC_TABLE);
+                    blocks[j] = _mm_xor_si128(t, dk[i]);
+                }
             }
+        }
+    }
 
-            Self { enc_keys, dec_keys }
+    unroll_par! {
+        i, {
+            let t = sub_bytes(blocks[i], &P_INV);
+            let t2 = _mm_xor_si128(t, ek[0]);
+            _mm_storeu_si128(bptr.add(i), t2)
         }
     }
 }
 
+impl NewBlockCipher for Kuznyechik {
+    type KeySize = U32;
+
+    #[inline]
+    fn new(key: &GenericArray<u8, U32>) -> Self {
+        unsafe { expand_key(key) }
+    }
+}
+
 impl BlockCipher for Kuznyechik {
     type BlockSize = U16;
     type ParBlocks = ParBlocks;
@@ -173,121 +291,23 @@ impl BlockCipher for Kuznyechik {
 impl BlockEncrypt for Kuznyechik {
     #[inline]
     fn encrypt_block(&self, block: &mut Block) {
-        let k = self.enc_keys;
-        unsafe {
-            let block_ptr = block.as_ptr() as *mut __m128i;
-            let mut block = _mm_loadu_si128(block_ptr);
-
-            unroll9! {
-                i, {
-                    block = _mm_xor_si128(block, k[i]);
-                    block = transform(block, &ENC_TABLE);
-                }
-            };
-            block = _mm_xor_si128(block, k[9]);
-            _mm_storeu_si128(block_ptr, block)
-        }
+        unsafe { encrypt1(&self.enc_keys, block) }
     }
 
     #[inline]
     fn encrypt_par_blocks(&self, blocks: &mut GenericArray<Block, ParBlocks>) {
-        let k = self.enc_keys;
-        unsafe {
-            let bptr = blocks.as_ptr() as *mut __m128i;
-            let mut blocks = [_mm_setzero_si128(); ParBlocks::USIZE];
-            unroll_par! {
-                i, {
-                    blocks[i] = _mm_loadu_si128(bptr.add(i));
-                }
-            };
-
-            unroll9! {
-                i, {
-                    unroll_par!{
-                        j, {
-                            let t = _mm_xor_si128(blocks[j], k[i]);
-                            blocks[j] = transform(t, &ENC_TABLE);
-                        }
-                    }
-                }
-            }
-
-            unroll_par! {
-                i, {
-                    let t = _mm_xor_si128(blocks[i], k[9]);
-                    _mm_storeu_si128(bptr.add(i), t);
-                }
-            }
-        }
+        unsafe { encrypt_par(&self.enc_keys, blocks) }
     }
 }
 
 impl BlockDecrypt for Kuznyechik {
     #[inline]
     fn decrypt_block(&self, block: &mut Block) {
-        let ek = self.enc_keys;
-        let dk = self.dec_keys;
-        unsafe {
-            let block_ptr = block.as_ptr() as *mut __m128i;
-            let mut block = _mm_loadu_si128(block_ptr);
-
-            block = _mm_xor_si128(block, ek[9]);
-
-            block = sub_bytes(block, &P);
-            block = transform(block, &DEC_TABLE);
-
-            unroll8! {
-                i, {
-                    block = transform(block, &DEC_TABLE);
-                    block = _mm_xor_si128(block, dk[i]);
-                }
-            }
-
-            block = sub_bytes(block, &P_INV);
-            block = _mm_xor_si128(block, ek[0]);
-            _mm_storeu_si128(block_ptr, block)
-        }
+        unsafe { decrypt1(&self.enc_keys, &self.dec_keys, block) }
     }
 
     #[inline]
     fn decrypt_par_blocks(&self, blocks: &mut GenericArray<Block, ParBlocks>) {
-        let ek = self.enc_keys;
-        let dk = self.dec_keys;
-        unsafe {
-            let bptr = blocks.as_ptr() as *mut __m128i;
-            let mut blocks = [_mm_setzero_si128(); ParBlocks::USIZE];
-            unroll_par! {
-                i, {
-                    blocks[i] = _mm_loadu_si128(bptr.add(i));
-                }
-            };
-
-            unroll_par! {
-                i, {
-                    let t = _mm_xor_si128(blocks[i], ek[9]);
-                    let t = sub_bytes(t, &P);
-                    blocks[i] = transform(t, &DEC_TABLE);
-                }
-            }
-
-            unroll8! {
-                i, {
-                    unroll_par!{
-                        j, {
-                            let t = transform(blocks[j], &DEC_TABLE);
-                            blocks[j] = _mm_xor_si128(t, dk[i]);
-                        }
-                    }
-                }
-            }
-
-            unroll_par! {
-                i, {
-                    let t = sub_bytes(blocks[i], &P_INV);
-                    let t2 = _mm_xor_si128(t, ek[0]);
-                    _mm_storeu_si128(bptr.add(i), t2)
-                }
-            }
-        }
+        unsafe { decrypt_par(&self.enc_keys, &self.dec_keys, blocks) }
     }
 }
@@ -22,3 +22,54 @@ fn kuznyechik() {
     state.decrypt_block(&mut block);
     assert_eq!(&plaintext, block.as_slice());
 }
+
+/// Parallel block processing must agree with single block processing
+#[test]
+fn kuznyechik_par_blocks() {
+    let key = hex!("
+        8899AABBCCDDEEFF0011223344556677
+        FEDCBA98765432100123456789ABCDEF
+    ");
+    let plaintext = hex!("1122334455667700FFEEDDCCBBAA9988");
+    let ciphertext = hex!("7F679D90BEBC24305a468d42b9d4EDCD");
+
+    let state = kuznyechik::Kuznyechik::new_from_slice(&key).unwrap();
+
+    let mut blocks = cipher::ParBlocks::<kuznyechik::Kuznyechik>::default();
+    for block in blocks.iter_mut() {
+        block.copy_from_slice(&plaintext);
+    }
+    state.encrypt_par_blocks(&mut blocks);
+    for block in blocks.iter() {
+        assert_eq!(&ciphertext, block.as_slice());
+    }
+
+    state.decrypt_par_blocks(&mut blocks);
+    for block in blocks.iter() {
+        assert_eq!(&plaintext, block.as_slice());
+    }
+}
+
+#[test]
+fn self_test() {
+    assert_eq!(kuznyechik::self_test(), Ok(()));
+}
+
+#[test]
+fn backend() {
+    let cipher = kuznyechik::Kuznyechik::new(&Default::default());
+    let soft = if cfg!(feature = "bitsliced") { "bitsliced" } else { "soft" };
+
+    #[cfg(all(
+        any(target_arch = "x86_64", target_arch = "x86"),
+        not(feature = "force-soft"),
+    ))]
+    {
+        if std::is_x86_feature_detected!("sse2") {
+            assert_eq!(cipher.backend(), "sse2");
+            return;
+        }
+    }
+
+    assert_eq!(cipher.backend(), soft);
+}
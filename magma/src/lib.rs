@@ -43,7 +43,10 @@ use cipher::{
 use core::{convert::TryInto, marker::PhantomData};
 
 mod sboxes;
+mod self_test;
 
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 pub use sboxes::Sbox;
 
 /// Block cipher defined in GOST 28147-89 generic over S-box
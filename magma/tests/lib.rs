@@ -0,0 +1,4 @@
+#[test]
+fn self_test() {
+    assert_eq!(magma::self_test(), Ok(()));
+}
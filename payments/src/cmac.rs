@@ -0,0 +1,164 @@
+//! CMAC (NIST SP 800-38B) and CBC-MAC used by key blocks and check values.
+
+use cipher::{
+    consts::{U128, U16, U32, U64, U8},
+    generic_array::{ArrayLength, GenericArray},
+    Block, BlockEncrypt,
+};
+
+/// Block size for which CMAC is defined.
+///
+/// This trait is sealed and implemented for block sizes of 64, 128, 256,
+/// 512 and 1024 bits.
+pub trait CmacBlockSize: ArrayLength<u8> + private::Sealed {
+    /// Reduction polynomial of GF(2^n) without the leading term
+    #[doc(hidden)]
+    const RB: u32;
+}
+
+impl CmacBlockSize for U8 {
+    const RB: u32 = 0x1B;
+}
+
+impl CmacBlockSize for U16 {
+    const RB: u32 = 0x87;
+}
+
+impl CmacBlockSize for U32 {
+    const RB: u32 = 0x425;
+}
+
+impl CmacBlockSize for U64 {
+    const RB: u32 = 0x125;
+}
+
+impl CmacBlockSize for U128 {
+    const RB: u32 = 0x8_0043;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::U8 {}
+    impl Sealed for super::U16 {}
+    impl Sealed for super::U32 {}
+    impl Sealed for super::U64 {}
+    impl Sealed for super::U128 {}
+}
+
+/// Multiply by `x` in GF(2^n)
+fn dbl<N: CmacBlockSize>(block: &mut GenericArray<u8, N>) {
+    let carry = u32::from(block[0] >> 7);
+    for i in 0..block.len() - 1 {
+        block[i] = (block[i] << 1) | (block[i + 1] >> 7);
+    }
+    let last = block.len() - 1;
+    block[last] <<= 1;
+    let rb = (N::RB * carry).to_be_bytes();
+    for (b, r) in block[last - 2..].iter_mut().zip(&rb[1..]) {
+        *b ^= r;
+    }
+}
+
+/// Compute CMAC of `data`
+pub(crate) fn cmac<C>(cipher: &C, data: &[u8]) -> Block<C>
+where
+    C: BlockEncrypt,
+    C::BlockSize: CmacBlockSize,
+{
+    let mut subkey = Block::<C>::default();
+    cipher.encrypt_block(&mut subkey);
+    dbl(&mut subkey);
+
+    let bs = subkey.len();
+    let last_len = match data.len() % bs {
+        0 if !data.is_empty() => bs,
+        n => n,
+    };
+    let (head, last) = data.split_at(data.len() - last_len);
+
+    let mut state = Block::<C>::default();
+    for chunk in head.chunks_exact(bs) {
+        xor(&mut state, chunk);
+        cipher.encrypt_block(&mut state);
+    }
+
+    if last.len() != bs {
+        dbl(&mut subkey);
+        state[last.len()] ^= 0x80;
+    }
+    xor(&mut state, last);
+    xor(&mut state, &subkey);
+    cipher.encrypt_block(&mut state);
+    state
+}
+
+/// Compute CBC-MAC (ISO 9797-1 MAC algorithm 1) of `data`, which length
+/// must be a multiple of the block size
+#[cfg(feature = "alloc")]
+pub(crate) fn cbc_mac<C: BlockEncrypt>(cipher: &C, data: &[u8]) -> Block<C> {
+    let mut state = Block::<C>::default();
+    debug_assert_eq!(data.len() % state.len(), 0);
+    for chunk in data.chunks_exact(state.len()) {
+        xor(&mut state, chunk);
+        cipher.encrypt_block(&mut state);
+    }
+    state
+}
+
+fn xor(a: &mut [u8], b: &[u8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cmac;
+    use aes::Aes128;
+    use cipher::{generic_array::GenericArray, NewBlockCipher};
+    use des::TdesEde3;
+    use hex_literal::hex;
+
+    /// AES test vectors from NIST SP 800-38B, appendix D
+    #[test]
+    fn cmac_vectors() {
+        let key = hex!("2b7e151628aed2a6abf7158809cf4f3c");
+        let aes = Aes128::new(GenericArray::from_slice(&key));
+        let msg = hex!(
+            "
+            6bc1bee22e409f96e93d7e117393172a
+            ae2d8a571e03ac9c9eb76fac45af8e51
+            30c81c46a35ce411e5fbc1191a0a52ef
+            f69f2445df4f9b17ad2b417be66c3710
+            "
+        );
+        let vectors = [
+            (0, hex!("bb1d6929e95937287fa37d129b756746")),
+            (16, hex!("070a16b46b4d4144f79bdd9dd04a287c")),
+            (40, hex!("dfa66747de9ae63030ca32611497c827")),
+            (64, hex!("51f0bebf7e3b9d92fc49741779363cfe")),
+        ];
+        for (len, tag) in vectors.iter() {
+            assert_eq!(cmac(&aes, &msg[..*len])[..], tag[..]);
+        }
+
+        let key = hex!(
+            "
+            8aa83bf8cbda1062
+            0bc1bf19fbb6cd58
+            bc313d4a371ca8b5
+            "
+        );
+        let tdes = TdesEde3::new(GenericArray::from_slice(&key));
+        let vectors = [
+            (0, hex!("b7a688e122ffaf95")),
+            (16, hex!("286d394673448197")),
+            (20, hex!("743ddbe0ce2dc2ed")),
+            (32, hex!("33e6b1092400eae5")),
+        ];
+        for (len, tag) in vectors.iter() {
+            assert_eq!(cmac(&tdes, &msg[..*len])[..], tag[..]);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! AES DUKPT defined in ANSI X9.24-3:2017.
+//!
+//! Derivation keys (BDK, initial and intermediate keys) are AES keys of
+//! type `D`, while working keys may be AES or TDES keys of type `W`, e.g.
+//! `working_key::<Aes128, TdesEde3>` derives a three-key TDES working key
+//! from an AES-128 initial key.
+
+use super::InvalidCounter;
+use aes::{Aes128, Aes192, Aes256};
+use cipher::{
+    consts::U16,
+    generic_array::{typenum::Unsigned, GenericArray},
+    BlockEncrypt, NewBlockCipher,
+};
+use des::{TdesEde2, TdesEde3};
+
+/// Key of the cipher `C`.
+pub type Key<C> = GenericArray<u8, <C as NewBlockCipher>::KeySize>;
+
+/// Initial key identifier: 32-bit BDK identifier followed by 32-bit
+/// derivation identifier.
+pub type InitialKeyId = [u8; 8];
+
+/// Key Serial Number: initial key identifier followed by 32-bit
+/// transaction counter.
+pub type Ksn = [u8; 12];
+
+/// Key usage indicator of intermediate derivation keys
+const KEY_DERIVATION: u16 = 0x8000;
+
+/// Key usage indicator of the initial key
+const KEY_DERIVATION_INITIAL_KEY: u16 = 0x8001;
+
+/// Maximum number of bits set in a transaction counter used by a terminal
+const MAX_COUNTER_ONES: u32 = 16;
+
+/// Algorithm of a key which can be derived using AES DUKPT.
+pub trait KeyType: NewBlockCipher {
+    /// Algorithm indicator used in the derivation data.
+    const ALGORITHM: u16;
+}
+
+impl KeyType for TdesEde2 {
+    const ALGORITHM: u16 = 0x0000;
+}
+
+impl KeyType for TdesEde3 {
+    const ALGORITHM: u16 = 0x0001;
+}
+
+impl KeyType for Aes128 {
+    const ALGORITHM: u16 = 0x0002;
+}
+
+impl KeyType for Aes192 {
+    const ALGORITHM: u16 = 0x0003;
+}
+
+impl KeyType for Aes256 {
+    const ALGORITHM: u16 = 0x0004;
+}
+
+/// Working key usage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyUsage {
+    /// Key encryption key.
+    KeyEncryption,
+    /// PIN encryption key.
+    PinEncryption,
+    /// MAC generation key.
+    MacGeneration,
+    /// MAC verification key.
+    MacVerification,
+    /// MAC generation and verification key.
+    MacBoth,
+    /// Data encryption key.
+    DataEncrypt,
+    /// Data decryption key.
+    DataDecrypt,
+    /// Data encryption and decryption key.
+    DataBoth,
+}
+
+impl KeyUsage {
+    /// Key usage indicator used in the derivation data
+    fn indicator(self) -> u16 {
+        match self {
+            KeyUsage::KeyEncryption => 0x0002,
+            KeyUsage::PinEncryption => 0x1000,
+            KeyUsage::MacGeneration => 0x2000,
+            KeyUsage::MacVerification => 0x2001,
+            KeyUsage::MacBoth => 0x2002,
+            KeyUsage::DataEncrypt => 0x3000,
+            KeyUsage::DataDecrypt => 0x3001,
+            KeyUsage::DataBoth => 0x3002,
+        }
+    }
+}
+
+/// Derive the initial key from BDK and initial key identifier.
+pub fn initial_key<D>(bdk: &Key<D>, initial_key_id: &InitialKeyId) -> Key<D>
+where
+    D: KeyType + BlockEncrypt<BlockSize = U16>,
+{
+    derive::<D, D>(bdk, KEY_DERIVATION_INITIAL_KEY, initial_key_id)
+}
+
+/// Derive the working key for the transaction identified by `ksn` from
+/// the initial key.
+///
+/// Returns an error if the transaction counter is zero or has more than 16
+/// bits set, since such counters are never used by terminals.
+pub fn working_key<D, W>(
+    initial_key: &Key<D>,
+    ksn: &Ksn,
+    usage: KeyUsage,
+) -> Result<Key<W>, InvalidCounter>
+where
+    D: KeyType + BlockEncrypt<BlockSize = U16>,
+    W: KeyType,
+{
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&ksn[8..]);
+    let counter = u32::from_be_bytes(buf);
+    if counter == 0 || counter.count_ones() > MAX_COUNTER_ONES {
+        return Err(InvalidCounter);
+    }
+
+    let mut data = [0u8; 8];
+    data[..4].copy_from_slice(&ksn[4..8]);
+
+    let mut key = initial_key.clone();
+    let mut working_counter = 0u32;
+    for i in (0..32).rev() {
+        let bit = 1 << i;
+        if counter & bit != 0 {
+            working_counter |= bit;
+            data[4..].copy_from_slice(&working_counter.to_be_bytes());
+            key = derive::<D, D>(&key, KEY_DERIVATION, &data);
+        }
+    }
+    Ok(derive::<D, W>(&key, usage.indicator(), &data))
+}
+
+/// Derive key of type `K` using the derivation key of type `D`
+fn derive<D, K>(key: &Key<D>, usage: u16, data: &[u8; 8]) -> Key<K>
+where
+    D: KeyType + BlockEncrypt<BlockSize = U16>,
+    K: KeyType,
+{
+    let cipher = D::new(key);
+    let len = (8 * K::KeySize::USIZE) as u16;
+
+    let mut res = Key::<K>::default();
+    for (i, chunk) in res.chunks_mut(16).enumerate() {
+        let mut block = GenericArray::<u8, U16>::default();
+        block[0] = 0x01;
+        block[1] = (i + 1) as u8;
+        block[2..4].copy_from_slice(&usage.to_be_bytes());
+        block[4..6].copy_from_slice(&K::ALGORITHM.to_be_bytes());
+        block[6..8].copy_from_slice(&len.to_be_bytes());
+        block[8..].copy_from_slice(data);
+        cipher.encrypt_block(&mut block);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    res
+}
@@ -0,0 +1,41 @@
+//! Derived Unique Key Per Transaction (DUKPT) key management.
+//!
+//! A host holding the Base Derivation Key (BDK) derives the initial key
+//! loaded into a terminal from the initial key identifier, and the key
+//! used for a particular transaction from the Key Serial Number (KSN)
+//! sent by the terminal. Two variants are supported:
+//! - [`tdes`]: ANSI X9.24-1:2009 with 80-bit KSNs and two-key TDES keys;
+//! - [`aes`]: ANSI X9.24-3:2017 with 96-bit KSNs and AES derivation keys.
+//!
+//! # Example
+//! ```
+//! use hex_literal::hex;
+//! use payments::dukpt::tdes;
+//!
+//! let bdk = hex!("0123456789ABCDEFFEDCBA9876543210").into();
+//! let ksn = hex!("FFFF9876543210E00001");
+//!
+//! let ipek = tdes::initial_key(&bdk, &ksn);
+//! let key = tdes::transaction_key(&ipek, &ksn).unwrap();
+//! let pin_key = tdes::working_key(&key, tdes::Variant::Pin);
+//! assert_eq!(pin_key[..], hex!("042666B49184CF5C68DE9628D0397B36"));
+//! ```
+
+use core::fmt;
+
+pub mod aes;
+pub mod tdes;
+
+/// Error returned for KSNs with a transaction counter which is never used
+/// by a terminal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidCounter;
+
+impl fmt::Display for InvalidCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("InvalidCounter")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidCounter {}
@@ -0,0 +1,146 @@
+//! TDES DUKPT defined in ANSI X9.24-1:2009.
+
+use super::InvalidCounter;
+use cipher::{consts::U16, generic_array::GenericArray, BlockEncrypt, NewBlockCipher};
+use des::{Des, TdesEde2};
+
+/// Two-key TDES key.
+pub type Key = GenericArray<u8, U16>;
+
+/// Key Serial Number: 59-bit initial key serial number followed by 21-bit
+/// transaction counter.
+pub type Ksn = [u8; 10];
+
+/// Mask applied to the BDK and to the key in the non-reversible key
+/// generation process
+const KEY_MASK: u64 = 0xC0C0_C0C0_0000_0000;
+
+/// Number of bits in the transaction counter
+const COUNTER_BITS: u32 = 21;
+
+/// Transaction counter mask in the rightmost 64 bits of KSN
+const COUNTER_MASK: u64 = (1 << COUNTER_BITS) - 1;
+
+/// Maximum number of bits set in a transaction counter used by a terminal
+const MAX_COUNTER_ONES: u32 = 10;
+
+/// Working key variant.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Variant {
+    /// PIN encryption key.
+    Pin,
+    /// MAC key for request messages.
+    MacRequest,
+    /// MAC key for response messages.
+    MacResponse,
+    /// Data encryption key for request messages.
+    DataRequest,
+    /// Data encryption key for response messages.
+    DataResponse,
+}
+
+impl Variant {
+    /// Position of the byte inverted in both halves of the key
+    fn byte(self) -> usize {
+        match self {
+            Variant::Pin => 7,
+            Variant::MacRequest => 6,
+            Variant::MacResponse => 4,
+            Variant::DataRequest => 5,
+            Variant::DataResponse => 3,
+        }
+    }
+}
+
+/// Derive the Initial PIN Encryption Key (IPEK) from BDK and KSN.
+///
+/// The transaction counter part of `ksn` is ignored.
+pub fn initial_key(bdk: &Key, ksn: &Ksn) -> Key {
+    let mut block = [0u8; 8];
+    block.copy_from_slice(&ksn[..8]);
+    block[7] &= 0xE0;
+
+    let mut masked_bdk = *bdk;
+    xor_mask(&mut masked_bdk, KEY_MASK);
+
+    let mut key = Key::default();
+    for (half, bdk) in key.chunks_exact_mut(8).zip([*bdk, masked_bdk].iter()) {
+        half.copy_from_slice(&block);
+        TdesEde2::new(bdk).encrypt_block(GenericArray::from_mut_slice(half));
+    }
+    key
+}
+
+/// Derive the current transaction key from IPEK and KSN.
+///
+/// Returns an error if the transaction counter is zero or has more than 10
+/// bits set, since such counters are never used by terminals.
+pub fn transaction_key(ipek: &Key, ksn: &Ksn) -> Result<Key, InvalidCounter> {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&ksn[2..]);
+    let ksn_reg = u64::from_be_bytes(buf);
+
+    let counter = ksn_reg & COUNTER_MASK;
+    if counter == 0 || counter.count_ones() > MAX_COUNTER_ONES {
+        return Err(InvalidCounter);
+    }
+
+    let mut reg = ksn_reg & !COUNTER_MASK;
+    let mut key = *ipek;
+    for i in (0..COUNTER_BITS).rev() {
+        let bit = 1 << i;
+        if counter & bit != 0 {
+            reg |= bit;
+            key = non_reversible_key(&key, reg);
+        }
+    }
+    Ok(key)
+}
+
+/// Derive a working key variant from the transaction key.
+///
+/// Data encryption keys are additionally encrypted with themselves as
+/// required by X9.24-1:2009.
+pub fn working_key(key: &Key, variant: Variant) -> Key {
+    let mut res = *key;
+    res[variant.byte()] ^= 0xFF;
+    res[variant.byte() + 8] ^= 0xFF;
+
+    if let Variant::DataRequest | Variant::DataResponse = variant {
+        let cipher = TdesEde2::new(&res);
+        for half in res.chunks_exact_mut(8) {
+            cipher.encrypt_block(GenericArray::from_mut_slice(half));
+        }
+    }
+    res
+}
+
+/// Non-reversible key generation process
+fn non_reversible_key(key: &Key, reg: u64) -> Key {
+    let encrypt_half = |key: &Key| {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&key[8..]);
+        let right = u64::from_be_bytes(buf);
+
+        let mut block = (reg ^ right).to_be_bytes();
+        Des::new(GenericArray::from_slice(&key[..8]))
+            .encrypt_block(GenericArray::from_mut_slice(&mut block));
+        (u64::from_be_bytes(block) ^ right).to_be_bytes()
+    };
+
+    let mut masked_key = *key;
+    xor_mask(&mut masked_key, KEY_MASK);
+
+    let mut res = Key::default();
+    res[..8].copy_from_slice(&encrypt_half(&masked_key));
+    res[8..].copy_from_slice(&encrypt_half(key));
+    res
+}
+
+/// XOR both halves of `key` with `mask`
+fn xor_mask(key: &mut Key, mask: u64) {
+    let mask = mask.to_be_bytes();
+    for (k, m) in key.iter_mut().zip(mask.iter().cycle()) {
+        *k ^= m;
+    }
+}
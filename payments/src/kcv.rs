@@ -0,0 +1,161 @@
+//! Key check values (KCV) used to verify keys during key ceremonies and
+//! key imports.
+//!
+//! Two methods defined in ANSI X9.24-1:2017 are supported:
+//! - [`Method::Legacy`]: the zero block encrypted with the key, usually
+//!   truncated to 3 bytes (6 hex digits);
+//! - [`Method::Cmac`]: CMAC of the zero block, usually truncated to 5 bytes
+//!   (10 hex digits), recommended for AES keys.
+//!
+//! The [`CheckValue`] trait is implemented for every block cipher. The
+//! legacy check value is available for any block size, while the CMAC
+//! method requires a block size of 64, 128, 256, 512 or 1024 bits, i.e. the
+//! sizes for which CMAC is defined (see [`CmacBlockSize`]).
+//!
+//! # Example
+//! ```
+//! use hex_literal::hex;
+//! use payments::{des::TdesEde2, kcv::{CheckValue, Method}};
+//!
+//! let key = hex!("0123456789ABCDEFFEDCBA9876543210");
+//! let tdes = TdesEde2::new_from_slice_with_kcv(&key, Method::Legacy, &hex!("08D7B4")).unwrap();
+//! assert_eq!(tdes.kcv(Method::Cmac)[..5], hex!("0A82458664"));
+//!
+//! // a key which does not match the check value is refused
+//! let res = TdesEde2::new_from_slice_with_kcv(&key, Method::Legacy, &hex!("08D7B5"));
+//! assert!(res.is_err());
+//! ```
+
+use crate::cmac::cmac;
+pub use crate::cmac::CmacBlockSize;
+use cipher::{
+    errors::InvalidLength, generic_array::GenericArray, Block, BlockEncrypt, NewBlockCipher,
+};
+use core::fmt;
+
+/// Length of the legacy check value commonly used in practice
+pub const LEGACY_LEN: usize = 3;
+
+/// Length of the CMAC check value commonly used in practice
+pub const CMAC_LEN: usize = 5;
+
+/// Key check value computation method.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    /// Encryption of the zero block.
+    Legacy,
+    /// CMAC of the zero block.
+    Cmac,
+}
+
+/// Key check value error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Key has invalid length for the cipher.
+    InvalidKeyLength,
+    /// Expected check value is empty or longer than the cipher block.
+    InvalidKcvLength,
+    /// Check value of the key does not match the expected value.
+    Mismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Error::InvalidKeyLength => "invalid key length",
+            Error::InvalidKcvLength => "invalid key check value length",
+            Error::Mismatch => "key check value mismatch",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl From<InvalidLength> for Error {
+    fn from(_: InvalidLength) -> Self {
+        Error::InvalidKeyLength
+    }
+}
+
+/// Key check value computation and verification.
+///
+/// Methods taking a [`Method`] require a block size supported by CMAC, the
+/// `legacy_*` methods are available for any block size.
+pub trait CheckValue: NewBlockCipher + BlockEncrypt + Sized {
+    /// Compute untruncated legacy check value of the cipher key, i.e. the
+    /// zero block encrypted with the key.
+    fn legacy_kcv(&self) -> Block<Self> {
+        let mut block = Block::<Self>::default();
+        self.encrypt_block(&mut block);
+        block
+    }
+
+    /// Check that the cipher key matches `expected` legacy check value,
+    /// which may be truncated to any length up to the block size.
+    fn verify_legacy_kcv(&self, expected: &[u8]) -> Result<(), Error> {
+        compare(&self.legacy_kcv(), expected)
+    }
+
+    /// Compute untruncated check value of the cipher key.
+    ///
+    /// The result is usually truncated to [`LEGACY_LEN`] or [`CMAC_LEN`]
+    /// bytes depending on the method.
+    fn kcv(&self, method: Method) -> Block<Self>
+    where
+        Self::BlockSize: CmacBlockSize,
+    {
+        match method {
+            Method::Legacy => self.legacy_kcv(),
+            Method::Cmac => cmac(self, &Block::<Self>::default()),
+        }
+    }
+
+    /// Check that the cipher key matches `expected` check value, which may
+    /// be truncated to any length up to the block size.
+    fn verify_kcv(&self, method: Method, expected: &[u8]) -> Result<(), Error>
+    where
+        Self::BlockSize: CmacBlockSize,
+    {
+        compare(&self.kcv(method), expected)
+    }
+
+    /// Create cipher instance, refusing the key if it does not match
+    /// `expected` check value.
+    fn new_with_kcv(
+        key: &GenericArray<u8, Self::KeySize>,
+        method: Method,
+        expected: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self::BlockSize: CmacBlockSize,
+    {
+        let cipher = Self::new(key);
+        cipher.verify_kcv(method, expected)?;
+        Ok(cipher)
+    }
+
+    /// Create cipher instance from a variable length key, refusing the key
+    /// if it does not match `expected` check value.
+    fn new_from_slice_with_kcv(key: &[u8], method: Method, expected: &[u8]) -> Result<Self, Error>
+    where
+        Self::BlockSize: CmacBlockSize,
+    {
+        let cipher = Self::new_from_slice(key)?;
+        cipher.verify_kcv(method, expected)?;
+        Ok(cipher)
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> CheckValue for C {}
+
+/// Compare check value with `expected` truncated check value
+fn compare(kcv: &[u8], expected: &[u8]) -> Result<(), Error> {
+    if expected.is_empty() || expected.len() > kcv.len() {
+        return Err(Error::InvalidKcvLength);
+    }
+    if kcv[..expected.len()] != *expected {
+        return Err(Error::Mismatch);
+    }
+    Ok(())
+}
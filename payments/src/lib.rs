@@ -0,0 +1,42 @@
+//! Cryptographic algorithms used by the payment card industry, implemented
+//! on top of the block ciphers from this repository.
+//!
+//! # Supported algorithms
+//! - [`dukpt`]: Derived Unique Key Per Transaction key management defined
+//!   in ANSI X9.24-1:2009 (TDES) and ANSI X9.24-3:2017 (AES).
+//! - [`kcv`]: key check values computed with the legacy and CMAC methods
+//!   defined in ANSI X9.24-1:2017.
+//! - [`pin_block`]: PIN block formats 0 to 4 defined in ISO 9564-1:2017.
+//! - [`tr31`]: key blocks defined in ASC X9 TR-31:2018 and ANSI X9.143
+//!   (requires `alloc`).
+//!
+//! # Crate features
+//! - `alloc` (default): enables the [`tr31`] module.
+//! - `std`: implements `std::error::Error` for error types.
+#![no_std]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg",
+    html_favicon_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg"
+)]
+#![deny(unsafe_code)]
+#![warn(missing_docs, rust_2018_idioms)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub use aes;
+#[cfg(feature = "alloc")]
+pub use block_modes;
+pub use cipher;
+pub use des;
+pub use rand_core;
+
+pub mod dukpt;
+pub mod kcv;
+pub mod pin_block;
+#[cfg(feature = "alloc")]
+pub mod tr31;
+
+mod cmac;
@@ -0,0 +1,443 @@
+//! PIN block formats defined in ISO 9564-1:2017.
+//!
+//! Formats 0 to 3 produce 8-byte blocks which can be encrypted with TDES,
+//! while format 4 produces 16-byte blocks and can only be used with AES.
+//! Formats 0, 3 and 4 bind the PIN to the Primary Account Number (PAN).
+//! Formats 1, 3 and 4 use random padding and take an RNG, formats 0 and 2
+//! are deterministic.
+//!
+//! # Example
+//! ```
+//! use hex_literal::hex;
+//! use payments::pin_block::{self, Format, Pan, Pin};
+//!
+//! let pin = Pin::new("1234").unwrap();
+//! let pan = Pan::new("43219876543210987").unwrap();
+//!
+//! // format 0 does not use random padding, so no RNG is needed
+//! let block = Format::Iso0.build(&pin, Some(&pan)).unwrap();
+//! assert_eq!(block, hex!("0412AC89ABCDEF67"));
+//!
+//! let (format, res) = pin_block::parse(&block, Some(&pan)).unwrap();
+//! assert_eq!(format, Format::Iso0);
+//! assert_eq!(res.as_str(), "1234");
+//! ```
+
+use cipher::{
+    consts::{U16, U8},
+    generic_array::GenericArray,
+    BlockDecrypt, BlockEncrypt,
+};
+use core::{fmt, str};
+use rand_core::{CryptoRng, RngCore};
+
+/// Minimum number of PIN digits
+const MIN_PIN_LEN: usize = 4;
+
+/// Maximum number of PIN digits
+const MAX_PIN_LEN: usize = 12;
+
+/// Maximum number of PAN digits
+const MAX_PAN_LEN: usize = 19;
+
+/// PIN block error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// PIN is not a string of 4 to 12 decimal digits.
+    InvalidPin,
+    /// PAN is not a string of 1 to 19 decimal digits.
+    InvalidPan,
+    /// PAN is required by the PIN block format, but was not provided.
+    MissingPan,
+    /// PIN block format uses random padding, but no RNG was provided.
+    MissingRng,
+    /// Control field of the PIN block contains an unsupported format.
+    UnsupportedFormat,
+    /// PIN length field is out of range.
+    InvalidPinLength,
+    /// PIN field contains a non-decimal digit.
+    InvalidPinDigit,
+    /// Padding of the PIN field is invalid for the format.
+    InvalidFill,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Error::InvalidPin => "invalid PIN",
+            Error::InvalidPan => "invalid PAN",
+            Error::MissingPan => "PAN is required by the PIN block format",
+            Error::MissingRng => "RNG is required by the PIN block format",
+            Error::UnsupportedFormat => "unsupported PIN block format",
+            Error::InvalidPinLength => "invalid PIN length field",
+            Error::InvalidPinDigit => "invalid PIN digit",
+            Error::InvalidFill => "invalid PIN block padding",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Personal Identification Number: 4 to 12 decimal digits.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Pin {
+    digits: [u8; MAX_PIN_LEN],
+    len: usize,
+}
+
+impl Pin {
+    /// Create PIN from a string of decimal digits.
+    pub fn new(pin: &str) -> Result<Self, Error> {
+        let pin = pin.as_bytes();
+        if !(MIN_PIN_LEN..=MAX_PIN_LEN).contains(&pin.len()) || !is_decimal(pin) {
+            return Err(Error::InvalidPin);
+        }
+        let mut digits = [0u8; MAX_PIN_LEN];
+        digits[..pin.len()].copy_from_slice(pin);
+        Ok(Self {
+            digits,
+            len: pin.len(),
+        })
+    }
+
+    /// PIN digits.
+    pub fn as_str(&self) -> &str {
+        // only ASCII digits are stored
+        str::from_utf8(&self.digits[..self.len]).unwrap()
+    }
+
+    /// Write PIN field nibbles: control, length and digits
+    fn write_nibbles(&self, control: u8, nibbles: &mut [u8]) {
+        nibbles[0] = control;
+        nibbles[1] = self.len as u8;
+        for (n, d) in nibbles[2..].iter_mut().zip(&self.digits[..self.len]) {
+            *n = d - b'0';
+        }
+    }
+
+    /// Read PIN from PIN field nibbles
+    fn read_nibbles(nibbles: &[u8]) -> Result<Self, Error> {
+        let len = nibbles[1] as usize;
+        if !(MIN_PIN_LEN..=MAX_PIN_LEN).contains(&len) {
+            return Err(Error::InvalidPinLength);
+        }
+        let mut digits = [0u8; MAX_PIN_LEN];
+        for (d, &n) in digits.iter_mut().zip(&nibbles[2..2 + len]) {
+            if n > 9 {
+                return Err(Error::InvalidPinDigit);
+            }
+            *d = b'0' + n;
+        }
+        Ok(Self { digits, len })
+    }
+}
+
+impl fmt::Debug for Pin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("Pin { ... }")
+    }
+}
+
+/// Primary Account Number: 1 to 19 decimal digits including the check
+/// digit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pan {
+    digits: [u8; MAX_PAN_LEN],
+    len: usize,
+}
+
+impl Pan {
+    /// Create PAN from a string of decimal digits.
+    pub fn new(pan: &str) -> Result<Self, Error> {
+        let pan = pan.as_bytes();
+        if pan.is_empty() || pan.len() > MAX_PAN_LEN || !is_decimal(pan) {
+            return Err(Error::InvalidPan);
+        }
+        let mut digits = [0u8; MAX_PAN_LEN];
+        for (d, c) in digits.iter_mut().zip(pan) {
+            *d = c - b'0';
+        }
+        Ok(Self {
+            digits,
+            len: pan.len(),
+        })
+    }
+
+    /// PAN field used by formats 0 and 3: the rightmost 12 digits excluding
+    /// the check digit, right-justified
+    fn field(&self) -> [u8; 8] {
+        let digits = &self.digits[..self.len - 1];
+        let digits = &digits[digits.len().saturating_sub(12)..];
+        let mut nibbles = [0u8; 16];
+        nibbles[16 - digits.len()..].copy_from_slice(digits);
+        let mut field = [0u8; 8];
+        pack(&nibbles, &mut field);
+        field
+    }
+
+    /// PAN field used by format 4: the number of digits over 12 followed by
+    /// the PAN left-justified and padded with zeros (PANs shorter than 12
+    /// digits are left-padded to 12 digits)
+    fn field4(&self) -> [u8; 16] {
+        let mut nibbles = [0u8; 32];
+        if self.len < 12 {
+            nibbles[13 - self.len..13].copy_from_slice(&self.digits[..self.len]);
+        } else {
+            nibbles[0] = (self.len - 12) as u8;
+            nibbles[1..1 + self.len].copy_from_slice(&self.digits[..self.len]);
+        }
+        let mut field = [0u8; 16];
+        pack(&nibbles, &mut field);
+        field
+    }
+}
+
+/// PIN block format with 8-byte blocks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Format 0 (ISO-0): `F` padding, bound to PAN.
+    Iso0,
+    /// Format 1 (ISO-1): random padding, not bound to PAN.
+    Iso1,
+    /// Format 2 (ISO-2): `F` padding, not bound to PAN, used only with
+    /// integrated circuit cards.
+    Iso2,
+    /// Format 3 (ISO-3): random padding with digits `A` to `F`, bound to
+    /// PAN.
+    Iso3,
+}
+
+impl Format {
+    /// Build a plaintext PIN block of format 0 or 2.
+    ///
+    /// `pan` is required by format 0 and ignored by format 2. Formats 1 and
+    /// 3 use random padding and return [`Error::MissingRng`], use
+    /// [`Format::build_with_rng`] for them.
+    pub fn build(self, pin: &Pin, pan: Option<&Pan>) -> Result<[u8; 8], Error> {
+        if self.uses_rng() {
+            return Err(Error::MissingRng);
+        }
+        self.build_with_fill(pin, pan, || 0xF)
+    }
+
+    /// Build a plaintext PIN block of any format.
+    ///
+    /// `pan` is required by formats 0 and 3 and ignored by other formats,
+    /// `rng` is used by formats 1 and 3 for padding.
+    pub fn build_with_rng<R: CryptoRng + RngCore>(
+        self,
+        pin: &Pin,
+        pan: Option<&Pan>,
+        rng: &mut R,
+    ) -> Result<[u8; 8], Error> {
+        self.build_with_fill(pin, pan, || match self {
+            Format::Iso0 | Format::Iso2 => 0xF,
+            Format::Iso1 => (rng.next_u32() & 0xF) as u8,
+            Format::Iso3 => random_fill3(rng),
+        })
+    }
+
+    /// Build a plaintext PIN block with padding nibbles produced by `fill`
+    fn build_with_fill(
+        self,
+        pin: &Pin,
+        pan: Option<&Pan>,
+        mut fill: impl FnMut() -> u8,
+    ) -> Result<[u8; 8], Error> {
+        let mut nibbles = [0u8; 16];
+        pin.write_nibbles(self.control(), &mut nibbles);
+        for n in nibbles[2 + pin.len..].iter_mut() {
+            *n = fill();
+        }
+        let mut block = [0u8; 8];
+        pack(&nibbles, &mut block);
+        if self.uses_pan() {
+            xor(&mut block, &pan.ok_or(Error::MissingPan)?.field());
+        }
+        Ok(block)
+    }
+
+    fn control(self) -> u8 {
+        match self {
+            Format::Iso0 => 0,
+            Format::Iso1 => 1,
+            Format::Iso2 => 2,
+            Format::Iso3 => 3,
+        }
+    }
+
+    fn uses_pan(self) -> bool {
+        matches!(self, Format::Iso0 | Format::Iso3)
+    }
+
+    fn uses_rng(self) -> bool {
+        matches!(self, Format::Iso1 | Format::Iso3)
+    }
+}
+
+/// Parse a plaintext 8-byte PIN block, detecting its format.
+///
+/// `pan` is required for formats 0 and 3.
+pub fn parse(block: &[u8; 8], pan: Option<&Pan>) -> Result<(Format, Pin), Error> {
+    let format = match block[0] >> 4 {
+        0 => Format::Iso0,
+        1 => Format::Iso1,
+        2 => Format::Iso2,
+        3 => Format::Iso3,
+        _ => return Err(Error::UnsupportedFormat),
+    };
+    let mut block = *block;
+    if format.uses_pan() {
+        xor(&mut block, &pan.ok_or(Error::MissingPan)?.field());
+    }
+
+    let mut nibbles = [0u8; 16];
+    unpack(&block, &mut nibbles);
+    let pin = Pin::read_nibbles(&nibbles)?;
+    let fill = &nibbles[2 + pin.len..];
+    let valid_fill = match format {
+        Format::Iso0 | Format::Iso2 => fill.iter().all(|&n| n == 0xF),
+        Format::Iso1 => true,
+        Format::Iso3 => fill.iter().all(|&n| n >= 0xA),
+    };
+    if !valid_fill {
+        return Err(Error::InvalidFill);
+    }
+    Ok((format, pin))
+}
+
+/// Build and encrypt an 8-byte PIN block of format 0 or 2, e.g. using
+/// `TdesEde3` or `TdesEde2`.
+///
+/// Formats 1 and 3 return [`Error::MissingRng`], use [`encrypt_with_rng`]
+/// for them.
+pub fn encrypt<C>(
+    cipher: &C,
+    format: Format,
+    pin: &Pin,
+    pan: Option<&Pan>,
+) -> Result<[u8; 8], Error>
+where
+    C: BlockEncrypt<BlockSize = U8>,
+{
+    let mut block = format.build(pin, pan)?;
+    cipher.encrypt_block(GenericArray::from_mut_slice(&mut block));
+    Ok(block)
+}
+
+/// Build and encrypt an 8-byte PIN block of any format, e.g. using
+/// `TdesEde3` or `TdesEde2`.
+pub fn encrypt_with_rng<C, R>(
+    cipher: &C,
+    format: Format,
+    pin: &Pin,
+    pan: Option<&Pan>,
+    rng: &mut R,
+) -> Result<[u8; 8], Error>
+where
+    C: BlockEncrypt<BlockSize = U8>,
+    R: CryptoRng + RngCore,
+{
+    let mut block = format.build_with_rng(pin, pan, rng)?;
+    cipher.encrypt_block(GenericArray::from_mut_slice(&mut block));
+    Ok(block)
+}
+
+/// Decrypt and parse an 8-byte PIN block.
+pub fn decrypt<C>(cipher: &C, block: &[u8; 8], pan: Option<&Pan>) -> Result<(Format, Pin), Error>
+where
+    C: BlockDecrypt<BlockSize = U8>,
+{
+    let mut block = *block;
+    cipher.decrypt_block(GenericArray::from_mut_slice(&mut block));
+    parse(&block, pan)
+}
+
+/// Build a plaintext format 4 PIN field: `4`, PIN length, PIN digits padded
+/// with `A`, followed by 8 random bytes.
+pub fn build_format4<R: CryptoRng + RngCore>(pin: &Pin, rng: &mut R) -> [u8; 16] {
+    let mut nibbles = [0xAu8; 16];
+    pin.write_nibbles(4, &mut nibbles);
+    let mut field = [0u8; 16];
+    pack(&nibbles, &mut field[..8]);
+    rng.fill_bytes(&mut field[8..]);
+    field
+}
+
+/// Parse a plaintext format 4 PIN field.
+pub fn parse_format4(field: &[u8; 16]) -> Result<Pin, Error> {
+    let mut nibbles = [0u8; 16];
+    unpack(&field[..8], &mut nibbles);
+    if nibbles[0] != 4 {
+        return Err(Error::UnsupportedFormat);
+    }
+    let pin = Pin::read_nibbles(&nibbles)?;
+    if nibbles[2 + pin.len..].iter().any(|&n| n != 0xA) {
+        return Err(Error::InvalidFill);
+    }
+    Ok(pin)
+}
+
+/// Build and encrypt a format 4 PIN block, binding it to `pan`.
+///
+/// The PIN field is encrypted, XORed with the PAN field and encrypted
+/// again.
+pub fn encrypt_format4<C, R>(cipher: &C, pin: &Pin, pan: &Pan, rng: &mut R) -> [u8; 16]
+where
+    C: BlockEncrypt<BlockSize = U16>,
+    R: CryptoRng + RngCore,
+{
+    let mut block = build_format4(pin, rng);
+    let block_ref = GenericArray::from_mut_slice(&mut block);
+    cipher.encrypt_block(block_ref);
+    xor(block_ref, &pan.field4());
+    cipher.encrypt_block(block_ref);
+    block
+}
+
+/// Decrypt a format 4 PIN block bound to `pan` and parse it.
+pub fn decrypt_format4<C>(cipher: &C, block: &[u8; 16], pan: &Pan) -> Result<Pin, Error>
+where
+    C: BlockDecrypt<BlockSize = U16>,
+{
+    let mut block = *block;
+    let block_ref = GenericArray::from_mut_slice(&mut block);
+    cipher.decrypt_block(block_ref);
+    xor(block_ref, &pan.field4());
+    cipher.decrypt_block(block_ref);
+    parse_format4(&block)
+}
+
+fn is_decimal(s: &[u8]) -> bool {
+    s.iter().all(u8::is_ascii_digit)
+}
+
+/// Random nibble in the range `A..=F`
+fn random_fill3<R: RngCore>(rng: &mut R) -> u8 {
+    loop {
+        let n = (rng.next_u32() & 7) as u8;
+        if n < 6 {
+            return 0xA + n;
+        }
+    }
+}
+
+fn xor(a: &mut [u8], b: &[u8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= b;
+    }
+}
+
+fn pack(nibbles: &[u8], bytes: &mut [u8]) {
+    for (b, n) in bytes.iter_mut().zip(nibbles.chunks_exact(2)) {
+        *b = (n[0] << 4) | n[1];
+    }
+}
+
+fn unpack(bytes: &[u8], nibbles: &mut [u8]) {
+    for (n, b) in nibbles.chunks_exact_mut(2).zip(bytes) {
+        n[0] = b >> 4;
+        n[1] = b & 0xF;
+    }
+}
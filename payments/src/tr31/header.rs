@@ -0,0 +1,448 @@
+//! Key block header.
+
+use super::Error;
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+/// Length of the fixed part of the header
+pub(super) const FIXED_LEN: usize = 16;
+
+/// Identifier of the padding optional block
+const PADDING_BLOCK: [u8; 2] = *b"PB";
+
+/// Key block version: defines the key block protection method.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Version {
+    /// TDES key variant binding method (deprecated).
+    A,
+    /// TDES key derivation binding method.
+    B,
+    /// TDES key variant binding method.
+    C,
+    /// AES key derivation binding method.
+    D,
+}
+
+impl Version {
+    fn from_byte(b: u8) -> Result<Self, Error> {
+        Ok(match b {
+            b'A' => Version::A,
+            b'B' => Version::B,
+            b'C' => Version::C,
+            b'D' => Version::D,
+            _ => return Err(Error::UnsupportedVersion),
+        })
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Version::A => b'A',
+            Version::B => b'B',
+            Version::C => b'C',
+            Version::D => b'D',
+        }
+    }
+
+    /// Block size of the key block protection cipher
+    pub(super) fn block_size(self) -> usize {
+        match self {
+            Version::D => 16,
+            _ => 8,
+        }
+    }
+}
+
+/// Intended function of the protected key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct KeyUsage(pub [u8; 2]);
+
+impl KeyUsage {
+    /// BDK base derivation key.
+    pub const BDK: Self = Self(*b"B0");
+    /// DUKPT initial key.
+    pub const DUKPT_INITIAL_KEY: Self = Self(*b"B1");
+    /// Base key variant key.
+    pub const BASE_KEY_VARIANT: Self = Self(*b"B2");
+    /// CVK card verification key.
+    pub const CVK: Self = Self(*b"C0");
+    /// Symmetric key for data encryption.
+    pub const DATA_ENCRYPTION: Self = Self(*b"D0");
+    /// Initialization vector.
+    pub const IV: Self = Self(*b"I0");
+    /// Key encryption or wrapping key.
+    pub const KEY_ENCRYPTION: Self = Self(*b"K0");
+    /// TR-31 key block protection key.
+    pub const KEY_BLOCK_PROTECTION: Self = Self(*b"K1");
+    /// ISO 16609 MAC algorithm 1 (TDES) key.
+    pub const MAC_ISO_16609: Self = Self(*b"M0");
+    /// ISO 9797-1 MAC algorithm 1 key.
+    pub const MAC_ISO_9797_1: Self = Self(*b"M1");
+    /// ISO 9797-1 MAC algorithm 3 key.
+    pub const MAC_ISO_9797_3: Self = Self(*b"M3");
+    /// ISO 9797-1 MAC algorithm 5 (CMAC) key.
+    pub const MAC_CMAC: Self = Self(*b"M6");
+    /// PIN encryption key.
+    pub const PIN_ENCRYPTION: Self = Self(*b"P0");
+    /// PIN verification key, IBM 3624 method.
+    pub const PIN_VERIFICATION_IBM_3624: Self = Self(*b"V1");
+    /// PIN verification key, VISA PVV method.
+    pub const PIN_VERIFICATION_VISA_PVV: Self = Self(*b"V2");
+}
+
+/// Algorithm of the protected key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Algorithm {
+    /// AES.
+    Aes,
+    /// DEA.
+    Des,
+    /// Elliptic curve.
+    Ec,
+    /// HMAC.
+    Hmac,
+    /// RSA.
+    Rsa,
+    /// DSA.
+    Dsa,
+    /// TDES.
+    Tdes,
+}
+
+impl Algorithm {
+    fn from_byte(b: u8) -> Result<Self, Error> {
+        Ok(match b {
+            b'A' => Algorithm::Aes,
+            b'D' => Algorithm::Des,
+            b'E' => Algorithm::Ec,
+            b'H' => Algorithm::Hmac,
+            b'R' => Algorithm::Rsa,
+            b'S' => Algorithm::Dsa,
+            b'T' => Algorithm::Tdes,
+            _ => return Err(Error::InvalidHeader),
+        })
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Algorithm::Aes => b'A',
+            Algorithm::Des => b'D',
+            Algorithm::Ec => b'E',
+            Algorithm::Hmac => b'H',
+            Algorithm::Rsa => b'R',
+            Algorithm::Dsa => b'S',
+            Algorithm::Tdes => b'T',
+        }
+    }
+}
+
+/// Operations the protected key can perform.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModeOfUse {
+    /// Encrypt, wrap and decrypt, unwrap.
+    Both,
+    /// Generate and verify.
+    GenerateVerify,
+    /// Decrypt and unwrap only.
+    DecryptOnly,
+    /// Encrypt and wrap only.
+    EncryptOnly,
+    /// Generate only.
+    GenerateOnly,
+    /// No special restrictions other than the key usage.
+    NoRestrictions,
+    /// Signature only.
+    SignatureOnly,
+    /// Signature and decrypt.
+    SignatureDecrypt,
+    /// Verify only.
+    VerifyOnly,
+    /// Key used to derive other keys.
+    Derivation,
+    /// Key used to create key variants.
+    Variant,
+}
+
+impl ModeOfUse {
+    fn from_byte(b: u8) -> Result<Self, Error> {
+        Ok(match b {
+            b'B' => ModeOfUse::Both,
+            b'C' => ModeOfUse::GenerateVerify,
+            b'D' => ModeOfUse::DecryptOnly,
+            b'E' => ModeOfUse::EncryptOnly,
+            b'G' => ModeOfUse::GenerateOnly,
+            b'N' => ModeOfUse::NoRestrictions,
+            b'S' => ModeOfUse::SignatureOnly,
+            b'T' => ModeOfUse::SignatureDecrypt,
+            b'V' => ModeOfUse::VerifyOnly,
+            b'X' => ModeOfUse::Derivation,
+            b'Y' => ModeOfUse::Variant,
+            _ => return Err(Error::InvalidHeader),
+        })
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            ModeOfUse::Both => b'B',
+            ModeOfUse::GenerateVerify => b'C',
+            ModeOfUse::DecryptOnly => b'D',
+            ModeOfUse::EncryptOnly => b'E',
+            ModeOfUse::GenerateOnly => b'G',
+            ModeOfUse::NoRestrictions => b'N',
+            ModeOfUse::SignatureOnly => b'S',
+            ModeOfUse::SignatureDecrypt => b'T',
+            ModeOfUse::VerifyOnly => b'V',
+            ModeOfUse::Derivation => b'X',
+            ModeOfUse::Variant => b'Y',
+        }
+    }
+}
+
+/// Rules for exporting the protected key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Exportability {
+    /// Exportable under a key encryption key in a form meeting X9.24.
+    Exportable,
+    /// Non-exportable.
+    NonExportable,
+    /// Sensitive: exportable under a key encryption key in a form not
+    /// necessarily meeting X9.24.
+    Sensitive,
+}
+
+impl Exportability {
+    fn from_byte(b: u8) -> Result<Self, Error> {
+        Ok(match b {
+            b'E' => Exportability::Exportable,
+            b'N' => Exportability::NonExportable,
+            b'S' => Exportability::Sensitive,
+            _ => return Err(Error::InvalidHeader),
+        })
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Exportability::Exportable => b'E',
+            Exportability::NonExportable => b'N',
+            Exportability::Sensitive => b'S',
+        }
+    }
+}
+
+/// Optional header block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptionalBlock {
+    /// Two-character block identifier, e.g. `KS` for a key set identifier.
+    pub id: [u8; 2],
+    /// Block data: printable ASCII characters.
+    pub data: String,
+}
+
+/// Key block header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Header {
+    /// Key block version.
+    pub version: Version,
+    /// Key usage.
+    pub key_usage: KeyUsage,
+    /// Key algorithm.
+    pub algorithm: Algorithm,
+    /// Key mode of use.
+    pub mode_of_use: ModeOfUse,
+    /// Key version number: two alphanumeric characters, `00` if key
+    /// versioning is not used.
+    pub key_version: [u8; 2],
+    /// Key exportability.
+    pub exportability: Exportability,
+    /// Optional blocks.
+    ///
+    /// The padding block `PB` is added automatically when the key block
+    /// is created, any padding block in this list is ignored.
+    pub optional_blocks: Vec<OptionalBlock>,
+}
+
+impl Header {
+    /// Create header without key version and optional blocks.
+    pub fn new(
+        version: Version,
+        key_usage: KeyUsage,
+        algorithm: Algorithm,
+        mode_of_use: ModeOfUse,
+        exportability: Exportability,
+    ) -> Self {
+        Self {
+            version,
+            key_usage,
+            algorithm,
+            mode_of_use,
+            key_version: *b"00",
+            exportability,
+            optional_blocks: Vec::new(),
+        }
+    }
+
+    /// Parse header from the beginning of `key_block`, returns the header
+    /// and its length
+    pub(super) fn parse(key_block: &[u8]) -> Result<(Self, usize), Error> {
+        if key_block.len() < FIXED_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let fixed = &key_block[..FIXED_LEN];
+        if !fixed.iter().all(u8::is_ascii_alphanumeric) {
+            return Err(Error::InvalidHeader);
+        }
+        let version = Version::from_byte(fixed[0])?;
+        let key_usage = KeyUsage([fixed[5], fixed[6]]);
+        let algorithm = Algorithm::from_byte(fixed[7])?;
+        let mode_of_use = ModeOfUse::from_byte(fixed[8])?;
+        let key_version = [fixed[9], fixed[10]];
+        let exportability = Exportability::from_byte(fixed[11])?;
+        let count = decimal(&fixed[12..14]).ok_or(Error::InvalidHeader)?;
+
+        let mut pos = FIXED_LEN;
+        let mut optional_blocks = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (block, len) = parse_optional_block(&key_block[pos..])?;
+            optional_blocks.push(block);
+            pos += len;
+        }
+
+        let header = Self {
+            version,
+            key_usage,
+            algorithm,
+            mode_of_use,
+            key_version,
+            exportability,
+            optional_blocks,
+        };
+        Ok((header, pos))
+    }
+
+    /// Encode header padded to a multiple of the version block size, the
+    /// key block length field is left zero
+    pub(super) fn encode(&self) -> Result<String, Error> {
+        if !self.key_usage.0.iter().all(u8::is_ascii_alphanumeric)
+            || !self.key_version.iter().all(u8::is_ascii_alphanumeric)
+        {
+            return Err(Error::InvalidHeader);
+        }
+        let blocks: Vec<_> = self
+            .optional_blocks
+            .iter()
+            .filter(|b| b.id != PADDING_BLOCK)
+            .collect();
+
+        let mut opt = String::new();
+        for block in blocks.iter() {
+            encode_optional_block(&mut opt, &block.id, &block.data)?;
+        }
+
+        let mut count = blocks.len();
+        let bs = self.version.block_size();
+        let rem = (FIXED_LEN + opt.len()) % bs;
+        if count != 0 && rem != 0 {
+            // padding block holds at least its identifier and length
+            let mut pad_len = bs - rem;
+            if pad_len < 4 {
+                pad_len += bs;
+            }
+            let data = "0".repeat(pad_len - 4);
+            encode_optional_block(&mut opt, &PADDING_BLOCK, &data)?;
+            count += 1;
+        }
+        if count > 99 {
+            return Err(Error::InvalidOptionalBlock);
+        }
+
+        let mut res = String::with_capacity(FIXED_LEN + opt.len());
+        res.push(self.version.to_byte() as char);
+        res.push_str("0000");
+        res.push(self.key_usage.0[0] as char);
+        res.push(self.key_usage.0[1] as char);
+        res.push(self.algorithm.to_byte() as char);
+        res.push(self.mode_of_use.to_byte() as char);
+        res.push(self.key_version[0] as char);
+        res.push(self.key_version[1] as char);
+        res.push(self.exportability.to_byte() as char);
+        write!(res, "{:02}00", count).unwrap();
+        res.push_str(&opt);
+        Ok(res)
+    }
+}
+
+/// Parse optional block, returns the block and its length
+fn parse_optional_block(data: &[u8]) -> Result<(OptionalBlock, usize), Error> {
+    if data.len() < 4 {
+        return Err(Error::InvalidOptionalBlock);
+    }
+    let id = [data[0], data[1]];
+    let short_len = hex(&data[2..4]).ok_or(Error::InvalidOptionalBlock)?;
+    let (len, data_start) = if short_len != 0 {
+        (short_len, 4)
+    } else {
+        // extended length: length of the length field followed by the length
+        let len_len = data
+            .get(4..6)
+            .and_then(hex)
+            .filter(|len_len| (1..=4).contains(len_len))
+            .ok_or(Error::InvalidOptionalBlock)?;
+        let len = data
+            .get(6..6 + len_len)
+            .and_then(hex)
+            .ok_or(Error::InvalidOptionalBlock)?;
+        (len, 6 + len_len)
+    };
+    if len < data_start || len > data.len() {
+        return Err(Error::InvalidOptionalBlock);
+    }
+    let block_data = &data[data_start..len];
+    if !id.iter().all(u8::is_ascii_alphanumeric) || !is_printable(block_data) {
+        return Err(Error::InvalidOptionalBlock);
+    }
+    let block = OptionalBlock {
+        id,
+        // checked above that all bytes are ASCII
+        data: core::str::from_utf8(block_data).unwrap().into(),
+    };
+    Ok((block, len))
+}
+
+/// Encode optional block, the extended length is used only if needed
+fn encode_optional_block(res: &mut String, id: &[u8; 2], data: &str) -> Result<(), Error> {
+    if !id.iter().all(u8::is_ascii_alphanumeric) || !is_printable(data.as_bytes()) {
+        return Err(Error::InvalidOptionalBlock);
+    }
+    res.push(id[0] as char);
+    res.push(id[1] as char);
+    if data.len() + 4 <= 0xFF {
+        write!(res, "{:02X}", data.len() + 4).unwrap();
+    } else {
+        let len = data.len() + 10;
+        if len > 0xFFFF {
+            return Err(Error::InvalidOptionalBlock);
+        }
+        write!(res, "0004{:04X}", len).unwrap();
+    }
+    res.push_str(data);
+    Ok(())
+}
+
+fn is_printable(data: &[u8]) -> bool {
+    data.iter().all(|b| (0x20..0x7F).contains(b))
+}
+
+/// Parse decimal number
+pub(super) fn decimal(s: &[u8]) -> Option<usize> {
+    s.iter().try_fold(0, |acc, &c| {
+        let d = (c as char).to_digit(10)?;
+        Some(10 * acc + d as usize)
+    })
+}
+
+/// Parse hexadecimal number
+fn hex(s: &[u8]) -> Option<usize> {
+    s.iter().try_fold(0, |acc, &c| {
+        let d = (c as char).to_digit(16)?;
+        Some(16 * acc + d as usize)
+    })
+}
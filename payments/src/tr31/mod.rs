@@ -0,0 +1,397 @@
+//! Key blocks defined in ASC X9 TR-31:2018 and ANSI X9.143.
+//!
+//! A key block is an ASCII string consisting of a header describing the
+//! key attributes, the hex-encoded encrypted key and a MAC binding the key
+//! to the header. Both the encryption and the MAC keys are obtained from
+//! the Key Block Protection Key (KBPK), the method depends on the version:
+//! - `A` and `C`: TDES key variant binding, KBPK is a TDES key;
+//! - `B`: TDES key derivation binding, KBPK is a TDES key;
+//! - `D`: AES key derivation binding, KBPK is an AES key.
+//!
+//! The cipher is selected by the KBPK length: 16 and 24 bytes are two-key
+//! and three-key TDES for versions `A`, `B` and `C`, while version `D`
+//! accepts 16, 24 and 32-byte AES keys.
+//!
+//! # Example
+//! ```
+//! use hex_literal::hex;
+//! use payments::tr31;
+//!
+//! let kbpk = hex!("89E88CF7931444F334BD7547FC3F380C");
+//! let key_block = "A0072P0TE00E0000F5161ED902807AF26F1D62263644BD24192FDB3193C730301CEE8701";
+//!
+//! let (header, key) = tr31::unwrap(&kbpk, key_block).unwrap();
+//! assert_eq!(header.key_usage, tr31::KeyUsage::PIN_ENCRYPTION);
+//! assert_eq!(header.algorithm, tr31::Algorithm::Tdes);
+//! assert_eq!(key, hex!("F039121BEC83D26B169BDCD5B22AAF8F"));
+//! ```
+
+use crate::cmac::{cbc_mac, cmac, CmacBlockSize};
+use aes::{Aes128, Aes192, Aes256};
+use alloc::{format, string::String, vec, vec::Vec};
+use block_modes::{block_padding::NoPadding, BlockMode, Cbc};
+use cipher::{
+    generic_array::{typenum::Unsigned, GenericArray},
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use core::fmt::{self, Write};
+use des::{TdesEde2, TdesEde3};
+use rand_core::{CryptoRng, RngCore};
+
+mod header;
+
+pub use header::{Algorithm, Exportability, Header, KeyUsage, ModeOfUse, OptionalBlock, Version};
+
+/// Maximum key block length allowed by the 4-digit length field
+const MAX_LEN: usize = 9999;
+
+/// Variant of KBPK used as the encryption key by versions `A` and `C`
+const ENCRYPTION_VARIANT: u8 = 0x45;
+
+/// Variant of KBPK used as the MAC key by versions `A` and `C`
+const MAC_VARIANT: u8 = 0x4D;
+
+/// Key usage indicator of the derived encryption key
+const ENCRYPTION_KEY: u16 = 0x0000;
+
+/// Key usage indicator of the derived MAC key
+const MAC_KEY: u16 = 0x0001;
+
+/// Block cipher which can be used as KBPK
+trait KbpkAlgorithm: NewBlockCipher {
+    /// Algorithm indicator used in the key derivation data
+    const ALGORITHM: u16;
+}
+
+impl KbpkAlgorithm for TdesEde2 {
+    const ALGORITHM: u16 = 0x0000;
+}
+
+impl KbpkAlgorithm for TdesEde3 {
+    const ALGORITHM: u16 = 0x0001;
+}
+
+impl KbpkAlgorithm for Aes128 {
+    const ALGORITHM: u16 = 0x0002;
+}
+
+impl KbpkAlgorithm for Aes192 {
+    const ALGORITHM: u16 = 0x0003;
+}
+
+impl KbpkAlgorithm for Aes256 {
+    const ALGORITHM: u16 = 0x0004;
+}
+
+/// Key block error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Header contains invalid characters or field values.
+    InvalidHeader,
+    /// Key block version is not supported.
+    UnsupportedVersion,
+    /// Key block length is invalid or does not match the length field.
+    InvalidLength,
+    /// KBPK length is not supported by the key block version.
+    InvalidKbpkLength,
+    /// Encrypted key or MAC is not a valid hex string.
+    InvalidEncoding,
+    /// Optional block is malformed.
+    InvalidOptionalBlock,
+    /// MAC verification failed.
+    MacMismatch,
+    /// Decrypted key length field is invalid, or the key is too long.
+    InvalidKeyLength,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Error::InvalidHeader => "invalid key block header",
+            Error::UnsupportedVersion => "unsupported key block version",
+            Error::InvalidLength => "invalid key block length",
+            Error::InvalidKbpkLength => "invalid key block protection key length",
+            Error::InvalidEncoding => "invalid key block encoding",
+            Error::InvalidOptionalBlock => "invalid optional block",
+            Error::MacMismatch => "key block MAC mismatch",
+            Error::InvalidKeyLength => "invalid key length",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Call generic function `$f` with the cipher selected by the key block
+/// version and the KBPK length
+macro_rules! with_cipher {
+    ($version:expr, $kbpk:expr, $f:ident($($arg:expr),*)) => {
+        match ($version, $kbpk.len()) {
+            (Version::D, 16) => $f::<Aes128>($($arg),*),
+            (Version::D, 24) => $f::<Aes192>($($arg),*),
+            (Version::D, 32) => $f::<Aes256>($($arg),*),
+            (Version::D, _) => Err(Error::InvalidKbpkLength),
+            (_, 16) => $f::<TdesEde2>($($arg),*),
+            (_, 24) => $f::<TdesEde3>($($arg),*),
+            (_, _) => Err(Error::InvalidKbpkLength),
+        }
+    };
+}
+
+/// Parse key block header without verifying the key block.
+pub fn parse_header(key_block: &str) -> Result<Header, Error> {
+    Header::parse(key_block.as_bytes()).map(|(header, _)| header)
+}
+
+/// Verify key block using KBPK and return its header and the decrypted
+/// key.
+///
+/// The MAC is compared in constant time.
+pub fn unwrap(kbpk: &[u8], key_block: &str) -> Result<(Header, Vec<u8>), Error> {
+    let key_block = key_block.as_bytes();
+    let (header, header_len) = Header::parse(key_block)?;
+    let len = header::decimal(&key_block[1..5]).ok_or(Error::InvalidHeader)?;
+    if len != key_block.len() {
+        return Err(Error::InvalidLength);
+    }
+
+    let bs = header.version.block_size();
+    let mac_len = mac_len(header.version);
+    let payload_end = len
+        .checked_sub(2 * mac_len)
+        .filter(|&end| end >= header_len + 2 * bs)
+        .ok_or(Error::InvalidLength)?;
+    if header_len % bs != 0 || (payload_end - header_len) % (2 * bs) != 0 {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut payload = decode_hex(&key_block[header_len..payload_end])?;
+    let mac = decode_hex(&key_block[payload_end..])?;
+    let header_bytes = &key_block[..header_len];
+    with_cipher!(
+        header.version,
+        kbpk,
+        decrypt(header.version, kbpk, header_bytes, &mut payload, &mac)
+    )?;
+
+    let key_len = usize::from(u16::from_be_bytes([payload[0], payload[1]]));
+    if key_len % 8 != 0 || key_len / 8 > payload.len() - 2 {
+        return Err(Error::InvalidKeyLength);
+    }
+    let key = payload[2..2 + key_len / 8].to_vec();
+    Ok((header, key))
+}
+
+/// Create key block protecting `key` with KBPK.
+///
+/// The key is padded with random bytes to a multiple of the cipher block
+/// size.
+pub fn wrap<R: CryptoRng + RngCore>(
+    kbpk: &[u8],
+    header: &Header,
+    key: &[u8],
+    rng: &mut R,
+) -> Result<String, Error> {
+    wrap_masked(kbpk, header, key, key.len(), rng)
+}
+
+/// Create key block protecting `key` with KBPK, hiding the key length.
+///
+/// The key is padded with random bytes to at least `masked_len` bytes and
+/// then to a multiple of the cipher block size, so keys of different
+/// lengths produce key blocks of the same length, e.g. `masked_len` of 24
+/// hides whether a TDES key has two or three parts.
+pub fn wrap_masked<R: CryptoRng + RngCore>(
+    kbpk: &[u8],
+    header: &Header,
+    key: &[u8],
+    masked_len: usize,
+    rng: &mut R,
+) -> Result<String, Error> {
+    if key.len() > usize::from(u16::MAX / 8) {
+        return Err(Error::InvalidKeyLength);
+    }
+    let mut res = header.encode()?;
+    let header_len = res.len();
+
+    let bs = header.version.block_size();
+    let data_len = 2 + key.len().max(masked_len);
+    let mut payload = vec![0u8; data_len + (bs - data_len % bs) % bs];
+    payload[..2].copy_from_slice(&(8 * key.len() as u16).to_be_bytes());
+    payload[2..2 + key.len()].copy_from_slice(key);
+    rng.fill_bytes(&mut payload[2 + key.len()..]);
+
+    let mac_len = mac_len(header.version);
+    let len = header_len + 2 * (payload.len() + mac_len);
+    if len > MAX_LEN {
+        return Err(Error::InvalidLength);
+    }
+    res.replace_range(1..5, &format!("{:04}", len));
+
+    let mac = with_cipher!(
+        header.version,
+        kbpk,
+        encrypt(header.version, kbpk, res.as_bytes(), &mut payload)
+    )?;
+    encode_hex(&mut res, &payload);
+    encode_hex(&mut res, &mac[..mac_len]);
+    Ok(res)
+}
+
+/// Length of the MAC in bytes
+fn mac_len(version: Version) -> usize {
+    match version {
+        Version::A | Version::C => 4,
+        Version::B => 8,
+        Version::D => 16,
+    }
+}
+
+/// Verify MAC and decrypt payload in place
+fn decrypt<C>(
+    version: Version,
+    kbpk: &[u8],
+    header: &[u8],
+    payload: &mut [u8],
+    mac: &[u8],
+) -> Result<(), Error>
+where
+    C: KbpkAlgorithm + BlockCipher + BlockEncrypt + BlockDecrypt,
+    C::BlockSize: CmacBlockSize,
+{
+    let kbpk = GenericArray::from_slice(kbpk);
+    let (kbek, kbmk) = keys::<C>(version, kbpk);
+    let bs = C::BlockSize::USIZE;
+    match version {
+        Version::A | Version::C => {
+            let mut data = header.to_vec();
+            data.extend_from_slice(payload);
+            let expected = cbc_mac(&C::new(&kbmk), &data);
+            if !ct_eq(&expected[..mac.len()], mac) {
+                return Err(Error::MacMismatch);
+            }
+            let iv = GenericArray::from_slice(&header[..bs]);
+            let cbc = Cbc::<C, NoPadding>::new(C::new(&kbek), iv);
+            cbc.decrypt(payload).map_err(|_| Error::InvalidLength)?;
+        }
+        Version::B | Version::D => {
+            let iv = GenericArray::from_slice(mac);
+            let cbc = Cbc::<C, NoPadding>::new(C::new(&kbek), iv);
+            cbc.decrypt(payload).map_err(|_| Error::InvalidLength)?;
+            let mut data = header.to_vec();
+            data.extend_from_slice(payload);
+            if !ct_eq(&cmac(&C::new(&kbmk), &data), mac) {
+                return Err(Error::MacMismatch);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encrypt payload in place and return the MAC
+fn encrypt<C>(
+    version: Version,
+    kbpk: &[u8],
+    header: &[u8],
+    payload: &mut [u8],
+) -> Result<Vec<u8>, Error>
+where
+    C: KbpkAlgorithm + BlockCipher + BlockEncrypt + BlockDecrypt,
+    C::BlockSize: CmacBlockSize,
+{
+    let kbpk = GenericArray::from_slice(kbpk);
+    let (kbek, kbmk) = keys::<C>(version, kbpk);
+    let bs = C::BlockSize::USIZE;
+    let pos = payload.len();
+    let mac = match version {
+        Version::A | Version::C => {
+            let iv = GenericArray::from_slice(&header[..bs]);
+            let cbc = Cbc::<C, NoPadding>::new(C::new(&kbek), iv);
+            cbc.encrypt(payload, pos)
+                .map_err(|_| Error::InvalidLength)?;
+            let mut data = header.to_vec();
+            data.extend_from_slice(payload);
+            cbc_mac(&C::new(&kbmk), &data)
+        }
+        Version::B | Version::D => {
+            let mut data = header.to_vec();
+            data.extend_from_slice(payload);
+            let mac = cmac(&C::new(&kbmk), &data);
+            let cbc = Cbc::<C, NoPadding>::new(C::new(&kbek), &mac);
+            cbc.encrypt(payload, pos)
+                .map_err(|_| Error::InvalidLength)?;
+            mac
+        }
+    };
+    Ok(mac.to_vec())
+}
+
+/// Key block encryption and MAC keys
+fn keys<C>(
+    version: Version,
+    kbpk: &GenericArray<u8, C::KeySize>,
+) -> (GenericArray<u8, C::KeySize>, GenericArray<u8, C::KeySize>)
+where
+    C: KbpkAlgorithm + BlockEncrypt,
+    C::BlockSize: CmacBlockSize,
+{
+    match version {
+        Version::A | Version::C => {
+            let variant = |v: u8| {
+                let mut key = kbpk.clone();
+                key.iter_mut().for_each(|b| *b ^= v);
+                key
+            };
+            (variant(ENCRYPTION_VARIANT), variant(MAC_VARIANT))
+        }
+        Version::B | Version::D => (
+            derive::<C>(kbpk, ENCRYPTION_KEY),
+            derive::<C>(kbpk, MAC_KEY),
+        ),
+    }
+}
+
+/// Derive key from KBPK using CMAC in counter mode
+fn derive<C>(kbpk: &GenericArray<u8, C::KeySize>, usage: u16) -> GenericArray<u8, C::KeySize>
+where
+    C: KbpkAlgorithm + BlockEncrypt,
+    C::BlockSize: CmacBlockSize,
+{
+    let cipher = C::new(kbpk);
+    let len = (8 * C::KeySize::USIZE) as u16;
+
+    let mut res = GenericArray::<u8, C::KeySize>::default();
+    for (i, chunk) in res.chunks_mut(C::BlockSize::USIZE).enumerate() {
+        let mut data = [0u8; 8];
+        data[0] = (i + 1) as u8;
+        data[1..3].copy_from_slice(&usage.to_be_bytes());
+        data[4..6].copy_from_slice(&C::ALGORITHM.to_be_bytes());
+        data[6..8].copy_from_slice(&len.to_be_bytes());
+        chunk.copy_from_slice(&cmac(&cipher, &data)[..chunk.len()]);
+    }
+    res
+}
+
+/// Compare slices of equal length in constant time
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn decode_hex(s: &[u8]) -> Result<Vec<u8>, Error> {
+    s.chunks_exact(2)
+        .map(|pair| {
+            let hi = (pair[0] as char).to_digit(16);
+            let lo = (pair[1] as char).to_digit(16);
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => Ok((16 * hi + lo) as u8),
+                _ => Err(Error::InvalidEncoding),
+            }
+        })
+        .collect()
+}
+
+fn encode_hex(res: &mut String, data: &[u8]) {
+    for b in data {
+        write!(res, "{:02X}", b).unwrap();
+    }
+}
@@ -0,0 +1,179 @@
+//! Test vectors are from ANSI X9.24-1:2009 Annex A and ANSI X9.24-3:2017
+//! Annex B.
+use cipher::{generic_array::GenericArray, BlockEncrypt, NewBlockCipher};
+use hex_literal::hex;
+use payments::{
+    aes::{Aes128, Aes256},
+    des::{TdesEde2, TdesEde3},
+    dukpt::{aes, tdes, InvalidCounter},
+};
+
+const TDES_BDK: [u8; 16] = hex!("0123456789ABCDEFFEDCBA9876543210");
+const TDES_KSN: [u8; 10] = hex!("FFFF9876543210E00000");
+
+fn tdes_ksn(counter: u32) -> tdes::Ksn {
+    let mut ksn = TDES_KSN;
+    ksn[7] |= (counter >> 16) as u8;
+    ksn[8] = (counter >> 8) as u8;
+    ksn[9] = counter as u8;
+    ksn
+}
+
+#[test]
+fn tdes_initial_key() {
+    let bdk = TDES_BDK.into();
+    let ipek = tdes::initial_key(&bdk, &TDES_KSN);
+    assert_eq!(ipek[..], hex!("6AC292FAA1315B4D858AB3A3D7D5933A"));
+    // transaction counter is ignored
+    assert_eq!(tdes::initial_key(&bdk, &tdes_ksn(0x1FFFFF)), ipek);
+}
+
+#[test]
+fn tdes_transaction_keys() {
+    let ipek = tdes::initial_key(&TDES_BDK.into(), &TDES_KSN);
+    let vectors = [
+        (0x000001, hex!("042666B49184CFA368DE9628D0397BC9")),
+        (0x000002, hex!("C46551CEF9FD24B0AA9AD834130D3BC7")),
+        (0x000003, hex!("0DF3D9422ACA56E547676D07AD6BADFA")),
+        (0x000004, hex!("279C0F6AEED0BE652B2C733E1383AE91")),
+        (0x000012, hex!("9CF640F279C2AEE615F725EEEAC2CBAF")),
+        (0x000400, hex!("03674910286A78FF1E736AAA82B8260D")),
+    ];
+    for (counter, key) in vectors.iter() {
+        let res = tdes::transaction_key(&ipek, &tdes_ksn(*counter)).unwrap();
+        assert_eq!(res[..], key[..]);
+    }
+
+    assert_eq!(
+        tdes::transaction_key(&ipek, &tdes_ksn(0)),
+        Err(InvalidCounter)
+    );
+    assert_eq!(
+        tdes::transaction_key(&ipek, &tdes_ksn(0x0007FF)),
+        Err(InvalidCounter)
+    );
+}
+
+#[test]
+fn tdes_working_keys() {
+    use tdes::Variant::*;
+
+    let ipek = tdes::initial_key(&TDES_BDK.into(), &TDES_KSN);
+    let key = tdes::transaction_key(&ipek, &tdes_ksn(1)).unwrap();
+    let vectors = [
+        (Pin, hex!("042666B49184CF5C68DE9628D0397B36")),
+        (MacRequest, hex!("042666B4918430A368DE9628D03984C9")),
+        (MacResponse, hex!("042666B46E84CFA368DE96282F397BC9")),
+        (DataRequest, hex!("448D3F076D8304036A55A3D7E0055A78")),
+        (DataResponse, hex!("AD7BFC8B06AD3A08A560B4105CF8D9E5")),
+    ];
+    for (variant, expected) in vectors.iter() {
+        assert_eq!(tdes::working_key(&key, *variant)[..], expected[..]);
+    }
+}
+
+#[test]
+fn tdes_encrypted_pin_blocks() {
+    // PIN 1234 and PAN 4012345678909 in ISO 9564 format 0
+    let pin_block = hex!("041274EDCBA9876F");
+    let ipek = tdes::initial_key(&TDES_BDK.into(), &TDES_KSN);
+    let vectors = [
+        (1, hex!("1B9C1845EB993A7A")),
+        (2, hex!("10A01C8D02C69107")),
+        (3, hex!("18DC07B94797B466")),
+    ];
+    for (counter, expected) in vectors.iter() {
+        let key = tdes::transaction_key(&ipek, &tdes_ksn(*counter)).unwrap();
+        let pin_key = tdes::working_key(&key, tdes::Variant::Pin);
+        let mut block = GenericArray::clone_from_slice(&pin_block);
+        TdesEde2::new(&pin_key).encrypt_block(&mut block);
+        assert_eq!(block[..], expected[..]);
+    }
+}
+
+const AES_INITIAL_KEY_ID: aes::InitialKeyId = hex!("1234567890123456");
+
+fn aes_ksn(counter: u32) -> aes::Ksn {
+    let mut ksn = [0u8; 12];
+    ksn[..8].copy_from_slice(&AES_INITIAL_KEY_ID);
+    ksn[8..].copy_from_slice(&counter.to_be_bytes());
+    ksn
+}
+
+#[test]
+fn aes128_dukpt() {
+    use aes::KeyUsage::*;
+
+    let bdk = hex!("FEDCBA9876543210F1F1F1F1F1F1F1F1").into();
+    let ik = aes::initial_key::<Aes128>(&bdk, &AES_INITIAL_KEY_ID);
+    assert_eq!(ik[..], hex!("1273671EA26AC29AFA4D1084127652A1"));
+
+    let vectors = [
+        (1, PinEncryption, hex!("AF8CB133A78F8DC2D1359F18527593FB")),
+        (1, MacGeneration, hex!("A2DC23DE6FDE0824A2BC321E08E4B8B7")),
+        (1, DataEncrypt, hex!("A35C412EFD41FDB98B69797C02DCD08F")),
+        (2, PinEncryption, hex!("D30BDC73EC9714B000BEC66BDB7B6D09")),
+        (2, MacGeneration, hex!("484C3B06E8562704528CD5B46FB12FB6")),
+        (2, DataEncrypt, hex!("D639514AA33AC43AD9229E433D6D4E5B")),
+        (
+            0x10010,
+            PinEncryption,
+            hex!("F49438C4EEF1F931D5E7EADBFFDE0953"),
+        ),
+    ];
+    for (counter, usage, expected) in vectors.iter() {
+        let key = aes::working_key::<Aes128, Aes128>(&ik, &aes_ksn(*counter), *usage).unwrap();
+        assert_eq!(key[..], expected[..]);
+    }
+
+    let ksn = aes_ksn(1);
+    let key = aes::working_key::<Aes128, TdesEde2>(&ik, &ksn, PinEncryption).unwrap();
+    assert_eq!(key[..], hex!("630C706D9546E47D4449313F61C4D4AB"));
+    let key = aes::working_key::<Aes128, TdesEde3>(&ik, &ksn, PinEncryption).unwrap();
+    assert_eq!(
+        key[..],
+        hex!("EA8B3F37EB9B15831167EF2977FD8762D9B5913F35766F6A")
+    );
+    let key = aes::working_key::<Aes128, Aes256>(&ik, &ksn, PinEncryption).unwrap();
+    assert_eq!(
+        key[..],
+        hex!("0407DBCB827AFC05398DBA36C3A6F6619DA78C9EC5C7B2E2EAFC3B380DDF318A")
+    );
+
+    let res = aes::working_key::<Aes128, Aes128>(&ik, &aes_ksn(0), PinEncryption);
+    assert_eq!(res, Err(InvalidCounter));
+    let res = aes::working_key::<Aes128, Aes128>(&ik, &aes_ksn(0x0001_FFFF), PinEncryption);
+    assert_eq!(res, Err(InvalidCounter));
+}
+
+#[test]
+fn aes256_dukpt() {
+    let bdk = hex!(
+        "
+        FEDCBA9876543210F1F1F1F1F1F1F1F1
+        FEDCBA9876543210F1F1F1F1F1F1F1F1
+        "
+    )
+    .into();
+    let ik = aes::initial_key::<Aes256>(&bdk, &AES_INITIAL_KEY_ID);
+    assert_eq!(
+        ik[..],
+        hex!("CE9CE0C101D1138F97FB6CAD4DF045A7083D4EAE2D35A31789D01CCF0949550F")
+    );
+
+    let ksn = aes_ksn(1);
+    let key = aes::working_key::<Aes256, Aes256>(&ik, &ksn, aes::KeyUsage::PinEncryption);
+    assert_eq!(
+        key.unwrap()[..],
+        hex!("8C1AB7BEE973829E30242E0BBBDD4946D540C98FC1B5BDCF94790001A23FD502")
+    );
+
+    let ksn = aes_ksn(2);
+    let key = aes::working_key::<Aes256, Aes256>(&ik, &ksn, aes::KeyUsage::DataEncrypt);
+    assert_eq!(
+        key.unwrap()[..],
+        hex!("D0AC9F71F875FFFF3A83778F72F39EBB0924A5A526617AF669665E0A19725465")
+    );
+    let key = aes::working_key::<Aes256, Aes128>(&ik, &ksn, aes::KeyUsage::MacGeneration);
+    assert_eq!(key.unwrap()[..], hex!("DD99D77B4E2AB538B26BB6A8B3FB0BE3"));
+}
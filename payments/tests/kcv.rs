@@ -0,0 +1,90 @@
+use hex_literal::hex;
+use payments::{
+    aes::{Aes128, Aes256},
+    cipher::NewBlockCipher,
+    des::{Des, TdesEde2},
+    kcv::{CheckValue, Error, Method, CMAC_LEN, LEGACY_LEN},
+};
+use rijndael::{
+    cipher::consts::{U16, U20},
+    Rijndael128, Rijndael160,
+};
+
+#[test]
+fn legacy() {
+    let key = hex!("0123456789ABCDEFFEDCBA9876543210");
+    let tdes = TdesEde2::new_from_slice(&key).unwrap();
+    assert_eq!(tdes.kcv(Method::Legacy)[..], hex!("08D7B4FB629D0885"));
+    assert_eq!(tdes.verify_kcv(Method::Legacy, &hex!("08D7B4")), Ok(()));
+
+    let des = Des::new_from_slice(&key[..8]).unwrap();
+    assert_eq!(des.kcv(Method::Legacy)[..LEGACY_LEN], hex!("D5D44F"));
+
+    let key = hex!("2B7E151628AED2A6ABF7158809CF4F3C");
+    let aes = Aes128::new_from_slice(&key).unwrap();
+    assert_eq!(
+        aes.kcv(Method::Legacy)[..],
+        hex!("7DF76B0C1AB899B33E42F047B91B546F")
+    );
+}
+
+#[test]
+fn legacy_any_block_size() {
+    let key = hex!("2B7E151628AED2A6ABF7158809CF4F3C");
+    let aes = Aes128::new_from_slice(&key).unwrap();
+    let rijndael = Rijndael128::<U16>::new_from_slice(&key).unwrap();
+    assert_eq!(rijndael.legacy_kcv(), aes.kcv(Method::Legacy));
+
+    // no CMAC is defined for 160-bit blocks
+    let key = [0u8; 20];
+    let rijndael = Rijndael160::<U20>::new_from_slice(&key).unwrap();
+    let kcv = rijndael.legacy_kcv();
+    assert_eq!(kcv.len(), 20);
+    assert_eq!(rijndael.verify_legacy_kcv(&kcv[..LEGACY_LEN]), Ok(()));
+    assert_eq!(rijndael.verify_legacy_kcv(&[!kcv[0]]), Err(Error::Mismatch));
+    assert_eq!(
+        rijndael.verify_legacy_kcv(&[0; 21]),
+        Err(Error::InvalidKcvLength)
+    );
+}
+
+#[test]
+fn cmac() {
+    let key = hex!("0123456789ABCDEFFEDCBA9876543210");
+    let tdes = TdesEde2::new_from_slice(&key).unwrap();
+    assert_eq!(tdes.kcv(Method::Cmac)[..], hex!("0A8245866490475B"));
+
+    let key = hex!("2B7E151628AED2A6ABF7158809CF4F3C");
+    let aes = Aes128::new_from_slice(&key).unwrap();
+    assert_eq!(aes.kcv(Method::Cmac)[..CMAC_LEN], hex!("7AD386C376"));
+
+    let key = hex!("603DEB1015CA71BE2B73AEF0857D77811F352C073B6108D72D9810A30914DFF4");
+    let aes = Aes256::new_from_slice(&key).unwrap();
+    assert_eq!(
+        aes.kcv(Method::Cmac)[..],
+        hex!("1A0B2DF26763CFD6AFB9D3B1A2A846DA")
+    );
+}
+
+#[test]
+fn constructors() {
+    let key = hex!("2B7E151628AED2A6ABF7158809CF4F3C");
+    assert!(Aes128::new_with_kcv(&key.into(), Method::Cmac, &hex!("7AD386C376")).is_ok());
+    assert!(Aes128::new_from_slice_with_kcv(&key, Method::Legacy, &hex!("7DF76B")).is_ok());
+    assert_eq!(
+        Aes128::new_from_slice_with_kcv(&key, Method::Cmac, &hex!("7DF76B")).err(),
+        Some(Error::Mismatch)
+    );
+    assert_eq!(
+        Aes128::new_from_slice_with_kcv(&key[..15], Method::Legacy, &hex!("7DF76B")).err(),
+        Some(Error::InvalidKeyLength)
+    );
+    assert_eq!(
+        Aes128::new_from_slice_with_kcv(&key, Method::Legacy, &[]).err(),
+        Some(Error::InvalidKcvLength)
+    );
+    assert_eq!(
+        Aes128::new_from_slice_with_kcv(&key, Method::Legacy, &[0; 17]).err(),
+        Some(Error::InvalidKcvLength)
+    );
+}
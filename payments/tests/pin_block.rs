@@ -0,0 +1,212 @@
+use cipher::{generic_array::GenericArray, NewBlockCipher};
+use hex_literal::hex;
+use payments::{
+    aes::Aes128,
+    des::{TdesEde2, TdesEde3},
+    pin_block::{self, Error, Format, Pan, Pin},
+    rand_core::{CryptoRng, Error as RngError, RngCore},
+};
+
+/// RNG producing consecutive bytes starting from zero
+struct CountingRng(u8);
+
+impl RngCore for CountingRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for b in dest.iter_mut() {
+            *b = self.0;
+            self.0 = self.0.wrapping_add(1);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for CountingRng {}
+
+fn pin(s: &str) -> Pin {
+    Pin::new(s).unwrap()
+}
+
+fn pan(s: &str) -> Pan {
+    Pan::new(s).unwrap()
+}
+
+#[test]
+fn pin_and_pan_validation() {
+    assert_eq!(Pin::new("123"), Err(Error::InvalidPin));
+    assert_eq!(Pin::new("1234567890123"), Err(Error::InvalidPin));
+    assert_eq!(Pin::new("12a4"), Err(Error::InvalidPin));
+    assert_eq!(Pin::new("123456789012").unwrap().as_str(), "123456789012");
+    assert_eq!(Pan::new(""), Err(Error::InvalidPan));
+    assert_eq!(Pan::new("12345678901234567890"), Err(Error::InvalidPan));
+    assert_eq!(Pan::new("4321 9876"), Err(Error::InvalidPan));
+}
+
+#[test]
+fn format0() {
+    let vectors = [
+        ("1234", "43219876543210987", hex!("0412AC89ABCDEF67")),
+        ("1234", "4012345678909", hex!("041274EDCBA9876F")),
+        ("123456789012", "4012345678909", hex!("0C1274444CC66A6F")),
+        // PAN shorter than 13 digits is padded with zeros
+        ("1234", "12345", hex!("041234FFFFFFEDCB")),
+    ];
+    for (p, n, block) in vectors.iter() {
+        let res = Format::Iso0.build(&pin(p), Some(&pan(n)));
+        assert_eq!(res, Ok(*block));
+        let (format, res) = pin_block::parse(block, Some(&pan(n))).unwrap();
+        assert_eq!(format, Format::Iso0);
+        assert_eq!(res.as_str(), *p);
+    }
+
+    let res = Format::Iso0.build(&pin("1234"), None);
+    assert_eq!(res, Err(Error::MissingPan));
+    let res = pin_block::parse(&hex!("0412AC89ABCDEF67"), None);
+    assert_eq!(res, Err(Error::MissingPan));
+    // PIN block can not be parsed with a wrong PAN
+    let res = pin_block::parse(&hex!("0412AC89ABCDEF67"), Some(&pan("4012345678909")));
+    assert!(res.is_err());
+}
+
+#[test]
+fn format1() {
+    let mut rng = CountingRng(0);
+    let block = Format::Iso1
+        .build_with_rng(&pin("1234"), None, &mut rng)
+        .unwrap();
+    assert_eq!(block, hex!("141234048C048C04"));
+    let (format, res) = pin_block::parse(&block, None).unwrap();
+    assert_eq!(format, Format::Iso1);
+    assert_eq!(res.as_str(), "1234");
+
+    // random padding requires an RNG
+    let res = Format::Iso1.build(&pin("1234"), None);
+    assert_eq!(res, Err(Error::MissingRng));
+}
+
+#[test]
+fn format2() {
+    let block = Format::Iso2.build(&pin("12345"), None).unwrap();
+    assert_eq!(block, hex!("2512345FFFFFFFFF"));
+    // the RNG is not used by deterministic formats
+    let mut rng = CountingRng(0);
+    let res = Format::Iso2.build_with_rng(&pin("12345"), None, &mut rng);
+    assert_eq!(res, Ok(block));
+    assert_eq!(rng.0, 0);
+    let (format, res) = pin_block::parse(&block, None).unwrap();
+    assert_eq!(format, Format::Iso2);
+    assert_eq!(res.as_str(), "12345");
+    assert_eq!(
+        pin_block::parse(&hex!("2512345FFFFFFFFE"), None),
+        Err(Error::InvalidFill)
+    );
+}
+
+#[test]
+fn format3() {
+    let mut rng = CountingRng(0);
+    let p = pan("43219876543210987");
+    let block = Format::Iso3
+        .build_with_rng(&pin("1234"), Some(&p), &mut rng)
+        .unwrap();
+    let (format, res) = pin_block::parse(&block, Some(&p)).unwrap();
+    assert_eq!(format, Format::Iso3);
+    assert_eq!(res.as_str(), "1234");
+
+    // padding digits must be in the range A to F
+    let mut field = hex!("341234ABCDEFABCD");
+    for (b, p) in field[2..].iter_mut().zip(&hex!("987654321098")) {
+        *b ^= p;
+    }
+    assert!(pin_block::parse(&field, Some(&p)).is_ok());
+    field[7] ^= 0x0D ^ 0x09;
+    assert_eq!(pin_block::parse(&field, Some(&p)), Err(Error::InvalidFill));
+}
+
+#[test]
+fn malformed_blocks() {
+    let vectors = [
+        (hex!("5412FFFFFFFFFFFF"), Error::UnsupportedFormat),
+        (hex!("2312FFFFFFFFFFFF"), Error::InvalidPinLength),
+        (hex!("2D12FFFFFFFFFFFF"), Error::InvalidPinLength),
+        (hex!("24123AFFFFFFFFFF"), Error::InvalidPinDigit),
+    ];
+    for (block, err) in vectors.iter() {
+        assert_eq!(pin_block::parse(block, None), Err(*err));
+    }
+}
+
+#[test]
+fn tdes_encryption() {
+    let mut rng = CountingRng(0);
+    let key3 = hex!("0123456789ABCDEFFEDCBA987654321089ABCDEF01234567");
+    let tdes3 = TdesEde3::new(GenericArray::from_slice(&key3));
+    let p = pan("43219876543210987");
+
+    let block = pin_block::encrypt(&tdes3, Format::Iso0, &pin("1234"), Some(&p));
+    assert_eq!(block, Ok(hex!("A5DC5F7F40A400FE")));
+    let (format, res) = pin_block::decrypt(&tdes3, &block.unwrap(), Some(&p)).unwrap();
+    assert_eq!(format, Format::Iso0);
+    assert_eq!(res.as_str(), "1234");
+
+    let tdes2 = TdesEde2::new(GenericArray::from_slice(&key3[..16]));
+    for &format in [Format::Iso0, Format::Iso1, Format::Iso3].iter() {
+        let block = pin_block::encrypt_with_rng(&tdes2, format, &pin("98765"), Some(&p), &mut rng);
+        let res = pin_block::decrypt(&tdes2, &block.unwrap(), Some(&p)).unwrap();
+        assert_eq!(res.0, format);
+        assert_eq!(res.1.as_str(), "98765");
+    }
+    let res = pin_block::encrypt(&tdes2, Format::Iso3, &pin("98765"), Some(&p));
+    assert_eq!(res, Err(Error::MissingRng));
+}
+
+#[test]
+fn format4() {
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let aes = Aes128::new(GenericArray::from_slice(&key));
+
+    let field = pin_block::build_format4(&pin("1234"), &mut CountingRng(0));
+    assert_eq!(field, hex!("441234AAAAAAAAAA0001020304050607"));
+    assert_eq!(pin_block::parse_format4(&field).unwrap().as_str(), "1234");
+
+    let vectors = [
+        (
+            "432198765432109870",
+            hex!("0FB2CE68F90E7F5E4BFD35056FFD9BC7"),
+        ),
+        ("1234567890", hex!("7AD5CE4FD39EE4E821E7C419CF324EF4")),
+    ];
+    for (n, block) in vectors.iter() {
+        let p = pan(n);
+        let res = pin_block::encrypt_format4(&aes, &pin("1234"), &p, &mut CountingRng(0));
+        assert_eq!(res, *block);
+        let res = pin_block::decrypt_format4(&aes, block, &p).unwrap();
+        assert_eq!(res.as_str(), "1234");
+    }
+
+    let res = pin_block::decrypt_format4(&aes, &vectors[0].1, &pan("1234567890"));
+    assert!(res.is_err());
+    assert_eq!(
+        pin_block::parse_format4(&hex!("041234AAAAAAAAAA0001020304050607")),
+        Err(Error::UnsupportedFormat)
+    );
+    assert_eq!(
+        pin_block::parse_format4(&hex!("441234AAAAAAAAAB0001020304050607")),
+        Err(Error::InvalidFill)
+    );
+}
@@ -0,0 +1,213 @@
+#![cfg(feature = "alloc")]
+
+use hex_literal::hex;
+use payments::{
+    rand_core::{CryptoRng, Error as RngError, RngCore},
+    tr31::{
+        self, Algorithm, Error, Exportability, Header, KeyUsage, ModeOfUse, OptionalBlock, Version,
+    },
+};
+
+/// RNG producing consecutive bytes starting from zero
+struct CountingRng(u8);
+
+impl RngCore for CountingRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for b in dest.iter_mut() {
+            *b = self.0;
+            self.0 = self.0.wrapping_add(1);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for CountingRng {}
+
+/// Example from ASC X9 TR-31:2018, section A.7.2
+#[test]
+fn version_a_c() {
+    let kbpk = hex!("89E88CF7931444F334BD7547FC3F380C");
+    let key = hex!("F039121BEC83D26B169BDCD5B22AAF8F");
+    let key_block = "A0072P0TE00E0000F5161ED902807AF26F1D62263644BD24192FDB3193C730301CEE8701";
+    let (header, res) = tr31::unwrap(&kbpk, key_block).unwrap();
+    assert_eq!(header.version, Version::A);
+    assert_eq!(header.key_usage, KeyUsage::PIN_ENCRYPTION);
+    assert_eq!(header.mode_of_use, ModeOfUse::EncryptOnly);
+    assert_eq!(res, key);
+
+    let header = Header {
+        version: Version::C,
+        ..header
+    };
+    let key_block = tr31::wrap(&kbpk, &header, &key, &mut CountingRng(0)).unwrap();
+    assert_eq!(&key_block[..16], "C0072P0TE00E0000");
+    assert_eq!(
+        tr31::unwrap(&kbpk, &key_block).unwrap(),
+        (header, key.to_vec())
+    );
+}
+
+#[test]
+fn version_b() {
+    let kbpk = hex!("DD7515F2BFC17F85CE48F3CA25CB21F6");
+    let key = hex!("E8BC63E5479455E26577F715D587FE68");
+    let mut header = Header::new(
+        Version::B,
+        KeyUsage::BDK,
+        Algorithm::Tdes,
+        ModeOfUse::Derivation,
+        Exportability::Sensitive,
+    );
+    header.key_version = *b"12";
+    header.optional_blocks.push(OptionalBlock {
+        id: *b"KS",
+        data: "00604B120F9292800000".into(),
+    });
+    let key_block = "\
+        B0104B0TX12S0100KS1800604B120F9292800000\
+        D78B9D97D48C8FC4CD6C57C0886872A584AB7205054DB103116333001D4D8C13";
+    assert_eq!(
+        tr31::wrap(&kbpk, &header, &key, &mut CountingRng(0)).unwrap(),
+        key_block
+    );
+    assert_eq!(
+        tr31::unwrap(&kbpk, key_block).unwrap(),
+        (header, key.to_vec())
+    );
+
+    let kbpk = hex!("B8ED59E0A279A295E9F5ED7944FD06B9137A2BD7FB8E1FDA");
+    let key_block = "\
+        B0096D0TB00E0000\
+        9CE00937CC6A86C9A854350F86AEFA1C4AFA6916708D728D244E1721AF6ADD16\
+        D061B96F652637FD";
+    let (header, key) = tr31::unwrap(&kbpk, key_block).unwrap();
+    assert_eq!(header.key_usage, KeyUsage::DATA_ENCRYPTION);
+    assert_eq!(header.exportability, Exportability::Exportable);
+    assert_eq!(
+        key,
+        hex!("F75EB6F91BDF26F6D3A04A2CD5D6C2F5BA31F2EA0B6D4AAB")
+    );
+}
+
+/// Example from ASC X9 TR-31:2018, section A.7.4
+#[test]
+fn version_d() {
+    let kbpk = hex!("88E1AB2A2E3DD38C1FA039A536500CC8A87AB9D62DC92C01058FA79F44657DE6");
+    let key_block = "\
+        D0112P0AE00E0000B82679114F470F540165EDFBF7E250FCEA43F810D215F8D2\
+        07E2E417C07156A27E8E31DA05F7425509593D03A457DC34";
+    let (header, key) = tr31::unwrap(&kbpk, key_block).unwrap();
+    assert_eq!(header.algorithm, Algorithm::Aes);
+    assert_eq!(key, hex!("3F419E1CB7079442AA37474C2EFBF8B8"));
+
+    let kbpk = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let header = Header::new(
+        Version::D,
+        KeyUsage::KEY_ENCRYPTION,
+        Algorithm::Aes,
+        ModeOfUse::Both,
+        Exportability::Exportable,
+    );
+    let key_block = "\
+        D0112K0AB00E00003684DC7B08A6737B619916CC64C8508D2945F668C62CC034\
+        E25ED572196736CD6E4976581064C62196D30FD45801C9E4";
+    assert_eq!(
+        tr31::wrap(&kbpk, &header, &key, &mut CountingRng(0)).unwrap(),
+        key_block
+    );
+    assert_eq!(
+        tr31::unwrap(&kbpk, key_block).unwrap(),
+        (header, key.to_vec())
+    );
+}
+
+#[test]
+fn optional_blocks() {
+    let kbpk = hex!("000102030405060708090A0B0C0D0E0F1011121314151617");
+    let long_data = "X".repeat(300);
+    let mut header = Header::new(
+        Version::D,
+        KeyUsage::MAC_CMAC,
+        Algorithm::Aes,
+        ModeOfUse::GenerateVerify,
+        Exportability::NonExportable,
+    );
+    header.optional_blocks.push(OptionalBlock {
+        id: *b"KV",
+        data: "00".into(),
+    });
+    header.optional_blocks.push(OptionalBlock {
+        id: *b"CT",
+        data: long_data.clone(),
+    });
+
+    let key = hex!("00112233445566778899AABBCCDDEEFF0011223344556677");
+    let key_block = tr31::wrap_masked(&kbpk, &header, &key, 32, &mut CountingRng(0)).unwrap();
+    // KV block, CT block with extended length and padding block
+    assert!(key_block.starts_with("D0464M6AC00N0300KV0600CT00040136XXX"));
+    assert!(key_block[..336].ends_with("XXXPB04"));
+    assert_eq!(key_block.len(), 336 + 2 * 48 + 32);
+
+    let parsed = tr31::parse_header(&key_block).unwrap();
+    assert_eq!(parsed.optional_blocks.len(), 3);
+    assert_eq!(parsed.optional_blocks[1].data, long_data);
+    assert_eq!(parsed.optional_blocks[2].id, *b"PB");
+
+    let (res_header, res_key) = tr31::unwrap(&kbpk, &key_block).unwrap();
+    assert_eq!(res_header, parsed);
+    assert_eq!(res_key, key);
+}
+
+#[test]
+fn errors() {
+    let kbpk = hex!("89E88CF7931444F334BD7547FC3F380C");
+    let key_block = "A0072P0TE00E0000F5161ED902807AF26F1D62263644BD24192FDB3193C730301CEE8701";
+
+    let mut modified = String::from(key_block);
+    modified.replace_range(16..17, "0");
+    assert_eq!(tr31::unwrap(&kbpk, &modified), Err(Error::MacMismatch));
+    modified.replace_range(5..7, "K0");
+    assert_eq!(tr31::unwrap(&kbpk, &modified), Err(Error::MacMismatch));
+
+    assert_eq!(
+        tr31::unwrap(&kbpk[..8], key_block),
+        Err(Error::InvalidKbpkLength)
+    );
+    assert_eq!(
+        tr31::unwrap(&kbpk, &key_block[..70]),
+        Err(Error::InvalidLength)
+    );
+    assert_eq!(
+        tr31::unwrap(&kbpk, &key_block.replace("A0072", "E0072")),
+        Err(Error::UnsupportedVersion)
+    );
+    assert_eq!(
+        tr31::unwrap(&kbpk, &key_block.replace("P0TE", "P0ZE")),
+        Err(Error::InvalidHeader)
+    );
+    assert_eq!(
+        tr31::unwrap(&kbpk, &key_block.replace("F5161ED9", "F5161EDG")),
+        Err(Error::InvalidEncoding)
+    );
+    assert_eq!(
+        tr31::parse_header("D0112P0AE00E0100KS40AB"),
+        Err(Error::InvalidOptionalBlock)
+    );
+}
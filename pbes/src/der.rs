@@ -0,0 +1,135 @@
+//! Minimal DER reader and writer covering the types used by algorithm
+//! identifiers of the password-based encryption schemes.
+
+use crate::Error;
+use alloc::vec::Vec;
+
+pub(crate) const INTEGER: u8 = 0x02;
+pub(crate) const OCTET_STRING: u8 = 0x04;
+pub(crate) const NULL: u8 = 0x05;
+pub(crate) const OID: u8 = 0x06;
+pub(crate) const SEQUENCE: u8 = 0x30;
+
+/// Reader over a sequence of DER encoded values
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Tag of the next value, if any
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    /// Read the next value and return its tag and content
+    pub(crate) fn read_any(&mut self) -> Result<(u8, &'a [u8]), Error> {
+        let (&tag, rest) = self.data.split_first().ok_or(Error::InvalidDer)?;
+        let (&first, mut rest) = rest.split_first().ok_or(Error::InvalidDer)?;
+        let len = match first {
+            0..=0x7F => first as usize,
+            0x81..=0x83 => {
+                let n = (first & 0x7F) as usize;
+                if rest.len() < n || rest[0] == 0 {
+                    return Err(Error::InvalidDer);
+                }
+                let len = rest[..n]
+                    .iter()
+                    .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+                if len < 0x80 {
+                    return Err(Error::InvalidDer);
+                }
+                rest = &rest[n..];
+                len
+            }
+            _ => return Err(Error::InvalidDer),
+        };
+        if rest.len() < len {
+            return Err(Error::InvalidDer);
+        }
+        let (content, rest) = rest.split_at(len);
+        self.data = rest;
+        Ok((tag, content))
+    }
+
+    /// Read the next value, which must have the given tag
+    pub(crate) fn read(&mut self, tag: u8) -> Result<&'a [u8], Error> {
+        match self.read_any()? {
+            (t, content) if t == tag => Ok(content),
+            _ => Err(Error::InvalidDer),
+        }
+    }
+
+    /// Read the next value as a SEQUENCE
+    pub(crate) fn sequence(&mut self) -> Result<Reader<'a>, Error> {
+        self.read(SEQUENCE).map(Reader::new)
+    }
+
+    /// Read the next value as a non-negative INTEGER fitting into `u32`
+    pub(crate) fn read_u32(&mut self) -> Result<u32, Error> {
+        let mut content = self.read(INTEGER)?;
+        match content {
+            [] => return Err(Error::InvalidDer),
+            [b, ..] if b & 0x80 != 0 => return Err(Error::InvalidDer),
+            [0, b, ..] if b & 0x80 == 0 => return Err(Error::InvalidDer),
+            [0, rest @ ..] => content = rest,
+            _ => {}
+        }
+        if content.len() > 4 {
+            return Err(Error::InvalidDer);
+        }
+        Ok(content.iter().fold(0, |acc, &b| (acc << 8) | b as u32))
+    }
+
+    /// Check that all data was consumed
+    pub(crate) fn finish(self) -> Result<(), Error> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidDer)
+        }
+    }
+}
+
+/// Append value with the given tag and content to `out`
+pub(crate) fn write(out: &mut Vec<u8>, tag: u8, content: &[u8]) {
+    out.push(tag);
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = (len as u32).to_be_bytes();
+        let skip = bytes.iter().take_while(|&&b| b == 0).count();
+        out.push(0x80 | (4 - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+    out.extend_from_slice(content);
+}
+
+/// Append SEQUENCE built by `f` to `out`
+pub(crate) fn write_sequence(out: &mut Vec<u8>, f: impl FnOnce(&mut Vec<u8>)) {
+    let mut content = Vec::new();
+    f(&mut content);
+    write(out, SEQUENCE, &content);
+}
+
+/// Append non-negative INTEGER to `out`
+pub(crate) fn write_u32(out: &mut Vec<u8>, n: u32) {
+    let bytes = n.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(3);
+    let content = &bytes[skip..];
+    if content[0] & 0x80 != 0 {
+        let mut buf = [0u8; 5];
+        buf[1..=content.len()].copy_from_slice(content);
+        write(out, INTEGER, &buf[..=content.len()]);
+    } else {
+        write(out, INTEGER, content);
+    }
+}
@@ -0,0 +1,153 @@
+//! Key derivation functions used by the password-based encryption schemes.
+//!
+//! - [`pbkdf1`]: PBKDF1 defined in [RFC 8018 section 5.1][1], used by PBES1;
+//! - [`pbkdf2`]: PBKDF2 with HMAC defined in [RFC 8018 section 5.2][2], used
+//!   by PBES2;
+//! - [`pkcs12`]: key derivation defined in [RFC 7292 appendix B][3], used by
+//!   the PKCS#12 password-based encryption algorithms;
+//! - [`evp_bytes_to_key`]: OpenSSL's `EVP_BytesToKey`, used by the
+//!   `openssl enc` command without the `-pbkdf2` option.
+//!
+//! [1]: https://tools.ietf.org/html/rfc8018#section-5.1
+//! [2]: https://tools.ietf.org/html/rfc8018#section-5.2
+//! [3]: https://tools.ietf.org/html/rfc7292#appendix-B
+
+use crate::Error;
+use alloc::vec::Vec;
+use cipher::generic_array::{typenum::Unsigned, ArrayLength};
+use digest::{BlockInput, Digest, FixedOutput, Reset, Update};
+use hmac::Hmac;
+
+/// Purpose of the material derived by the PKCS#12 key derivation function.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pkcs12Id {
+    /// Encryption key.
+    Key = 1,
+    /// Initialization vector.
+    Iv = 2,
+    /// MAC key.
+    Mac = 3,
+}
+
+/// Derive `output` from `password` and `salt` using PBKDF1.
+///
+/// Returns an error if `iterations` is zero or `output` is longer than the
+/// digest output.
+pub fn pbkdf1<D: Digest>(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    output: &mut [u8],
+) -> Result<(), Error> {
+    if iterations == 0 || output.len() > D::output_size() {
+        return Err(Error::InvalidParameters);
+    }
+    let mut t = D::new().chain(password).chain(salt).finalize();
+    for _ in 1..iterations {
+        t = D::digest(&t);
+    }
+    output.copy_from_slice(&t[..output.len()]);
+    Ok(())
+}
+
+/// Derive `output` from `password` and `salt` using PBKDF2 with HMAC-`D` as
+/// the pseudorandom function.
+///
+/// Zero `iterations` is processed as a single iteration.
+pub fn pbkdf2<D>(password: &[u8], salt: &[u8], iterations: u32, output: &mut [u8])
+where
+    D: Update + BlockInput + FixedOutput + Reset + Default + Clone + Sync,
+    D::BlockSize: ArrayLength<u8>,
+{
+    pbkdf2::pbkdf2::<Hmac<D>>(password, salt, iterations, output);
+}
+
+/// Derive `output` from `password` and `salt` using the PKCS#12 key
+/// derivation function with the digest `D`.
+///
+/// The password is converted to a null-terminated big-endian BMPString as
+/// required by PKCS#12. Zero `iterations` is processed as a single
+/// iteration.
+pub fn pkcs12<D: Digest + BlockInput>(
+    password: &str,
+    salt: &[u8],
+    id: Pkcs12Id,
+    iterations: u32,
+    output: &mut [u8],
+) {
+    let v = D::BlockSize::USIZE;
+
+    let mut bmp = Vec::with_capacity(2 * password.len() + 2);
+    for c in password.encode_utf16() {
+        bmp.extend_from_slice(&c.to_be_bytes());
+    }
+    bmp.extend_from_slice(&[0, 0]);
+
+    let d = alloc::vec![id as u8; v];
+    let mut i = fill(salt, v);
+    i.extend(fill(&bmp, v));
+
+    for chunk in output.chunks_mut(D::output_size()) {
+        let mut a = D::new().chain(&d).chain(&i).finalize();
+        for _ in 1..iterations {
+            a = D::digest(&a);
+        }
+        chunk.copy_from_slice(&a[..chunk.len()]);
+
+        // I_j = (I_j + B + 1) mod 2^v for each v-byte block of I
+        let b: Vec<u8> = a.iter().cycle().take(v).copied().collect();
+        for block in i.chunks_mut(v) {
+            let mut carry = 1u16;
+            for (x, y) in block.iter_mut().zip(b.iter()).rev() {
+                carry += u16::from(*x) + u16::from(*y);
+                *x = carry as u8;
+                carry >>= 8;
+            }
+        }
+    }
+}
+
+/// Derive `key` and `iv` from `password` and optional `salt` using the
+/// PBKDF1 extension implemented by OpenSSL's `EVP_BytesToKey`.
+///
+/// Digests are chained until enough bytes are produced to fill `key` and
+/// then `iv`. The `openssl enc` command uses a single iteration. Zero
+/// `iterations` is processed as a single iteration.
+pub fn evp_bytes_to_key<D: Digest>(
+    password: &[u8],
+    salt: Option<&[u8; 8]>,
+    iterations: u32,
+    key: &mut [u8],
+    iv: &mut [u8],
+) {
+    let mut out = key.iter_mut().chain(iv.iter_mut()).peekable();
+    let mut prev = None;
+    while out.peek().is_some() {
+        let mut d = D::new();
+        if let Some(prev) = &prev {
+            d.update(prev);
+        }
+        d.update(password);
+        if let Some(salt) = salt {
+            d.update(salt);
+        }
+        let mut t = d.finalize();
+        for _ in 1..iterations {
+            t = D::digest(&t);
+        }
+        // `t` goes first, so no output byte is consumed once it is exhausted
+        for (b, o) in t.iter().zip(&mut out) {
+            *o = *b;
+        }
+        prev = Some(t);
+    }
+}
+
+/// Repeat `data` to fill a multiple of `v` bytes
+fn fill(data: &[u8], v: usize) -> Vec<u8> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+    let len = v * ((data.len() - 1) / v + 1);
+    data.iter().cycle().take(len).copied().collect()
+}
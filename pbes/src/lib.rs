@@ -0,0 +1,134 @@
+//! Password-based encryption schemes used to protect PKCS#8 private keys
+//! and PKCS#12 bundles, implemented on top of the block ciphers from this
+//! repository.
+//!
+//! # Supported schemes
+//! - PBES2 ([RFC 8018][1]) with PBKDF2 using HMAC-SHA-1 or HMAC-SHA-2 and
+//!   AES-CBC, DES-EDE3-CBC or RC2-CBC;
+//! - PBES1 ([RFC 8018][1]): `pbeWithMD5AndDES-CBC`, `pbeWithMD5AndRC2-CBC`,
+//!   `pbeWithSHA1AndDES-CBC` and `pbeWithSHA1AndRC2-CBC`;
+//! - PKCS#12 ([RFC 7292][2]): `pbeWithSHAAnd3-KeyTripleDES-CBC`,
+//!   `pbeWithSHAAnd2-KeyTripleDES-CBC`, `pbeWithSHAAnd128BitRC2-CBC` and
+//!   `pbeWithSHAAnd40BitRC2-CBC`.
+//!
+//! The underlying key derivation functions are available in the [`kdf`]
+//! module and encrypted PKCS#8 keys can be processed with the [`pkcs8`]
+//! module. Files produced by the `openssl enc` command can be read and
+//! written with the [`openssl_enc`] module.
+//!
+//! PBES1 and PKCS#12 schemes rely on weak ciphers and key derivation
+//! functions and should be used only to read legacy data.
+//!
+//! # Example
+//! ```
+//! use hex_literal::hex;
+//! use pbes::{EncryptionScheme, Pbes2, Pbes2Cipher, Pbkdf2Params, Prf};
+//!
+//! let scheme = EncryptionScheme::Pbes2(Pbes2 {
+//!     kdf: Pbkdf2Params {
+//!         salt: hex!("79d982e70df91a88").to_vec(),
+//!         iterations: 2048,
+//!         key_length: None,
+//!         prf: Prf::HmacSha256,
+//!     },
+//!     cipher: Pbes2Cipher::Aes256Cbc(hex!("b2d02d78b2efd9dff694cf8e0af40925")),
+//! });
+//!
+//! let ciphertext = scheme.encrypt(b"password", b"secret").unwrap();
+//! let parsed = EncryptionScheme::from_der(&scheme.to_der().unwrap()).unwrap();
+//! assert_eq!(parsed.decrypt(b"password", &ciphertext).unwrap(), b"secret");
+//! ```
+//!
+//! # Crate features
+//! - `std`: implements `std::error::Error` for [`Error`].
+//!
+//! [1]: https://tools.ietf.org/html/rfc8018
+//! [2]: https://tools.ietf.org/html/rfc7292
+#![no_std]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg",
+    html_favicon_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg"
+)]
+#![deny(unsafe_code)]
+#![warn(missing_docs, rust_2018_idioms)]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub use aes;
+pub use block_modes;
+pub use blowfish;
+pub use cast5;
+pub use cipher;
+pub use des;
+pub use idea;
+pub use rc2;
+pub use sm4;
+
+pub mod kdf;
+pub mod openssl_enc;
+pub mod pkcs8;
+
+mod der;
+mod scheme;
+
+pub use scheme::{
+    EncryptionScheme, Pbes1, Pbes1Algorithm, Pbes2, Pbes2Cipher, Pbkdf2Params, Pkcs12,
+    Pkcs12Algorithm, Prf,
+};
+
+use alloc::vec::Vec;
+use block_modes::{block_padding::Pkcs7, BlockMode};
+use cipher::{errors::InvalidLength, BlockCipher};
+use core::fmt;
+
+/// Password-based encryption error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Malformed DER encoding.
+    InvalidDer,
+    /// Algorithm identifier is not supported.
+    UnsupportedAlgorithm,
+    /// Invalid algorithm parameters, e.g. salt, IV or key length.
+    InvalidParameters,
+    /// Password is not valid UTF-8, required by PKCS#12 schemes.
+    InvalidPassword,
+    /// Invalid padding of the decrypted data, usually means that the
+    /// password is wrong.
+    DecryptionFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Error::InvalidDer => "invalid DER encoding",
+            Error::UnsupportedAlgorithm => "unsupported algorithm",
+            Error::InvalidParameters => "invalid algorithm parameters",
+            Error::InvalidPassword => "password is not valid UTF-8",
+            Error::DecryptionFailed => "decryption failed",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl From<InvalidLength> for Error {
+    fn from(_: InvalidLength) -> Self {
+        Error::InvalidParameters
+    }
+}
+
+/// Encrypt or decrypt `data` with PKCS#7 padding using `mode`
+fn crypt<C: BlockCipher, M: BlockMode<C, Pkcs7>>(
+    mode: M,
+    data: &[u8],
+    encrypt: bool,
+) -> Result<Vec<u8>, Error> {
+    if encrypt {
+        Ok(mode.encrypt_vec(data))
+    } else {
+        mode.decrypt_vec(data).map_err(|_| Error::DecryptionFailed)
+    }
+}
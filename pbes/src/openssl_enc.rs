@@ -0,0 +1,386 @@
+//! Container format of the `openssl enc` command.
+//!
+//! Salted files start with the `Salted__` magic followed by an 8-byte salt
+//! and the ciphertext. Files produced with the `-nosalt` option contain only
+//! the ciphertext. Key and IV are derived from the password either with
+//! [`evp_bytes_to_key`] (the default, with SHA-256 since OpenSSL 1.1.0 and
+//! MD5 before) or with PBKDF2 when the `-pbkdf2` or `-iter` options are
+//! used.
+//!
+//! Only the padded CBC and ECB modes are supported.
+//!
+//! # Example
+//! ```
+//! use pbes::openssl_enc::{self, Cipher, KeyDerivation, Md};
+//!
+//! let cipher = Cipher::from_name("aes-256-cbc").unwrap();
+//! let kdf = KeyDerivation::Pbkdf2 { md: Md::Sha256, iterations: 10_000 };
+//! let salt = [0x42; 8];
+//!
+//! let data = openssl_enc::encrypt(cipher, kdf, b"password", Some(&salt), b"secret").unwrap();
+//! assert_eq!(&data[..8], openssl_enc::MAGIC);
+//! let res = openssl_enc::decrypt(cipher, kdf, b"password", &data).unwrap();
+//! assert_eq!(res, b"secret");
+//! ```
+//!
+//! [`evp_bytes_to_key`]: crate::kdf::evp_bytes_to_key
+
+use crate::{kdf, Error};
+use aes::{Aes128, Aes192, Aes256};
+use alloc::vec::Vec;
+use block_modes::{block_padding::Pkcs7, BlockMode, Cbc, Ecb};
+use blowfish::Blowfish;
+use cast5::Cast5;
+use cipher::{
+    generic_array::GenericArray, BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use core::convert::TryInto;
+use des::TdesEde3;
+use idea::Idea;
+use md5::Md5;
+use rc2::Rc2;
+use sha1::Sha1;
+use sha2::{Sha256, Sha384, Sha512};
+use sm4::Sm4;
+
+/// Magic bytes at the start of salted files
+pub const MAGIC: &[u8; 8] = b"Salted__";
+
+/// Block cipher algorithm with the key length used by `openssl enc`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Algorithm {
+    /// AES-128
+    Aes128,
+    /// AES-192
+    Aes192,
+    /// AES-256
+    Aes256,
+    /// Three-key Triple DES
+    TdesEde3,
+    /// Blowfish with a 128-bit key
+    Blowfish,
+    /// CAST5 with a 128-bit key
+    Cast5,
+    /// IDEA
+    Idea,
+    /// RC2 with a 128-bit key
+    Rc2,
+    /// RC2 with a 40-bit key
+    Rc2_40,
+    /// RC2 with a 64-bit key
+    Rc2_64,
+    /// SM4
+    Sm4,
+}
+
+/// Block cipher mode of operation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// CBC mode with PKCS#7 padding.
+    Cbc,
+    /// ECB mode with PKCS#7 padding.
+    Ecb,
+}
+
+/// Cipher identified by an OpenSSL cipher name.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cipher {
+    /// Block cipher algorithm.
+    pub algorithm: Algorithm,
+    /// Mode of operation.
+    pub mode: Mode,
+}
+
+/// Message digest selected with the `-md` option.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Md {
+    /// MD5, the default before OpenSSL 1.1.0.
+    Md5,
+    /// SHA-1
+    Sha1,
+    /// SHA-256, the default since OpenSSL 1.1.0.
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+}
+
+/// Key and IV derivation function.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyDerivation {
+    /// `EVP_BytesToKey` with a single iteration, used by default.
+    BytesToKey(Md),
+    /// PBKDF2 with HMAC, used with the `-pbkdf2` or `-iter` options.
+    ///
+    /// OpenSSL uses 10000 iterations unless `-iter` is given.
+    Pbkdf2 {
+        /// Digest used by HMAC.
+        md: Md,
+        /// Iteration count.
+        iterations: u32,
+    },
+}
+
+/// OpenSSL names and their aliases
+const NAMES: &[(&str, Algorithm, Mode)] = &[
+    ("aes-128-cbc", Algorithm::Aes128, Mode::Cbc),
+    ("aes-128-ecb", Algorithm::Aes128, Mode::Ecb),
+    ("aes128", Algorithm::Aes128, Mode::Cbc),
+    ("aes-192-cbc", Algorithm::Aes192, Mode::Cbc),
+    ("aes-192-ecb", Algorithm::Aes192, Mode::Ecb),
+    ("aes192", Algorithm::Aes192, Mode::Cbc),
+    ("aes-256-cbc", Algorithm::Aes256, Mode::Cbc),
+    ("aes-256-ecb", Algorithm::Aes256, Mode::Ecb),
+    ("aes256", Algorithm::Aes256, Mode::Cbc),
+    ("des-ede3-cbc", Algorithm::TdesEde3, Mode::Cbc),
+    ("des-ede3", Algorithm::TdesEde3, Mode::Ecb),
+    ("des3", Algorithm::TdesEde3, Mode::Cbc),
+    ("bf-cbc", Algorithm::Blowfish, Mode::Cbc),
+    ("bf-ecb", Algorithm::Blowfish, Mode::Ecb),
+    ("bf", Algorithm::Blowfish, Mode::Cbc),
+    ("blowfish", Algorithm::Blowfish, Mode::Cbc),
+    ("cast5-cbc", Algorithm::Cast5, Mode::Cbc),
+    ("cast5-ecb", Algorithm::Cast5, Mode::Ecb),
+    ("cast-cbc", Algorithm::Cast5, Mode::Cbc),
+    ("cast", Algorithm::Cast5, Mode::Cbc),
+    ("idea-cbc", Algorithm::Idea, Mode::Cbc),
+    ("idea-ecb", Algorithm::Idea, Mode::Ecb),
+    ("idea", Algorithm::Idea, Mode::Cbc),
+    ("rc2-cbc", Algorithm::Rc2, Mode::Cbc),
+    ("rc2-ecb", Algorithm::Rc2, Mode::Ecb),
+    ("rc2", Algorithm::Rc2, Mode::Cbc),
+    ("rc2-128", Algorithm::Rc2, Mode::Cbc),
+    ("rc2-40-cbc", Algorithm::Rc2_40, Mode::Cbc),
+    ("rc2-40", Algorithm::Rc2_40, Mode::Cbc),
+    ("rc2-64-cbc", Algorithm::Rc2_64, Mode::Cbc),
+    ("rc2-64", Algorithm::Rc2_64, Mode::Cbc),
+    ("sm4-cbc", Algorithm::Sm4, Mode::Cbc),
+    ("sm4-ecb", Algorithm::Sm4, Mode::Ecb),
+    ("sm4", Algorithm::Sm4, Mode::Cbc),
+];
+
+impl Cipher {
+    /// Find cipher by its OpenSSL name, e.g. `aes-256-cbc` or `des3`. The
+    /// name is case-insensitive and may start with `-`.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        let name = name.trim_start_matches('-');
+        NAMES
+            .iter()
+            .find(|(n, _, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, algorithm, mode)| Cipher { algorithm, mode })
+            .ok_or(Error::UnsupportedAlgorithm)
+    }
+
+    /// Key length in bytes.
+    pub fn key_len(&self) -> usize {
+        match self.algorithm {
+            Algorithm::Rc2_40 => 5,
+            Algorithm::Rc2_64 => 8,
+            Algorithm::Aes192 | Algorithm::TdesEde3 => 24,
+            Algorithm::Aes256 => 32,
+            _ => 16,
+        }
+    }
+
+    /// Block length in bytes.
+    pub fn block_len(&self) -> usize {
+        match self.algorithm {
+            Algorithm::Aes128 | Algorithm::Aes192 | Algorithm::Aes256 | Algorithm::Sm4 => 16,
+            _ => 8,
+        }
+    }
+
+    /// IV length in bytes, zero for the ECB mode.
+    pub fn iv_len(&self) -> usize {
+        match self.mode {
+            Mode::Cbc => self.block_len(),
+            Mode::Ecb => 0,
+        }
+    }
+
+    /// Encrypt `plaintext` with PKCS#7 padding.
+    pub fn encrypt(&self, key: &[u8], iv: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        self.crypt(key, iv, plaintext, true)
+    }
+
+    /// Decrypt `ciphertext` and remove PKCS#7 padding.
+    pub fn decrypt(&self, key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+        self.crypt(key, iv, ciphertext, false)
+    }
+
+    fn crypt(&self, key: &[u8], iv: &[u8], data: &[u8], enc: bool) -> Result<Vec<u8>, Error> {
+        if key.len() != self.key_len() || iv.len() != self.iv_len() {
+            return Err(Error::InvalidParameters);
+        }
+        let mode = self.mode;
+        match self.algorithm {
+            Algorithm::Aes128 => mode.crypt(Aes128::new_from_slice(key)?, iv, data, enc),
+            Algorithm::Aes192 => mode.crypt(Aes192::new_from_slice(key)?, iv, data, enc),
+            Algorithm::Aes256 => mode.crypt(Aes256::new_from_slice(key)?, iv, data, enc),
+            Algorithm::TdesEde3 => mode.crypt(TdesEde3::new_from_slice(key)?, iv, data, enc),
+            Algorithm::Blowfish => mode.crypt(<Blowfish>::new_from_slice(key)?, iv, data, enc),
+            Algorithm::Cast5 => mode.crypt(Cast5::new_from_slice(key)?, iv, data, enc),
+            Algorithm::Idea => mode.crypt(Idea::new_from_slice(key)?, iv, data, enc),
+            Algorithm::Rc2 | Algorithm::Rc2_40 | Algorithm::Rc2_64 => {
+                let cipher = Rc2::new_with_eff_key_len(key, 8 * key.len());
+                mode.crypt(cipher, iv, data, enc)
+            }
+            Algorithm::Sm4 => mode.crypt(Sm4::new_from_slice(key)?, iv, data, enc),
+        }
+    }
+}
+
+impl Mode {
+    fn crypt<C>(self, cipher: C, iv: &[u8], data: &[u8], enc: bool) -> Result<Vec<u8>, Error>
+    where
+        C: BlockCipher + BlockEncrypt + BlockDecrypt,
+    {
+        match self {
+            Mode::Cbc => {
+                let iv = GenericArray::from_slice(iv);
+                crate::crypt(Cbc::<C, Pkcs7>::new(cipher, iv), data, enc)
+            }
+            Mode::Ecb => crate::crypt(Ecb::<C, Pkcs7>::new(cipher, &Default::default()), data, enc),
+        }
+    }
+}
+
+impl Md {
+    /// Find digest by its OpenSSL name, e.g. `sha256`. The name is
+    /// case-insensitive.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        let md = match name.to_ascii_lowercase().as_str() {
+            "md5" => Md::Md5,
+            "sha1" => Md::Sha1,
+            "sha256" => Md::Sha256,
+            "sha384" => Md::Sha384,
+            "sha512" => Md::Sha512,
+            _ => return Err(Error::UnsupportedAlgorithm),
+        };
+        Ok(md)
+    }
+}
+
+impl Default for KeyDerivation {
+    fn default() -> Self {
+        KeyDerivation::BytesToKey(Md::Sha256)
+    }
+}
+
+impl KeyDerivation {
+    /// Derive key and IV for `cipher` from `password` and optional `salt`.
+    ///
+    /// Returns buffers of 32 and 16 bytes which should be truncated to
+    /// [`Cipher::key_len`] and [`Cipher::iv_len`] respectively.
+    pub fn derive(
+        &self,
+        cipher: Cipher,
+        password: &[u8],
+        salt: Option<&[u8; 8]>,
+    ) -> ([u8; 32], [u8; 16]) {
+        let mut key = [0u8; 32];
+        let mut iv = [0u8; 16];
+        self.derive_into(
+            password,
+            salt,
+            &mut key[..cipher.key_len()],
+            &mut iv[..cipher.iv_len()],
+        );
+        (key, iv)
+    }
+
+    /// Derive `key` and `iv` of arbitrary lengths from `password` and
+    /// optional `salt`, e.g. for ciphers not covered by [`Cipher`].
+    pub fn derive_into(
+        &self,
+        password: &[u8],
+        salt: Option<&[u8; 8]>,
+        key: &mut [u8],
+        iv: &mut [u8],
+    ) {
+        let (k, i) = (key, iv);
+        match *self {
+            KeyDerivation::BytesToKey(md) => match md {
+                Md::Md5 => kdf::evp_bytes_to_key::<Md5>(password, salt, 1, k, i),
+                Md::Sha1 => kdf::evp_bytes_to_key::<Sha1>(password, salt, 1, k, i),
+                Md::Sha256 => kdf::evp_bytes_to_key::<Sha256>(password, salt, 1, k, i),
+                Md::Sha384 => kdf::evp_bytes_to_key::<Sha384>(password, salt, 1, k, i),
+                Md::Sha512 => kdf::evp_bytes_to_key::<Sha512>(password, salt, 1, k, i),
+            },
+            KeyDerivation::Pbkdf2 { md, iterations } => {
+                // key and IV are derived together
+                let mut buf = alloc::vec![0u8; k.len() + i.len()];
+                let salt = salt.map(|s| &s[..]).unwrap_or(&[]);
+                match md {
+                    Md::Md5 => kdf::pbkdf2::<Md5>(password, salt, iterations, &mut buf),
+                    Md::Sha1 => kdf::pbkdf2::<Sha1>(password, salt, iterations, &mut buf),
+                    Md::Sha256 => kdf::pbkdf2::<Sha256>(password, salt, iterations, &mut buf),
+                    Md::Sha384 => kdf::pbkdf2::<Sha384>(password, salt, iterations, &mut buf),
+                    Md::Sha512 => kdf::pbkdf2::<Sha512>(password, salt, iterations, &mut buf),
+                }
+                let (a, b) = buf.split_at(k.len());
+                k.copy_from_slice(a);
+                i.copy_from_slice(b);
+            }
+        }
+    }
+}
+
+/// Header of salted files, i.e. the [`MAGIC`] bytes followed by `salt`.
+pub fn header(salt: &[u8; 8]) -> [u8; 16] {
+    let mut header = [0u8; 16];
+    header[..8].copy_from_slice(MAGIC);
+    header[8..].copy_from_slice(salt);
+    header
+}
+
+/// Encrypt `plaintext` like `openssl enc -e`.
+///
+/// If `salt` is `None` the output has no header, like with the `-nosalt`
+/// option. Otherwise `salt` must be generated randomly for each message.
+pub fn encrypt(
+    cipher: Cipher,
+    kdf: KeyDerivation,
+    password: &[u8],
+    salt: Option<&[u8; 8]>,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let (key, iv) = kdf.derive(cipher, password, salt);
+    let ciphertext = cipher.encrypt(&key[..cipher.key_len()], &iv[..cipher.iv_len()], plaintext)?;
+    let mut out = Vec::with_capacity(16 + ciphertext.len());
+    if let Some(salt) = salt {
+        out.extend_from_slice(&header(salt));
+    }
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt `data` like `openssl enc -d`.
+///
+/// If `data` starts with the [`MAGIC`] bytes, the salt is read from the
+/// header. Otherwise `data` is processed as produced with the `-nosalt`
+/// option.
+pub fn decrypt(
+    cipher: Cipher,
+    kdf: KeyDerivation,
+    password: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let (salt, ciphertext) = match parse(data) {
+        Some((salt, ciphertext)) => (Some(salt), ciphertext),
+        None => (None, data),
+    };
+    let (key, iv) = kdf.derive(cipher, password, salt);
+    cipher.decrypt(&key[..cipher.key_len()], &iv[..cipher.iv_len()], ciphertext)
+}
+
+/// Split salted `data` into salt and ciphertext, returns `None` if `data`
+/// does not start with the [`MAGIC`] bytes.
+pub fn parse(data: &[u8]) -> Option<(&[u8; 8], &[u8])> {
+    if data.len() < 16 || &data[..8] != MAGIC {
+        return None;
+    }
+    let salt = data[8..16].try_into().ok()?;
+    Some((salt, &data[16..]))
+}
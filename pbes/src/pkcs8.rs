@@ -0,0 +1,47 @@
+//! Encrypted PKCS#8 private keys (`EncryptedPrivateKeyInfo`) defined in
+//! [RFC 5208 section 6][1].
+//!
+//! [1]: https://tools.ietf.org/html/rfc5208#section-6
+
+use crate::{
+    der::{self, Reader},
+    EncryptionScheme, Error,
+};
+use alloc::vec::Vec;
+
+/// Decrypt DER encoded `EncryptedPrivateKeyInfo` and return DER encoded
+/// `PrivateKeyInfo`.
+pub fn decrypt(der: &[u8], password: &[u8]) -> Result<Vec<u8>, Error> {
+    let (scheme, data) = parse(der)?;
+    scheme.decrypt(password, data)
+}
+
+/// Parse DER encoded `EncryptedPrivateKeyInfo` and return the encryption
+/// scheme with the encrypted data.
+pub fn parse(der: &[u8]) -> Result<(EncryptionScheme, &[u8]), Error> {
+    let mut reader = Reader::new(der);
+    let mut info = reader.sequence()?;
+    reader.finish()?;
+    let scheme = EncryptionScheme::parse(info.sequence()?)?;
+    let data = info.read(der::OCTET_STRING)?;
+    info.finish()?;
+    Ok((scheme, data))
+}
+
+/// Encrypt DER encoded `PrivateKeyInfo` and return DER encoded
+/// `EncryptedPrivateKeyInfo`.
+pub fn encrypt(
+    scheme: &EncryptionScheme,
+    password: &[u8],
+    private_key_info: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let data = scheme.encrypt(password, private_key_info)?;
+    let mut alg_id = Vec::new();
+    scheme.encode(&mut alg_id)?;
+    let mut out = Vec::new();
+    der::write_sequence(&mut out, |out| {
+        out.extend_from_slice(&alg_id);
+        der::write(out, der::OCTET_STRING, &data);
+    });
+    Ok(out)
+}
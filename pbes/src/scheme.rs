@@ -0,0 +1,655 @@
+use crate::{
+    der::{self, Reader},
+    kdf::{self, Pkcs12Id},
+    Error,
+};
+use aes::{Aes128, Aes192, Aes256};
+use alloc::vec::Vec;
+use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
+use cipher::{
+    generic_array::GenericArray, BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use des::{Des, TdesEde2, TdesEde3};
+use md5::Md5;
+use rc2::Rc2;
+use sha1::Sha1;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+
+const PBE_MD5_DES: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x05, 0x03];
+const PBE_MD5_RC2: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x05, 0x06];
+const PBE_SHA1_DES: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x05, 0x0A];
+const PBE_SHA1_RC2: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x05, 0x0B];
+const PBKDF2: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x05, 0x0C];
+const PBES2: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x05, 0x0D];
+
+const PKCS12_3DES: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x0C, 0x01, 0x03];
+const PKCS12_2DES: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x0C, 0x01, 0x04];
+const PKCS12_RC2_128: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x0C, 0x01, 0x05];
+const PKCS12_RC2_40: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x0C, 0x01, 0x06];
+
+const HMAC_SHA1: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x02, 0x07];
+const HMAC_SHA224: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x02, 0x08];
+const HMAC_SHA256: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x02, 0x09];
+const HMAC_SHA384: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x02, 0x0A];
+const HMAC_SHA512: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x02, 0x0B];
+
+const AES_128_CBC: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x02];
+const AES_192_CBC: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x16];
+const AES_256_CBC: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2A];
+const DES_EDE3_CBC: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x03, 0x07];
+const RC2_CBC: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x03, 0x02];
+
+/// Password-based encryption scheme with its parameters, as described by a
+/// DER encoded `AlgorithmIdentifier`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EncryptionScheme {
+    /// PBES1 defined in RFC 8018.
+    Pbes1(Pbes1),
+    /// PBES2 defined in RFC 8018.
+    Pbes2(Pbes2),
+    /// Password-based encryption defined in PKCS#12 (RFC 7292).
+    Pkcs12(Pkcs12),
+}
+
+/// PBES1 parameters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pbes1 {
+    /// Digest and cipher combination.
+    pub algorithm: Pbes1Algorithm,
+    /// PBKDF1 salt.
+    pub salt: [u8; 8],
+    /// PBKDF1 iteration count.
+    pub iterations: u32,
+}
+
+/// PBES1 digest and cipher combination.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pbes1Algorithm {
+    /// `pbeWithMD5AndDES-CBC`
+    Md5Des,
+    /// `pbeWithMD5AndRC2-CBC` (64-bit effective key length)
+    Md5Rc2,
+    /// `pbeWithSHA1AndDES-CBC`
+    Sha1Des,
+    /// `pbeWithSHA1AndRC2-CBC` (64-bit effective key length)
+    Sha1Rc2,
+}
+
+/// PBES2 parameters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pbes2 {
+    /// PBKDF2 parameters.
+    pub kdf: Pbkdf2Params,
+    /// Encryption scheme.
+    pub cipher: Pbes2Cipher,
+}
+
+/// PBKDF2 parameters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pbkdf2Params {
+    /// Salt.
+    pub salt: Vec<u8>,
+    /// Iteration count.
+    pub iterations: u32,
+    /// Optional length of the derived key in bytes.
+    ///
+    /// It must match the key length of the cipher if present. For RC2 the
+    /// key length defaults to 16 bytes.
+    pub key_length: Option<u16>,
+    /// Pseudorandom function.
+    pub prf: Prf,
+}
+
+/// PBKDF2 pseudorandom function.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Prf {
+    /// `hmacWithSHA1`, the default PRF.
+    HmacSha1,
+    /// `hmacWithSHA224`
+    HmacSha224,
+    /// `hmacWithSHA256`
+    HmacSha256,
+    /// `hmacWithSHA384`
+    HmacSha384,
+    /// `hmacWithSHA512`
+    HmacSha512,
+}
+
+/// PBES2 encryption scheme with its initialization vector.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pbes2Cipher {
+    /// AES-128 in the CBC mode.
+    Aes128Cbc([u8; 16]),
+    /// AES-192 in the CBC mode.
+    Aes192Cbc([u8; 16]),
+    /// AES-256 in the CBC mode.
+    Aes256Cbc([u8; 16]),
+    /// Three-key Triple DES in the CBC mode.
+    DesEde3Cbc([u8; 8]),
+    /// RC2 in the CBC mode.
+    Rc2Cbc {
+        /// Effective key length in bits: 32, 40, 64, 128 or from 256 to
+        /// 1024.
+        eff_key_bits: u16,
+        /// Initialization vector.
+        iv: [u8; 8],
+    },
+}
+
+/// PKCS#12 password-based encryption parameters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pkcs12 {
+    /// Cipher used with the SHA-1 based key derivation.
+    pub algorithm: Pkcs12Algorithm,
+    /// Salt.
+    pub salt: Vec<u8>,
+    /// Iteration count.
+    pub iterations: u32,
+}
+
+/// PKCS#12 password-based encryption algorithm.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pkcs12Algorithm {
+    /// `pbeWithSHAAnd3-KeyTripleDES-CBC`
+    ShaAnd3KeyTripleDesCbc,
+    /// `pbeWithSHAAnd2-KeyTripleDES-CBC`
+    ShaAnd2KeyTripleDesCbc,
+    /// `pbeWithSHAAnd128BitRC2-CBC`
+    ShaAnd128BitRc2Cbc,
+    /// `pbeWithSHAAnd40BitRC2-CBC`
+    ShaAnd40BitRc2Cbc,
+}
+
+impl EncryptionScheme {
+    /// Parse DER encoded `AlgorithmIdentifier`.
+    pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(der);
+        let alg_id = reader.sequence()?;
+        reader.finish()?;
+        Self::parse(alg_id)
+    }
+
+    /// Encode scheme as DER encoded `AlgorithmIdentifier`.
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] for RC2 effective key lengths
+    /// which can not be encoded.
+    pub fn to_der(&self) -> Result<Vec<u8>, Error> {
+        let mut out = Vec::new();
+        self.encode(&mut out)?;
+        Ok(out)
+    }
+
+    /// Encrypt `plaintext` with PKCS#7 padding.
+    ///
+    /// PKCS#12 schemes require `password` to be valid UTF-8.
+    pub fn encrypt(&self, password: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let (cipher, key, iv) = self.derive(password)?;
+        cipher.encrypt(
+            &key[..cipher.key_len()],
+            &iv[..cipher.block_len()],
+            plaintext,
+        )
+    }
+
+    /// Decrypt `ciphertext` and remove PKCS#7 padding.
+    ///
+    /// Invalid padding is reported as [`Error::DecryptionFailed`], which
+    /// usually means that the password is wrong.
+    pub fn decrypt(&self, password: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+        let (cipher, key, iv) = self.derive(password)?;
+        cipher.decrypt(
+            &key[..cipher.key_len()],
+            &iv[..cipher.block_len()],
+            ciphertext,
+        )
+    }
+
+    /// Parse content of `AlgorithmIdentifier`
+    pub(crate) fn parse(mut alg_id: Reader<'_>) -> Result<Self, Error> {
+        let oid = alg_id.read(der::OID)?;
+        let mut params = alg_id.sequence()?;
+        alg_id.finish()?;
+
+        let scheme = if oid == PBES2 {
+            let mut kdf = params.sequence()?;
+            let mut enc = params.sequence()?;
+            params.finish()?;
+            if kdf.read(der::OID)? != PBKDF2 {
+                return Err(Error::UnsupportedAlgorithm);
+            }
+            let kdf_params = Pbkdf2Params::parse(kdf.sequence()?)?;
+            kdf.finish()?;
+            let cipher = Pbes2Cipher::parse(&mut enc)?;
+            enc.finish()?;
+            EncryptionScheme::Pbes2(Pbes2 {
+                kdf: kdf_params,
+                cipher,
+            })
+        } else {
+            let salt = params.read(der::OCTET_STRING)?;
+            let iterations = params.read_u32()?;
+            params.finish()?;
+            if iterations == 0 {
+                return Err(Error::InvalidParameters);
+            }
+
+            if let Some(algorithm) = Pbes1Algorithm::from_oid(oid) {
+                if salt.len() != 8 {
+                    return Err(Error::InvalidParameters);
+                }
+                let mut buf = [0; 8];
+                buf.copy_from_slice(salt);
+                EncryptionScheme::Pbes1(Pbes1 {
+                    algorithm,
+                    salt: buf,
+                    iterations,
+                })
+            } else if let Some(algorithm) = Pkcs12Algorithm::from_oid(oid) {
+                EncryptionScheme::Pkcs12(Pkcs12 {
+                    algorithm,
+                    salt: salt.to_vec(),
+                    iterations,
+                })
+            } else {
+                return Err(Error::UnsupportedAlgorithm);
+            }
+        };
+        Ok(scheme)
+    }
+
+    /// Append `AlgorithmIdentifier` to `out`
+    pub(crate) fn encode(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        // cipher parameters are the only part which may fail to encode
+        let mut cipher = Vec::new();
+        if let EncryptionScheme::Pbes2(p) = self {
+            p.cipher.encode(&mut cipher)?;
+        }
+        der::write_sequence(out, |out| match self {
+            EncryptionScheme::Pbes1(p) => {
+                der::write(out, der::OID, p.algorithm.oid());
+                write_pbe_params(out, &p.salt, p.iterations);
+            }
+            EncryptionScheme::Pkcs12(p) => {
+                der::write(out, der::OID, p.algorithm.oid());
+                write_pbe_params(out, &p.salt, p.iterations);
+            }
+            EncryptionScheme::Pbes2(p) => {
+                der::write(out, der::OID, PBES2);
+                der::write_sequence(out, |out| {
+                    der::write_sequence(out, |out| {
+                        der::write(out, der::OID, PBKDF2);
+                        p.kdf.encode(out);
+                    });
+                    der::write_sequence(out, |out| out.extend_from_slice(&cipher));
+                });
+            }
+        });
+        Ok(())
+    }
+
+    /// Derive cipher, key and IV from the password
+    fn derive(&self, password: &[u8]) -> Result<(Cipher, [u8; 32], [u8; 16]), Error> {
+        let mut key = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let cipher = match self {
+            EncryptionScheme::Pbes1(p) => {
+                let mut dk = [0u8; 16];
+                match p.algorithm {
+                    Pbes1Algorithm::Md5Des | Pbes1Algorithm::Md5Rc2 => {
+                        kdf::pbkdf1::<Md5>(password, &p.salt, p.iterations, &mut dk)?
+                    }
+                    Pbes1Algorithm::Sha1Des | Pbes1Algorithm::Sha1Rc2 => {
+                        kdf::pbkdf1::<Sha1>(password, &p.salt, p.iterations, &mut dk)?
+                    }
+                }
+                key[..8].copy_from_slice(&dk[..8]);
+                iv[..8].copy_from_slice(&dk[8..]);
+                match p.algorithm {
+                    Pbes1Algorithm::Md5Des | Pbes1Algorithm::Sha1Des => Cipher::Des,
+                    Pbes1Algorithm::Md5Rc2 | Pbes1Algorithm::Sha1Rc2 => Cipher::Rc2 {
+                        key_len: 8,
+                        eff_key_bits: 64,
+                    },
+                }
+            }
+            EncryptionScheme::Pbes2(p) => {
+                let cipher = p.cipher.cipher(p.kdf.key_length)?;
+                let key = &mut key[..cipher.key_len()];
+                let (salt, iterations) = (&p.kdf.salt, p.kdf.iterations);
+                match p.kdf.prf {
+                    Prf::HmacSha1 => kdf::pbkdf2::<Sha1>(password, salt, iterations, key),
+                    Prf::HmacSha224 => kdf::pbkdf2::<Sha224>(password, salt, iterations, key),
+                    Prf::HmacSha256 => kdf::pbkdf2::<Sha256>(password, salt, iterations, key),
+                    Prf::HmacSha384 => kdf::pbkdf2::<Sha384>(password, salt, iterations, key),
+                    Prf::HmacSha512 => kdf::pbkdf2::<Sha512>(password, salt, iterations, key),
+                }
+                let param_iv = p.cipher.iv();
+                iv[..param_iv.len()].copy_from_slice(param_iv);
+                cipher
+            }
+            EncryptionScheme::Pkcs12(p) => {
+                let password =
+                    core::str::from_utf8(password).map_err(|_| Error::InvalidPassword)?;
+                let cipher = p.algorithm.cipher();
+                let (salt, iterations) = (&p.salt, p.iterations);
+                let key = &mut key[..cipher.key_len()];
+                kdf::pkcs12::<Sha1>(password, salt, Pkcs12Id::Key, iterations, key);
+                let iv = &mut iv[..cipher.block_len()];
+                kdf::pkcs12::<Sha1>(password, salt, Pkcs12Id::Iv, iterations, iv);
+                cipher
+            }
+        };
+        Ok((cipher, key, iv))
+    }
+}
+
+impl Pbes1Algorithm {
+    fn from_oid(oid: &[u8]) -> Option<Self> {
+        match oid {
+            PBE_MD5_DES => Some(Pbes1Algorithm::Md5Des),
+            PBE_MD5_RC2 => Some(Pbes1Algorithm::Md5Rc2),
+            PBE_SHA1_DES => Some(Pbes1Algorithm::Sha1Des),
+            PBE_SHA1_RC2 => Some(Pbes1Algorithm::Sha1Rc2),
+            _ => None,
+        }
+    }
+
+    fn oid(self) -> &'static [u8] {
+        match self {
+            Pbes1Algorithm::Md5Des => PBE_MD5_DES,
+            Pbes1Algorithm::Md5Rc2 => PBE_MD5_RC2,
+            Pbes1Algorithm::Sha1Des => PBE_SHA1_DES,
+            Pbes1Algorithm::Sha1Rc2 => PBE_SHA1_RC2,
+        }
+    }
+}
+
+impl Pkcs12Algorithm {
+    fn from_oid(oid: &[u8]) -> Option<Self> {
+        match oid {
+            PKCS12_3DES => Some(Pkcs12Algorithm::ShaAnd3KeyTripleDesCbc),
+            PKCS12_2DES => Some(Pkcs12Algorithm::ShaAnd2KeyTripleDesCbc),
+            PKCS12_RC2_128 => Some(Pkcs12Algorithm::ShaAnd128BitRc2Cbc),
+            PKCS12_RC2_40 => Some(Pkcs12Algorithm::ShaAnd40BitRc2Cbc),
+            _ => None,
+        }
+    }
+
+    fn oid(self) -> &'static [u8] {
+        match self {
+            Pkcs12Algorithm::ShaAnd3KeyTripleDesCbc => PKCS12_3DES,
+            Pkcs12Algorithm::ShaAnd2KeyTripleDesCbc => PKCS12_2DES,
+            Pkcs12Algorithm::ShaAnd128BitRc2Cbc => PKCS12_RC2_128,
+            Pkcs12Algorithm::ShaAnd40BitRc2Cbc => PKCS12_RC2_40,
+        }
+    }
+
+    fn cipher(self) -> Cipher {
+        match self {
+            Pkcs12Algorithm::ShaAnd3KeyTripleDesCbc => Cipher::TdesEde3,
+            Pkcs12Algorithm::ShaAnd2KeyTripleDesCbc => Cipher::TdesEde2,
+            Pkcs12Algorithm::ShaAnd128BitRc2Cbc => Cipher::Rc2 {
+                key_len: 16,
+                eff_key_bits: 128,
+            },
+            Pkcs12Algorithm::ShaAnd40BitRc2Cbc => Cipher::Rc2 {
+                key_len: 5,
+                eff_key_bits: 40,
+            },
+        }
+    }
+}
+
+impl Pbkdf2Params {
+    fn parse(mut params: Reader<'_>) -> Result<Self, Error> {
+        let salt = params.read(der::OCTET_STRING)?.to_vec();
+        let iterations = params.read_u32()?;
+        if iterations == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let key_length = match params.peek() {
+            Some(der::INTEGER) => match params.read_u32()? {
+                n @ 1..=32 => Some(n as u16),
+                _ => return Err(Error::InvalidParameters),
+            },
+            _ => None,
+        };
+        let prf = if params.is_empty() {
+            Prf::HmacSha1
+        } else {
+            let mut alg_id = params.sequence()?;
+            let prf = match alg_id.read(der::OID)? {
+                HMAC_SHA1 => Prf::HmacSha1,
+                HMAC_SHA224 => Prf::HmacSha224,
+                HMAC_SHA256 => Prf::HmacSha256,
+                HMAC_SHA384 => Prf::HmacSha384,
+                HMAC_SHA512 => Prf::HmacSha512,
+                _ => return Err(Error::UnsupportedAlgorithm),
+            };
+            if !alg_id.is_empty() && !alg_id.read(der::NULL)?.is_empty() {
+                return Err(Error::InvalidDer);
+            }
+            alg_id.finish()?;
+            prf
+        };
+        params.finish()?;
+        Ok(Self {
+            salt,
+            iterations,
+            key_length,
+            prf,
+        })
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        der::write_sequence(out, |out| {
+            der::write(out, der::OCTET_STRING, &self.salt);
+            der::write_u32(out, self.iterations);
+            if let Some(n) = self.key_length {
+                der::write_u32(out, n.into());
+            }
+            let oid = match self.prf {
+                // DEFAULT value is omitted in DER
+                Prf::HmacSha1 => return,
+                Prf::HmacSha224 => HMAC_SHA224,
+                Prf::HmacSha256 => HMAC_SHA256,
+                Prf::HmacSha384 => HMAC_SHA384,
+                Prf::HmacSha512 => HMAC_SHA512,
+            };
+            der::write_sequence(out, |out| {
+                der::write(out, der::OID, oid);
+                der::write(out, der::NULL, &[]);
+            });
+        });
+    }
+}
+
+impl Pbes2Cipher {
+    fn parse(enc: &mut Reader<'_>) -> Result<Self, Error> {
+        let oid = enc.read(der::OID)?;
+        let cipher = match oid {
+            AES_128_CBC | AES_192_CBC | AES_256_CBC => {
+                let mut iv = [0; 16];
+                read_iv(enc, &mut iv)?;
+                match oid {
+                    AES_128_CBC => Pbes2Cipher::Aes128Cbc(iv),
+                    AES_192_CBC => Pbes2Cipher::Aes192Cbc(iv),
+                    _ => Pbes2Cipher::Aes256Cbc(iv),
+                }
+            }
+            DES_EDE3_CBC => {
+                let mut iv = [0; 8];
+                read_iv(enc, &mut iv)?;
+                Pbes2Cipher::DesEde3Cbc(iv)
+            }
+            RC2_CBC => {
+                let mut params = enc.sequence()?;
+                // absent version means 32 effective key bits
+                let eff_key_bits = match params.peek() {
+                    Some(der::INTEGER) => match params.read_u32()? {
+                        160 => 40,
+                        120 => 64,
+                        58 => 128,
+                        v @ 256..=1024 => v as u16,
+                        _ => return Err(Error::UnsupportedAlgorithm),
+                    },
+                    _ => 32,
+                };
+                let mut iv = [0; 8];
+                read_iv(&mut params, &mut iv)?;
+                params.finish()?;
+                Pbes2Cipher::Rc2Cbc { eff_key_bits, iv }
+            }
+            _ => return Err(Error::UnsupportedAlgorithm),
+        };
+        Ok(cipher)
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        match self {
+            Pbes2Cipher::Aes128Cbc(iv) => write_cipher(out, AES_128_CBC, iv),
+            Pbes2Cipher::Aes192Cbc(iv) => write_cipher(out, AES_192_CBC, iv),
+            Pbes2Cipher::Aes256Cbc(iv) => write_cipher(out, AES_256_CBC, iv),
+            Pbes2Cipher::DesEde3Cbc(iv) => write_cipher(out, DES_EDE3_CBC, iv),
+            Pbes2Cipher::Rc2Cbc { eff_key_bits, iv } => {
+                // other lengths below 256 bits would have to be encoded with
+                // the full table of RFC 2268
+                let version = match *eff_key_bits {
+                    32 => None,
+                    40 => Some(160),
+                    64 => Some(120),
+                    128 => Some(58),
+                    n @ 256..=1024 => Some(n.into()),
+                    _ => return Err(Error::UnsupportedAlgorithm),
+                };
+                der::write(out, der::OID, RC2_CBC);
+                der::write_sequence(out, |out| {
+                    if let Some(version) = version {
+                        der::write_u32(out, version);
+                    }
+                    der::write(out, der::OCTET_STRING, iv);
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn iv(&self) -> &[u8] {
+        match self {
+            Pbes2Cipher::Aes128Cbc(iv)
+            | Pbes2Cipher::Aes192Cbc(iv)
+            | Pbes2Cipher::Aes256Cbc(iv) => iv,
+            Pbes2Cipher::DesEde3Cbc(iv) | Pbes2Cipher::Rc2Cbc { iv, .. } => iv,
+        }
+    }
+
+    fn cipher(&self, key_length: Option<u16>) -> Result<Cipher, Error> {
+        let cipher = match *self {
+            Pbes2Cipher::Aes128Cbc(_) => Cipher::Aes128,
+            Pbes2Cipher::Aes192Cbc(_) => Cipher::Aes192,
+            Pbes2Cipher::Aes256Cbc(_) => Cipher::Aes256,
+            Pbes2Cipher::DesEde3Cbc(_) => Cipher::TdesEde3,
+            Pbes2Cipher::Rc2Cbc { eff_key_bits, .. } => {
+                let key_len = key_length.unwrap_or(16);
+                match (key_len, eff_key_bits) {
+                    (1..=32, 32) | (1..=32, 40) | (1..=32, 64) | (1..=32, 128) => {}
+                    (1..=32, 256..=1024) => {}
+                    _ => return Err(Error::InvalidParameters),
+                }
+                Cipher::Rc2 {
+                    key_len: key_len.into(),
+                    eff_key_bits: eff_key_bits.into(),
+                }
+            }
+        };
+        match key_length {
+            Some(n) if usize::from(n) != cipher.key_len() => Err(Error::InvalidParameters),
+            _ => Ok(cipher),
+        }
+    }
+}
+
+/// Block cipher used in the CBC mode by a scheme
+#[derive(Clone, Copy, Debug)]
+enum Cipher {
+    Des,
+    TdesEde2,
+    TdesEde3,
+    Aes128,
+    Aes192,
+    Aes256,
+    Rc2 { key_len: usize, eff_key_bits: usize },
+}
+
+impl Cipher {
+    fn key_len(self) -> usize {
+        match self {
+            Cipher::Des => 8,
+            Cipher::TdesEde2 | Cipher::Aes128 => 16,
+            Cipher::TdesEde3 | Cipher::Aes192 => 24,
+            Cipher::Aes256 => 32,
+            Cipher::Rc2 { key_len, .. } => key_len,
+        }
+    }
+
+    fn block_len(self) -> usize {
+        match self {
+            Cipher::Aes128 | Cipher::Aes192 | Cipher::Aes256 => 16,
+            _ => 8,
+        }
+    }
+
+    fn encrypt(self, key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>, Error> {
+        self.apply(key, iv, data, true)
+    }
+
+    fn decrypt(self, key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>, Error> {
+        self.apply(key, iv, data, false)
+    }
+
+    fn apply(self, key: &[u8], iv: &[u8], data: &[u8], enc: bool) -> Result<Vec<u8>, Error> {
+        match self {
+            Cipher::Des => cbc(Des::new_from_slice(key)?, iv, data, enc),
+            Cipher::TdesEde2 => cbc(TdesEde2::new_from_slice(key)?, iv, data, enc),
+            Cipher::TdesEde3 => cbc(TdesEde3::new_from_slice(key)?, iv, data, enc),
+            Cipher::Aes128 => cbc(Aes128::new_from_slice(key)?, iv, data, enc),
+            Cipher::Aes192 => cbc(Aes192::new_from_slice(key)?, iv, data, enc),
+            Cipher::Aes256 => cbc(Aes256::new_from_slice(key)?, iv, data, enc),
+            Cipher::Rc2 { eff_key_bits, .. } => {
+                cbc(Rc2::new_with_eff_key_len(key, eff_key_bits), iv, data, enc)
+            }
+        }
+    }
+}
+
+fn cbc<C>(cipher: C, iv: &[u8], data: &[u8], enc: bool) -> Result<Vec<u8>, Error>
+where
+    C: BlockCipher + BlockEncrypt + BlockDecrypt,
+{
+    crate::crypt(
+        Cbc::<C, Pkcs7>::new(cipher, GenericArray::from_slice(iv)),
+        data,
+        enc,
+    )
+}
+
+fn read_iv(reader: &mut Reader<'_>, iv: &mut [u8]) -> Result<(), Error> {
+    let data = reader.read(der::OCTET_STRING)?;
+    if data.len() != iv.len() {
+        return Err(Error::InvalidParameters);
+    }
+    iv.copy_from_slice(data);
+    Ok(())
+}
+
+fn write_cipher(out: &mut Vec<u8>, oid: &[u8], iv: &[u8]) {
+    der::write(out, der::OID, oid);
+    der::write(out, der::OCTET_STRING, iv);
+}
+
+fn write_pbe_params(out: &mut Vec<u8>, salt: &[u8], iterations: u32) {
+    der::write_sequence(out, |out| {
+        der::write(out, der::OCTET_STRING, salt);
+        der::write_u32(out, iterations);
+    });
+}
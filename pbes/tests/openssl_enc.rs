@@ -0,0 +1,274 @@
+//! Test vectors generated with `openssl enc` using the password "hunter2".
+use hex_literal::hex;
+use pbes::{
+    kdf,
+    openssl_enc::{self, Algorithm, Cipher, KeyDerivation, Md, Mode},
+    Error,
+};
+
+const PASSWORD: &[u8] = b"hunter2";
+const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog.\n";
+
+fn vectors() -> [(&'static str, KeyDerivation, &'static [u8]); 16] {
+    [
+        (
+            "aes-128-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5ffbcdfc9b9c58d83dfce1fcba0e2ea8dd9e26dc18387f21c3
+                0dc25446dd7d54ed9cd90299092aac250bef2ce86b6ddc52c2cbd3cee840a5bc
+                "
+            ),
+        ),
+        (
+            "aes-192-cbc",
+            KeyDerivation::BytesToKey(Md::Md5),
+            &hex!(
+                "
+                53616c7465645f5fd800de1cfdf9a5712eb2d25fe3ca9758bd2953bbde46ec99
+                4e6350f9816c5b33d992d0167905e560eb178e739525cdb062bcf02594a7ffa9
+                "
+            ),
+        ),
+        (
+            "aes-256-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5f36edf97bf61764741662ce8e526a2c8cd41b98f6206aff13
+                aa057d03715f9a0065b2d1d404dded035fd8c878e7ad0fbf988798cc98acba99
+                "
+            ),
+        ),
+        (
+            "aes-256-ecb",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5fb768cfabfd0658e79d7d44ec43ecfb21dd4e95faf37c96bf
+                bd6983a2f74f4ab21b501f54898c1ed29dfbdf19d320c369af836b19e812cd77
+                "
+            ),
+        ),
+        (
+            "des-ede3-cbc",
+            KeyDerivation::BytesToKey(Md::Md5),
+            &hex!(
+                "
+                53616c7465645f5f6e22b344d9e1b3240be70b759fd757601aecf5819ef3f2e2
+                c05097faeb0f16b6e0b311e1be7d867b9f58e0dab655a0e11edce1390d5372ad
+                "
+            ),
+        ),
+        (
+            "des-ede3",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5f2e88cfdc367425fd29c20e1b9eb34403a48b757b285ee3e3
+                272036d82eb283b9d36977cab4b1ac22c551d335b4ec72ccec9232a5df37b658
+                "
+            ),
+        ),
+        (
+            "bf-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5f3c88eab06dc520d2281b703a22653ae46fbf369f62802f86
+                5fca7dfb6e6174f7a6215b41a1792a22b567e5582b46b85f3adcc27607ab1251
+                "
+            ),
+        ),
+        (
+            "cast5-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5f2afdb93fd19d63ad4feac5828f0b9d7dbad1935400540688
+                4694f0c5a1892ab87bc771b1dad19709a2b8b5225695673029b4088da5a3ba0b
+                "
+            ),
+        ),
+        (
+            "idea-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5f1395bce806f40f94cd0fc2ca1ece0bbb9861d54400d7b279
+                fa1848961c87c66c50ac84beb94fdad6548768f5dbb8e0df22cd824fc34f7322
+                "
+            ),
+        ),
+        (
+            "rc2-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5fb93918f99e88c0c453771c8ac946d01ccf7acdc2c8ec48da
+                9d6a055f841dc7069a97108185c3af23b23e348e604d3987838b7e7212507cca
+                "
+            ),
+        ),
+        (
+            "rc2-40-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5f91cbcb2a7be46e11dbdaf9edf7e0e21ee6e5e808bfaabf4b
+                692cd758474059168aef38d9acadbbe81e5e83b7d88c6304e92ce5d7d9cd60c7
+                "
+            ),
+        ),
+        (
+            "rc2-64-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5f1032fc29fa71e4fcf17fb7aafbce1da043ed15a02c7e86fd
+                06f799b9619f8dbc35b0b8a2b7acad249892df35e86740737c5a47a8e520b4d4
+                "
+            ),
+        ),
+        (
+            "sm4-cbc",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                53616c7465645f5fa574123fde53607bfdbe027c643435858db309b07c75d2c9
+                f3e1ced09429ca4e3c31f6d3e6562806041859767c6d08e6300addba04236b63
+                "
+            ),
+        ),
+        (
+            "aes-256-cbc",
+            KeyDerivation::Pbkdf2 {
+                md: Md::Sha256,
+                iterations: 10000,
+            },
+            &hex!(
+                "
+                53616c7465645f5fb0553a0d78d85a67fb21b8ecf0f4e765771b541b64f7b3f8
+                4b3d5ae28189a2436de0ae21e2f83c7656359a0c3f1a2cb114b4af49ba8f7b56
+                "
+            ),
+        ),
+        (
+            "aes-128-cbc",
+            KeyDerivation::Pbkdf2 {
+                md: Md::Sha1,
+                iterations: 1000,
+            },
+            &hex!(
+                "
+                53616c7465645f5f8e08327ac64f46ae7d00bf6b11ffc6fd9799e29f7debf74d
+                041b17ade10d48a415525e8116921bbee699a165e387e6fc2133fe6562104dc0
+                "
+            ),
+        ),
+        (
+            "bf-ecb",
+            KeyDerivation::BytesToKey(Md::Sha256),
+            &hex!(
+                "
+                b34d8ce80ff518f5b475b4708f47e9260ff08d7ea6111a019e877fd6a8244d88
+                b0d218756a199b8a0899eef81c5436a0
+                "
+            ),
+        ),
+    ]
+}
+
+#[test]
+fn decrypt() {
+    for &(name, kdf, data) in vectors().iter() {
+        let cipher = Cipher::from_name(name).unwrap();
+        let res = openssl_enc::decrypt(cipher, kdf, PASSWORD, data);
+        assert_eq!(res.as_deref(), Ok(MSG), "{}", name);
+    }
+}
+
+#[test]
+fn encrypt() {
+    for &(name, kdf, data) in vectors().iter() {
+        let cipher = Cipher::from_name(name).unwrap();
+        let salt = openssl_enc::parse(data).map(|(salt, _)| salt);
+        let res = openssl_enc::encrypt(cipher, kdf, PASSWORD, salt, MSG).unwrap();
+        assert_eq!(res[..], data[..], "{}", name);
+    }
+}
+
+#[test]
+fn names() {
+    let cipher = Cipher::from_name("-DES3").unwrap();
+    assert_eq!(cipher.algorithm, Algorithm::TdesEde3);
+    assert_eq!(cipher.mode, Mode::Cbc);
+    assert_eq!((cipher.key_len(), cipher.iv_len()), (24, 8));
+
+    let cipher = Cipher::from_name("des-ede3").unwrap();
+    assert_eq!(cipher.mode, Mode::Ecb);
+    assert_eq!(cipher.iv_len(), 0);
+
+    let cipher = Cipher::from_name("rc2-40-cbc").unwrap();
+    assert_eq!((cipher.key_len(), cipher.iv_len()), (5, 8));
+
+    assert_eq!(Md::from_name("SHA256"), Ok(Md::Sha256));
+    assert_eq!(
+        Cipher::from_name("aes-256-gcm"),
+        Err(Error::UnsupportedAlgorithm)
+    );
+    assert_eq!(Md::from_name("sha3-256"), Err(Error::UnsupportedAlgorithm));
+}
+
+/// Values printed by `openssl enc -aes-256-cbc -P`
+#[test]
+fn evp_bytes_to_key() {
+    let mut key = [0u8; 32];
+    let mut iv = [0u8; 16];
+    let salt = hex!("0102030405060708");
+    kdf::evp_bytes_to_key::<md5::Md5>(PASSWORD, Some(&salt), 1, &mut key, &mut iv);
+    assert_eq!(
+        key,
+        hex!("DD076B4BCD49C33676D8185C3DD67E935D3B7324FF7D8E1074D9734059F0971E")
+    );
+    assert_eq!(iv, hex!("FEB9D83342D7AF5BEAE1FCD7AA9415A6"));
+
+    kdf::evp_bytes_to_key::<sha1::Sha1>(PASSWORD, None, 1, &mut key, &mut iv);
+    assert_eq!(
+        key,
+        hex!("F3BBBD66A63D4BF1747940578EC3D0103530E21D633D7FBF01355EFBB1B248F7")
+    );
+    assert_eq!(iv, hex!("09FCC346F103ED24D91A17652462BA4C"));
+}
+
+#[test]
+fn errors() {
+    let (name, kdf, data) = vectors()[2];
+    let cipher = Cipher::from_name(name).unwrap();
+    let res = openssl_enc::decrypt(cipher, kdf, b"hunter3", data);
+    assert_eq!(res, Err(Error::DecryptionFailed));
+    let res = openssl_enc::decrypt(cipher, kdf, PASSWORD, &data[..data.len() - 1]);
+    assert_eq!(res, Err(Error::DecryptionFailed));
+
+    assert_eq!(
+        cipher.encrypt(&[0; 16], &[0; 16], MSG),
+        Err(Error::InvalidParameters)
+    );
+}
+
+#[test]
+fn derive_into() {
+    let (name, kdf, data) = vectors()[13];
+    let cipher = Cipher::from_name(name).unwrap();
+    let (salt, _) = openssl_enc::parse(data).unwrap();
+    let (key, iv) = kdf.derive(cipher, PASSWORD, Some(salt));
+
+    // longer than the buffers returned by `derive`
+    let mut buf = [0u8; 64];
+    let (k, i) = buf.split_at_mut(32);
+    kdf.derive_into(PASSWORD, Some(salt), k, i);
+    assert_eq!((&buf[..32], &buf[32..48]), (&key[..], &iv[..]));
+
+    assert_eq!(openssl_enc::header(salt)[..], data[..16]);
+}
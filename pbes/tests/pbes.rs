@@ -0,0 +1,273 @@
+//! Test vectors generated with `openssl pkcs8 -topk8` using the password
+//! "hunter2".
+use hex_literal::hex;
+use pbes::{
+    kdf, pkcs8, EncryptionScheme, Error, Pbes1Algorithm, Pbes2Cipher, Pkcs12Algorithm, Prf,
+};
+
+const PASSWORD: &[u8] = b"hunter2";
+
+/// Ed25519 private key
+const KEY: [u8; 48] = hex!(
+    "
+    302e020100300506032b6570042204209073158e9fcd6c91213d43eb0b74d362
+    b24c8479f10cc647e1970f3426b7c528
+    "
+);
+
+fn vectors() -> [(&'static str, &'static [u8]); 10] {
+    [
+        (
+            "pbes2-aes128-sha1",
+            &hex!(
+                "
+        308195305106092a864886f70d01050d3044302306092a864886f70d01050c30
+        1604108fa0e0c95ae611301cadaafe1fb032a102020800301d06096086480165
+        030401020410a6de18d66f67dec7639487e8908e22630440b38086d5bdbf2f0b
+        c1f914cb2eefb61875ac87195b7cf4a4f8df11ee8c61b592b3a032d45b5b0b1e
+        1fc5953bdc5afe51465ea9187e7945e94cbcb3cbd80eb8ef
+        "
+            ),
+        ),
+        (
+            "pbes2-aes256-sha256",
+            &hex!(
+                "
+        3081a3305f06092a864886f70d01050d3052303106092a864886f70d01050c30
+        24041009d832bb62b23ab2c4a334ef85c4060c02020800300c06082a864886f7
+        0d02090500301d060960864801650304012a04105f5027265cc6c7ced632cea0
+        1f83a26c04409ed96c215761a3bb046d002514a158c88281a23323f610e48e95
+        f8b799cb4e7b74ddb0906008b3cf1fb3cbdfb7dd58bb03d3f187fd27940ae93f
+        66b82aff8d7f
+        "
+            ),
+        ),
+        (
+            "pbes2-aes192-sha512",
+            &hex!(
+                "
+        3081a3305f06092a864886f70d01050d3052303106092a864886f70d01050c30
+        240410bf7fc4c02e8a5943bb2a6a8960b25fe8020203e8300c06082a864886f7
+        0d020b0500301d060960864801650304011604100100510d3515a3441da44ef2
+        c74c67bb044093e0300b35230e0b4b9501c85e2bac2b82948418952abd4b4357
+        61f5ba508fe02484505644d3834b6099270fa9424072a3ed5c1b831bb8ba0df7
+        ed6bd27a8ee9
+        "
+            ),
+        ),
+        (
+            "pbes2-des3",
+            &hex!(
+                "
+        308192305606092a864886f70d01050d3049303106092a864886f70d01050c30
+        240410d126d28e22d449d4a340aabcaac87b2f02020800300c06082a864886f7
+        0d02090500301406082a864886f70d030704081d3a1963b14db1c60438a3430a
+        10d53115a79f56205049b443a5a6bd28b2a66a182060ed3600a4cafb9793ecd0
+        d99e18474aabe933f108f21015686d40e681720415
+        "
+            ),
+        ),
+        (
+            "pbes2-rc2",
+            &hex!(
+                "
+        30819a305e06092a864886f70d01050d3051303406092a864886f70d01050c30
+        270410122db61555f3266b640a502ef92f160102020800020110300c06082a86
+        4886f70d02090500301906082a864886f70d0302300d02013a0408339073a3b9
+        57efb70438d8f955159d720cdf749b557e562886eea6acdba467a0fc69f362f3
+        cda11666e95aff7ce440be50f6cea65208bbc7272f6de84984b49607f8
+        "
+            ),
+        ),
+        (
+            "pbes2-rc2-40",
+            &hex!(
+                "
+        30819b305f06092a864886f70d01050d3052303406092a864886f70d01050c30
+        270410c0690497b5bcaeb5c96d0142e65a379e02020800020105300c06082a86
+        4886f70d02090500301a06082a864886f70d0302300e020200a0040855071cbf
+        990331f00438bf42c2d92cef98b7c1db706b6be44d5176db1ea2820960827903
+        9bfd3d72ad1b3cf6dfa0c374f6b3dd72e25a628617d6c62e1b416c13288a
+        "
+            ),
+        ),
+        (
+            "pbes1-md5-des",
+            &hex!(
+                "
+        3057301b06092a864886f70d010503300e04088c1686e62a3552670202080004
+        382d3ecdc7b5f19b61a9720b595cde063283ddaf0d76fbff6b411583c7107ff1
+        34540bf086851244771007a809c43371b3a5a05c5137ebc382
+        "
+            ),
+        ),
+        (
+            "pbes1-sha1-rc2",
+            &hex!(
+                "
+        3057301b06092a864886f70d01050b300e040849c631b1e288a0d70202080004
+        381e15d3902bdee797a6836ef77942b2b88430a78ebb0d30935dd82c1adeb633
+        22ee9f5541f348991c26726697ea43be43fbc12744ecb27018
+        "
+            ),
+        ),
+        (
+            "pkcs12-3des",
+            &hex!(
+                "
+        3058301c060a2a864886f70d010c0103300e0408d2e4a06128a0ea3d02020800
+        04387eba1835dc741957aa1a153fe933d4935a59b85017ee86158ddf32c846cd
+        7611ad618658e5cc5ff6974d806d8e10552a6e2452e86a9d2a4f
+        "
+            ),
+        ),
+        (
+            "pkcs12-rc2-40",
+            &hex!(
+                "
+        3058301c060a2a864886f70d010c0106300e0408dc3f12dda9cf8d1c02020800
+        0438f16dd8554a9c3262b1dfc2c73f08ba5410eecbe7638276787a4ecf1a627b
+        26116fa9a543f1e909450cb28dc24464b78396ea503abcd4646b
+        "
+            ),
+        ),
+    ]
+}
+
+#[test]
+fn decrypt() {
+    for (name, der) in vectors().iter() {
+        let key = pkcs8::decrypt(der, PASSWORD).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        assert_eq!(key[..], KEY[..], "{}", name);
+    }
+}
+
+#[test]
+fn encrypt() {
+    for (name, der) in vectors().iter() {
+        let (scheme, _) = pkcs8::parse(der).unwrap();
+        let res = pkcs8::encrypt(&scheme, PASSWORD, &KEY).unwrap();
+        assert_eq!(res[..], der[..], "{}", name);
+    }
+}
+
+#[test]
+fn parse() {
+    let v = vectors();
+    let params = |i: usize| pkcs8::parse(v[i].1).unwrap().0;
+
+    match params(1) {
+        EncryptionScheme::Pbes2(p) => {
+            assert_eq!(p.kdf.prf, Prf::HmacSha256);
+            assert_eq!(p.kdf.iterations, 2048);
+            assert_eq!(p.kdf.key_length, None);
+            assert_eq!(
+                p.cipher,
+                Pbes2Cipher::Aes256Cbc(hex!("5f5027265cc6c7ced632cea01f83a26c"))
+            );
+        }
+        s => panic!("unexpected scheme: {:?}", s),
+    }
+    match params(5) {
+        EncryptionScheme::Pbes2(p) => {
+            assert_eq!(p.kdf.prf, Prf::HmacSha256);
+            assert_eq!(p.kdf.key_length, Some(5));
+            assert_eq!(
+                p.cipher,
+                Pbes2Cipher::Rc2Cbc {
+                    eff_key_bits: 40,
+                    iv: hex!("55071cbf990331f0"),
+                }
+            );
+        }
+        s => panic!("unexpected scheme: {:?}", s),
+    }
+    match params(6) {
+        EncryptionScheme::Pbes1(p) => {
+            assert_eq!(p.algorithm, Pbes1Algorithm::Md5Des);
+            assert_eq!(p.salt, hex!("8c1686e62a355267"));
+        }
+        s => panic!("unexpected scheme: {:?}", s),
+    }
+    match params(9) {
+        EncryptionScheme::Pkcs12(p) => {
+            assert_eq!(p.algorithm, Pkcs12Algorithm::ShaAnd40BitRc2Cbc);
+            assert_eq!(p.salt, hex!("dc3f12dda9cf8d1c"));
+            assert_eq!(p.iterations, 2048);
+        }
+        s => panic!("unexpected scheme: {:?}", s),
+    }
+}
+
+#[test]
+fn errors() {
+    for (name, der) in vectors().iter() {
+        let res = pkcs8::decrypt(der, b"hunter3");
+        assert_eq!(res, Err(Error::DecryptionFailed), "{}", name);
+        assert_eq!(
+            pkcs8::decrypt(&der[..der.len() - 1], PASSWORD),
+            Err(Error::InvalidDer)
+        );
+    }
+
+    let (scheme, data) = pkcs8::parse(vectors()[8].1).unwrap();
+    assert_eq!(scheme.decrypt(b"\xFF", data), Err(Error::InvalidPassword));
+
+    // PBES1 with the RC4 based PKCS#12 scheme
+    let alg_id = hex!("301c060a2a864886f70d010c0101300e04088c1686e62a35526702020800");
+    assert_eq!(
+        EncryptionScheme::from_der(&alg_id),
+        Err(Error::UnsupportedAlgorithm)
+    );
+    // PBES1 with a 7-byte salt
+    let alg_id = hex!("301a06092a864886f70d010503300d04078c1686e62a355202020800");
+    assert_eq!(
+        EncryptionScheme::from_der(&alg_id),
+        Err(Error::InvalidParameters)
+    );
+
+    // RC2 effective key lengths which have no RC2 version besides the table
+    let (mut scheme, _) = pkcs8::parse(vectors()[5].1).unwrap();
+    for &(eff_key_bits, ok) in &[(40, true), (100, false), (255, false), (512, true)] {
+        if let EncryptionScheme::Pbes2(p) = &mut scheme {
+            if let Pbes2Cipher::Rc2Cbc {
+                eff_key_bits: e, ..
+            } = &mut p.cipher
+            {
+                *e = eff_key_bits;
+            }
+        }
+        match scheme.to_der() {
+            Ok(der) => {
+                assert!(ok, "{}", eff_key_bits);
+                assert_eq!(EncryptionScheme::from_der(&der).as_ref(), Ok(&scheme));
+            }
+            Err(e) => {
+                assert!(!ok, "{}", eff_key_bits);
+                assert_eq!(e, Error::UnsupportedAlgorithm);
+                let res = pkcs8::encrypt(&scheme, PASSWORD, &KEY);
+                assert!(res.is_err(), "{}", eff_key_bits);
+            }
+        }
+    }
+}
+
+/// RFC 6070 test vectors
+#[test]
+fn pbkdf2() {
+    let mut out = [0u8; 20];
+    kdf::pbkdf2::<sha1::Sha1>(b"password", b"salt", 4096, &mut out);
+    assert_eq!(out, hex!("4b007901b765489abead49d926f721d065a429c1"));
+
+    let mut out = [0u8; 25];
+    kdf::pbkdf2::<sha1::Sha1>(
+        b"passwordPASSWORDpassword",
+        b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+        4096,
+        &mut out,
+    );
+    assert_eq!(
+        out,
+        hex!("3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038")
+    );
+}
@@ -20,7 +20,10 @@ use cipher::{
 };
 
 mod consts;
+mod self_test;
 use crate::consts::PI_TABLE;
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 
 /// A structure that represents the block cipher initialized with a key
 pub struct Rc2 {
@@ -36,11 +39,11 @@ impl Rc2 {
     }
 
     fn expand_key(key: &[u8], t1: usize) -> [u16; 64] {
-        let key_len = key.len() as usize;
+        let key_len = key.len();
 
         let t8: usize = (t1 + 7) >> 3;
 
-        let tm: usize = (255 % ((2 as u32).pow((8 + t1 - 8 * t8) as u32))) as usize;
+        let tm: usize = (255 % (2u32.pow((8 + t1 - 8 * t8) as u32))) as usize;
 
         let mut key_buffer: [u8; 128] = [0; 128];
         key_buffer[..key_len].copy_from_slice(&key[..key_len]);
@@ -71,28 +74,28 @@ impl Rc2 {
             .wrapping_add(r[3] & r[2])
             .wrapping_add(!r[3] & r[1]);
         *j += 1;
-        r[0] = (r[0] << 1) | (r[0] >> 15);
+        r[0] = r[0].rotate_left(1);
 
         r[1] = r[1]
             .wrapping_add(self.exp_key[*j])
             .wrapping_add(r[0] & r[3])
             .wrapping_add(!r[0] & r[2]);
         *j += 1;
-        r[1] = (r[1] << 2) | (r[1] >> 14);
+        r[1] = r[1].rotate_left(2);
 
         r[2] = r[2]
             .wrapping_add(self.exp_key[*j])
             .wrapping_add(r[1] & r[0])
             .wrapping_add(!r[1] & r[3]);
         *j += 1;
-        r[2] = (r[2] << 3) | (r[2] >> 13);
+        r[2] = r[2].rotate_left(3);
 
         r[3] = r[3]
             .wrapping_add(self.exp_key[*j])
             .wrapping_add(r[2] & r[1])
             .wrapping_add(!r[2] & r[0]);
         *j += 1;
-        r[3] = (r[3] << 5) | (r[3] >> 11);
+        r[3] = r[3].rotate_left(5);
     }
 
     fn mash(&self, r: &mut [u16; 4]) {
@@ -103,28 +106,28 @@ impl Rc2 {
     }
 
     fn reverse_mix(&self, r: &mut [u16; 4], j: &mut usize) {
-        r[3] = (r[3] << 11) | (r[3] >> 5);
+        r[3] = r[3].rotate_left(11);
         r[3] = r[3]
             .wrapping_sub(self.exp_key[*j])
             .wrapping_sub(r[2] & r[1])
             .wrapping_sub(!r[2] & r[0]);
         *j -= 1;
 
-        r[2] = (r[2] << 13) | (r[2] >> 3);
+        r[2] = r[2].rotate_left(13);
         r[2] = r[2]
             .wrapping_sub(self.exp_key[*j])
             .wrapping_sub(r[1] & r[0])
             .wrapping_sub(!r[1] & r[3]);
         *j -= 1;
 
-        r[1] = (r[1] << 14) | (r[1] >> 2);
+        r[1] = r[1].rotate_left(14);
         r[1] = r[1]
             .wrapping_sub(self.exp_key[*j])
             .wrapping_sub(r[0] & r[3])
             .wrapping_sub(!r[0] & r[2]);
         *j -= 1;
 
-        r[0] = (r[0] << 15) | (r[0] >> 1);
+        r[0] = r[0].rotate_left(15);
         r[0] = r[0]
             .wrapping_sub(self.exp_key[*j])
             .wrapping_sub(r[3] & r[2])
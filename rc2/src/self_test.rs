@@ -0,0 +1,18 @@
+//! Power-on self-test.
+
+use crate::Rc2;
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// RFC 2268 section 5
+const KEY: [u8; 8] = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+
+/// Plaintext of the RFC 2268 example
+const PLAINTEXT: [u8; 8] = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+
+/// Ciphertext of the RFC 2268 example
+const CIPHERTEXT: [u8; 8] = [0x27, 0x8b, 0x27, 0xe4, 0x2e, 0x2f, 0x0d, 0x49];
+
+/// Run the known answer test of RC2 with a 64-bit effective key length.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Rc2>("RC2", &KEY, &PLAINTEXT, &CIPHERTEXT)
+}
@@ -67,3 +67,8 @@ fn rc2_effective_key_129() {
         assert_eq!(test.input, &buf[..]);
     }
 }
+
+#[test]
+fn self_test() {
+    assert_eq!(rc2::self_test(), Ok(()));
+}
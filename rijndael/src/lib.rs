@@ -0,0 +1,310 @@
+//! Pure Rust implementation of the [Rijndael] block cipher with all block
+//! and key sizes of the original AES submission: 128, 160, 192, 224 and 256
+//! bits.
+//!
+//! AES is the subset of Rijndael with 128-bit blocks and 128, 192 or 256-bit
+//! keys. The larger block sizes are still used by some legacy formats, e.g.
+//! Rijndael-256 by older crypto-currency wallets.
+//!
+//! The cipher is built on the round functions of the [`aes::hazmat`] module,
+//! so it uses AES-NI or the ARMv8 Cryptography Extensions when available and
+//! the constant-time fixsliced implementation of the `aes` crate otherwise.
+//! The larger blocks are processed as chunks of four columns with the bytes
+//! rearranged to implement the `ShiftRows` offsets of the wider state, which
+//! only depend on the block size, so all operations are constant-time.
+//!
+//! # Usage example
+//! ```
+//! use rijndael::cipher::{
+//!     consts::U32, generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+//! };
+//! use rijndael::Rijndael256;
+//!
+//! let key = GenericArray::from_slice(&[0u8; 32]);
+//! let cipher = Rijndael256::<U32>::new(&key);
+//!
+//! let mut block = GenericArray::clone_from_slice(&[42u8; 32]);
+//! cipher.encrypt_block(&mut block);
+//! cipher.decrypt_block(&mut block);
+//! assert_eq!(block.as_slice(), &[42u8; 32]);
+//! ```
+//!
+//! [Rijndael]: https://csrc.nist.gov/csrc/media/projects/cryptographic-standards-and-guidelines/documents/aes-development/rijndael-ammended.pdf
+
+#![no_std]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg",
+    html_favicon_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg"
+)]
+#![forbid(unsafe_code)]
+#![warn(missing_docs, rust_2018_idioms)]
+
+mod self_test;
+
+pub use aes;
+pub use cipher;
+
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
+
+use aes::{hazmat, Block};
+use cipher::{
+    consts::{U1, U16, U20, U24, U28, U32},
+    generic_array::{ArrayLength, GenericArray},
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use core::{cmp, fmt, marker::PhantomData};
+
+/// Maximum number of round keys (14 rounds)
+const MAX_ROUND_KEYS: usize = 15;
+
+/// Round key or state of up to 8 columns, stored as two 128-bit chunks of
+/// four columns each. Unused columns are zero.
+type Columns = [u8; 32];
+
+/// Block and key sizes supported by Rijndael in bytes: [`U16`], [`U20`],
+/// [`U24`], [`U28`] and [`U32`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Size: ArrayLength<u8> + private::Sealed {}
+
+impl Size for U16 {}
+impl Size for U20 {}
+impl Size for U24 {}
+impl Size for U28 {}
+impl Size for U32 {}
+
+mod private {
+    use cipher::consts::{U16, U20, U24, U28, U32};
+
+    pub trait Sealed {}
+
+    impl Sealed for U16 {}
+    impl Sealed for U20 {}
+    impl Sealed for U24 {}
+    impl Sealed for U28 {}
+    impl Sealed for U32 {}
+}
+
+/// Rijndael block cipher with a block size of `B` bytes and a key size of
+/// `K` bytes.
+#[derive(Clone)]
+pub struct Rijndael<B: Size, K: Size> {
+    enc_keys: [Columns; MAX_ROUND_KEYS],
+    /// Round keys of the equivalent inverse cipher
+    dec_keys: [Columns; MAX_ROUND_KEYS],
+    rounds: usize,
+    sizes: PhantomData<(B, K)>,
+}
+
+/// Rijndael with a 128-bit block, i.e. AES for 128, 192 and 256-bit keys.
+pub type Rijndael128<K> = Rijndael<U16, K>;
+
+/// Rijndael with a 160-bit block.
+pub type Rijndael160<K> = Rijndael<U20, K>;
+
+/// Rijndael with a 192-bit block.
+pub type Rijndael192<K> = Rijndael<U24, K>;
+
+/// Rijndael with a 224-bit block.
+pub type Rijndael224<K> = Rijndael<U28, K>;
+
+/// Rijndael with a 256-bit block.
+pub type Rijndael256<K> = Rijndael<U32, K>;
+
+impl<B: Size, K: Size> Rijndael<B, K> {
+    /// Number of columns of the state, `Nb`
+    const NB: usize = B::USIZE / 4;
+
+    /// Number of chunks of four columns which contain the state
+    const CHUNKS: usize = if B::USIZE > 16 { 2 } else { 1 };
+
+    /// Apply all rounds to the `state` using AES round functions on chunks
+    /// of four columns. `gather` maps a byte of the input of a chunk to
+    /// the index of the byte in the state.
+    fn apply_rounds(
+        &self,
+        state: &mut Columns,
+        round_keys: &[Columns; MAX_ROUND_KEYS],
+        gather: impl Fn(usize, usize, usize) -> usize,
+        round: fn(&mut Block, &Block),
+        round_last: fn(&mut Block, &Block),
+    ) {
+        xor_in_place(state, &round_keys[0]);
+
+        for (i, round_key) in round_keys.iter().enumerate().take(self.rounds + 1).skip(1) {
+            let mut output = Columns::default();
+            let chunks = output.chunks_exact_mut(16).zip(round_key.chunks_exact(16));
+
+            for (chunk, (dst, key)) in chunks.enumerate().take(Self::CHUNKS) {
+                let mut block = Block::default();
+
+                for column in 0..4 {
+                    for row in 0..4 {
+                        block[4 * column + row] = state[gather(chunk, column, row)];
+                    }
+                }
+
+                if i < self.rounds {
+                    round(&mut block, Block::from_slice(key));
+                } else {
+                    round_last(&mut block, Block::from_slice(key));
+                }
+
+                dst.copy_from_slice(&block);
+            }
+
+            *state = output;
+        }
+    }
+}
+
+impl<B: Size, K: Size> NewBlockCipher for Rijndael<B, K> {
+    type KeySize = K;
+
+    fn new(key: &GenericArray<u8, K>) -> Self {
+        let nb = Self::NB;
+        let nk = K::USIZE / 4;
+        let rounds = cmp::max(nb, nk) + 6;
+
+        let mut words = [[0u8; 4]; 8 * MAX_ROUND_KEYS];
+        for (word, chunk) in words.iter_mut().zip(key.chunks_exact(4)) {
+            word.copy_from_slice(chunk);
+        }
+
+        let mut rcon = 1;
+        for i in nk..(nb * (rounds + 1)) {
+            let mut word = words[i - 1];
+
+            if i % nk == 0 {
+                word = sub_word([word[1], word[2], word[3], word[0]]);
+                word[0] ^= rcon;
+                rcon = xtime(rcon);
+            } else if nk > 6 && i % nk == 4 {
+                word = sub_word(word);
+            }
+
+            for j in 0..4 {
+                words[i][j] = words[i - nk][j] ^ word[j];
+            }
+        }
+
+        let mut enc_keys = [Columns::default(); MAX_ROUND_KEYS];
+        for (round_key, words) in enc_keys.iter_mut().zip(words.chunks_exact(nb)) {
+            for (column, word) in round_key.chunks_exact_mut(4).zip(words) {
+                column.copy_from_slice(word);
+            }
+        }
+
+        let mut dec_keys = [Columns::default(); MAX_ROUND_KEYS];
+        for (i, round_key) in dec_keys.iter_mut().enumerate().take(rounds + 1) {
+            *round_key = enc_keys[rounds - i];
+
+            if i != 0 && i != rounds {
+                for chunk in round_key.chunks_exact_mut(16) {
+                    hazmat::inv_mix_columns(Block::from_mut_slice(chunk));
+                }
+            }
+        }
+
+        Self {
+            enc_keys,
+            dec_keys,
+            rounds,
+            sizes: PhantomData,
+        }
+    }
+}
+
+impl<B: Size, K: Size> BlockCipher for Rijndael<B, K> {
+    type BlockSize = B;
+    type ParBlocks = U1;
+}
+
+impl<B: Size, K: Size> BlockEncrypt for Rijndael<B, K> {
+    fn encrypt_block(&self, block: &mut GenericArray<u8, B>) {
+        let nb = Self::NB;
+        let shifts = shift_offsets(nb);
+
+        // The AES round function applies `ShiftRows` for four columns, which
+        // moves the byte of `column` to `(column - row) mod 4`. Its input is
+        // gathered so that this yields `ShiftRows` for `Nb` columns.
+        let gather = |chunk: usize, column: usize, row: usize| {
+            4 * ((4 * chunk + (column + 4 - row) % 4 + shifts[row]) % nb) + row
+        };
+
+        let mut state = Columns::default();
+        state[..B::USIZE].copy_from_slice(block);
+        self.apply_rounds(
+            &mut state,
+            &self.enc_keys,
+            gather,
+            hazmat::cipher_round,
+            hazmat::cipher_round_last,
+        );
+        block.copy_from_slice(&state[..B::USIZE]);
+    }
+}
+
+impl<B: Size, K: Size> BlockDecrypt for Rijndael<B, K> {
+    fn decrypt_block(&self, block: &mut GenericArray<u8, B>) {
+        let nb = Self::NB;
+        let shifts = shift_offsets(nb);
+
+        // Same as for encryption, but with `InvShiftRows`
+        let gather = |chunk: usize, column: usize, row: usize| {
+            4 * ((4 * chunk + (column + row) % 4 + nb - shifts[row]) % nb) + row
+        };
+
+        let mut state = Columns::default();
+        state[..B::USIZE].copy_from_slice(block);
+        self.apply_rounds(
+            &mut state,
+            &self.dec_keys,
+            gather,
+            hazmat::equiv_inv_cipher_round,
+            hazmat::equiv_inv_cipher_round_last,
+        );
+        block.copy_from_slice(&state[..B::USIZE]);
+    }
+}
+
+impl<B: Size, K: Size> fmt::Debug for Rijndael<B, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("Rijndael { ... }")
+    }
+}
+
+/// `ShiftRows` offsets of the rows for `nb` columns
+fn shift_offsets(nb: usize) -> [usize; 4] {
+    match nb {
+        7 => [0, 1, 2, 4],
+        8 => [0, 1, 3, 4],
+        _ => [0, 1, 2, 3],
+    }
+}
+
+/// Apply the S-box to each byte of a key schedule word.
+///
+/// All columns of the block are equal, so `ShiftRows` of the final round
+/// function has no effect.
+fn sub_word(word: [u8; 4]) -> [u8; 4] {
+    let mut block = Block::default();
+    for column in block.chunks_exact_mut(4) {
+        column.copy_from_slice(&word);
+    }
+
+    hazmat::cipher_round_last(&mut block, &Block::default());
+    [block[0], block[1], block[2], block[3]]
+}
+
+/// Multiplication by `x` in GF(2^8), used for the public round constants.
+fn xtime(x: u8) -> u8 {
+    (x << 1) ^ ((x >> 7) * 0x1b)
+}
+
+fn xor_in_place(dst: &mut Columns, src: &Columns) {
+    for (a, b) in dst.iter_mut().zip(src.iter()) {
+        *a ^= *b;
+    }
+}
@@ -0,0 +1,76 @@
+//! Power-on self-test.
+
+use crate::{Rijndael128, Rijndael160, Rijndael192, Rijndael224, Rijndael256};
+use block_ciphers_self_test::{kat, SelfTestError};
+use cipher::consts::{U16, U20, U24, U28, U32};
+
+/// Key of the example vectors of the Rijndael submission
+const KEY: [u8; 32] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+    0x76, 0x2e, 0x71, 0x60, 0xf3, 0x8b, 0x4d, 0xa5, 0x6a, 0x78, 0x4d, 0x90, 0x45, 0x19, 0x0c, 0xfe,
+];
+
+/// Plaintext of the example vectors of the Rijndael submission
+const PLAINTEXT: [u8; 32] = [
+    0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34,
+    0x4a, 0x40, 0x93, 0x82, 0x22, 0x99, 0xf3, 0x1d, 0x00, 0x82, 0xef, 0xa9, 0x8e, 0xc4, 0xe6, 0xc8,
+];
+
+/// 128-bit block and key
+const CIPHERTEXT_128: [u8; 16] = [
+    0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32,
+];
+
+/// 160-bit block and key
+const CIPHERTEXT_160: [u8; 20] = [
+    0x05, 0x53, 0xeb, 0x69, 0x16, 0x70, 0xdd, 0x8a, 0x5a, 0x5b, 0x5a, 0xdd, 0xf1, 0xaa, 0x74, 0x50,
+    0xf7, 0xa0, 0xe5, 0x87,
+];
+
+/// 192-bit block and key
+const CIPHERTEXT_192: [u8; 24] = [
+    0x72, 0x5a, 0xe4, 0x3b, 0x5f, 0x31, 0x61, 0xde, 0x80, 0x6a, 0x7c, 0x93, 0xe0, 0xbc, 0xa9, 0x3c,
+    0x96, 0x7e, 0xc1, 0xae, 0x1b, 0x71, 0xe1, 0xcf,
+];
+
+/// 224-bit block and key
+const CIPHERTEXT_224: [u8; 28] = [
+    0xef, 0x52, 0x95, 0x98, 0xec, 0xbc, 0xe2, 0x97, 0x81, 0x1b, 0x49, 0xbb, 0xed, 0x2c, 0x33, 0xbb,
+    0xe1, 0x24, 0x1d, 0x6e, 0x1a, 0x83, 0x3d, 0xbe, 0x11, 0x95, 0x69, 0xe8,
+];
+
+/// 256-bit block and key
+const CIPHERTEXT_256: [u8; 32] = [
+    0xa4, 0x94, 0x06, 0x11, 0x5d, 0xfb, 0x30, 0xa4, 0x04, 0x18, 0xaa, 0xfa, 0x48, 0x69, 0xb7, 0xc6,
+    0xa8, 0x86, 0xff, 0x31, 0x60, 0x2a, 0x7d, 0xd1, 0x9c, 0x88, 0x9d, 0xc6, 0x4f, 0x7e, 0x4e, 0x7a,
+];
+
+/// Run known answer tests of all block sizes with a key of the same size.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Rijndael128<U16>>(
+        "Rijndael-128",
+        &KEY[..16],
+        &PLAINTEXT[..16],
+        &CIPHERTEXT_128,
+    )?;
+    kat::<Rijndael160<U20>>(
+        "Rijndael-160",
+        &KEY[..20],
+        &PLAINTEXT[..20],
+        &CIPHERTEXT_160,
+    )?;
+    kat::<Rijndael192<U24>>(
+        "Rijndael-192",
+        &KEY[..24],
+        &PLAINTEXT[..24],
+        &CIPHERTEXT_192,
+    )?;
+    kat::<Rijndael224<U28>>(
+        "Rijndael-224",
+        &KEY[..28],
+        &PLAINTEXT[..28],
+        &CIPHERTEXT_224,
+    )?;
+    kat::<Rijndael256<U32>>("Rijndael-256", &KEY, &PLAINTEXT, &CIPHERTEXT_256)?;
+    Ok(())
+}
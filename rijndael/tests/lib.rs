@@ -0,0 +1,225 @@
+//! Test vectors from the examples of the Rijndael submission: the leading
+//! bytes of a fixed key and plaintext for all block and key sizes.
+
+use hex_literal::hex;
+use rijndael::cipher::{
+    consts::{U16, U20, U24, U28, U32},
+    generic_array::{typenum::Unsigned, GenericArray},
+    BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use rijndael::{aes, Rijndael};
+
+const KEY: [u8; 32] = hex!("2b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfe");
+const PLAINTEXT: [u8; 32] =
+    hex!("3243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c8");
+
+macro_rules! rijndael_test {
+    ($name:ident, $block:ty, $key:ty, $ciphertext:expr) => {
+        fn $name() {
+            let ct = $ciphertext;
+            let key = GenericArray::from_slice(&KEY[..<$key>::USIZE]);
+            let cipher = Rijndael::<$block, $key>::new(key);
+
+            let mut block = GenericArray::clone_from_slice(&PLAINTEXT[..ct.len()]);
+            cipher.encrypt_block(&mut block);
+            assert_eq!(block[..], ct[..]);
+            cipher.decrypt_block(&mut block);
+            assert_eq!(block[..], PLAINTEXT[..ct.len()]);
+        }
+    };
+}
+
+rijndael_test!(
+    b128_k128,
+    U16,
+    U16,
+    hex!("3925841d02dc09fbdc118597196a0b32")
+);
+rijndael_test!(
+    b128_k160,
+    U16,
+    U20,
+    hex!("231d844639b31b412211cfe93712b880")
+);
+rijndael_test!(
+    b128_k192,
+    U16,
+    U24,
+    hex!("f9fb29aefc384a250340d833b87ebc00")
+);
+rijndael_test!(
+    b128_k224,
+    U16,
+    U28,
+    hex!("8faa8fe4dee9eb17caa4797502fc9d3f")
+);
+rijndael_test!(
+    b128_k256,
+    U16,
+    U32,
+    hex!("1a6e6c2c662e7da6501ffb62bc9e93f3")
+);
+rijndael_test!(
+    b160_k128,
+    U20,
+    U16,
+    hex!("16e73aec921314c29df905432bc8968ab64b1f51")
+);
+rijndael_test!(
+    b160_k160,
+    U20,
+    U20,
+    hex!("0553eb691670dd8a5a5b5addf1aa7450f7a0e587")
+);
+rijndael_test!(
+    b160_k192,
+    U20,
+    U24,
+    hex!("73cd6f3423036790463aa9e19cfcde894ea16623")
+);
+rijndael_test!(
+    b160_k224,
+    U20,
+    U28,
+    hex!("601b5dcd1cf4ece954c740445340bf0afdc048df")
+);
+rijndael_test!(
+    b160_k256,
+    U20,
+    U32,
+    hex!("579e930b36c1529aa3e86628bacfe146942882cf")
+);
+rijndael_test!(
+    b192_k128,
+    U24,
+    U16,
+    hex!("b24d275489e82bb8f7375e0d5fcdb1f481757c538b65148a")
+);
+rijndael_test!(
+    b192_k160,
+    U24,
+    U20,
+    hex!("738dae25620d3d3beff4a037a04290d73eb33521a63ea568")
+);
+rijndael_test!(
+    b192_k192,
+    U24,
+    U24,
+    hex!("725ae43b5f3161de806a7c93e0bca93c967ec1ae1b71e1cf")
+);
+rijndael_test!(
+    b192_k224,
+    U24,
+    U28,
+    hex!("bbfc14180afbf6a36382a061843f0b63e769acdc98769130")
+);
+rijndael_test!(
+    b192_k256,
+    U24,
+    U32,
+    hex!("0ebacf199e3315c2e34b24fcc7c46ef4388aa475d66c194c")
+);
+rijndael_test!(
+    b224_k128,
+    U28,
+    U16,
+    hex!("b0a8f78f6b3c66213f792ffd2a61631f79331407a5e5c8d3793aceb1")
+);
+rijndael_test!(
+    b224_k160,
+    U28,
+    U20,
+    hex!("08b99944edfce33a2acb131183ab0168446b2d15e958480010f545e3")
+);
+rijndael_test!(
+    b224_k192,
+    U28,
+    U24,
+    hex!("be4c597d8f7efe22a2f7e5b1938e2564d452a5bfe72399c7af1101e2")
+);
+rijndael_test!(
+    b224_k224,
+    U28,
+    U28,
+    hex!("ef529598ecbce297811b49bbed2c33bbe1241d6e1a833dbe119569e8")
+);
+rijndael_test!(
+    b224_k256,
+    U28,
+    U32,
+    hex!("02fafc200176ed05deb8edb82a3555b0b10d47a388dfd59cab2f6c11")
+);
+rijndael_test!(
+    b256_k128,
+    U32,
+    U16,
+    hex!("7d15479076b69a46ffb3b3beae97ad8313f622f67fedb487de9f06b9ed9c8f19")
+);
+rijndael_test!(
+    b256_k160,
+    U32,
+    U20,
+    hex!("514f93fb296b5ad16aa7df8b577abcbd484decacccc7fb1f18dc567309ceeffd")
+);
+rijndael_test!(
+    b256_k192,
+    U32,
+    U24,
+    hex!("5d7101727bb25781bf6715b0e6955282b9610e23a43c2eb062699f0ebf5887b2")
+);
+rijndael_test!(
+    b256_k224,
+    U32,
+    U28,
+    hex!("d56c5a63627432579e1dd308b2c8f157b40a4bfb56fea1377b25d3ed3d6dbf80")
+);
+rijndael_test!(
+    b256_k256,
+    U32,
+    U32,
+    hex!("a49406115dfb30a40418aafa4869b7c6a886ff31602a7dd19c889dc64f7e4e7a")
+);
+
+fn all_vectors() {
+    b128_k128();
+    b128_k160();
+    b128_k192();
+    b128_k224();
+    b128_k256();
+    b160_k128();
+    b160_k160();
+    b160_k192();
+    b160_k224();
+    b160_k256();
+    b192_k128();
+    b192_k160();
+    b192_k192();
+    b192_k224();
+    b192_k256();
+    b224_k128();
+    b224_k160();
+    b224_k192();
+    b224_k224();
+    b224_k256();
+    b256_k128();
+    b256_k160();
+    b256_k192();
+    b256_k224();
+    b256_k256();
+}
+
+// Both backends are checked in a single test since forcing the software
+// backend affects the whole process.
+#[test]
+fn rijndael() {
+    aes::set_force_soft(false);
+    all_vectors();
+    aes::set_force_soft(true);
+    all_vectors();
+    aes::set_force_soft(false);
+}
+
+#[test]
+fn self_test() {
+    assert_eq!(rijndael::self_test(), Ok(()));
+}
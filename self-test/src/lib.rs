@@ -0,0 +1,122 @@
+//! Known answer tests shared by the power-on self-tests of the block cipher
+//! crates from this repository.
+//!
+//! Every cipher crate exposes a `self_test()` function which runs [`kat`]
+//! with its own test vectors and returns [`SelfTestError`] naming the
+//! algorithm which failed. The error type is re-exported by the cipher
+//! crates, so this crate is rarely used directly.
+
+#![no_std]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg",
+    html_favicon_url = "https://raw.githubusercontent.com/RustCrypto/meta/master/logo.svg"
+)]
+#![forbid(unsafe_code)]
+#![warn(missing_docs, rust_2018_idioms)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use cipher::{
+    consts::U35, generic_array::GenericArray, Block, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+    ParBlocks,
+};
+use core::fmt;
+
+/// Error returned by a self-test if a cipher produces a wrong result.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SelfTestError {
+    algorithm: &'static str,
+}
+
+impl SelfTestError {
+    /// Create an error for the failed `algorithm`.
+    pub fn new(algorithm: &'static str) -> Self {
+        Self { algorithm }
+    }
+
+    /// Name of the algorithm which failed the test, e.g. `"AES-128"`.
+    pub fn algorithm(&self) -> &'static str {
+        self.algorithm
+    }
+}
+
+impl fmt::Display for SelfTestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{} self-test failed", self.algorithm)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SelfTestError {}
+
+/// Number of blocks of the multi-block check, more than the widest
+/// parallel code path of any backend (32 blocks) and not a multiple of
+/// common parallel block counts
+type MultiBlocks = U35;
+
+/// Check the known answer for a single block and that encryption of
+/// parallel blocks and of slices of blocks agrees with encryption of blocks
+/// one by one.
+///
+/// The cipher is created with `C::new_from_slice`, so the test runs against
+/// the backend selected for any other instance created by the caller.
+pub fn kat<C>(
+    algorithm: &'static str,
+    key: &[u8],
+    pt: &[u8],
+    ct: &[u8],
+) -> Result<(), SelfTestError>
+where
+    C: NewBlockCipher + BlockEncrypt + BlockDecrypt,
+{
+    let error = SelfTestError { algorithm };
+    let cipher = C::new_from_slice(key).map_err(|_| error)?;
+
+    let mut block = GenericArray::clone_from_slice(pt);
+    cipher.encrypt_block(&mut block);
+    if block.as_slice() != ct {
+        return Err(error);
+    }
+    cipher.decrypt_block(&mut block);
+    if block.as_slice() != pt {
+        return Err(error);
+    }
+
+    // The i-th block is the plaintext encrypted i times, so encryption of
+    // all blocks at once must shift the sequence by one
+    let mut blocks = ParBlocks::<C>::default();
+    for b in blocks.iter_mut() {
+        *b = block.clone();
+        cipher.encrypt_block(&mut block);
+    }
+    let expected = blocks.clone();
+    let n = blocks.len();
+    cipher.encrypt_par_blocks(&mut blocks);
+    if blocks[..n - 1] != expected[1..] || blocks[n - 1] != block {
+        return Err(error);
+    }
+    cipher.decrypt_par_blocks(&mut blocks);
+    if blocks != expected {
+        return Err(error);
+    }
+
+    // The same check with `encrypt_blocks` and `decrypt_blocks`, which
+    // some backends implement with wider code paths
+    let mut blocks = GenericArray::<Block<C>, MultiBlocks>::default();
+    for b in blocks.iter_mut() {
+        *b = block.clone();
+        cipher.encrypt_block(&mut block);
+    }
+    let expected = blocks.clone();
+    let n = blocks.len();
+    cipher.encrypt_blocks(&mut blocks);
+    if blocks[..n - 1] != expected[1..] || blocks[n - 1] != block {
+        return Err(error);
+    }
+    cipher.decrypt_blocks(&mut blocks);
+    if blocks != expected {
+        return Err(error);
+    }
+    Ok(())
+}
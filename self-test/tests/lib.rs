@@ -0,0 +1,102 @@
+use block_ciphers_self_test::{kat, SelfTestError};
+use cipher::{
+    consts::{U2, U4},
+    generic_array::GenericArray,
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher, ParBlocks,
+};
+use std::marker::PhantomData;
+
+/// Toy cipher which adds the key to each byte, with an optional fault in
+/// the parallel block path or in the path for more than 32 blocks
+struct Add<F> {
+    key: [u8; 4],
+    fault: PhantomData<F>,
+}
+
+trait Fault {
+    const PAR_BLOCKS: bool = false;
+    const MANY_BLOCKS: bool = false;
+}
+
+struct Correct;
+
+impl Fault for Correct {}
+
+struct Faulty;
+
+impl Fault for Faulty {
+    const PAR_BLOCKS: bool = true;
+}
+
+struct FaultyMany;
+
+impl Fault for FaultyMany {
+    const MANY_BLOCKS: bool = true;
+}
+
+impl<F: Fault> NewBlockCipher for Add<F> {
+    type KeySize = U4;
+
+    fn new(key: &GenericArray<u8, U4>) -> Self {
+        Self {
+            key: (*key).into(),
+            fault: PhantomData,
+        }
+    }
+}
+
+impl<F: Fault> BlockCipher for Add<F> {
+    type BlockSize = U4;
+    type ParBlocks = U2;
+}
+
+impl<F: Fault> BlockEncrypt for Add<F> {
+    fn encrypt_block(&self, block: &mut GenericArray<u8, U4>) {
+        for (b, k) in block.iter_mut().zip(&self.key) {
+            *b = b.wrapping_add(*k);
+        }
+    }
+
+    fn encrypt_par_blocks(&self, blocks: &mut ParBlocks<Self>) {
+        for block in blocks.iter_mut() {
+            self.encrypt_block(block);
+        }
+        if F::PAR_BLOCKS {
+            blocks[1][0] ^= 1;
+        }
+    }
+
+    fn encrypt_blocks(&self, blocks: &mut [GenericArray<u8, U4>]) {
+        for block in blocks.iter_mut() {
+            self.encrypt_block(block);
+        }
+        if F::MANY_BLOCKS && blocks.len() > 32 {
+            blocks[32][0] ^= 1;
+        }
+    }
+}
+
+impl<F: Fault> BlockDecrypt for Add<F> {
+    fn decrypt_block(&self, block: &mut GenericArray<u8, U4>) {
+        for (b, k) in block.iter_mut().zip(&self.key) {
+            *b = b.wrapping_sub(*k);
+        }
+    }
+}
+
+#[test]
+fn known_answer() {
+    let (key, pt, ct) = ([1, 2, 3, 4], [0, 0, 0, 0xff], [1, 2, 3, 3]);
+    assert_eq!(kat::<Add<Correct>>("Add", &key, &pt, &ct), Ok(()));
+
+    let err = SelfTestError::new("Add");
+    assert_eq!(
+        kat::<Add<Correct>>("Add", &key, &pt, &[1, 2, 3, 4]),
+        Err(err)
+    );
+    assert_eq!(kat::<Add<Correct>>("Add", &key[..3], &pt, &ct), Err(err));
+    assert_eq!(kat::<Add<Faulty>>("Add", &key, &pt, &ct), Err(err));
+    assert_eq!(kat::<Add<FaultyMany>>("Add", &key, &pt, &ct), Err(err));
+    assert_eq!(err.algorithm(), "Add");
+    assert_eq!(err.to_string(), "Add self-test failed");
+}
@@ -24,6 +24,9 @@ use cipher::{
 };
 
 mod consts;
+mod self_test;
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 use consts::{PHI, ROUNDS, S, S_INVERSE};
 
 type Key = [u8; 16];
@@ -173,7 +176,7 @@ fn xor_block(b1: Block128, k: Key) -> Block128 {
 }
 
 fn expand_key(source: &[u8], len_bits: usize, key: &mut [u8; 32]) {
-    key[..source.len()].copy_from_slice(&source);
+    key[..source.len()].copy_from_slice(source);
     if len_bits < 256 {
         let byte_i = len_bits / 8;
         let bit_i = len_bits % 8;
@@ -204,19 +207,19 @@ impl Serpent {
         let mut k = [0u32; 132];
         for i in 0..r {
             let sbox_index = (ROUNDS + 3 - i) % ROUNDS;
-            let a = words[(4 * i) as usize];
-            let b = words[(4 * i + 1) as usize];
-            let c = words[(4 * i + 2) as usize];
-            let d = words[(4 * i + 3) as usize];
+            let a = words[4 * i];
+            let b = words[4 * i + 1];
+            let c = words[4 * i + 2];
+            let d = words[4 * i + 3];
             // calculate keys in bitslicing mode
             for j in 0..32 {
                 let input = get_bit(a as usize, j)
                     | get_bit(b as usize, j) << 1
                     | get_bit(c as usize, j) << 2
                     | get_bit(d as usize, j) << 3;
-                let output = apply_s(sbox_index, input as u8);
+                let output = apply_s(sbox_index, input);
                 for l in 0..4 {
-                    k[(4 * i + l) as usize] |= u32::from(get_bit(output as usize, l)) << j;
+                    k[4 * i + l] |= u32::from(get_bit(output as usize, l)) << j;
                 }
             }
         }
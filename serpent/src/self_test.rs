@@ -0,0 +1,32 @@
+//! Power-on self-test.
+
+use crate::Serpent;
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// NESSIE set 1 vector 0 key, truncated for the 128-bit test
+const KEY: [u8; 32] = [
+    0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// Plaintext of the NESSIE set 1 vectors
+const PLAINTEXT: [u8; 16] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// NESSIE set 1 vector 0 with 128-bit key
+const CIPHERTEXT_128: [u8; 16] = [
+    0x26, 0x4e, 0x54, 0x81, 0xef, 0xf4, 0x2a, 0x46, 0x06, 0xab, 0xda, 0x06, 0xc0, 0xbf, 0xda, 0x3d,
+];
+
+/// NESSIE set 1 vector 0 with 256-bit key
+const CIPHERTEXT_256: [u8; 16] = [
+    0xa2, 0x23, 0xaa, 0x12, 0x88, 0x46, 0x3c, 0x0e, 0x2b, 0xe3, 0x8e, 0xbd, 0x82, 0x56, 0x16, 0xc0,
+];
+
+/// Run known answer tests of Serpent with 128-bit and 256-bit keys.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Serpent>("Serpent-128", &KEY[..16], &PLAINTEXT, &CIPHERTEXT_128)?;
+    kat::<Serpent>("Serpent-256", &KEY, &PLAINTEXT, &CIPHERTEXT_256)?;
+    Ok(())
+}
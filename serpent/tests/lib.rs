@@ -4,3 +4,8 @@
 cipher::block_cipher_test!(serpent128_test, "serpent128", serpent::Serpent);
 cipher::block_cipher_test!(serpent192_test, "serpent192", serpent::Serpent);
 cipher::block_cipher_test!(serpent256_test, "serpent256", serpent::Serpent);
+
+#[test]
+fn self_test() {
+    assert_eq!(serpent::self_test(), Ok(()));
+}
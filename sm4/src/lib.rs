@@ -13,6 +13,10 @@
 
 pub use cipher;
 
+mod self_test;
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
+
 use byteorder::{ByteOrder, BE};
 use cipher::{
     consts::{U1, U16},
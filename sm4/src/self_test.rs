@@ -0,0 +1,19 @@
+//! Power-on self-test.
+
+use crate::Sm4;
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// Example 1 from GB/T 32907-2016, the plaintext is equal to the key
+const KEY: [u8; 16] = [
+    0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10,
+];
+
+/// Ciphertext of the GB/T 32907-2016 example 1
+const CIPHERTEXT: [u8; 16] = [
+    0x68, 0x1e, 0xdf, 0x34, 0xd2, 0x06, 0x96, 0x5e, 0x86, 0xb3, 0xe9, 0x4f, 0x53, 0x6e, 0x42, 0x46,
+];
+
+/// Run the known answer test of SM4.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Sm4>("SM4", &KEY, &KEY, &CIPHERTEXT)
+}
@@ -39,3 +39,8 @@ fn sm4_example_2() {
     }
     assert_eq!(&plaintext, block.as_slice());
 }
+
+#[test]
+fn self_test() {
+    assert_eq!(sm4::self_test(), Ok(()));
+}
@@ -9,6 +9,7 @@
 #![warn(rust_2018_idioms)]
 
 mod consts;
+mod self_test;
 
 use crate::consts::{C240, P_1024, P_256, P_512, R_1024, R_256, R_512};
 use cipher::{
@@ -18,6 +19,9 @@ use cipher::{
 };
 use core::{convert::TryInto, ops::BitXor};
 
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
+
 fn mix(r: u32, x: (u64, u64)) -> (u64, u64) {
     let y0 = x.0.wrapping_add(x.1);
     let y1 = x.1.rotate_left(r) ^ y0;
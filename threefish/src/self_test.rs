@@ -0,0 +1,41 @@
+//! Power-on self-test.
+
+use crate::{Threefish1024, Threefish256, Threefish512};
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// All-zero key and plaintext of the Skein 1.3 test vectors
+const ZERO: [u8; 128] = [0; 128];
+
+/// Threefish-256 test vector from Skein 1.3
+const CIPHERTEXT_256: [u8; 32] = [
+    0x84, 0xda, 0x2a, 0x1f, 0x8b, 0xea, 0xee, 0x94, 0x70, 0x66, 0xae, 0x3e, 0x31, 0x03, 0xf1, 0xad,
+    0x53, 0x6d, 0xb1, 0xf4, 0xa1, 0x19, 0x24, 0x95, 0x11, 0x6b, 0x9f, 0x3c, 0xe6, 0x13, 0x3f, 0xd8,
+];
+
+/// Threefish-512 test vector from Skein 1.3
+const CIPHERTEXT_512: [u8; 64] = [
+    0xb1, 0xa2, 0xbb, 0xc6, 0xef, 0x60, 0x25, 0xbc, 0x40, 0xeb, 0x38, 0x22, 0x16, 0x1f, 0x36, 0xe3,
+    0x75, 0xd1, 0xbb, 0x0a, 0xee, 0x31, 0x86, 0xfb, 0xd1, 0x9e, 0x47, 0xc5, 0xd4, 0x79, 0x94, 0x7b,
+    0x7b, 0xc2, 0xf8, 0x58, 0x6e, 0x35, 0xf0, 0xcf, 0xf7, 0xe7, 0xf0, 0x30, 0x84, 0xb0, 0xb7, 0xb1,
+    0xf1, 0xab, 0x39, 0x61, 0xa5, 0x80, 0xa3, 0xe9, 0x7e, 0xb4, 0x1e, 0xa1, 0x4a, 0x6d, 0x7b, 0xbe,
+];
+
+/// Threefish-1024 test vector from Skein 1.3
+const CIPHERTEXT_1024: [u8; 128] = [
+    0xf0, 0x5c, 0x3d, 0x0a, 0x3d, 0x05, 0xb3, 0x04, 0xf7, 0x85, 0xdd, 0xc7, 0xd1, 0xe0, 0x36, 0x01,
+    0x5c, 0x8a, 0xa7, 0x6e, 0x2f, 0x21, 0x7b, 0x06, 0xc6, 0xe1, 0x54, 0x4c, 0x0b, 0xc1, 0xa9, 0x0d,
+    0xf0, 0xac, 0xcb, 0x94, 0x73, 0xc2, 0x4e, 0x0f, 0xd5, 0x4f, 0xea, 0x68, 0x05, 0x7f, 0x43, 0x32,
+    0x9c, 0xb4, 0x54, 0x76, 0x1d, 0x6d, 0xf5, 0xcf, 0x7b, 0x2e, 0x9b, 0x36, 0x14, 0xfb, 0xd5, 0xa2,
+    0x0b, 0x2e, 0x47, 0x60, 0xb4, 0x06, 0x03, 0x54, 0x0d, 0x82, 0xea, 0xbc, 0x54, 0x82, 0xc1, 0x71,
+    0xc8, 0x32, 0xaf, 0xbe, 0x68, 0x40, 0x6b, 0xc3, 0x95, 0x00, 0x36, 0x7a, 0x59, 0x29, 0x43, 0xfa,
+    0x9a, 0x5b, 0x4a, 0x43, 0x28, 0x6c, 0xa3, 0xc4, 0xcf, 0x46, 0x10, 0x4b, 0x44, 0x31, 0x43, 0xd5,
+    0x60, 0xa4, 0xb2, 0x30, 0x48, 0x83, 0x11, 0xdf, 0x4f, 0xee, 0xf7, 0xe1, 0xdf, 0xe8, 0x39, 0x1e,
+];
+
+/// Run known answer tests of all Threefish variants with the zero tweak.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Threefish256>("Threefish-256", &ZERO[..32], &ZERO[..32], &CIPHERTEXT_256)?;
+    kat::<Threefish512>("Threefish-512", &ZERO[..64], &ZERO[..64], &CIPHERTEXT_512)?;
+    kat::<Threefish1024>("Threefish-1024", &ZERO, &ZERO, &CIPHERTEXT_1024)?;
+    Ok(())
+}
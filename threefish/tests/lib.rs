@@ -0,0 +1,4 @@
+#[test]
+fn self_test() {
+    assert_eq!(threefish::self_test(), Ok(()));
+}
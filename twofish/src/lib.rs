@@ -20,7 +20,10 @@ use cipher::{
 };
 
 mod consts;
+mod self_test;
 use crate::consts::{MDS_POLY, QBOX, QORD, RS, RS_POLY};
+pub use crate::self_test::self_test;
+pub use block_ciphers_self_test::SelfTestError;
 
 type Block = GenericArray<u8, U16>;
 
@@ -0,0 +1,17 @@
+//! Power-on self-test.
+
+use crate::Twofish;
+use block_ciphers_self_test::{kat, SelfTestError};
+
+/// All-zero key and plaintext of the first Twofish ECB_TBL.TXT vector
+const ZERO: [u8; 16] = [0; 16];
+
+/// Ciphertext of the first ECB_TBL.TXT vector
+const CIPHERTEXT: [u8; 16] = [
+    0x9f, 0x58, 0x9f, 0x5c, 0xf6, 0x12, 0x2c, 0x32, 0xb6, 0xbf, 0xec, 0x2f, 0x2a, 0xe8, 0xc3, 0x5a,
+];
+
+/// Run the known answer test of Twofish with a 128-bit key.
+pub fn self_test() -> Result<(), SelfTestError> {
+    kat::<Twofish>("Twofish", &ZERO, &ZERO, &CIPHERTEXT)
+}
@@ -75,3 +75,8 @@ new_test!(
     "3059D6D61753B958D92F4781C8640E58",
     "431058F4DBC7F734DA4F02F04CC4F459",
 );
+
+#[test]
+fn self_test() {
+    assert_eq!(twofish::self_test(), Ok(()));
+}